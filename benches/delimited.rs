@@ -0,0 +1,45 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use grud::{delimited::Delimiter, Grid};
+
+/// Returns a `size` by `size` grid of floats as whitespace separated text.
+fn text(size: usize) -> String {
+    let mut text = String::new();
+    for y in 0..size {
+        for x in 0..size {
+            if x > 0 {
+                text.push(' ');
+            }
+            text.push_str(&format!("{:.4}", (x * y) as f64 / 7.0));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn read_delimited(c: &mut Criterion) {
+    let text = text(500);
+    let mut group = c.benchmark_group("read_delimited");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("str::parse", |b| {
+        b.iter(|| {
+            Grid::read_delimited(
+                black_box(text.as_bytes()),
+                Delimiter::Whitespace,
+                str::parse::<f64>,
+            )
+            .unwrap()
+        });
+    });
+    group.bench_function("lexical", |b| {
+        b.iter(|| {
+            Grid::<f64>::read_delimited_numbers(black_box(text.as_bytes()), Delimiter::Whitespace)
+                .unwrap()
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, read_delimited);
+criterion_main!(benches);
@@ -0,0 +1,13 @@
+//! Self-contained algorithms over grids, gathered in one place.
+//!
+//! Each algorithm is defined in its own module (e.g. [`seam`](crate::seam)), and re-exported here
+//! so they can be found together:
+//!
+//! ```
+//! use grud::{Grid, algo::seam_carve};
+//!
+//! let grid = Grid::with_width(3, vec![0.0, 9.0, 0.0, 0.0, 9.0, 0.0]);
+//! assert_eq!(seam_carve(&grid, 2).width(), 2);
+//! ```
+
+pub use crate::seam::{energy, find_seam, seam_carve, seam_carve_height};
@@ -0,0 +1,160 @@
+//! Aligning the rows of two grids, i.e. for diffing revisions where rows were inserted or removed.
+//!
+//! See [`Grid::align_rows`] for details.
+
+use crate::grid::Grid;
+
+/// An operation that transforms one row of a grid into the rows of another grid.
+///
+/// Returned by [`Grid::align_rows`]; `from` is a row of the original grid, and `to` is a row of
+/// the other grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RowEdit {
+    /// The row is unchanged.
+    Keep { from: usize, to: usize },
+
+    /// The row was changed.
+    Replace { from: usize, to: usize },
+
+    /// The row was inserted.
+    Insert { to: usize },
+
+    /// The row was deleted.
+    Delete { from: usize },
+}
+
+impl RowEdit {
+    /// Returns whether the row is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::align::RowEdit;
+    ///
+    /// assert!(RowEdit::Keep { from: 0, to: 1 }.is_keep());
+    /// assert!(!RowEdit::Delete { from: 0 }.is_keep());
+    /// ```
+    pub fn is_keep(&self) -> bool {
+        matches!(self, RowEdit::Keep { .. })
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone + PartialEq,
+{
+    /// Returns the shortest sequence of row insertions, deletions, and replacements that turns this
+    /// grid into `other`, interleaved with the rows that are kept, in order.
+    ///
+    /// Unlike comparing cell by cell, inserting or removing a row only affects that row, rather
+    /// than every row after it. Rows of different widths are never equal, so they are replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, align::RowEdit};
+    ///
+    /// let before = Grid::from(vec![vec!['a'], vec!['b'], vec!['c']]);
+    /// let after = Grid::from(vec![vec!['a'], vec!['x'], vec!['b'], vec!['C']]);
+    ///
+    /// assert_eq!(before.align_rows(&after), vec![
+    ///     RowEdit::Keep { from: 0, to: 0 },
+    ///     RowEdit::Insert { to: 1 },
+    ///     RowEdit::Keep { from: 1, to: 2 },
+    ///     RowEdit::Replace { from: 2, to: 3 },
+    /// ]);
+    /// ```
+    pub fn align_rows(&self, other: &Grid<T>) -> Vec<RowEdit> {
+        let a = rows(self);
+        let b = rows(other);
+        let (n, m) = (a.len(), b.len());
+
+        // Edit distance between the first `i` rows of `a` and the first `j` rows of `b`.
+        let mut distances = vec![vec![0; m + 1]; n + 1];
+        for (i, row) in distances.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        for (j, distance) in distances[0].iter_mut().enumerate() {
+            *distance = j;
+        }
+        for i in 1..=n {
+            for j in 1..=m {
+                let replace = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+                let delete = distances[i - 1][j] + 1;
+                let insert = distances[i][j - 1] + 1;
+                distances[i][j] = replace.min(delete).min(insert);
+            }
+        }
+
+        let mut edits = Vec::with_capacity(n.max(m));
+        let (mut i, mut j) = (n, m);
+        while i > 0 || j > 0 {
+            if i > 0 && j > 0 {
+                let same = a[i - 1] == b[j - 1];
+                if distances[i][j] == distances[i - 1][j - 1] + usize::from(!same) {
+                    let (from, to) = (i - 1, j - 1);
+                    edits.push(if same {
+                        RowEdit::Keep { from, to }
+                    } else {
+                        RowEdit::Replace { from, to }
+                    });
+                    i -= 1;
+                    j -= 1;
+                    continue;
+                }
+            }
+            if i > 0 && distances[i][j] == distances[i - 1][j] + 1 {
+                edits.push(RowEdit::Delete { from: i - 1 });
+                i -= 1;
+            } else {
+                edits.push(RowEdit::Insert { to: j - 1 });
+                j -= 1;
+            }
+        }
+        edits.reverse();
+        edits
+    }
+}
+
+/// Returns the rows of `grid` as slices.
+fn rows<T>(grid: &Grid<T>) -> Vec<&[T]>
+where
+    T: Clone,
+{
+    let (width, _) = grid.size();
+    if width == 0 {
+        return Vec::new();
+    }
+    grid.as_vec().chunks(width).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn align_rows_identical() {
+        let grid = Grid::with_width(2, (0..6).collect::<Vec<_>>());
+
+        assert!(grid.align_rows(&grid).iter().all(RowEdit::is_keep));
+    }
+
+    #[test]
+    fn align_rows_deleted_and_empty() {
+        let grid = Grid::with_width(1, vec![1, 2, 3]);
+        let removed = Grid::with_width(1, vec![1, 3]);
+
+        assert_eq!(
+            grid.align_rows(&removed),
+            vec![
+                RowEdit::Keep { from: 0, to: 0 },
+                RowEdit::Delete { from: 1 },
+                RowEdit::Keep { from: 2, to: 1 },
+            ]
+        );
+        assert_eq!(
+            Grid::from(Vec::new()).align_rows(&removed),
+            vec![RowEdit::Insert { to: 0 }, RowEdit::Insert { to: 1 }]
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Generating arbitrary grids with [`arbitrary`], i.e. for fuzzing code that consumes grids.
+//!
+//! Requires the `arbitrary` feature.
+//!
+//! See the `proptest` module (with the `proptest` feature) for property-based testing instead.
+
+use std::ops::RangeInclusive;
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::grid::Grid;
+
+/// The range of widths and heights of grids generated by [`Arbitrary::arbitrary`].
+const DEFAULT_SIZE: RangeInclusive<usize> = 0..=32;
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Generates a grid with a width in `widths` and a height in `heights`, where each cell is
+    /// generated by `cell` in row-major order.
+    ///
+    /// Requires the `arbitrary` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arbitrary::Unstructured;
+    /// use grud::Grid;
+    ///
+    /// let mut u = Unstructured::new(&[7; 64]);
+    /// let grid = Grid::arbitrary_with(&mut u, 2..=4, 1..=1, |u| u.int_in_range(0..=9)).unwrap();
+    ///
+    /// assert!((2..=4).contains(&grid.width()));
+    /// assert_eq!(grid.height(), 1);
+    /// assert!(grid.as_vec().iter().all(|cell| *cell <= 9));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `cell` fails.
+    pub fn arbitrary_with<'a>(
+        u: &mut Unstructured<'a>,
+        widths: RangeInclusive<usize>,
+        heights: RangeInclusive<usize>,
+        mut cell: impl FnMut(&mut Unstructured<'a>) -> Result<T>,
+    ) -> Result<Self> {
+        let width = u.int_in_range(widths)?;
+        let height = u.int_in_range(heights)?;
+        let data = (0..width * height)
+            .map(|_| cell(u))
+            .collect::<Result<Vec<T>>>()?;
+        if data.is_empty() {
+            return Ok(Grid::from(Vec::new()));
+        }
+        Ok(Grid::with_width(width, data))
+    }
+}
+
+impl<'a, T> Arbitrary<'a> for Grid<T>
+where
+    T: Arbitrary<'a> + Clone,
+{
+    /// Generates a grid up to 32 by 32 cells; see [`Grid::arbitrary_with`] to choose the size.
+    ///
+    /// Requires the `arbitrary` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arbitrary::{Arbitrary, Unstructured};
+    /// use grud::Grid;
+    ///
+    /// let mut u = Unstructured::new(&[3; 256]);
+    /// let grid = Grid::<u8>::arbitrary(&mut u).unwrap();
+    ///
+    /// assert!(grid.width() <= 32);
+    /// ```
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Self::arbitrary_with(u, DEFAULT_SIZE, DEFAULT_SIZE, T::arbitrary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arbitrary_runs_out_of_data() {
+        let mut u = Unstructured::new(&[]);
+        let grid = Grid::<u32>::arbitrary(&mut u).unwrap();
+
+        assert!(grid.as_vec().is_empty() || grid.as_vec().iter().all(|cell| *cell == 0));
+    }
+
+    #[test]
+    fn arbitrary_with_fixed_size() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let mut u = Unstructured::new(&bytes);
+        let grid = Grid::arbitrary_with(&mut u, 3..=3, 5..=5, u8::arbitrary).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 5));
+    }
+}
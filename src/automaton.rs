@@ -0,0 +1,200 @@
+//! Stepping cellular automata, i.e. [Conway's Game of Life] or falling-sand simulations.
+//!
+//! [Conway's Game of Life]: https://en.wikipedia.org/wiki/Conway%27s_Game_of_Life
+//!
+//! See [`Grid::step`] for details.
+
+use crate::{grid::Grid, point::Connectivity};
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Computes the next generation, where each cell is `rule(cell, neighbors)`.
+    ///
+    /// The `neighbors` are the cells in the [Moore neighborhood] of each cell, in row-major order;
+    /// cells outside of the grid are omitted, so cells on the edges have fewer than 8 neighbors.
+    /// Every cell is computed from the _current_ generation, regardless of iteration order.
+    ///
+    /// [Moore neighborhood]: https://en.wikipedia.org/wiki/Moore_neighborhood
+    ///
+    /// # Examples
+    ///
+    /// A blinker in Conway's Game of Life:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let life = |alive: &bool, neighbors: &[&bool]| {
+    ///     let n = neighbors.iter().filter(|n| ***n).count();
+    ///     n == 3 || (*alive && n == 2)
+    /// };
+    ///
+    /// let grid = Grid::with_width(3, vec![
+    ///     false, true, false,
+    ///     false, true, false,
+    ///     false, true, false,
+    /// ]);
+    ///
+    /// assert_eq!(grid.step(life).as_vec(), &vec![
+    ///     false, false, false,
+    ///     true,  true,  true,
+    ///     false, false, false,
+    /// ]);
+    /// ```
+    pub fn step(&self, rule: impl FnMut(&T, &[&T]) -> T) -> Grid<T> {
+        let mut next = self.clone();
+        self.step_into(&mut next, false, rule);
+        next
+    }
+
+    /// Computes the next generation on a torus, where each cell is `rule(cell, neighbors)`.
+    ///
+    /// This is the same as [`Grid::step`], except the edges of the grid wrap around, so every cell
+    /// has 8 neighbors (some of which may be the same cell on grids narrower or shorter than 3).
+    ///
+    /// # Examples
+    ///
+    /// A blinker on the left edge wraps around to the right edge:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let life = |alive: &bool, neighbors: &[&bool]| {
+    ///     let n = neighbors.iter().filter(|n| ***n).count();
+    ///     n == 3 || (*alive && n == 2)
+    /// };
+    ///
+    /// let grid = Grid::with_width(5, vec![
+    ///     false, false, false, false, false,
+    ///     true,  false, false, false, false,
+    ///     true,  false, false, false, false,
+    ///     true,  false, false, false, false,
+    ///     false, false, false, false, false,
+    /// ]);
+    ///
+    /// assert_eq!(grid.step_wrapping(life).as_vec(), &vec![
+    ///     false, false, false, false, false,
+    ///     false, false, false, false, false,
+    ///     true,  true,  false, false, true,
+    ///     false, false, false, false, false,
+    ///     false, false, false, false, false,
+    /// ]);
+    /// ```
+    pub fn step_wrapping(&self, rule: impl FnMut(&T, &[&T]) -> T) -> Grid<T> {
+        let mut next = self.clone();
+        self.step_into(&mut next, true, rule);
+        next
+    }
+
+    /// Computes the next generation in place, using `buffer` to hold the next generation.
+    ///
+    /// This is the same as [`Grid::step`], but double-buffered: after stepping, this grid holds
+    /// the next generation and `buffer` holds the previous one. Passing the same `buffer` to every
+    /// step avoids allocating a new grid per generation. If `buffer` is not the same size as this
+    /// grid, it is replaced first.
+    ///
+    /// # Examples
+    ///
+    /// A blinker in Conway's Game of Life has a period of 2:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let life = |alive: &bool, neighbors: &[&bool]| {
+    ///     let n = neighbors.iter().filter(|n| ***n).count();
+    ///     n == 3 || (*alive && n == 2)
+    /// };
+    ///
+    /// let start = vec![
+    ///     false, true, false,
+    ///     false, true, false,
+    ///     false, true, false,
+    /// ];
+    ///
+    /// let mut grid = Grid::with_width(3, start.clone());
+    /// let mut buffer = grid.clone();
+    ///
+    /// grid.step_in_place(&mut buffer, life);
+    /// assert_eq!(buffer.as_vec(), &start);
+    ///
+    /// grid.step_in_place(&mut buffer, life);
+    /// assert_eq!(grid.as_vec(), &start);
+    /// ```
+    pub fn step_in_place(&mut self, buffer: &mut Grid<T>, rule: impl FnMut(&T, &[&T]) -> T) {
+        if buffer.width() != self.width() || buffer.area() != self.area() {
+            *buffer = self.clone();
+        }
+        self.step_into(buffer, false, rule);
+        std::mem::swap(self, buffer);
+    }
+
+    /// Writes the next generation of this grid into `next`, which must be the same size.
+    ///
+    /// If `wrap` is true, neighbors wrap around the edges of the grid.
+    fn step_into(&self, next: &mut Grid<T>, wrap: bool, mut rule: impl FnMut(&T, &[&T]) -> T) {
+        let (width, height) = self.size();
+        let mut neighbors = Vec::with_capacity(8);
+        for y in 0..height {
+            for x in 0..width {
+                neighbors.clear();
+                if wrap {
+                    neighbors.extend(
+                        Connectivity::Eight
+                            .neighbors_wrapping((x, y), width, height)
+                            .map(|point| &self[point]),
+                    );
+                } else {
+                    neighbors.extend(
+                        Connectivity::Eight
+                            .neighbors((x, y), width, height)
+                            .map(|point| &self[point]),
+                    );
+                }
+                next[(x, y)] = rule(&self[(x, y)], &neighbors);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(_: &u8, neighbors: &[&u8]) -> u8 {
+        neighbors.len() as u8
+    }
+
+    #[test]
+    fn step_neighbor_counts() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(grid.step(count).as_vec(), &vec![3, 5, 3, 5, 8, 5, 3, 5, 3]);
+    }
+
+    #[test]
+    fn step_reads_previous_generation() {
+        let grid = Grid::with_width(4, vec![1, 0, 0, 0]);
+        let spread =
+            |cell: &u8, neighbors: &[&u8]| *cell | neighbors.iter().fold(0, |a, n| a | **n);
+
+        assert_eq!(grid.step(spread).as_vec(), &vec![1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn step_in_place_resizes_buffer() {
+        let mut grid = Grid::new(2, 2, 0);
+        let mut buffer = Grid::new(1, 1, 9);
+        grid.step_in_place(&mut buffer, count);
+
+        assert_eq!(grid.as_vec(), &vec![3, 3, 3, 3]);
+        assert_eq!(buffer.as_vec(), &vec![0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn step_wrapping_neighbor_counts() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(grid.step_wrapping(count).as_vec(), &vec![8; 9]);
+    }
+}
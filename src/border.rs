@@ -0,0 +1,134 @@
+//! Iterating the border and interior of grids, i.e. for boundary conditions in simulations.
+//!
+//! See [`Grid::border`] and [`Grid::interior`] for details.
+
+use crate::{grid::Grid, point::Rect};
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns an iterator over the cells in the outermost ring of the grid, clockwise from the
+    /// top-left corner.
+    ///
+    /// Every cell is yielded once, even in grids only one cell wide or tall.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, (1..=9).collect());
+    /// let border: Vec<_> = grid.border().copied().collect();
+    ///
+    /// assert_eq!(border, vec![1, 2, 3, 6, 9, 8, 7, 4]);
+    /// ```
+    pub fn border(&self) -> impl Iterator<Item = &T> {
+        self.border_points().map(|point| &self[point])
+    }
+
+    /// Returns an iterator over the points in the outermost ring of the grid, clockwise from the
+    /// top-left corner.
+    ///
+    /// The iterator does not borrow the grid, so cells can be modified while iterating.
+    ///
+    /// # Examples
+    ///
+    /// Setting a fixed boundary condition:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(4, 3, 0.5);
+    /// for point in grid.border_points() {
+    ///     grid[point] = 0.0;
+    /// }
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![
+    ///     vec![0.0, 0.0, 0.0, 0.0],
+    ///     vec![0.0, 0.5, 0.5, 0.0],
+    ///     vec![0.0, 0.0, 0.0, 0.0],
+    /// ]);
+    /// ```
+    pub fn border_points(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = self.size();
+        let top = (0..width).map(|x| (x, 0));
+        let right = (1..height).map(move |y| (width - 1, y));
+        let bottom = (0..if height > 1 { width - 1 } else { 0 })
+            .rev()
+            .map(move |x| (x, height - 1));
+        let left = (1..if width > 1 { height - 1 } else { 1 })
+            .rev()
+            .map(|y| (0, y));
+        top.chain(right).chain(bottom).chain(left)
+    }
+
+    /// Returns an iterator over the cells not in the outermost ring of the grid, in row-major
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(4, (1..=12).collect());
+    /// let interior: Vec<_> = grid.interior().copied().collect();
+    ///
+    /// assert_eq!(interior, vec![6, 7]);
+    /// ```
+    pub fn interior(&self) -> impl Iterator<Item = &T> {
+        self.interior_points().map(|point| &self[point])
+    }
+
+    /// Returns an iterator over the points not in the outermost ring of the grid, in row-major
+    /// order.
+    ///
+    /// The iterator does not borrow the grid, so cells can be modified while iterating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::new(4, 4, ());
+    /// let interior: Vec<_> = grid.interior_points().collect();
+    ///
+    /// assert_eq!(interior, vec![(1, 1), (2, 1), (1, 2), (2, 2)]);
+    /// ```
+    pub fn interior_points(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = self.size();
+        Rect::new(1, 1, width.saturating_sub(2), height.saturating_sub(2)).iter_points()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn border_thin_grids() {
+        let row = Grid::with_width(3, vec![1, 2, 3]);
+        let col = Grid::with_width(1, vec![1, 2, 3]);
+
+        assert_eq!(row.border().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(col.border().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(row.interior().count(), 0);
+    }
+
+    #[test]
+    fn border_and_interior_partition_grid() {
+        let grid = Grid::with_width(5, (0..20).collect::<Vec<_>>());
+        let mut cells: Vec<_> = grid.border().chain(grid.interior()).copied().collect();
+        cells.sort();
+
+        assert_eq!(cells, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn border_empty_grid() {
+        let grid: Grid<u8> = Grid::from(Vec::new());
+
+        assert_eq!(grid.border().count(), 0);
+        assert_eq!(grid.interior().count(), 0);
+    }
+}
@@ -0,0 +1,1231 @@
+//! Chunked grids, where the world is split into fixed-size [`Grid`]s keyed by chunk coordinates.
+//!
+//! See [`ChunkedGrid`] for chunks kept in memory, and [`StreamingGrid`] for chunks faulted in and
+//! out of a [`ChunkStore`].
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    error::Error,
+    fmt::{Display, Formatter},
+    future::Future,
+};
+
+use crate::{grid::Grid, memory::MemoryReport};
+
+/// Coordinates of a chunk, where `(0, 0)` is the chunk containing the world origin.
+pub type ChunkKey = (i64, i64);
+
+/// A backing store that chunks can be loaded from and saved to.
+///
+/// Methods return futures so that implementations can perform I/O asynchronously (e.g. reading
+/// from disk or a network service); synchronous stores can implement them using `async fn`.
+///
+/// The futures must be [`Send`], so that generic code can load and save chunks from a background
+/// task (i.e. with `tokio::spawn`).
+pub trait ChunkStore<T>
+where
+    T: Clone,
+{
+    /// Error that may occur when loading or saving a chunk.
+    type Error;
+
+    /// Loads the chunk at `key`, returning `None` if the chunk has never been saved.
+    fn load_chunk(
+        &mut self,
+        key: ChunkKey,
+    ) -> impl Future<Output = Result<Option<Grid<T>>, Self::Error>> + Send;
+
+    /// Saves `chunk` at `key`, replacing any previously saved chunk.
+    fn save_chunk(
+        &mut self,
+        key: ChunkKey,
+        chunk: &Grid<T>,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// A [`ChunkStore`] that keeps every saved chunk in memory.
+///
+/// Useful for tests, or as a starting point for a store that persists chunks elsewhere.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore<T>
+where
+    T: Clone,
+{
+    chunks: HashMap<ChunkKey, Grid<T>>,
+}
+
+impl<T> MemoryStore<T>
+where
+    T: Clone,
+{
+    /// Creates an empty store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::MemoryStore;
+    ///
+    /// let store = MemoryStore::<u8>::new();
+    /// assert_eq!(store.len(), 0);
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// Returns the saved chunk at `key`, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::MemoryStore;
+    ///
+    /// let store = MemoryStore::<u8>::new();
+    /// assert!(store.get((0, 0)).is_none());
+    /// ```
+    pub fn get(&self, key: ChunkKey) -> Option<&Grid<T>> {
+        self.chunks.get(&key)
+    }
+
+    /// Returns the number of saved chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::MemoryStore;
+    ///
+    /// let store = MemoryStore::<u8>::new();
+    /// assert_eq!(store.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns whether no chunks have been saved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::MemoryStore;
+    ///
+    /// let store = MemoryStore::<u8>::new();
+    /// assert!(store.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+}
+
+impl<T> ChunkStore<T> for MemoryStore<T>
+where
+    T: Clone + Send + Sync,
+{
+    type Error = Infallible;
+
+    async fn load_chunk(&mut self, key: ChunkKey) -> Result<Option<Grid<T>>, Self::Error> {
+        Ok(self.chunks.get(&key).cloned())
+    }
+
+    async fn save_chunk(&mut self, key: ChunkKey, chunk: &Grid<T>) -> Result<(), Self::Error> {
+        self.chunks.insert(key, chunk.clone());
+        Ok(())
+    }
+}
+
+/// An unbounded grid that lazily allocates fixed-size chunks in memory as cells are written.
+///
+/// Cells are addressed with signed world coordinates, so the grid extends in every direction, and
+/// cells in chunks that were never written read as a default value.
+///
+/// # Examples
+///
+/// ```
+/// use grud::chunk::ChunkedGrid;
+///
+/// let mut world = ChunkedGrid::new(16, 16, '.');
+/// world.set(-100, 40, '#');
+///
+/// assert_eq!(world.get(-100, 40), &'#');
+/// assert_eq!(world.get(1_000_000, -1_000_000), &'.');
+/// assert_eq!(world.chunk_len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ChunkedGrid<T>
+where
+    T: Clone,
+{
+    chunk_width: usize,
+    chunk_height: usize,
+    default: T,
+    chunks: HashMap<ChunkKey, Grid<T>>,
+    stamps: HashMap<ChunkKey, u64>,
+    clock: u64,
+    clean: u64,
+}
+
+impl<T> ChunkedGrid<T>
+where
+    T: Clone,
+{
+    /// Creates a new grid of `chunk_width` by `chunk_height` chunks, where every cell is `default`
+    /// and no chunks are allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let world = ChunkedGrid::new(32, 32, 0);
+    /// assert_eq!(world.chunk_len(), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `chunk_width` or `chunk_height` is `0`.
+    pub fn new(chunk_width: usize, chunk_height: usize, default: T) -> Self {
+        assert!(
+            chunk_width > 0 && chunk_height > 0,
+            "Chunk size {chunk_width}x{chunk_height} must not be empty"
+        );
+        Self {
+            chunk_width,
+            chunk_height,
+            default,
+            chunks: HashMap::new(),
+            stamps: HashMap::new(),
+            clock: 0,
+            clean: 0,
+        }
+    }
+
+    /// Returns the key of the chunk containing world coordinate `(x, y)`, and the position of the
+    /// cell within that chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let world = ChunkedGrid::new(16, 16, 0);
+    /// assert_eq!(world.locate(-1, 16), ((-1, 1), (15, 0)));
+    /// ```
+    pub fn locate(&self, x: i64, y: i64) -> (ChunkKey, (usize, usize)) {
+        locate(x, y, self.chunk_width, self.chunk_height)
+    }
+
+    /// Returns the cell at world coordinate `(x, y)`, which is the default value if its chunk is
+    /// not allocated.
+    ///
+    /// This never allocates a chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let world = ChunkedGrid::new(16, 16, 7);
+    ///
+    /// assert_eq!(world.get(-5, 5), &7);
+    /// assert_eq!(world.chunk_len(), 0);
+    /// ```
+    pub fn get(&self, x: i64, y: i64) -> &T {
+        let (key, point) = self.locate(x, y);
+        self.chunks
+            .get(&key)
+            .map_or(&self.default, |chunk| &chunk[point])
+    }
+
+    /// Returns a mutable reference to the cell at world coordinate `(x, y)`, allocating its chunk
+    /// if necessary.
+    ///
+    /// The chunk is marked as modified; see [`ChunkedGrid::dirty_chunks`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0);
+    /// *world.get_mut(3, -3) += 1;
+    ///
+    /// assert_eq!(world.get(3, -3), &1);
+    /// ```
+    pub fn get_mut(&mut self, x: i64, y: i64) -> &mut T {
+        let (key, point) = self.locate(x, y);
+        let (width, height) = (self.chunk_width, self.chunk_height);
+        self.touch(key);
+        let chunk = self
+            .chunks
+            .entry(key)
+            .or_insert_with(|| Grid::new(width, height, self.default.clone()));
+        &mut chunk[point]
+    }
+
+    /// Sets the cell at world coordinate `(x, y)` to `value`, allocating its chunk if necessary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0);
+    /// world.set(20, 0, 9);
+    ///
+    /// assert_eq!(world.chunk((1, 0)).unwrap()[(4, 0)], 9);
+    /// ```
+    pub fn set(&mut self, x: i64, y: i64, value: T) {
+        *self.get_mut(x, y) = value;
+    }
+
+    /// Returns the number of allocated chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0);
+    /// world.set(0, 0, 1);
+    /// world.set(15, 15, 1);
+    /// world.set(16, 0, 1);
+    ///
+    /// assert_eq!(world.chunk_len(), 2);
+    /// ```
+    pub fn chunk_len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the chunk at `key`, or `None` if it is not allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(2, 2, 0);
+    /// world.set(-1, -1, 5);
+    ///
+    /// assert_eq!(world.chunk((-1, -1)).unwrap().as_vec(), &vec![0, 0, 0, 5]);
+    /// assert!(world.chunk((0, 0)).is_none());
+    /// ```
+    pub fn chunk(&self, key: ChunkKey) -> Option<&Grid<T>> {
+        self.chunks.get(&key)
+    }
+
+    /// Returns an iterator over every allocated chunk and its key, in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0);
+    /// world.set(-1, 0, 1);
+    ///
+    /// let keys: Vec<_> = world.chunks().map(|(key, _)| key).collect();
+    /// assert_eq!(keys, vec![(-1, 0)]);
+    /// ```
+    pub fn chunks(&self) -> impl Iterator<Item = (ChunkKey, &Grid<T>)> {
+        self.chunks.iter().map(|(key, chunk)| (*key, chunk))
+    }
+
+    /// Removes and returns the chunk at `key`, so its cells read as the default value again.
+    ///
+    /// If a chunk was removed, `key` is marked as modified until [`ChunkedGrid::clear_dirty`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0);
+    /// world.set(1, 1, 1);
+    ///
+    /// assert!(world.remove_chunk((0, 0)).is_some());
+    /// assert_eq!(world.get(1, 1), &0);
+    /// ```
+    pub fn remove_chunk(&mut self, key: ChunkKey) -> Option<Grid<T>> {
+        let chunk = self.chunks.remove(&key)?;
+        self.touch(key);
+        Some(chunk)
+    }
+
+    /// Returns the current modification stamp, which increases every time a chunk is modified.
+    ///
+    /// Pass a previously returned stamp to [`ChunkedGrid::modified_since`] to find which chunks
+    /// changed after it was taken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0);
+    /// let before = world.stamp();
+    /// world.set(0, 0, 1);
+    ///
+    /// assert!(world.stamp() > before);
+    /// ```
+    pub fn stamp(&self) -> u64 {
+        self.clock
+    }
+
+    /// Returns the stamp of the last modification to the chunk at `key`, or `None` if it was not
+    /// modified since the last [`ChunkedGrid::clear_dirty`] and is not allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0);
+    /// world.set(0, 0, 1);
+    ///
+    /// assert_eq!(world.chunk_stamp((0, 0)), Some(world.stamp()));
+    /// assert_eq!(world.chunk_stamp((1, 0)), None);
+    /// ```
+    pub fn chunk_stamp(&self, key: ChunkKey) -> Option<u64> {
+        self.stamps.get(&key).copied()
+    }
+
+    /// Returns the keys of chunks modified after `stamp` was taken, in arbitrary order.
+    ///
+    /// Removed chunks are included until [`ChunkedGrid::clear_dirty`] is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0);
+    /// world.set(0, 0, 1);
+    ///
+    /// let stamp = world.stamp();
+    /// world.set(-1, 0, 1);
+    ///
+    /// let keys: Vec<_> = world.modified_since(stamp).collect();
+    /// assert_eq!(keys, vec![(-1, 0)]);
+    /// ```
+    pub fn modified_since(&self, stamp: u64) -> impl Iterator<Item = ChunkKey> + '_ {
+        self.stamps
+            .iter()
+            .filter(move |(_, modified)| **modified > stamp)
+            .map(|(key, _)| *key)
+    }
+
+    /// Returns the keys of chunks modified since the last [`ChunkedGrid::clear_dirty`], or since
+    /// the grid was created, in arbitrary order.
+    ///
+    /// Downstream systems (i.e. rebuilding meshes, or saving) can recompute only these chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0);
+    /// world.set(0, 0, 1);
+    /// world.set(1, 1, 1);
+    ///
+    /// let keys: Vec<_> = world.dirty_chunks().collect();
+    /// assert_eq!(keys, vec![(0, 0)]);
+    ///
+    /// world.clear_dirty();
+    /// assert_eq!(world.dirty_chunks().count(), 0);
+    /// ```
+    pub fn dirty_chunks(&self) -> impl Iterator<Item = ChunkKey> + '_ {
+        self.modified_since(self.clean)
+    }
+
+    /// Marks every chunk as not modified, so [`ChunkedGrid::dirty_chunks`] is empty.
+    ///
+    /// Stamps of allocated chunks are kept; stamps of removed chunks are forgotten.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0);
+    /// world.set(0, 0, 1);
+    /// world.clear_dirty();
+    ///
+    /// assert_eq!(world.dirty_chunks().count(), 0);
+    /// assert!(world.chunk_stamp((0, 0)).is_some());
+    /// ```
+    pub fn clear_dirty(&mut self) {
+        self.clean = self.clock;
+        let chunks = &self.chunks;
+        self.stamps.retain(|key, _| chunks.contains_key(key));
+    }
+
+    /// Records a modification of the chunk at `key`.
+    fn touch(&mut self, key: ChunkKey) {
+        self.clock += 1;
+        self.stamps.insert(key, self.clock);
+    }
+
+    /// Returns the smallest rectangle of world coordinates containing every allocated chunk, as
+    /// the inclusive minimum and exclusive maximum `(x, y)`, or `None` if no chunks are allocated.
+    ///
+    /// The exclusive maximum of chunks at the far edge of the world is past [`i64::MAX`], and
+    /// saturates at [`i64::MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0);
+    /// world.set(-1, 0, 1);
+    /// world.set(20, 40, 1);
+    ///
+    /// assert_eq!(world.bounds(), Some(((-16, 0), (32, 48))));
+    /// ```
+    pub fn bounds(&self) -> Option<((i64, i64), (i64, i64))> {
+        let ((min_x, min_y), (max_x, max_y)) = self.key_bounds()?;
+        let (w, h) = (self.chunk_width as i64, self.chunk_height as i64);
+        let end = |key: i64, size: i64| key.saturating_add(1).saturating_mul(size);
+        Some((
+            (min_x.saturating_mul(w), min_y.saturating_mul(h)),
+            (end(max_x, w), end(max_y, h)),
+        ))
+    }
+
+    /// Returns the smallest and largest key of any allocated chunk in each axis, or `None` if no
+    /// chunks are allocated.
+    fn key_bounds(&self) -> Option<(ChunkKey, ChunkKey)> {
+        let min_x = self.chunks.keys().map(|(x, _)| *x).min()?;
+        let min_y = self.chunks.keys().map(|(_, y)| *y).min()?;
+        let max_x = self.chunks.keys().map(|(x, _)| *x).max()?;
+        let max_y = self.chunks.keys().map(|(_, y)| *y).max()?;
+        Some(((min_x, min_y), (max_x, max_y)))
+    }
+
+    /// Returns a dense copy of the cells within [`ChunkedGrid::bounds`], and the world coordinate
+    /// of its top-left cell, or `None` if no chunks are allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(2, 1, 0);
+    /// world.set(-1, 0, 1);
+    /// world.set(2, 0, 2);
+    ///
+    /// let (grid, origin) = world.to_grid().unwrap();
+    ///
+    /// assert_eq!(origin, (-2, 0));
+    /// assert_eq!(grid.as_vec(), &vec![0, 1, 0, 0, 2, 0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the bounds are too large to allocate as a single grid.
+    pub fn to_grid(&self) -> Option<(Grid<T>, (i64, i64))> {
+        let ((min_x, min_y), _) = self.bounds()?;
+        let (_, (max_x, max_y)) = self.key_bounds()?;
+        // The exclusive maximum may not fit in an `i64`, so measure the extent without saturating.
+        let extent = |max: i64, min: i64, size: usize| {
+            let end = (i128::from(max) + 1) * size as i128;
+            usize::try_from(end - i128::from(min)).expect("Bounds are too large for a grid")
+        };
+        let width = extent(max_x, min_x, self.chunk_width);
+        let height = extent(max_y, min_y, self.chunk_height);
+        let mut grid = Grid::new(width, height, self.default.clone());
+        for ((cx, cy), chunk) in &self.chunks {
+            let x = (cx * self.chunk_width as i64 - min_x) as usize;
+            let y = (cy * self.chunk_height as i64 - min_y) as usize;
+            for row in 0..self.chunk_height {
+                for col in 0..self.chunk_width {
+                    grid[(x + col, y + row)] = chunk[(col, row)].clone();
+                }
+            }
+        }
+        Some((grid, (min_x, min_y)))
+    }
+
+    /// Returns how much memory the grid uses, with the cells of every allocated chunk combined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let mut world = ChunkedGrid::new(16, 16, 0_u8);
+    /// world.set(0, 0, 1);
+    /// world.set(-1, 0, 1);
+    ///
+    /// let report = world.memory_usage();
+    ///
+    /// assert_eq!(report.chunk_len, 2);
+    /// assert_eq!(report.cells.used_bytes(), 512);
+    /// assert!(report.total_bytes() > 512);
+    /// ```
+    pub fn memory_usage(&self) -> ChunkedMemoryReport {
+        let chunks = self.chunks.values().map(Grid::memory_usage);
+        ChunkedMemoryReport {
+            chunk_len: self.chunks.len(),
+            cells: MemoryReport {
+                len: chunks.clone().map(|chunk| chunk.len).sum(),
+                capacity: chunks.map(|chunk| chunk.capacity).sum(),
+                cell_size: std::mem::size_of::<T>(),
+                cell_align: std::mem::align_of::<T>(),
+                inline_bytes: std::mem::size_of::<Self>(),
+            },
+            index_bytes: self.chunks.capacity() * std::mem::size_of::<(ChunkKey, Grid<T>)>()
+                + self.stamps.capacity() * std::mem::size_of::<(ChunkKey, u64)>(),
+        }
+    }
+}
+
+/// The memory used by a [`ChunkedGrid`], returned by [`ChunkedGrid::memory_usage`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChunkedMemoryReport {
+    /// The number of allocated chunks.
+    pub chunk_len: usize,
+
+    /// The cells of every allocated chunk combined; `inline_bytes` is the size of the
+    /// [`ChunkedGrid`] itself.
+    pub cells: MemoryReport,
+
+    /// The approximate size in bytes of the maps from keys to chunks and modification stamps,
+    /// excluding the cells.
+    pub index_bytes: usize,
+}
+
+impl ChunkedMemoryReport {
+    /// Returns the approximate total size of the grid, its chunks, and its index in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::ChunkedGrid;
+    ///
+    /// let world = ChunkedGrid::new(16, 16, 0_u8);
+    /// let report = world.memory_usage();
+    ///
+    /// assert_eq!(report.total_bytes(), report.cells.inline_bytes + report.index_bytes);
+    /// ```
+    pub fn total_bytes(&self) -> usize {
+        self.cells.total_bytes() + self.index_bytes
+    }
+}
+
+/// Returns the key of the chunk containing world coordinate `(x, y)`, and the position of the
+/// cell within that chunk, for chunks of `chunk_width` by `chunk_height`.
+fn locate(x: i64, y: i64, chunk_width: usize, chunk_height: usize) -> (ChunkKey, (usize, usize)) {
+    let (w, h) = (chunk_width as i64, chunk_height as i64);
+    (
+        (x.div_euclid(w), y.div_euclid(h)),
+        (x.rem_euclid(w) as usize, y.rem_euclid(h) as usize),
+    )
+}
+
+/// Error returned by [`StreamingGrid`] when a chunk cannot be loaded or saved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StreamError<E> {
+    /// The store failed to load or save a chunk.
+    Store(E),
+
+    /// The store loaded a chunk that is not the grid's chunk size.
+    ChunkSize {
+        /// The key of the chunk.
+        key: ChunkKey,
+
+        /// The width of the loaded chunk.
+        width: usize,
+
+        /// The height of the loaded chunk.
+        height: usize,
+    },
+}
+
+impl<E> Display for StreamError<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Store(e) => write!(f, "Chunk store failed: {e}"),
+            Self::ChunkSize { key, width, height } => {
+                write!(
+                    f,
+                    "Chunk {key:?} is {width}x{height}, expected the grid's chunk size"
+                )
+            }
+        }
+    }
+}
+
+impl<E> Error for StreamError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Store(e) => Some(e),
+            Self::ChunkSize { .. } => None,
+        }
+    }
+}
+
+/// A chunk that is currently loaded into a [`StreamingGrid`].
+#[derive(Clone, Debug)]
+struct Resident<T>
+where
+    T: Clone,
+{
+    chunk: Grid<T>,
+    dirty: bool,
+    used: u64,
+}
+
+/// An unbounded grid that faults fixed-size chunks in and out of a [`ChunkStore`] on demand.
+///
+/// At most `budget` chunks are resident at a time; when another chunk is needed, the least
+/// recently used chunk is evicted (and saved to the store, if it was modified). Chunks that were
+/// never saved are created filled with a default value.
+///
+/// Cells are addressed with signed world coordinates, so the grid extends in every direction.
+///
+/// # Examples
+///
+/// ```
+/// use grud::chunk::{MemoryStore, StreamingGrid};
+///
+/// pollster::block_on(async {
+///     let mut grid = StreamingGrid::new(MemoryStore::new(), 16, 16, '.', 1);
+///
+///     grid.set(-1, -1, '#').await.unwrap();
+///     assert_eq!(grid.get(100, 100).await.unwrap(), &'.');
+///
+///     // The chunk containing (-1, -1) was evicted and saved, and is loaded again on demand.
+///     assert_eq!(grid.get(-1, -1).await.unwrap(), &'#');
+/// });
+/// ```
+#[derive(Debug)]
+pub struct StreamingGrid<T, S>
+where
+    T: Clone,
+    S: ChunkStore<T>,
+{
+    store: S,
+    chunk_width: usize,
+    chunk_height: usize,
+    default: T,
+    budget: usize,
+    resident: HashMap<ChunkKey, Resident<T>>,
+    clock: u64,
+}
+
+impl<T, S> StreamingGrid<T, S>
+where
+    T: Clone,
+    S: ChunkStore<T>,
+{
+    /// Creates a new streaming grid backed by `store`.
+    ///
+    /// Chunks are `chunk_width` by `chunk_height` cells, filled with `default` when they are not
+    /// found in the store, and at most `budget` chunks are kept resident at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::{MemoryStore, StreamingGrid};
+    ///
+    /// let grid = StreamingGrid::new(MemoryStore::new(), 32, 32, 0, 4);
+    /// assert_eq!(grid.resident_len(), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `chunk_width`, `chunk_height`, or `budget` is `0`.
+    pub fn new(
+        store: S,
+        chunk_width: usize,
+        chunk_height: usize,
+        default: T,
+        budget: usize,
+    ) -> Self {
+        assert!(
+            chunk_width > 0 && chunk_height > 0,
+            "Chunk size {chunk_width}x{chunk_height} must not be empty"
+        );
+        assert!(budget > 0, "Budget must allow at least one resident chunk");
+        Self {
+            store,
+            chunk_width,
+            chunk_height,
+            default,
+            budget,
+            resident: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    /// Returns the key of the chunk containing world coordinate `(x, y)`, and the position of the
+    /// cell within that chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::{MemoryStore, StreamingGrid};
+    ///
+    /// let grid = StreamingGrid::new(MemoryStore::new(), 16, 16, 0, 4);
+    /// assert_eq!(grid.locate(17, 3), ((1, 0), (1, 3)));
+    /// assert_eq!(grid.locate(-1, -16), ((-1, -1), (15, 0)));
+    /// ```
+    pub fn locate(&self, x: i64, y: i64) -> (ChunkKey, (usize, usize)) {
+        locate(x, y, self.chunk_width, self.chunk_height)
+    }
+
+    /// Returns the number of chunks currently resident.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::{MemoryStore, StreamingGrid};
+    ///
+    /// let grid = StreamingGrid::new(MemoryStore::new(), 16, 16, 0, 4);
+    /// assert_eq!(grid.resident_len(), 0);
+    /// ```
+    pub fn resident_len(&self) -> usize {
+        self.resident.len()
+    }
+
+    /// Returns whether the chunk at `key` is currently resident.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::{MemoryStore, StreamingGrid};
+    ///
+    /// let grid = StreamingGrid::new(MemoryStore::new(), 16, 16, 0, 4);
+    /// assert!(!grid.is_resident((0, 0)));
+    /// ```
+    pub fn is_resident(&self, key: ChunkKey) -> bool {
+        self.resident.contains_key(&key)
+    }
+
+    /// Returns the cell at world coordinate `(x, y)` if its chunk is already resident.
+    ///
+    /// Unlike [`StreamingGrid::get`], this never loads a chunk and does not count as a use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::{MemoryStore, StreamingGrid};
+    ///
+    /// let grid = StreamingGrid::new(MemoryStore::new(), 16, 16, 0, 4);
+    /// assert_eq!(grid.get_resident(0, 0), None);
+    /// ```
+    pub fn get_resident(&self, x: i64, y: i64) -> Option<&T> {
+        let (key, point) = self.locate(x, y);
+        self.resident.get(&key).map(|r| &r.chunk[point])
+    }
+
+    /// Returns the keys of resident chunks modified since they were last loaded or saved, in
+    /// arbitrary order.
+    ///
+    /// These are the chunks that [`StreamingGrid::flush`] would save.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::{MemoryStore, StreamingGrid};
+    ///
+    /// let grid = StreamingGrid::new(MemoryStore::<u8>::new(), 16, 16, 0, 4);
+    /// assert_eq!(grid.dirty_chunks().count(), 0);
+    /// ```
+    pub fn dirty_chunks(&self) -> impl Iterator<Item = ChunkKey> + '_ {
+        self.resident
+            .iter()
+            .filter(|(_, resident)| resident.dirty)
+            .map(|(key, _)| *key)
+    }
+
+    /// Returns a reference to the backing store.
+    ///
+    /// Chunks that are resident and modified have not necessarily been saved yet; see
+    /// [`StreamingGrid::flush`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::{MemoryStore, StreamingGrid};
+    ///
+    /// let grid = StreamingGrid::new(MemoryStore::<u8>::new(), 16, 16, 0, 4);
+    /// assert!(grid.store().is_empty());
+    /// ```
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the cell at world coordinate `(x, y)`, loading its chunk if necessary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::{MemoryStore, StreamingGrid};
+    ///
+    /// pollster::block_on(async {
+    ///     let mut grid = StreamingGrid::new(MemoryStore::new(), 16, 16, 0, 4);
+    ///     assert_eq!(grid.get(-5, 7).await.unwrap(), &0);
+    ///     assert!(grid.is_resident((-1, 0)));
+    /// });
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the chunk had to be loaded, or another chunk evicted, and the store failed, or the store
+    /// loaded a chunk of the wrong size.
+    pub async fn get(&mut self, x: i64, y: i64) -> Result<&T, StreamError<S::Error>> {
+        let (key, point) = self.locate(x, y);
+        let resident = self.fault(key).await?;
+        Ok(&resident.chunk[point])
+    }
+
+    /// Returns a mutable reference to the cell at world coordinate `(x, y)`, loading its chunk if
+    /// necessary.
+    ///
+    /// The chunk is marked as modified, and is saved when evicted or flushed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::{MemoryStore, StreamingGrid};
+    ///
+    /// pollster::block_on(async {
+    ///     let mut grid = StreamingGrid::new(MemoryStore::new(), 16, 16, 0, 4);
+    ///     *grid.get_mut(3, 3).await.unwrap() += 1;
+    ///     assert_eq!(grid.get(3, 3).await.unwrap(), &1);
+    /// });
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the chunk had to be loaded, or another chunk evicted, and the store failed, or the store
+    /// loaded a chunk of the wrong size.
+    pub async fn get_mut(&mut self, x: i64, y: i64) -> Result<&mut T, StreamError<S::Error>> {
+        let (key, point) = self.locate(x, y);
+        let resident = self.fault(key).await?;
+        resident.dirty = true;
+        Ok(&mut resident.chunk[point])
+    }
+
+    /// Sets the cell at world coordinate `(x, y)` to `value`, loading its chunk if necessary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::{MemoryStore, StreamingGrid};
+    ///
+    /// pollster::block_on(async {
+    ///     let mut grid = StreamingGrid::new(MemoryStore::new(), 16, 16, 0, 4);
+    ///     grid.set(3, 3, 9).await.unwrap();
+    ///     assert_eq!(grid.get_resident(3, 3), Some(&9));
+    /// });
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the chunk had to be loaded, or another chunk evicted, and the store failed, or the store
+    /// loaded a chunk of the wrong size.
+    pub async fn set(&mut self, x: i64, y: i64, value: T) -> Result<(), StreamError<S::Error>> {
+        *self.get_mut(x, y).await? = value;
+        Ok(())
+    }
+
+    /// Saves every resident chunk that was modified since it was last loaded or saved.
+    ///
+    /// Chunks remain resident after flushing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::chunk::{MemoryStore, StreamingGrid};
+    ///
+    /// pollster::block_on(async {
+    ///     let mut grid = StreamingGrid::new(MemoryStore::new(), 16, 16, 0, 4);
+    ///     grid.set(3, 3, 9).await.unwrap();
+    ///     assert!(grid.store().is_empty());
+    ///
+    ///     grid.flush().await.unwrap();
+    ///     assert_eq!(grid.store().get((0, 0)).unwrap()[(3, 3)], 9);
+    /// });
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the store failed to save a chunk; chunks that were not saved remain modified.
+    pub async fn flush(&mut self) -> Result<(), StreamError<S::Error>> {
+        for (key, resident) in self.resident.iter_mut() {
+            if resident.dirty {
+                self.store
+                    .save_chunk(*key, &resident.chunk)
+                    .await
+                    .map_err(StreamError::Store)?;
+                resident.dirty = false;
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensures the chunk at `key` is resident, marks it as used, and returns it.
+    async fn fault(&mut self, key: ChunkKey) -> Result<&mut Resident<T>, StreamError<S::Error>> {
+        self.clock += 1;
+        if !self.resident.contains_key(&key) {
+            while self.resident.len() >= self.budget {
+                self.evict_lru().await?;
+            }
+            let loaded = self
+                .store
+                .load_chunk(key)
+                .await
+                .map_err(StreamError::Store)?;
+            let chunk = match loaded {
+                Some(chunk) => {
+                    if chunk.width() != self.chunk_width || chunk.height() != self.chunk_height {
+                        return Err(StreamError::ChunkSize {
+                            key,
+                            width: chunk.width(),
+                            height: chunk.height(),
+                        });
+                    }
+                    chunk
+                }
+                None => Grid::new(self.chunk_width, self.chunk_height, self.default.clone()),
+            };
+            self.resident.insert(
+                key,
+                Resident {
+                    chunk,
+                    dirty: false,
+                    used: 0,
+                },
+            );
+        }
+        let resident = self.resident.get_mut(&key).unwrap();
+        resident.used = self.clock;
+        Ok(resident)
+    }
+
+    /// Evicts the least recently used chunk, saving it first if it was modified.
+    async fn evict_lru(&mut self) -> Result<(), StreamError<S::Error>> {
+        let key = match self.resident.iter().min_by_key(|(_, r)| r.used) {
+            Some((key, _)) => *key,
+            None => return Ok(()),
+        };
+        let resident = self.resident.remove(&key).unwrap();
+        if resident.dirty {
+            if let Err(e) = self.store.save_chunk(key, &resident.chunk).await {
+                self.resident.insert(key, resident);
+                return Err(StreamError::Store(e));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pollster::block_on;
+
+    use super::*;
+
+    /// A store that counts loads and saves, and fails to save when asked to.
+    #[derive(Default)]
+    struct CountingStore {
+        inner: MemoryStore<u8>,
+        loads: usize,
+        saves: usize,
+        fail_saves: bool,
+    }
+
+    impl ChunkStore<u8> for CountingStore {
+        type Error = &'static str;
+
+        async fn load_chunk(&mut self, key: ChunkKey) -> Result<Option<Grid<u8>>, Self::Error> {
+            self.loads += 1;
+            Ok(self.inner.get(key).cloned())
+        }
+
+        async fn save_chunk(&mut self, key: ChunkKey, chunk: &Grid<u8>) -> Result<(), Self::Error> {
+            if self.fail_saves {
+                return Err("save failed");
+            }
+            self.saves += 1;
+            self.inner.chunks.insert(key, chunk.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn chunk_store_futures_are_send() {
+        fn assert_send(_: impl Send) {}
+
+        fn check<S: ChunkStore<u8>>(store: &mut S, chunk: &Grid<u8>) {
+            assert_send(store.load_chunk((0, 0)));
+            assert_send(store.save_chunk((0, 0), chunk));
+        }
+
+        check(&mut MemoryStore::new(), &Grid::new(1, 1, 0));
+        check(&mut CountingStore::default(), &Grid::new(1, 1, 0));
+    }
+
+    #[test]
+    fn streaming_grid_locate_negative() {
+        let grid = StreamingGrid::new(MemoryStore::<u8>::new(), 4, 2, 0, 1);
+
+        assert_eq!(grid.locate(0, 0), ((0, 0), (0, 0)));
+        assert_eq!(grid.locate(-1, -1), ((-1, -1), (3, 1)));
+        assert_eq!(grid.locate(-4, -3), ((-1, -2), (0, 1)));
+    }
+
+    #[test]
+    fn streaming_grid_evicts_least_recently_used() {
+        block_on(async {
+            let mut grid = StreamingGrid::new(CountingStore::default(), 2, 2, 0, 2);
+
+            grid.set(0, 0, 1).await.unwrap();
+            grid.set(2, 0, 2).await.unwrap();
+            grid.get(0, 0).await.unwrap();
+            grid.get(4, 0).await.unwrap();
+
+            assert!(grid.is_resident((0, 0)));
+            assert!(!grid.is_resident((1, 0)));
+            assert!(grid.is_resident((2, 0)));
+            assert_eq!(grid.store().saves, 1);
+            assert_eq!(grid.store().inner.get((1, 0)).unwrap()[(0, 0)], 2);
+        });
+    }
+
+    #[test]
+    fn streaming_grid_does_not_save_clean_chunks() {
+        block_on(async {
+            let mut grid = StreamingGrid::new(CountingStore::default(), 2, 2, 0, 1);
+
+            grid.get(0, 0).await.unwrap();
+            grid.get(10, 10).await.unwrap();
+            grid.flush().await.unwrap();
+
+            assert_eq!(grid.store().loads, 2);
+            assert_eq!(grid.store().saves, 0);
+        });
+    }
+
+    #[test]
+    fn streaming_grid_keeps_chunk_when_save_fails() {
+        block_on(async {
+            let mut grid = StreamingGrid::new(CountingStore::default(), 2, 2, 0, 1);
+
+            grid.set(0, 0, 7).await.unwrap();
+            grid.store.fail_saves = true;
+
+            assert_eq!(
+                grid.get(10, 10).await,
+                Err(StreamError::Store("save failed"))
+            );
+            assert_eq!(grid.get_resident(0, 0), Some(&7));
+        });
+    }
+
+    #[test]
+    fn streaming_grid_dirty_chunks() {
+        block_on(async {
+            let mut grid = StreamingGrid::new(CountingStore::default(), 2, 2, 0, 2);
+
+            grid.set(0, 0, 1).await.unwrap();
+            grid.get(2, 0).await.unwrap();
+            assert_eq!(grid.dirty_chunks().collect::<Vec<_>>(), vec![(0, 0)]);
+
+            grid.flush().await.unwrap();
+            assert_eq!(grid.dirty_chunks().count(), 0);
+        });
+    }
+
+    #[test]
+    fn streaming_grid_rejects_wrong_size_chunk() {
+        block_on(async {
+            let mut store = CountingStore::default();
+            store.inner.chunks.insert((0, 0), Grid::new(3, 2, 0));
+            let mut grid = StreamingGrid::new(store, 2, 2, 0, 1);
+
+            assert_eq!(
+                grid.get(0, 0).await,
+                Err(StreamError::ChunkSize {
+                    key: (0, 0),
+                    width: 3,
+                    height: 2
+                })
+            );
+            assert!(!grid.is_resident((0, 0)));
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn streaming_grid_zero_budget() {
+        StreamingGrid::new(MemoryStore::<u8>::new(), 2, 2, 0, 0);
+    }
+
+    #[test]
+    fn chunked_grid_negative_coordinates() {
+        let mut world = ChunkedGrid::new(4, 4, 0);
+        for i in -10..10 {
+            world.set(i, -i, i);
+        }
+
+        for i in -10..10 {
+            assert_eq!(world.get(i, -i), &i);
+        }
+        assert_eq!(world.get(-10, -10), &0);
+        let (grid, origin) = world.to_grid().unwrap();
+        assert_eq!(origin, (-12, -12));
+        assert_eq!(grid[(2, 22)], -10);
+    }
+
+    #[test]
+    fn chunked_grid_bounds_far_away() {
+        let mut world = ChunkedGrid::new(16, 16, 0);
+        world.set(i64::MAX, i64::MIN, 1);
+
+        assert_eq!(
+            world.bounds(),
+            Some(((i64::MAX - 15, i64::MIN), (i64::MAX, i64::MIN + 16)))
+        );
+        let (grid, origin) = world.to_grid().unwrap();
+        assert_eq!(origin, (i64::MAX - 15, i64::MIN));
+        assert_eq!(grid[(15, 0)], 1);
+    }
+
+    #[test]
+    fn chunked_grid_dirty_chunks() {
+        let mut world = ChunkedGrid::new(4, 4, 0);
+        world.set(0, 0, 1);
+        world.set(-1, 0, 1);
+        world.clear_dirty();
+        let stamp = world.stamp();
+
+        assert_eq!(world.get(0, 0), &1);
+        assert_eq!(world.dirty_chunks().count(), 0);
+
+        world.set(1, 1, 2);
+        world.remove_chunk((-1, 0));
+        world.remove_chunk((5, 5));
+        let mut dirty: Vec<_> = world.dirty_chunks().collect();
+        dirty.sort();
+        assert_eq!(dirty, vec![(-1, 0), (0, 0)]);
+        assert_eq!(world.modified_since(stamp).count(), 2);
+        assert!(world.chunk_stamp((0, 0)) < world.chunk_stamp((-1, 0)));
+
+        world.clear_dirty();
+        assert_eq!(world.dirty_chunks().count(), 0);
+        assert_eq!(world.chunk_stamp((-1, 0)), None);
+        assert!(world.chunk_stamp((0, 0)).is_some());
+    }
+}
@@ -0,0 +1,559 @@
+//! A registry of grid operations invoked by name with string arguments, i.e. for command-line
+//! tools.
+//!
+//! See [`Operations`] for details, and [`Format`] for reading, writing, and converting grids in
+//! formats chosen by name.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+    io::{self, Read, Write},
+    str::FromStr,
+};
+
+use crate::{
+    delimited::{Delimiter, ReadDelimitedError},
+    grid::Grid,
+    point::Rect,
+};
+
+/// An operation that transforms a grid given its arguments, or returns a message describing why
+/// the arguments are invalid.
+type Operation<T> = dyn Fn(Grid<T>, &[&str]) -> Result<Grid<T>, String>;
+
+/// A set of named operations on grids, each taking string arguments.
+///
+/// This provides a shared dispatch layer for binaries that transform grids, i.e. where the
+/// operation and its arguments come from the command line. Grids can be read and written with a
+/// [`Format`].
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, cli::Operations};
+///
+/// let ops = Operations::<i32>::standard();
+/// let grid = Grid::with_width(3, vec![1, 2, 3, 4, 5, 6]);
+///
+/// let grid = ops.run("rotate", grid, &["90"]).unwrap();
+/// let grid = ops.run("threshold", grid, &["3", "0", "1"]).unwrap();
+/// assert_eq!(grid.to_matrix(), vec![vec![1, 0], vec![1, 0], vec![1, 1]]);
+///
+/// let error = ops.run("rotate", grid, &["45"]).unwrap_err();
+/// assert_eq!(error.to_string(), "Invalid arguments to rotate: Expected 90, 180, or 270 (usage: rotate <90|180|270>)");
+/// ```
+pub struct Operations<T>
+where
+    T: Clone,
+{
+    operations: BTreeMap<String, (String, Box<Operation<T>>)>,
+}
+
+impl<T> Operations<T>
+where
+    T: Clone,
+{
+    /// Creates an empty set of operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::cli::Operations;
+    ///
+    /// let ops = Operations::<i32>::new();
+    /// assert_eq!(ops.iter().count(), 0);
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            operations: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a set of the standard operations:
+    ///
+    /// - `crop <x> <y> <width> <height>`: the rectangle must not be empty.
+    /// - `flip <horizontal|vertical>`
+    /// - `rotate <90|180|270>` (clockwise)
+    /// - `threshold <value> <below> <above>`: replaces cells less than `value` with `below`, and
+    ///   all other cells with `above`.
+    /// - `transpose`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::cli::Operations;
+    ///
+    /// let ops = Operations::<u8>::standard();
+    /// let names: Vec<_> = ops.iter().map(|(name, _)| name).collect();
+    ///
+    /// assert_eq!(names, vec!["crop", "flip", "rotate", "threshold", "transpose"]);
+    /// ```
+    pub fn standard() -> Self
+    where
+        T: FromStr + PartialOrd + 'static,
+    {
+        let mut ops = Self::new();
+        ops.register("crop", "crop <x> <y> <width> <height>", |grid, args| {
+            let [x, y, width, height] = parse_args::<usize, 4>(args)?;
+            let rect = Rect::new(x, y, width, height);
+            if rect.is_empty() {
+                return Err("Rectangle is empty".to_string());
+            }
+            let (grid_width, grid_height) = grid.size();
+            if !rect.is_within(grid_width, grid_height) {
+                return Err(format!(
+                    "Rectangle is out of bounds of {grid_width}x{grid_height} grid"
+                ));
+            }
+            Ok(grid.crop(rect))
+        });
+        ops.register(
+            "flip",
+            "flip <horizontal|vertical>",
+            |grid, args| match args {
+                ["horizontal"] => Ok(grid.flip_horizontal()),
+                ["vertical"] => Ok(grid.flip_vertical()),
+                _ => Err("Expected horizontal or vertical".to_string()),
+            },
+        );
+        ops.register("rotate", "rotate <90|180|270>", |grid, args| match args {
+            ["90"] => Ok(grid.rotate_clockwise()),
+            ["180"] => Ok(grid.rotate_180()),
+            ["270"] => Ok(grid.rotate_counter_clockwise()),
+            _ => Err("Expected 90, 180, or 270".to_string()),
+        });
+        ops.register(
+            "threshold",
+            "threshold <value> <below> <above>",
+            |grid, args| {
+                let [value, below, above] = parse_args::<T, 3>(args)?;
+                let (width, _) = grid.size();
+                let data: Vec<_> = grid
+                    .as_vec()
+                    .iter()
+                    .map(|cell| {
+                        if *cell < value {
+                            below.clone()
+                        } else {
+                            above.clone()
+                        }
+                    })
+                    .collect();
+                if data.is_empty() {
+                    return Ok(grid);
+                }
+                Ok(Grid::with_width(width, data))
+            },
+        );
+        ops.register("transpose", "transpose", |grid, args| {
+            parse_args::<usize, 0>(args)?;
+            Ok(grid.transpose())
+        });
+        ops
+    }
+
+    /// Adds an operation named `name`, replacing any operation with the same name.
+    ///
+    /// The operation is given the grid and its arguments, and returns the transformed grid or a
+    /// message describing why the arguments are invalid. `usage` describes the arguments, and is
+    /// included in errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, cli::Operations};
+    ///
+    /// let mut ops = Operations::new();
+    /// ops.register("add", "add <n>", |grid: Grid<i32>, args| {
+    ///     let n: i32 = args.first().and_then(|n| n.parse().ok()).ok_or("Expected a number")?;
+    ///     let data = grid.as_vec().iter().map(|cell| cell + n).collect();
+    ///     Ok(Grid::with_width(grid.width(), data))
+    /// });
+    ///
+    /// let grid = ops.run("add", Grid::new(2, 1, 1), &["2"]).unwrap();
+    /// assert_eq!(grid.as_vec(), &vec![3, 3]);
+    /// ```
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        usage: impl Into<String>,
+        operation: impl Fn(Grid<T>, &[&str]) -> Result<Grid<T>, String> + 'static,
+    ) -> &mut Self {
+        self.operations
+            .insert(name.into(), (usage.into(), Box::new(operation)));
+        self
+    }
+
+    /// Returns an iterator over the name and usage of every operation, in order of name.
+    ///
+    /// # Examples
+    ///
+    /// Printing help text:
+    ///
+    /// ```
+    /// use grud::cli::Operations;
+    ///
+    /// let ops = Operations::<u8>::standard();
+    /// for (name, usage) in ops.iter() {
+    ///     println!("{name:>10}: {usage}");
+    /// }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.operations
+            .iter()
+            .map(|(name, (usage, _))| (name.as_str(), usage.as_str()))
+    }
+
+    /// Runs the operation named `name` on `grid` with `args`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, cli::Operations};
+    ///
+    /// let ops = Operations::standard();
+    /// let grid = ops.run("flip", Grid::with_width(2, vec![1, 2]), &["horizontal"]).unwrap();
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![2, 1]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If there is no operation named `name`, or the operation rejects `args`.
+    pub fn run(&self, name: &str, grid: Grid<T>, args: &[&str]) -> Result<Grid<T>, OperationError> {
+        let (usage, operation) = self
+            .operations
+            .get(name)
+            .ok_or_else(|| OperationError::Unknown(name.to_string()))?;
+        operation(grid, args).map_err(|message| OperationError::InvalidArguments {
+            name: name.to_string(),
+            usage: usage.clone(),
+            message,
+        })
+    }
+}
+
+impl<T> Default for Operations<T>
+where
+    T: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for Operations<T>
+where
+    T: Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Parses exactly `N` arguments as `A`.
+fn parse_args<A, const N: usize>(args: &[&str]) -> Result<[A; N], String>
+where
+    A: FromStr,
+{
+    let args: [&str; N] = args
+        .try_into()
+        .map_err(|_| format!("Expected {N} arguments, found {}", args.len()))?;
+    let mut parsed = Vec::with_capacity(N);
+    for arg in args {
+        parsed.push(
+            arg.parse()
+                .map_err(|_| format!("Invalid argument {arg:?}"))?,
+        );
+    }
+    Ok(parsed.try_into().ok().unwrap())
+}
+
+/// Error returned when running an operation from [`Operations`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OperationError {
+    /// There is no operation with this name.
+    Unknown(String),
+
+    /// The operation rejected its arguments.
+    InvalidArguments {
+        /// The name of the operation.
+        name: String,
+
+        /// The usage of the operation.
+        usage: String,
+
+        /// A description of why the arguments are invalid.
+        message: String,
+    },
+}
+
+impl Display for OperationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown(name) => write!(f, "Unknown operation {name:?}"),
+            Self::InvalidArguments {
+                name,
+                usage,
+                message,
+            } => write!(f, "Invalid arguments to {name}: {message} (usage: {usage})"),
+        }
+    }
+}
+
+impl Error for OperationError {}
+
+/// A delimited text format that grids are read from and written to, chosen by name.
+///
+/// Each line is a row, and each cell is parsed with [`FromStr`] and written with [`Display`]; see
+/// [`Grid::read_delimited`] for details. Quoting is not supported.
+///
+/// # Examples
+///
+/// Converting between formats:
+///
+/// ```
+/// use grud::cli::Format;
+///
+/// let from: Format = "tsv".parse().unwrap();
+/// let to: Format = "csv".parse().unwrap();
+///
+/// let mut csv = Vec::new();
+/// Format::convert::<i32>(from, "1\t2\n3\t4\n".as_bytes(), to, &mut csv).unwrap();
+///
+/// assert_eq!(String::from_utf8(csv).unwrap(), "1,2\n3,4\n");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Comma separated, named `csv`.
+    Csv,
+
+    /// Tab separated, named `tsv`.
+    Tsv,
+
+    /// Whitespace separated when read, and space separated when written, named `txt`.
+    Txt,
+}
+
+impl Format {
+    /// Returns the name of the format, as parsed by [`FromStr`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::cli::Format;
+    ///
+    /// assert_eq!(Format::Tsv.name(), "tsv");
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+            Self::Txt => "txt",
+        }
+    }
+
+    /// Reads a grid in this format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, cli::Format};
+    ///
+    /// let grid: Grid<u8> = Format::Csv.read("1, 2\n3, 4\n".as_bytes()).unwrap();
+    /// assert_eq!(grid.as_vec(), &vec![1, 2, 3, 4]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, the lines do not all have the same number of cells, or a cell cannot be
+    /// parsed.
+    pub fn read<T>(self, reader: impl Read) -> Result<Grid<T>, ReadDelimitedError>
+    where
+        T: Clone + FromStr,
+        T::Err: Display,
+    {
+        Grid::read_delimited(reader, self.delimiter(), str::parse)
+    }
+
+    /// Writes `grid` in this format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, cli::Format};
+    ///
+    /// let mut text = Vec::new();
+    /// Format::Txt.write(&Grid::with_width(2, vec![1, 20, 3, 4]), &mut text).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(text).unwrap(), "1 20\n3 4\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write<T>(self, grid: &Grid<T>, mut writer: impl Write) -> io::Result<()>
+    where
+        T: Clone + Display,
+    {
+        let separator = match self {
+            Self::Csv => ",",
+            Self::Tsv => "\t",
+            Self::Txt => " ",
+        };
+        let mut text = String::new();
+        for row in grid.as_vec().chunks(grid.width().max(1)) {
+            for (i, cell) in row.iter().enumerate() {
+                if i > 0 {
+                    text.push_str(separator);
+                }
+                text.push_str(&cell.to_string());
+            }
+            text.push('\n');
+        }
+        writer.write_all(text.as_bytes())
+    }
+
+    /// Reads a grid in the `from` format and writes it in the `to` format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::cli::Format;
+    ///
+    /// let mut text = Vec::new();
+    /// Format::convert::<f64>(Format::Csv, "1.5,2\n".as_bytes(), Format::Txt, &mut text).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(text).unwrap(), "1.5 2\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If reading or writing fails, or the input is invalid; see [`Format::read`].
+    pub fn convert<T>(
+        from: Self,
+        reader: impl Read,
+        to: Self,
+        writer: impl Write,
+    ) -> Result<(), ReadDelimitedError>
+    where
+        T: Clone + Display + FromStr,
+        T::Err: Display,
+    {
+        let grid: Grid<T> = from.read(reader)?;
+        to.write(&grid, writer)?;
+        Ok(())
+    }
+
+    fn delimiter(self) -> Delimiter {
+        match self {
+            Self::Csv => Delimiter::Char(','),
+            Self::Tsv => Delimiter::Char('\t'),
+            Self::Txt => Delimiter::Whitespace,
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "tsv" => Ok(Self::Tsv),
+            "txt" => Ok(Self::Txt),
+            _ => Err(UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned when parsing a [`Format`] from an unknown name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownFormat(pub String);
+
+impl Display for UnknownFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown format {:?}, expected csv, tsv, or txt", self.0)
+    }
+}
+
+impl Error for UnknownFormat {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operations_unknown() {
+        let error = Operations::<i32>::standard()
+            .run("blur", Grid::new(1, 1, 0), &[])
+            .unwrap_err();
+
+        assert_eq!(error, OperationError::Unknown("blur".to_string()));
+    }
+
+    #[test]
+    fn operations_crop_out_of_bounds_is_an_error() {
+        let error = Operations::<i32>::standard()
+            .run("crop", Grid::new(2, 2, 0), &["1", "1", "2", "1"])
+            .unwrap_err();
+
+        assert!(matches!(error, OperationError::InvalidArguments { .. }));
+    }
+
+    #[test]
+    fn operations_crop_empty_is_an_error() {
+        let ops = Operations::<i32>::standard();
+
+        for args in [
+            ["0", "0", "0", "0"],
+            ["0", "0", "0", "1"],
+            ["0", "0", "2", "0"],
+        ] {
+            let error = ops.run("crop", Grid::new(2, 2, 0), &args).unwrap_err();
+            assert!(error.to_string().contains("Rectangle is empty"), "{error}");
+        }
+    }
+
+    #[test]
+    fn format_round_trip() {
+        let grid = Grid::with_width(3, vec![1.5, -2.0, 3.0, 4.0, 0.25, 6.0]);
+
+        for format in [Format::Csv, Format::Tsv, Format::Txt] {
+            let mut text = Vec::new();
+            format.write(&grid, &mut text).unwrap();
+
+            let read: Grid<f64> = format.read(text.as_slice()).unwrap();
+            assert_eq!(read.size(), grid.size(), "{format}");
+            assert_eq!(read.as_vec(), grid.as_vec(), "{format}");
+            assert_eq!(format.name().parse(), Ok(format));
+        }
+    }
+
+    #[test]
+    fn format_unknown() {
+        assert_eq!(
+            "json".parse::<Format>().unwrap_err().to_string(),
+            "Unknown format \"json\", expected csv, tsv, or txt"
+        );
+    }
+
+    #[test]
+    fn operations_wrong_argument_count() {
+        let error = Operations::<i32>::standard()
+            .run("transpose", Grid::new(2, 2, 0), &["1"])
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid arguments to transpose: Expected 0 arguments, found 1 (usage: transpose)"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Applying a kernel to every cell of a numeric grid, i.e. for blurs and neighbor-count passes.
+//!
+//! See [`convolve`] for details.
+
+use std::ops::{Add, Mul};
+
+use crate::grid::Grid;
+
+/// How cells outside of the grid are treated when a kernel overlaps an edge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Edge<T> {
+    /// Uses the nearest cell on the edge of the grid (`aaa|abc|ccc`).
+    Clamp,
+
+    /// Wraps around to the opposite edge of the grid (`abc|abc|abc`).
+    Wrap,
+
+    /// Reflects the grid about the edge cell, without repeating it (`cb|abc|ba`).
+    Mirror,
+
+    /// Uses a constant value (`xxx|abc|xxx`).
+    Constant(T),
+}
+
+impl<T> Edge<T>
+where
+    T: Copy,
+{
+    /// Returns the value at `(x, y)`, which may be outside of `grid`.
+    fn sample(&self, grid: &Grid<T>, x: isize, y: isize) -> T {
+        let resolve = |i: isize, n: usize| -> Option<usize> {
+            if (0..n as isize).contains(&i) {
+                return Some(i as usize);
+            }
+            let n = n as isize;
+            match self {
+                Self::Clamp => Some(i.clamp(0, n - 1) as usize),
+                Self::Wrap => Some(i.rem_euclid(n) as usize),
+                Self::Mirror if n == 1 => Some(0),
+                Self::Mirror => {
+                    let period = 2 * (n - 1);
+                    let i = i.rem_euclid(period);
+                    Some(if i < n { i } else { period - i } as usize)
+                }
+                Self::Constant(_) => None,
+            }
+        };
+        match (resolve(x, grid.width()), resolve(y, grid.height())) {
+            (Some(x), Some(y)) => grid[(x, y)],
+            _ => match self {
+                Self::Constant(value) => *value,
+                _ => unreachable!("only constant edges resolve outside of the grid"),
+            },
+        }
+    }
+}
+
+/// Applies `kernel` to every cell of `grid`, returning a new grid of the same size.
+///
+/// The kernel is centered on each cell (at `(width / 2, height / 2)` of the kernel), and each
+/// output cell is the sum of the overlapped cells multiplied by the corresponding kernel weights.
+/// As is the convention in image processing, the kernel is _not_ flipped, so asymmetric kernels
+/// are applied as written. Cells outside of the grid are resolved using `edge`.
+///
+/// # Examples
+///
+/// Counting live neighbors, i.e. for [Conway's Game of Life]:
+///
+/// [Conway's Game of Life]: https://en.wikipedia.org/wiki/Conway%27s_Game_of_Life
+///
+/// ```
+/// use grud::{Grid, convolve::{convolve, Edge}};
+///
+/// let grid = Grid::with_width(3, vec![
+///     0, 1, 0,
+///     0, 1, 0,
+///     0, 1, 0,
+/// ]);
+///
+/// let kernel = Grid::with_width(3, vec![
+///     1, 1, 1,
+///     1, 0, 1,
+///     1, 1, 1,
+/// ]);
+///
+/// let neighbors = convolve(&grid, &kernel, Edge::Constant(0));
+/// assert_eq!(neighbors.as_vec(), &vec![
+///     2, 1, 2,
+///     3, 2, 3,
+///     2, 1, 2,
+/// ]);
+/// ```
+///
+/// Smoothing a heightmap with a box blur:
+///
+/// ```
+/// use grud::{Grid, convolve::{convolve, Edge}};
+///
+/// let heights = Grid::with_width(3, vec![0.0, 9.0, 0.0]);
+/// let kernel = Grid::with_width(3, vec![1.0 / 3.0; 3]);
+///
+/// let smooth = convolve(&heights, &kernel, Edge::Clamp);
+/// assert_eq!(smooth.as_vec(), &vec![3.0, 3.0, 3.0]);
+/// ```
+///
+/// # Panics
+///
+/// If `kernel` is empty.
+pub fn convolve<T>(grid: &Grid<T>, kernel: &Grid<T>, edge: Edge<T>) -> Grid<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    assert!(!kernel.as_vec().is_empty(), "Kernel must not be empty");
+    if grid.as_vec().is_empty() {
+        return grid.clone();
+    }
+    let (kw, kh) = (kernel.width(), kernel.height());
+    let (ax, ay) = ((kw / 2) as isize, (kh / 2) as isize);
+    let mut data = Vec::with_capacity(grid.area());
+    for y in 0..grid.height() as isize {
+        for x in 0..grid.width() as isize {
+            let mut sum = T::default();
+            for j in 0..kh {
+                for i in 0..kw {
+                    let cell = edge.sample(grid, x + i as isize - ax, y + j as isize - ay);
+                    sum = sum + cell * kernel[(i, j)];
+                }
+            }
+            data.push(sum);
+        }
+    }
+    Grid::with_width(grid.width(), data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(data: Vec<i32>) -> Grid<i32> {
+        Grid::with_width(data.len(), data)
+    }
+
+    /// A kernel that shifts the grid to the right by `n` cells.
+    fn shift(n: usize) -> Grid<i32> {
+        let mut kernel = vec![0; 2 * n + 1];
+        kernel[0] = 1;
+        row(kernel)
+    }
+
+    #[test]
+    fn convolve_edge_clamp() {
+        let out = convolve(&row(vec![1, 2, 3]), &shift(2), Edge::Clamp);
+
+        assert_eq!(out.as_vec(), &vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn convolve_edge_wrap() {
+        let out = convolve(&row(vec![1, 2, 3]), &shift(1), Edge::Wrap);
+
+        assert_eq!(out.as_vec(), &vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn convolve_edge_mirror() {
+        let out = convolve(&row(vec![1, 2, 3]), &shift(2), Edge::Mirror);
+
+        assert_eq!(out.as_vec(), &vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn convolve_edge_mirror_single_cell() {
+        let out = convolve(&row(vec![7]), &shift(3), Edge::Mirror);
+
+        assert_eq!(out.as_vec(), &vec![7]);
+    }
+
+    #[test]
+    fn convolve_edge_constant() {
+        let out = convolve(&row(vec![1, 2, 3]), &shift(1), Edge::Constant(9));
+
+        assert_eq!(out.as_vec(), &vec![9, 1, 2]);
+    }
+
+    #[test]
+    fn convolve_even_kernel_anchor() {
+        let out = convolve(&row(vec![1, 2, 3]), &row(vec![1, 0]), Edge::Constant(0));
+
+        assert_eq!(out.as_vec(), &vec![0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn convolve_empty_kernel() {
+        let kernel: Grid<i32> = vec![].into();
+        convolve(&row(vec![1]), &kernel, Edge::Clamp);
+    }
+}
@@ -0,0 +1,287 @@
+//! Reading and writing grids as CSV, i.e. for round-tripping spreadsheets.
+//!
+//! Requires the `csv` feature.
+//!
+//! See [`Grid::from_csv_reader`] and [`Grid::write_csv`] for details.
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    io::{Read, Write},
+    str::FromStr,
+};
+
+use crate::grid::Grid;
+
+/// Error returned when reading or writing a grid as CSV.
+#[derive(Debug)]
+pub enum CsvError {
+    /// The underlying CSV reader or writer failed, i.e. due to I/O or invalid UTF-8.
+    Csv(::csv::Error),
+
+    /// A row has a different number of cells than the first row.
+    Ragged {
+        /// The row (starting at `0`).
+        row: usize,
+
+        /// The number of cells in the first row.
+        expected: usize,
+
+        /// The number of cells in this row.
+        found: usize,
+    },
+
+    /// A cell could not be parsed.
+    Parse {
+        /// The row (starting at `0`).
+        row: usize,
+
+        /// The column (starting at `0`).
+        column: usize,
+
+        /// A description of why the cell could not be parsed.
+        message: String,
+    },
+}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Csv(error) => write!(f, "{error}"),
+            Self::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(f, "Row {row} has {found} cells, expected {expected}"),
+            Self::Parse {
+                row,
+                column,
+                message,
+            } => write!(f, "Cell ({column}, {row}) could not be parsed: {message}"),
+        }
+    }
+}
+
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Csv(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<::csv::Error> for CsvError {
+    fn from(error: ::csv::Error) -> Self {
+        Self::Csv(error)
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Reads a grid from CSV (without a header row), parsing each cell with [`FromStr`].
+    ///
+    /// Requires the `csv` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid: Grid<i32> = Grid::from_csv_reader("1,2,3\n4,5,6\n".as_bytes()).unwrap();
+    /// assert_eq!(grid.to_matrix(), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the CSV is invalid, the rows are not all the same length, or a cell cannot be parsed.
+    pub fn from_csv_reader(reader: impl Read) -> Result<Self, CsvError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        Self::from_csv_reader_with(reader, str::parse)
+    }
+
+    /// Reads a grid from CSV (without a header row), parsing each cell with `parse`.
+    ///
+    /// Requires the `csv` feature.
+    ///
+    /// # Examples
+    ///
+    /// Treating empty cells as missing:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::from_csv_reader_with("1,,3\n".as_bytes(), |cell| {
+    ///     if cell.is_empty() { Ok(None) } else { cell.parse().map(Some) }
+    /// }).unwrap();
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![Some(1), None, Some(3)]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the CSV is invalid, the rows are not all the same length, or `parse` fails.
+    pub fn from_csv_reader_with<E>(
+        reader: impl Read,
+        mut parse: impl FnMut(&str) -> Result<T, E>,
+    ) -> Result<Self, CsvError>
+    where
+        E: Display,
+    {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(reader);
+        let mut data = Vec::new();
+        let mut width = None;
+        for (row, record) in reader.records().enumerate() {
+            let record = record?;
+            let expected = *width.get_or_insert(record.len());
+            if record.len() != expected {
+                return Err(CsvError::Ragged {
+                    row,
+                    expected,
+                    found: record.len(),
+                });
+            }
+            for (column, cell) in record.iter().enumerate() {
+                let cell = parse(cell).map_err(|error| CsvError::Parse {
+                    row,
+                    column,
+                    message: error.to_string(),
+                })?;
+                data.push(cell);
+            }
+        }
+        Ok(match width {
+            Some(width) => Self::with_width(width, data),
+            None => Self::from(Vec::new()),
+        })
+    }
+
+    /// Writes the grid as CSV (without a header row), formatting each cell with [`Display`].
+    ///
+    /// Requires the `csv` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1.5, 2.0, 3.0, 4.25]);
+    /// let mut csv = Vec::new();
+    /// grid.write_csv(&mut csv).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(csv).unwrap(), "1.5,2\n3,4.25\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_csv(&self, writer: impl Write) -> Result<(), CsvError>
+    where
+        T: Display,
+    {
+        self.write_csv_with(writer, |cell| cell.to_string())
+    }
+
+    /// Writes the grid as CSV (without a header row), formatting each cell with `format`.
+    ///
+    /// Requires the `csv` feature.
+    ///
+    /// # Examples
+    ///
+    /// Cells containing separators are quoted:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![(1, 2), (3, 4)]);
+    /// let mut csv = Vec::new();
+    /// grid.write_csv_with(&mut csv, |(a, b)| format!("{a},{b}")).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(csv).unwrap(), "\"1,2\",\"3,4\"\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_csv_with<D>(
+        &self,
+        writer: impl Write,
+        mut format: impl FnMut(&T) -> D,
+    ) -> Result<(), CsvError>
+    where
+        D: AsRef<[u8]>,
+    {
+        let mut writer = ::csv::WriterBuilder::new()
+            .has_headers(false)
+            .from_writer(writer);
+        if self.width() > 0 {
+            for row in self.as_vec().chunks(self.width()) {
+                writer.write_record(row.iter().map(&mut format))?;
+            }
+        }
+        writer.flush().map_err(::csv::Error::from)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_round_trip() {
+        let grid = Grid::with_width(3, vec!["a", "b,c", "\"d\"", "", " ", "f"]);
+        let mut csv = Vec::new();
+        grid.write_csv(&mut csv).unwrap();
+        let back: Grid<String> = Grid::from_csv_reader(csv.as_slice()).unwrap();
+
+        assert_eq!(back.to_matrix(), grid.to_matrix());
+    }
+
+    #[test]
+    fn csv_empty() {
+        let grid: Grid<i32> = Grid::from_csv_reader("".as_bytes()).unwrap();
+
+        assert!(grid.as_vec().is_empty());
+    }
+
+    #[test]
+    fn csv_ragged_rows() {
+        let error = Grid::<i32>::from_csv_reader("1,2\n3\n".as_bytes()).unwrap_err();
+
+        assert!(matches!(
+            error,
+            CsvError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            }
+        ));
+    }
+
+    #[test]
+    fn csv_parse_error() {
+        let error = Grid::<i32>::from_csv_reader("1,x\n".as_bytes()).unwrap_err();
+
+        assert!(matches!(
+            error,
+            CsvError::Parse {
+                row: 0,
+                column: 1,
+                ..
+            }
+        ));
+        assert_eq!(
+            error.to_string(),
+            "Cell (1, 0) could not be parsed: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Coalescing changed cells into rectangles, i.e. for redrawing or sending only what changed.
+//!
+//! See [`DamageTracker`] for details.
+
+use crate::point::{Point, Rect};
+
+/// Aggregates changed cells and rectangles into a small set of bounding rectangles.
+///
+/// Two rectangles are merged into their bounding rectangle when it covers at most `threshold`
+/// cells that neither of them covered. A threshold of `0` only merges rectangles that exactly tile
+/// their bounds, while larger thresholds trade redrawing unchanged cells for fewer rectangles.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{damage::DamageTracker, point::Rect};
+///
+/// let mut damage = DamageTracker::new(0);
+/// for x in 0..100 {
+///     damage.add((x, 3));
+/// }
+/// damage.add((50, 20));
+///
+/// assert_eq!(damage.take(), vec![Rect::new(0, 3, 100, 1), Rect::new(50, 20, 1, 1)]);
+/// assert!(damage.is_empty());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DamageTracker {
+    threshold: usize,
+    rects: Vec<Rect>,
+}
+
+impl DamageTracker {
+    /// Creates a tracker with no damage, merging rectangles that waste at most `threshold` cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{damage::DamageTracker, point::Rect};
+    ///
+    /// let mut damage = DamageTracker::new(2);
+    /// damage.add((0, 0));
+    /// damage.add((1, 1));
+    ///
+    /// assert_eq!(damage.rects(), &[Rect::new(0, 0, 2, 2)]);
+    /// ```
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            rects: Vec::new(),
+        }
+    }
+
+    /// Returns the maximum number of unchanged cells a merge may add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::damage::DamageTracker;
+    ///
+    /// assert_eq!(DamageTracker::new(16).threshold(), 16);
+    /// ```
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Marks the cell at `point` as changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{damage::DamageTracker, point::Rect};
+    ///
+    /// let mut damage = DamageTracker::new(0);
+    /// damage.add((2, 3));
+    ///
+    /// assert_eq!(damage.rects(), &[Rect::new(2, 3, 1, 1)]);
+    /// ```
+    pub fn add(&mut self, point: impl Point) {
+        self.add_rect(Rect::new(point.x(), point.y(), 1, 1));
+    }
+
+    /// Marks every cell within `rect` as changed; empty rectangles are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{damage::DamageTracker, point::Rect};
+    ///
+    /// let mut damage = DamageTracker::new(0);
+    /// damage.add_rect(Rect::new(0, 0, 2, 1));
+    /// damage.add_rect(Rect::new(0, 1, 2, 1));
+    ///
+    /// assert_eq!(damage.rects(), &[Rect::new(0, 0, 2, 2)]);
+    /// ```
+    pub fn add_rect(&mut self, rect: Rect) {
+        if rect.is_empty() {
+            return;
+        }
+        let mut merged = rect;
+        while let Some(i) = self
+            .rects
+            .iter()
+            .position(|other| waste(&merged, other) <= self.threshold)
+        {
+            merged = bounds(&merged, &self.rects.swap_remove(i));
+        }
+        self.rects.push(merged);
+    }
+
+    /// Returns the current set of damaged rectangles, which may overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::damage::DamageTracker;
+    ///
+    /// assert!(DamageTracker::new(0).rects().is_empty());
+    /// ```
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+
+    /// Returns whether no cells were marked as changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::damage::DamageTracker;
+    ///
+    /// let mut damage = DamageTracker::new(0);
+    /// damage.add((0, 0));
+    ///
+    /// assert!(!damage.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Returns the damaged rectangles, sorted in row-major order of their top-left corners, and
+    /// resets the tracker for the next frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{damage::DamageTracker, point::Rect};
+    ///
+    /// let mut damage = DamageTracker::new(0);
+    /// damage.add((5, 5));
+    /// damage.add((0, 0));
+    ///
+    /// assert_eq!(damage.take(), vec![Rect::new(0, 0, 1, 1), Rect::new(5, 5, 1, 1)]);
+    /// assert!(damage.take().is_empty());
+    /// ```
+    pub fn take(&mut self) -> Vec<Rect> {
+        let mut rects = std::mem::take(&mut self.rects);
+        rects.sort_by_key(|rect| (rect.y(), rect.x()));
+        rects
+    }
+}
+
+impl Extend<Rect> for DamageTracker {
+    /// Marks every cell within each rectangle as changed, as with [`DamageTracker::add_rect`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{damage::DamageTracker, point::Rect};
+    ///
+    /// let mut damage = DamageTracker::new(0);
+    /// damage.extend([Rect::new(0, 0, 1, 1), Rect::new(1, 0, 1, 1)]);
+    ///
+    /// assert_eq!(damage.rects(), &[Rect::new(0, 0, 2, 1)]);
+    /// ```
+    fn extend<I: IntoIterator<Item = Rect>>(&mut self, iter: I) {
+        for rect in iter {
+            self.add_rect(rect);
+        }
+    }
+}
+
+/// Returns the smallest rectangle containing both `a` and `b`.
+fn bounds(a: &Rect, b: &Rect) -> Rect {
+    let (left, top) = (a.x().min(b.x()), a.y().min(b.y()));
+    let right = (a.x() + a.width()).max(b.x() + b.width());
+    let bottom = (a.y() + a.height()).max(b.y() + b.height());
+    Rect::new(left, top, right - left, bottom - top)
+}
+
+/// Returns the number of cells in the bounds of `a` and `b` that neither covers.
+fn waste(a: &Rect, b: &Rect) -> usize {
+    let area = |rect: &Rect| rect.width() * rect.height();
+    let overlap = a.intersect(b).map_or(0, |rect| area(&rect));
+    (area(&bounds(a, b)) + overlap).saturating_sub(area(a) + area(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn damage_covers_every_point() {
+        let points: Vec<(usize, usize)> = (0..200).map(|i| ((i * 7) % 31, (i * 13) % 17)).collect();
+        for threshold in [0, 4, 64] {
+            let mut damage = DamageTracker::new(threshold);
+            for point in &points {
+                damage.add(*point);
+            }
+            let rects = damage.take();
+
+            assert!(points
+                .iter()
+                .all(|point| rects.iter().any(|rect| rect.contains(*point))));
+        }
+    }
+
+    #[test]
+    fn damage_threshold_coarsens() {
+        let mut fine = DamageTracker::new(0);
+        let mut coarse = DamageTracker::new(100);
+        for point in [(0, 0), (4, 0), (0, 4), (4, 4)] {
+            fine.add(point);
+            coarse.add(point);
+        }
+
+        assert_eq!(fine.rects().len(), 4);
+        assert_eq!(coarse.rects(), &[Rect::new(0, 0, 5, 5)]);
+    }
+}
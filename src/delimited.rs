@@ -0,0 +1,318 @@
+//! Reading grids from delimited text, i.e. whitespace, tab, or semicolon separated numeric data.
+//!
+//! Unlike [`csv`](crate::csv), quoting is not supported, but no dependencies are required. See
+//! [`Grid::read_delimited`] for details.
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    io::{self, Read},
+};
+
+use crate::grid::Grid;
+
+/// How cells are separated within each line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Delimiter {
+    /// Cells are separated by any amount of whitespace.
+    #[default]
+    Whitespace,
+
+    /// Cells are separated by exactly one of this character, and surrounding whitespace is
+    /// trimmed.
+    Char(char),
+}
+
+impl From<char> for Delimiter {
+    fn from(delimiter: char) -> Self {
+        Self::Char(delimiter)
+    }
+}
+
+/// Error returned when reading a grid from delimited text.
+#[derive(Debug)]
+pub enum ReadDelimitedError {
+    /// Reading failed, i.e. due to I/O or invalid UTF-8.
+    Io(io::Error),
+
+    /// A line has a different number of cells than the first line.
+    Ragged {
+        /// The line (starting at `1`).
+        line: usize,
+
+        /// The number of cells in the first line.
+        expected: usize,
+
+        /// The number of cells in this line.
+        found: usize,
+    },
+
+    /// A cell could not be parsed.
+    Parse {
+        /// The line (starting at `1`).
+        line: usize,
+
+        /// The column (starting at `1`, in [`char`]s) of the start of the cell.
+        column: usize,
+
+        /// A description of why the cell could not be parsed.
+        message: String,
+    },
+}
+
+impl Display for ReadDelimitedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(f, "Line {line} has {found} cells, expected {expected}"),
+            Self::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Cell at line {line}, column {column} could not be parsed: {message}"
+            ),
+        }
+    }
+}
+
+impl Error for ReadDelimitedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadDelimitedError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Reads a grid from delimited text, where each line is a row and each cell is `parse(cell)`.
+    ///
+    /// Blank lines are skipped, and both `\n` and `\r\n` line endings are supported. The whole
+    /// input is read before parsing, so cells can be allocated at once.
+    ///
+    /// # Examples
+    ///
+    /// Whitespace separated:
+    ///
+    /// ```
+    /// use grud::{Grid, delimited::Delimiter};
+    ///
+    /// let text = "1.5  2.0\n3.0\t4.25\n";
+    /// let grid = Grid::read_delimited(text.as_bytes(), Delimiter::Whitespace, str::parse::<f64>);
+    ///
+    /// assert_eq!(grid.unwrap().as_vec(), &vec![1.5, 2.0, 3.0, 4.25]);
+    /// ```
+    ///
+    /// Semicolon separated, with an invalid cell:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let text = "1; 2; 3\n4; x; 6\n";
+    /// let error = Grid::read_delimited(text.as_bytes(), ';', str::parse::<i32>).unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Cell at line 2, column 4 could not be parsed: invalid digit found in string",
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, the lines do not all have the same number of cells, or `parse` fails.
+    pub fn read_delimited<E>(
+        mut reader: impl Read,
+        delimiter: impl Into<Delimiter>,
+        parse: impl FnMut(&str) -> Result<T, E>,
+    ) -> Result<Self, ReadDelimitedError>
+    where
+        E: Display,
+    {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        parse_delimited(&text, delimiter.into(), parse)
+    }
+
+    /// Reads a grid of numbers from delimited text, where each line is a row.
+    ///
+    /// This is the same as [`Grid::read_delimited`], but parses cells with [`lexical_core`], which
+    /// is faster than [`str::parse`] (especially for floats). Like [`str::parse`], parsing does not
+    /// depend on the locale (i.e. `.` is always the decimal separator).
+    ///
+    /// Requires the `lexical` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, delimited::Delimiter};
+    ///
+    /// let text = "1.5 -2e3\n0.25 4\n";
+    /// let grid = Grid::<f64>::read_delimited_numbers(text.as_bytes(), Delimiter::Whitespace);
+    ///
+    /// assert_eq!(grid.unwrap().as_vec(), &vec![1.5, -2000.0, 0.25, 4.0]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, the lines do not all have the same number of cells, or a cell is not a
+    /// valid number.
+    #[cfg(feature = "lexical")]
+    pub fn read_delimited_numbers(
+        reader: impl Read,
+        delimiter: impl Into<Delimiter>,
+    ) -> Result<Self, ReadDelimitedError>
+    where
+        T: lexical_core::FromLexical,
+    {
+        Self::read_delimited(reader, delimiter, |cell| {
+            lexical_core::parse::<T>(cell.as_bytes())
+        })
+    }
+}
+
+/// Parses a grid from delimited `text`, where each cell is `parse(cell)`.
+fn parse_delimited<T, E>(
+    text: &str,
+    delimiter: Delimiter,
+    mut parse: impl FnMut(&str) -> Result<T, E>,
+) -> Result<Grid<T>, ReadDelimitedError>
+where
+    T: Clone,
+    E: Display,
+{
+    let lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    // Counting rows first (which is cheap compared to parsing) means cells are allocated once.
+    let rows = lines.clone().count();
+    let mut data = Vec::new();
+    let mut width = None;
+    let mut cells = Vec::new();
+    for (i, line) in lines {
+        cells.clear();
+        match delimiter {
+            Delimiter::Whitespace => cells.extend(line.split_whitespace()),
+            Delimiter::Char(c) => cells.extend(line.split(c).map(str::trim)),
+        }
+        let expected = *width.get_or_insert_with(|| {
+            // Every cell but the last in each row takes at least one byte, so this never
+            // reserves more than the text could hold, even if later rows are shorter.
+            data.reserve_exact((cells.len() * rows).min(text.len() + rows));
+            cells.len()
+        });
+        if cells.len() != expected {
+            return Err(ReadDelimitedError::Ragged {
+                line: i + 1,
+                expected,
+                found: cells.len(),
+            });
+        }
+        for cell in &cells {
+            let value = parse(cell).map_err(|error| ReadDelimitedError::Parse {
+                line: i + 1,
+                column: column(line, cell),
+                message: error.to_string(),
+            })?;
+            data.push(value);
+        }
+    }
+    Ok(match width {
+        Some(width) => Grid::with_width(width, data),
+        None => Grid::from(Vec::new()),
+    })
+}
+
+/// Returns the column (starting at `1`, in [`char`]s) of `cell`, which must be a slice of `line`.
+fn column(line: &str, cell: &str) -> usize {
+    let offset = cell.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_delimited_ragged() {
+        let error = Grid::read_delimited("1 2\n\n3\n".as_bytes(), Delimiter::Whitespace, |cell| {
+            cell.parse::<u8>()
+        })
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ReadDelimitedError::Ragged {
+                line: 3,
+                expected: 2,
+                found: 1
+            }
+        ));
+    }
+
+    #[test]
+    fn read_delimited_tabs_and_crlf() {
+        let grid = Grid::read_delimited("a\tb\r\nc\t\r\n".as_bytes(), '\t', |cell| {
+            Ok::<_, String>(cell.to_string())
+        })
+        .unwrap();
+
+        assert_eq!(grid.to_matrix(), vec![vec!["a", "b"], vec!["c", ""]]);
+    }
+
+    #[test]
+    fn read_delimited_column_counts_chars() {
+        let error = Grid::read_delimited("é, ü,x".as_bytes(), ',', |cell| match cell {
+            "x" => Err("bad"),
+            _ => Ok(()),
+        })
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ReadDelimitedError::Parse {
+                line: 1,
+                column: 6,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn read_delimited_empty() {
+        let grid = Grid::read_delimited("\n\n".as_bytes(), ' ', str::parse::<u8>).unwrap();
+
+        assert!(grid.as_vec().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "lexical")]
+    fn read_delimited_numbers_invalid() {
+        let error = Grid::<u8>::read_delimited_numbers("1,2\n3,300\n".as_bytes(), ',').unwrap_err();
+
+        assert!(matches!(
+            error,
+            ReadDelimitedError::Parse {
+                line: 2,
+                column: 3,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,250 @@
+//! Iterating the diagonals of grids, i.e. for word searches or connect-four style checks.
+//!
+//! See [`Grid::diagonals`] and [`Grid::anti_diagonals`] for details.
+
+use std::iter::FusedIterator;
+
+use crate::grid::Grid;
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns an iterator over every diagonal running down and to the right.
+    ///
+    /// Diagonals start with the bottom-left cell and end with the top-right cell; each diagonal is
+    /// an iterator over its cells from top-left to bottom-right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![
+    ///     1, 2, 3,
+    ///     4, 5, 6,
+    /// ]);
+    ///
+    /// let diagonals: Vec<Vec<_>> = grid.diagonals().map(|d| d.copied().collect()).collect();
+    /// assert_eq!(diagonals, vec![vec![4], vec![1, 5], vec![2, 6], vec![3]]);
+    /// ```
+    pub fn diagonals(&self) -> Diagonals<'_, T> {
+        Diagonals::new(self, false)
+    }
+
+    /// Returns an iterator over every anti-diagonal, running down and to the left.
+    ///
+    /// Anti-diagonals start with the top-left cell and end with the bottom-right cell; each
+    /// anti-diagonal is an iterator over its cells from top-right to bottom-left.
+    ///
+    /// # Examples
+    ///
+    /// Checking for four in a row:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(4, vec![
+    ///     '.', '.', '.', 'x',
+    ///     '.', '.', 'x', '.',
+    ///     '.', 'x', '.', '.',
+    ///     'x', '.', '.', '.',
+    /// ]);
+    ///
+    /// let won = grid.anti_diagonals().any(|d| {
+    ///     let cells: Vec<_> = d.collect();
+    ///     cells.windows(4).any(|w| w.iter().all(|c| **c == 'x'))
+    /// });
+    /// assert!(won);
+    /// ```
+    pub fn anti_diagonals(&self) -> Diagonals<'_, T> {
+        Diagonals::new(self, true)
+    }
+}
+
+/// An iterator over the diagonals or anti-diagonals of a grid.
+///
+/// This struct is created by [`Grid::diagonals`] and [`Grid::anti_diagonals`].
+#[derive(Debug)]
+pub struct Diagonals<'a, T>
+where
+    T: Clone,
+{
+    grid: &'a Grid<T>,
+    anti: bool,
+    next: usize,
+    end: usize,
+}
+
+impl<'a, T> Diagonals<'a, T>
+where
+    T: Clone,
+{
+    /// Creates an iterator over the diagonals of `grid`, or its anti-diagonals if `anti` is true.
+    fn new(grid: &'a Grid<T>, anti: bool) -> Self {
+        let (width, height) = grid.size();
+        Self {
+            grid,
+            anti,
+            next: 0,
+            end: (width + height).saturating_sub(1),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Diagonals<'a, T>
+where
+    T: Clone,
+{
+    type Item = Diagonal<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+        let k = self.next;
+        self.next += 1;
+        let (width, height) = self.grid.size();
+        let (x, y, remaining) = if self.anti {
+            let (x, y) = if k < width {
+                (k, 0)
+            } else {
+                (width - 1, k - (width - 1))
+            };
+            (x, y, (x + 1).min(height - y))
+        } else {
+            let (x, y) = if k < height {
+                (0, height - 1 - k)
+            } else {
+                (k - (height - 1), 0)
+            };
+            (x, y, (width - x).min(height - y))
+        };
+        Some(Diagonal {
+            grid: self.grid,
+            anti: self.anti,
+            x,
+            y,
+            remaining,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.next;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for Diagonals<'_, T> where T: Clone {}
+
+impl<T> FusedIterator for Diagonals<'_, T> where T: Clone {}
+
+/// An iterator over the cells of a single diagonal or anti-diagonal of a grid.
+///
+/// This struct is created by [`Diagonals`].
+#[derive(Debug)]
+pub struct Diagonal<'a, T>
+where
+    T: Clone,
+{
+    grid: &'a Grid<T>,
+    anti: bool,
+    x: usize,
+    y: usize,
+    remaining: usize,
+}
+
+impl<T> Diagonal<'_, T>
+where
+    T: Clone,
+{
+    /// Returns the point of the next cell, or `None` if there are no cells left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::new(3, 2, 0);
+    /// let mut diagonal = grid.anti_diagonals().nth(2).unwrap();
+    ///
+    /// assert_eq!(diagonal.point(), Some((2, 0)));
+    /// diagonal.next();
+    /// assert_eq!(diagonal.point(), Some((1, 1)));
+    /// diagonal.next();
+    /// assert_eq!(diagonal.point(), None);
+    /// ```
+    pub fn point(&self) -> Option<(usize, usize)> {
+        (self.remaining > 0).then_some((self.x, self.y))
+    }
+}
+
+impl<'a, T> Iterator for Diagonal<'a, T>
+where
+    T: Clone,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let cell = &self.grid[(self.x, self.y)];
+        if self.remaining > 0 {
+            self.x = if self.anti { self.x - 1 } else { self.x + 1 };
+            self.y += 1;
+        }
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Diagonal<'_, T> where T: Clone {}
+
+impl<T> FusedIterator for Diagonal<'_, T> where T: Clone {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(diagonals: Diagonals<'_, i32>) -> Vec<Vec<i32>> {
+        diagonals.map(|d| d.copied().collect()).collect()
+    }
+
+    #[test]
+    fn diagonals_tall_grid() {
+        let grid = Grid::with_width(2, (0..6).collect::<Vec<_>>());
+
+        assert_eq!(
+            collect(grid.diagonals()),
+            vec![vec![4], vec![2, 5], vec![0, 3], vec![1]]
+        );
+        assert_eq!(
+            collect(grid.anti_diagonals()),
+            vec![vec![0], vec![1, 2], vec![3, 4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn diagonals_cover_every_cell() {
+        let grid = Grid::with_width(4, (0..12).collect::<Vec<_>>());
+
+        for diagonals in [grid.diagonals(), grid.anti_diagonals()] {
+            let mut cells: Vec<_> = collect(diagonals).concat();
+            cells.sort();
+            assert_eq!(cells, (0..12).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn diagonals_empty_grid() {
+        let grid: Grid<i32> = Grid::from(Vec::new());
+
+        assert_eq!(grid.diagonals().count(), 0);
+        assert_eq!(grid.anti_diagonals().count(), 0);
+    }
+}
@@ -0,0 +1,649 @@
+//! Configurable text rendering of grids, i.e. as tables with separators and padding.
+//!
+//! See [`Grid::display`] for details.
+
+use std::{
+    fmt::{self, Debug, Display, Formatter, Result},
+    io,
+};
+
+use crate::grid::Grid;
+
+/// How cells narrower than their column are aligned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Align {
+    /// Pads cells on the right.
+    #[default]
+    Left,
+
+    /// Pads cells on the left.
+    Right,
+
+    /// Pads cells on both sides, with any odd remainder on the right.
+    Center,
+}
+
+/// A configurable [`Display`] adapter for a [`Grid`].
+///
+/// Every column is padded to the width of its widest cell, so multi-character cells line up. With
+/// no options set, the output is the same as [`Grid`]'s own [`Display`] for grids where every cell
+/// is the same width.
+///
+/// By default, width is measured in [`char`]s. With the `unicode-width` feature, width is instead
+/// measured in terminal columns, so cells containing wide characters (i.e. emoji or CJK) line up.
+/// See [`GridFormatter::measure`] to choose explicitly.
+///
+/// This struct is created by [`Grid::display`] and [`Grid::display_with`].
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, display::Align};
+///
+/// let grid = Grid::with_width(2, vec![1, 20, 300, 4]);
+/// let table = grid
+///     .display()
+///     .column_separator(" | ")
+///     .row_separator('-')
+///     .align(Align::Right)
+///     .border(true)
+///     .to_string();
+///
+/// assert_eq!(table, concat!(
+///     "+--------+\n",
+///     "|  1 | 20|\n",
+///     "|--------|\n",
+///     "|300 |  4|\n",
+///     "+--------+\n",
+/// ));
+/// ```
+pub struct GridFormatter<'a, T>
+where
+    T: Clone,
+{
+    grid: &'a Grid<T>,
+    format: Box<dyn Fn(&T) -> String + 'a>,
+    column_separator: &'a str,
+    row_separator: Option<char>,
+    padding: usize,
+    align: Align,
+    border: bool,
+    measure: fn(&str) -> usize,
+    line_ending: &'a str,
+    trailing_line_ending: bool,
+    trailing_separator: bool,
+}
+
+impl<'a, T> GridFormatter<'a, T>
+where
+    T: Clone,
+{
+    /// Sets the string written between cells in a row.
+    ///
+    /// Defaults to no separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// assert_eq!(grid.display().column_separator(",").to_string(), "1,2\n3,4\n");
+    /// ```
+    pub fn column_separator(mut self, separator: &'a str) -> Self {
+        self.column_separator = separator;
+        self
+    }
+
+    /// Sets a character that is repeated to form a line between rows.
+    ///
+    /// Defaults to no separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// assert_eq!(grid.display().row_separator('=').to_string(), "12\n==\n34\n");
+    /// ```
+    pub fn row_separator(mut self, separator: char) -> Self {
+        self.row_separator = Some(separator);
+        self
+    }
+
+    /// Sets the number of spaces written on each side of every cell.
+    ///
+    /// Defaults to `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// assert_eq!(grid.display().padding(1).to_string(), " 1  2 \n 3  4 \n");
+    /// ```
+    pub fn padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets how cells narrower than their column are aligned.
+    ///
+    /// Defaults to [`Align::Left`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, display::Align};
+    ///
+    /// let grid = Grid::with_width(1, vec![1, 100]);
+    /// assert_eq!(grid.display().align(Align::Center).to_string(), " 1 \n100\n");
+    /// ```
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets whether an ASCII border (`+`, `-`, and `|`) is drawn around the grid.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// assert_eq!(grid.display().border(true).to_string(), "+--+\n|12|\n|34|\n+--+\n");
+    /// ```
+    pub fn border(mut self, border: bool) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Sets how the width of each cell (and the column separator) is measured when padding.
+    ///
+    /// Defaults to `terminal_width` with the `unicode-width` feature, and [`char_count`]
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// Measuring in bytes, i.e. for output that is only ever ASCII:
+    ///
+    /// ```
+    /// use grud::{Grid, display::Align};
+    ///
+    /// let grid = Grid::with_width(1, vec!["é", "ab"]);
+    /// let out = grid.display().align(Align::Right).measure(str::len);
+    ///
+    /// assert_eq!(out.to_string(), "é\nab\n");
+    /// ```
+    pub fn measure(mut self, measure: fn(&str) -> usize) -> Self {
+        self.measure = measure;
+        self
+    }
+
+    /// Sets the string written at the end of every line.
+    ///
+    /// Defaults to `"\n"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// assert_eq!(grid.display().line_ending("\r\n").to_string(), "12\r\n34\r\n");
+    /// ```
+    pub fn line_ending(mut self, line_ending: &'a str) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Sets whether the last line ends with a line ending.
+    ///
+    /// Defaults to `true`; setting `false` is useful when embedding the grid in other output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// let out = grid.display().trailing_line_ending(false);
+    ///
+    /// assert_eq!(format!("[{out}]"), "[12\n34]");
+    /// ```
+    pub fn trailing_line_ending(mut self, trailing: bool) -> Self {
+        self.trailing_line_ending = trailing;
+        self
+    }
+
+    /// Sets whether the column separator is also written after the last cell in each row.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// let out = grid.display().column_separator(";").trailing_separator(true);
+    ///
+    /// assert_eq!(out.to_string(), "1;2;\n3;4;\n");
+    /// ```
+    pub fn trailing_separator(mut self, trailing: bool) -> Self {
+        self.trailing_separator = trailing;
+        self
+    }
+
+    /// Writes the formatted grid to `writer`, without building an intermediate [`String`].
+    ///
+    /// This is the same as `write!(writer, "{formatter}")`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut out = String::from("Grid:\n");
+    /// Grid::with_width(2, vec![1, 2]).display().column_separator(" ").write_to(&mut out).unwrap();
+    ///
+    /// assert_eq!(out, "Grid:\n1 2\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_to(&self, writer: &mut impl fmt::Write) -> Result {
+        write!(writer, "{self}")
+    }
+
+    /// Writes the formatted grid to `writer` as UTF-8, without building an intermediate
+    /// [`String`].
+    ///
+    /// Output is written one cell at a time, so slow writers (i.e. files or sockets) should be
+    /// wrapped in a [`BufWriter`](std::io::BufWriter).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut out = Vec::new();
+    /// Grid::with_width(2, vec![1, 2]).display().column_separator(" ").write_io(&mut out).unwrap();
+    ///
+    /// assert_eq!(out, b"1 2\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_io(&self, mut writer: impl io::Write) -> io::Result<()> {
+        write!(writer, "{self}")?;
+        writer.flush()
+    }
+
+    /// Writes a line ending, unless this is the `first` line.
+    fn start_line(&self, f: &mut Formatter<'_>, first: &mut bool) -> Result {
+        if !std::mem::take(first) {
+            write!(f, "{}", self.line_ending)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a full-width line of `fill`, within the border (if any).
+    fn write_line(&self, f: &mut Formatter<'_>, edge: char, fill: char, width: usize) -> Result {
+        if self.border {
+            write!(f, "{edge}")?;
+        }
+        for _ in 0..width {
+            write!(f, "{fill}")?;
+        }
+        if self.border {
+            write!(f, "{edge}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Debug for GridFormatter<'_, T>
+where
+    T: Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("GridFormatter")
+            .field("column_separator", &self.column_separator)
+            .field("row_separator", &self.row_separator)
+            .field("padding", &self.padding)
+            .field("align", &self.align)
+            .field("border", &self.border)
+            .field("line_ending", &self.line_ending)
+            .field("trailing_line_ending", &self.trailing_line_ending)
+            .field("trailing_separator", &self.trailing_separator)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Display for GridFormatter<'_, T>
+where
+    T: Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let width = self.grid.width();
+        if width == 0 {
+            return Ok(());
+        }
+        // Cells are formatted twice (once to measure, once to write) rather than collected, so
+        // rendering very large grids does not hold every formatted cell in memory at once.
+        let mut widths = vec![0; width];
+        for (i, cell) in self.grid.as_vec().iter().enumerate() {
+            let measured = (self.measure)(&(self.format)(cell));
+            widths[i % width] = widths[i % width].max(measured);
+        }
+        let separators = width - 1 + usize::from(self.trailing_separator);
+        let inner = widths.iter().map(|w| w + 2 * self.padding).sum::<usize>()
+            + (self.measure)(self.column_separator) * separators;
+
+        let mut first = true;
+        if self.border {
+            self.start_line(f, &mut first)?;
+            self.write_line(f, '+', '-', inner)?;
+        }
+        for (j, row) in self.grid.as_vec().chunks(width).enumerate() {
+            if let (true, Some(separator)) = (j > 0, self.row_separator) {
+                self.start_line(f, &mut first)?;
+                self.write_line(f, '|', separator, inner)?;
+            }
+            self.start_line(f, &mut first)?;
+            if self.border {
+                write!(f, "|")?;
+            }
+            for (i, cell) in row.iter().enumerate() {
+                if i > 0 {
+                    write!(f, "{}", self.column_separator)?;
+                }
+                let cell = (self.format)(cell);
+                let fill = widths[i] - (self.measure)(&cell);
+                let (before, after) = match self.align {
+                    Align::Left => (0, fill),
+                    Align::Right => (fill, 0),
+                    Align::Center => (fill / 2, fill - fill / 2),
+                };
+                let (before, after) = (before + self.padding, after + self.padding);
+                write!(f, "{:before$}{cell}{:after$}", "", "")?;
+            }
+            if self.trailing_separator {
+                write!(f, "{}", self.column_separator)?;
+            }
+            if self.border {
+                write!(f, "|")?;
+            }
+        }
+        if self.border {
+            self.start_line(f, &mut first)?;
+            self.write_line(f, '+', '-', inner)?;
+        }
+        if self.trailing_line_ending {
+            write!(f, "{}", self.line_ending)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the width of `text` when displayed in a terminal, in columns.
+///
+/// Wide characters (i.e. emoji or CJK) are two columns, and zero-width characters (i.e. combining
+/// accents) are zero columns.
+///
+/// Requires the `unicode-width` feature.
+///
+/// # Examples
+///
+/// ```
+/// use grud::display::terminal_width;
+///
+/// assert_eq!(terminal_width("ab"), 2);
+/// assert_eq!(terminal_width("猫"), 2);
+/// assert_eq!(terminal_width("e\u{301}"), 1);
+/// ```
+#[cfg(feature = "unicode-width")]
+pub fn terminal_width(text: &str) -> usize {
+    unicode_width::UnicodeWidthStr::width(text)
+}
+
+/// Returns the width of `text` when displayed, assuming every [`char`] is one column wide.
+///
+/// # Examples
+///
+/// ```
+/// use grud::display::char_count;
+///
+/// assert_eq!(char_count("ab"), 2);
+/// assert_eq!(char_count("é"), 1);
+/// ```
+pub fn char_count(text: &str) -> usize {
+    text.chars().count()
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns a configurable [`Display`] adapter, i.e. for rendering the grid as a table.
+    ///
+    /// See [`GridFormatter`] for options, and [`Grid::display_with`] to format cells differently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 10, 100, 1000, 2, 3]);
+    ///
+    /// assert_eq!(grid.to_string(), "110100\n100023\n");
+    /// assert_eq!(
+    ///     grid.display().column_separator(" ").to_string(),
+    ///     "1    10 100\n1000 2  3  \n",
+    /// );
+    /// ```
+    pub fn display(&self) -> GridFormatter<'_, T>
+    where
+        T: Display,
+    {
+        self.display_with(|cell| cell.to_string())
+    }
+
+    /// Returns a configurable [`Display`] adapter, where each cell is displayed as `format(cell)`.
+    ///
+    /// Unlike mapping into a new grid first, cells are formatted as they are written, so the grid
+    /// is not copied. See [`GridFormatter`] for options.
+    ///
+    /// # Examples
+    ///
+    /// Rendering tiles as map characters:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// #[derive(Clone)]
+    /// enum Tile {
+    ///     Floor,
+    ///     Wall,
+    /// }
+    ///
+    /// let grid = Grid::with_width(2, vec![Tile::Wall, Tile::Floor, Tile::Floor, Tile::Wall]);
+    /// let map = grid.display_with(|tile| match tile {
+    ///     Tile::Floor => '.',
+    ///     Tile::Wall => '#',
+    /// });
+    ///
+    /// assert_eq!(map.to_string(), "#.\n.#\n");
+    /// ```
+    pub fn display_with<'a, D>(&'a self, format: impl Fn(&T) -> D + 'a) -> GridFormatter<'a, T>
+    where
+        D: Display,
+    {
+        GridFormatter {
+            grid: self,
+            format: Box::new(move |cell| format(cell).to_string()),
+            column_separator: "",
+            row_separator: None,
+            padding: 0,
+            align: Align::default(),
+            border: false,
+            #[cfg(feature = "unicode-width")]
+            measure: terminal_width,
+            #[cfg(not(feature = "unicode-width"))]
+            measure: char_count,
+            line_ending: "\n",
+            trailing_line_ending: true,
+            trailing_separator: false,
+        }
+    }
+
+    /// Writes the grid to `writer` as it is [displayed](Display), without building an intermediate
+    /// [`String`].
+    ///
+    /// This is the same as `write!(writer, "{grid}")`, and is useful for appending very large
+    /// grids to an existing buffer. See [`GridFormatter::write_to`] for configurable output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut out = String::from("Generation 1:\n");
+    /// Grid::with_width(2, vec!['#', '.']).write_display(&mut out).unwrap();
+    ///
+    /// assert_eq!(out, "Generation 1:\n#.\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_display(&self, writer: &mut impl fmt::Write) -> Result
+    where
+        T: Display,
+    {
+        write!(writer, "{self}")
+    }
+
+    /// Writes the grid to `writer` as it is [displayed](Display) in UTF-8, without building an
+    /// intermediate [`String`].
+    ///
+    /// Output is written one cell at a time, so slow writers (i.e. files or sockets) should be
+    /// wrapped in a [`BufWriter`](std::io::BufWriter). See [`GridFormatter::write_io`] for
+    /// configurable output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::BufWriter;
+    ///
+    /// use grud::Grid;
+    ///
+    /// let mut out = Vec::new();
+    /// Grid::with_width(2, vec![1, 2, 3, 4]).write_display_io(BufWriter::new(&mut out)).unwrap();
+    ///
+    /// assert_eq!(out, b"12\n34\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_display_io(&self, mut writer: impl io::Write) -> io::Result<()>
+    where
+        T: Display,
+    {
+        write!(writer, "{self}")?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_default_matches_grid_display() {
+        let grid = Grid::with_width(3, vec!['a', 'b', 'c', 'd', 'e', 'f']);
+
+        assert_eq!(grid.display().to_string(), grid.to_string());
+    }
+
+    #[test]
+    fn display_with_aligns_formatted_cells() {
+        let grid = Grid::with_width(2, vec![1, 22, 333, 4]);
+        let out = grid.display_with(|n| n * 2).column_separator(" ");
+
+        assert_eq!(out.to_string(), "2   44\n666 8 \n");
+    }
+
+    #[test]
+    fn display_empty_grid() {
+        let grid: Grid<i32> = vec![].into();
+
+        assert_eq!(grid.display().border(true).to_string(), "");
+    }
+
+    #[test]
+    #[cfg(not(feature = "unicode-width"))]
+    fn display_measures_chars_not_bytes() {
+        let grid = Grid::with_width(1, vec!["é", "ab"]);
+
+        assert_eq!(grid.display().align(Align::Right).to_string(), " é\nab\n");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-width")]
+    fn display_measures_unicode_width() {
+        let grid = Grid::with_width(2, vec!["猫", "a", "b", "🦀"]);
+
+        assert_eq!(
+            grid.display().column_separator("|").to_string(),
+            "猫|a \nb |🦀\n"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-width")]
+    fn display_measure_overrides_unicode_width() {
+        let grid = Grid::with_width(1, vec!["猫", "ab"]);
+
+        assert_eq!(grid.display().measure(char_count).to_string(), "猫 \nab\n");
+    }
+
+    #[test]
+    fn display_border_without_trailing_line_ending() {
+        let grid = Grid::with_width(1, vec![1]);
+        let out = grid
+            .display()
+            .border(true)
+            .line_ending("\r\n")
+            .trailing_line_ending(false);
+
+        assert_eq!(out.to_string(), "+-+\r\n|1|\r\n+-+");
+    }
+
+    #[test]
+    fn display_row_separator_with_padding_and_border() {
+        let grid = Grid::with_width(1, vec![1, 2]);
+        let out = grid.display().padding(1).row_separator('-').border(true);
+
+        assert_eq!(out.to_string(), "+---+\n| 1 |\n|---|\n| 2 |\n+---+\n");
+    }
+
+    #[test]
+    fn display_write_io_matches_to_string() {
+        let grid = Grid::with_width(2, vec!["a", "bb", "猫", "d"]);
+        let formatter = grid.display().border(true).row_separator('-');
+        let mut out = Vec::new();
+        formatter.write_io(&mut out).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), formatter.to_string());
+    }
+}
@@ -0,0 +1,263 @@
+//! Dithering grids of brightness to `Grid<bool>`, i.e. for 1-bit displays or Braille rendering.
+//!
+//! Brightness is in the range `0.0` (black) to `1.0` (white), and cells of the dithered grid are
+//! `true` where white. See [`Grid::dither_floyd_steinberg_with`] and [`Grid::dither_ordered_with`]
+//! for details.
+
+use crate::grid::Grid;
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns a grid where each cell is `true` (white) or `false` (black), approximating
+    /// `brightness(cell)` using [Floyd–Steinberg] error diffusion.
+    ///
+    /// Error diffusion preserves detail and the average brightness of each area, but the pattern
+    /// changes wherever the grid changes, so it can flicker when animated; see
+    /// [`Grid::dither_ordered_with`] for a stable pattern.
+    ///
+    /// [Floyd–Steinberg]: https://en.wikipedia.org/wiki/Floyd%E2%80%93Steinberg_dithering
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let gray = Grid::new(4, 4, 0.5_f32);
+    /// let dithered = gray.dither_floyd_steinberg_with(|cell| *cell);
+    ///
+    /// assert_eq!(dithered.count(|white| *white), 8);
+    /// ```
+    pub fn dither_floyd_steinberg_with(&self, mut brightness: impl FnMut(&T) -> f32) -> Grid<bool> {
+        let (width, _) = self.size();
+        let mut error: Vec<f32> = self.as_vec().iter().map(&mut brightness).collect();
+        let mut dithered = Vec::with_capacity(error.len());
+        for index in 0..error.len() {
+            let value = error[index];
+            let white = value >= 0.5;
+            dithered.push(white);
+
+            let remaining = value - if white { 1.0 } else { 0.0 };
+            let x = index % width;
+            let has_left = x > 0;
+            let has_right = x + 1 < width;
+            let mut spread = |index: usize, weight: f32| {
+                if let Some(cell) = error.get_mut(index) {
+                    *cell += remaining * weight;
+                }
+            };
+            if has_right {
+                spread(index + 1, 7.0 / 16.0);
+            }
+            if has_left {
+                spread(index + width - 1, 3.0 / 16.0);
+            }
+            spread(index + width, 5.0 / 16.0);
+            if has_right {
+                spread(index + width + 1, 1.0 / 16.0);
+            }
+        }
+        if dithered.is_empty() {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(width, dithered)
+        }
+    }
+
+    /// Returns a grid where each cell is `true` (white) or `false` (black), approximating
+    /// `brightness(cell)` using an ordered dither with a `size` by `size` [Bayer matrix].
+    ///
+    /// Each cell is compared to a threshold that depends only on its position, so the pattern is
+    /// stable when the grid changes (i.e. when animated). Larger matrices can represent more levels
+    /// of brightness.
+    ///
+    /// [Bayer matrix]: https://en.wikipedia.org/wiki/Ordered_dithering
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let gray = Grid::new(4, 2, 0.5_f32);
+    /// let dithered = gray.dither_ordered_with(2, |cell| *cell);
+    ///
+    /// assert_eq!(dithered.to_matrix(), vec![
+    ///     vec![true, false, true, false],
+    ///     vec![false, true, false, true],
+    /// ]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `size` is not a power of two.
+    pub fn dither_ordered_with(
+        &self,
+        size: usize,
+        mut brightness: impl FnMut(&T) -> f32,
+    ) -> Grid<bool> {
+        let matrix = bayer(size);
+        let levels = (size * size) as f32;
+        let (width, _) = self.size();
+        let dithered: Vec<bool> = self
+            .as_vec()
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                let (x, y) = (index % width, index / width);
+                let threshold = (matrix[(y % size) * size + x % size] as f32 + 0.5) / levels;
+                brightness(cell) > threshold
+            })
+            .collect();
+        if dithered.is_empty() {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(width, dithered)
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Returns a grid where each cell is `true` (white) or `false` (black), approximating each
+    /// cell's brightness (`0` to `255`) using Floyd–Steinberg error diffusion.
+    ///
+    /// See [`Grid::dither_floyd_steinberg_with`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![0, 128, 255]);
+    /// assert_eq!(grid.dither_floyd_steinberg().as_vec(), &vec![false, true, true]);
+    /// ```
+    pub fn dither_floyd_steinberg(&self) -> Grid<bool> {
+        self.dither_floyd_steinberg_with(|cell| f32::from(*cell) / 255.0)
+    }
+
+    /// Returns a grid where each cell is `true` (white) or `false` (black), approximating each
+    /// cell's brightness (`0` to `255`) using an ordered dither with a `size` by `size` Bayer
+    /// matrix.
+    ///
+    /// See [`Grid::dither_ordered_with`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![0, 255, 0, 255]);
+    /// assert_eq!(grid.dither_ordered(4).as_vec(), &vec![false, true, false, true]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `size` is not a power of two.
+    pub fn dither_ordered(&self, size: usize) -> Grid<bool> {
+        self.dither_ordered_with(size, |cell| f32::from(*cell) / 255.0)
+    }
+}
+
+impl Grid<f32> {
+    /// Returns a grid where each cell is `true` (white) or `false` (black), approximating each
+    /// cell's brightness (`0.0` to `1.0`) using Floyd–Steinberg error diffusion.
+    ///
+    /// See [`Grid::dither_floyd_steinberg_with`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![0.0, 0.6, 1.0]);
+    /// assert_eq!(grid.dither_floyd_steinberg().as_vec(), &vec![false, true, true]);
+    /// ```
+    pub fn dither_floyd_steinberg(&self) -> Grid<bool> {
+        self.dither_floyd_steinberg_with(|cell| *cell)
+    }
+
+    /// Returns a grid where each cell is `true` (white) or `false` (black), approximating each
+    /// cell's brightness (`0.0` to `1.0`) using an ordered dither with a `size` by `size` Bayer
+    /// matrix.
+    ///
+    /// See [`Grid::dither_ordered_with`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::new(8, 8, 0.25);
+    /// assert_eq!(grid.dither_ordered(8).count(|white| *white), 16);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `size` is not a power of two.
+    pub fn dither_ordered(&self, size: usize) -> Grid<bool> {
+        self.dither_ordered_with(size, |cell| *cell)
+    }
+}
+
+/// Returns the `size` by `size` Bayer matrix in row-major order, with values from `0` to
+/// `size * size - 1`.
+fn bayer(size: usize) -> Vec<usize> {
+    assert!(
+        size.is_power_of_two(),
+        "Bayer matrix size {size} is not a power of two"
+    );
+    let mut matrix = vec![0];
+    let mut current = 1;
+    while current < size {
+        let next = current * 2;
+        let mut larger = vec![0; next * next];
+        for y in 0..current {
+            for x in 0..current {
+                let value = 4 * matrix[y * current + x];
+                larger[y * next + x] = value;
+                larger[y * next + x + current] = value + 2;
+                larger[(y + current) * next + x] = value + 3;
+                larger[(y + current) * next + x + current] = value + 1;
+            }
+        }
+        matrix = larger;
+        current = next;
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bayer_4x4() {
+        assert_eq!(
+            bayer(4),
+            vec![0, 8, 2, 10, 12, 4, 14, 6, 3, 11, 1, 9, 15, 7, 13, 5]
+        );
+    }
+
+    #[test]
+    fn floyd_steinberg_preserves_average() {
+        let gradient = Grid::with_width(16, (0..256).map(|i| (i % 16) as f32 / 15.0).collect());
+        let dithered = gradient.dither_floyd_steinberg();
+        let total = dithered.count(|white| *white) as f32 / 256.0;
+
+        assert!((total - 0.5).abs() < 0.05, "{total}");
+    }
+
+    #[test]
+    fn dither_empty_grid() {
+        let grid: Grid<u8> = Grid::from(Vec::new());
+
+        assert!(grid.dither_floyd_steinberg().as_vec().is_empty());
+        assert!(grid.dither_ordered(2).as_vec().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Bayer matrix size 3 is not a power of two")]
+    fn dither_ordered_invalid_size() {
+        Grid::new(2, 2, 0_u8).dither_ordered(3);
+    }
+}
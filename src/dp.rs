@@ -0,0 +1,316 @@
+//! Dynamic programming over grids, i.e. for minimum path sums and counting paths.
+//!
+//! Moves are `(dx, dy)` offsets, as returned by [`Connectivity::offsets`]. [`min_path_sum`] and
+//! [`count_paths`] require every move to advance in row-major order (`dy > 0`, or `dy == 0` and
+//! `dx > 0`), so cells can be solved in a single pass, while [`longest_path`] accepts any moves and
+//! instead requires the allowed steps to be acyclic.
+//!
+//! [`Connectivity::offsets`]: crate::point::Connectivity::offsets
+
+use std::collections::VecDeque;
+
+use crate::{grid::Grid, point::Point};
+
+/// The optimal value of a dynamic programming problem, and a path that achieves it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DpPath<V> {
+    /// The optimal value, i.e. the minimum sum or the number of paths.
+    pub value: V,
+
+    /// The points of a path achieving the value, from start to end.
+    pub path: Vec<(usize, usize)>,
+}
+
+/// Returns the minimum sum of `cost(cell)` over a path from the top-left to the bottom-right cell
+/// using `moves`, and that path, or `None` if no path exists.
+///
+/// Cells where `cost` returns `None` are impassable. Both the first and last cells are included
+/// in the sum, which saturates at [`u64::MAX`] instead of overflowing.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, dp::min_path_sum};
+///
+/// let grid = Grid::with_width(3, vec![
+///     1, 3, 1,
+///     1, 5, 1,
+///     4, 2, 1,
+/// ]);
+///
+/// let best = min_path_sum(&grid, &[(1, 0), (0, 1)], |cell| Some(*cell)).unwrap();
+///
+/// assert_eq!(best.value, 7);
+/// assert_eq!(best.path, vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]);
+/// ```
+///
+/// # Panics
+///
+/// If a move does not advance in row-major order.
+pub fn min_path_sum<T>(
+    grid: &Grid<T>,
+    moves: &[(isize, isize)],
+    mut cost: impl FnMut(&T) -> Option<u64>,
+) -> Option<DpPath<u64>>
+where
+    T: Clone,
+{
+    check_forward(moves);
+    let costs: Vec<Option<u64>> = grid.as_vec().iter().map(&mut cost).collect();
+    let mut sums: Vec<Option<u64>> = vec![None; costs.len()];
+    let mut previous = vec![usize::MAX; costs.len()];
+    *sums.first_mut()? = costs[0];
+    for i in 0..sums.len() {
+        let Some(sum) = sums[i] else {
+            continue;
+        };
+        for next in successors(grid, i, moves) {
+            let Some(cost) = costs[next] else {
+                continue;
+            };
+            let candidate = sum.saturating_add(cost);
+            if sums[next].is_none_or(|best| candidate < best) {
+                sums[next] = Some(candidate);
+                previous[next] = i;
+            }
+        }
+    }
+    let end = sums.len() - 1;
+    Some(DpPath {
+        value: sums[end]?,
+        path: reconstruct(grid, &previous, end),
+    })
+}
+
+/// Returns the number of paths from the top-left to the bottom-right cell using `moves`, and one
+/// of those paths, or `None` if no path exists.
+///
+/// Cells where `passable` returns `false` cannot be entered. The returned path is the one that
+/// always arrives from the earliest cell in row-major order.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, dp::count_paths};
+///
+/// let grid = Grid::with_width(3, vec![
+///     '.', '.', '.',
+///     '.', '#', '.',
+///     '.', '.', '.',
+/// ]);
+///
+/// let paths = count_paths(&grid, &[(1, 0), (0, 1)], |cell| *cell == '.').unwrap();
+///
+/// assert_eq!(paths.value, 2);
+/// assert_eq!(paths.path, vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]);
+/// ```
+///
+/// # Panics
+///
+/// If a move does not advance in row-major order.
+pub fn count_paths<T>(
+    grid: &Grid<T>,
+    moves: &[(isize, isize)],
+    mut passable: impl FnMut(&T) -> bool,
+) -> Option<DpPath<u128>>
+where
+    T: Clone,
+{
+    check_forward(moves);
+    let open: Vec<bool> = grid.as_vec().iter().map(&mut passable).collect();
+    let mut counts = vec![0_u128; open.len()];
+    let mut previous = vec![usize::MAX; open.len()];
+    *counts.first_mut()? = u128::from(open[0]);
+    for i in 0..counts.len() {
+        if counts[i] == 0 {
+            continue;
+        }
+        for next in successors(grid, i, moves) {
+            if !open[next] {
+                continue;
+            }
+            if counts[next] == 0 {
+                previous[next] = i;
+            }
+            counts[next] = counts[next].saturating_add(counts[i]);
+        }
+    }
+    let end = counts.len() - 1;
+    (counts[end] > 0).then(|| DpPath {
+        value: counts[end],
+        path: reconstruct(grid, &previous, end),
+    })
+}
+
+/// Returns the number of steps in the longest path using `moves`, where a step from one cell to
+/// another is only allowed if `step(from, to)` returns `true`, and that path, or `None` if the grid
+/// is empty.
+///
+/// The path may start and end anywhere; ties are broken towards the path ending earliest in
+/// row-major order.
+///
+/// # Examples
+///
+/// Longest strictly increasing path:
+///
+/// ```
+/// use grud::{Grid, dp::longest_path, point::Connectivity};
+///
+/// let grid = Grid::with_width(3, vec![
+///     9, 9, 4,
+///     6, 6, 8,
+///     2, 1, 1,
+/// ]);
+///
+/// let longest = longest_path(&grid, Connectivity::Four.offsets(), |a, b| a < b).unwrap();
+///
+/// assert_eq!(longest.value, 3);
+/// assert_eq!(longest.path, vec![(1, 2), (0, 2), (0, 1), (0, 0)]);
+/// ```
+///
+/// # Panics
+///
+/// If the allowed steps form a cycle.
+pub fn longest_path<T>(
+    grid: &Grid<T>,
+    moves: &[(isize, isize)],
+    mut step: impl FnMut(&T, &T) -> bool,
+) -> Option<DpPath<usize>>
+where
+    T: Clone,
+{
+    let cells = grid.as_vec();
+    let edges: Vec<Vec<usize>> = (0..cells.len())
+        .map(|i| {
+            successors(grid, i, moves)
+                .filter(|next| step(&cells[i], &cells[*next]))
+                .collect()
+        })
+        .collect();
+
+    // Kahn's algorithm, relaxing each cell's successors once all of its predecessors are done.
+    let mut incoming = vec![0; cells.len()];
+    for next in edges.iter().flatten() {
+        incoming[*next] += 1;
+    }
+    let mut ready: VecDeque<usize> = (0..cells.len()).filter(|i| incoming[*i] == 0).collect();
+    let mut lengths = vec![0; cells.len()];
+    let mut previous = vec![usize::MAX; cells.len()];
+    let mut solved = 0;
+    while let Some(i) = ready.pop_front() {
+        solved += 1;
+        for next in &edges[i] {
+            if lengths[i] + 1 > lengths[*next] {
+                lengths[*next] = lengths[i] + 1;
+                previous[*next] = i;
+            }
+            incoming[*next] -= 1;
+            if incoming[*next] == 0 {
+                ready.push_back(*next);
+            }
+        }
+    }
+    assert!(
+        solved == cells.len(),
+        "Allowed steps form a cycle, so the longest path is unbounded"
+    );
+
+    let end = (0..lengths.len()).rev().max_by_key(|i| lengths[*i])?;
+    Some(DpPath {
+        value: lengths[end],
+        path: reconstruct(grid, &previous, end),
+    })
+}
+
+/// Panics if any of `moves` does not advance in row-major order.
+fn check_forward(moves: &[(isize, isize)]) {
+    for (dx, dy) in moves {
+        assert!(
+            *dy > 0 || (*dy == 0 && *dx > 0),
+            "Move ({dx}, {dy}) does not advance in row-major order"
+        );
+    }
+}
+
+/// Returns the indices of the cells reachable from the cell at index `i` using `moves`.
+fn successors<'a, T>(
+    grid: &'a Grid<T>,
+    i: usize,
+    moves: &'a [(isize, isize)],
+) -> impl Iterator<Item = usize> + 'a
+where
+    T: Clone,
+{
+    let (width, height) = grid.size();
+    let point = (i % width, i / width);
+    moves.iter().filter_map(move |(dx, dy)| {
+        let (x, y) = point.offset(*dx, *dy)?;
+        (x < width && y < height).then(|| (x, y).to_index(width))
+    })
+}
+
+/// Returns the points from the start of the path to the cell at index `end`, following `previous`.
+fn reconstruct<T>(grid: &Grid<T>, previous: &[usize], end: usize) -> Vec<(usize, usize)>
+where
+    T: Clone,
+{
+    let width = grid.width();
+    let mut path = vec![(end % width, end / width)];
+    let mut current = end;
+    while previous[current] != usize::MAX {
+        current = previous[current];
+        path.push((current % width, current / width));
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_paths_binomial() {
+        let grid = Grid::new(17, 17, ());
+        let paths = count_paths(&grid, &[(1, 0), (0, 1)], |_| true).unwrap();
+
+        // C(32, 16)
+        assert_eq!(paths.value, 601_080_390);
+        assert_eq!(paths.path.len(), 33);
+    }
+
+    #[test]
+    fn min_path_sum_blocked() {
+        let grid = Grid::with_width(2, vec![1, 0, 0, 1]);
+
+        assert_eq!(
+            min_path_sum(&grid, &[(1, 0), (0, 1)], |c| (*c > 0).then_some(1)),
+            None
+        );
+        assert_eq!(
+            min_path_sum(&grid, &[(1, 1)], |_| Some(1)).map(|p| p.value),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn min_path_sum_saturates() {
+        let grid = Grid::with_width(2, vec![u64::MAX, 1, u64::MAX, u64::MAX]);
+        let best = min_path_sum(&grid, &[(1, 0), (0, 1)], |c| Some(*c)).unwrap();
+
+        assert_eq!(best.value, u64::MAX);
+        assert_eq!(best.path.len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Move (-1, 0) does not advance in row-major order")]
+    fn min_path_sum_backwards_move() {
+        min_path_sum(&Grid::new(2, 2, 0), &[(-1, 0)], |_| Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Allowed steps form a cycle")]
+    fn longest_path_cycle() {
+        longest_path(&Grid::new(2, 2, 0), &[(1, 0), (-1, 0)], |_, _| true);
+    }
+}
@@ -0,0 +1,519 @@
+//! Drawing shapes into a grid, i.e. for area-of-effect templates or editor tools.
+//!
+//! Shapes are centered on a cell, and parts of a shape outside of the grid are ignored. Angles are
+//! in radians, starting at the positive x-axis and increasing clockwise (as `y` increases
+//! downwards), so `0` is east and `PI / 2` is south.
+//!
+//! Outlines are drawn with a single cell by default (i.e. [`Grid::draw_ellipse`]), or with a
+//! [`Brush`] using the `stroke_` variants (i.e. [`Grid::stroke_ellipse`]).
+//!
+//! See [`Grid::fill_ellipse`], [`Grid::fill_sector`], and [`Grid::stroke`] for details.
+
+use std::{
+    f64::consts::TAU,
+    ops::{Range, RangeInclusive},
+};
+
+use crate::{
+    grid::Grid,
+    point::{line, Point},
+};
+
+/// The shape of cells set around each point of a stroke, i.e. by [`Grid::stroke`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Brush {
+    /// A square extending the radius in each direction, so `Square(1)` is 3x3 cells.
+    ///
+    /// `Square(0)` is a single cell.
+    Square(usize),
+
+    /// A circle with the radius, as drawn by [`Grid::fill_ellipse`].
+    ///
+    /// `Round(0)` is a single cell.
+    Round(usize),
+}
+
+impl Brush {
+    /// Returns the offsets from the center of every cell of the brush, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::draw::Brush;
+    ///
+    /// assert_eq!(Brush::Round(1).offsets().count(), 5);
+    /// assert_eq!(Brush::Square(1).offsets().count(), 9);
+    /// ```
+    pub fn offsets(&self) -> impl Iterator<Item = (isize, isize)> {
+        self.offsets_within(isize::MIN..=isize::MAX, isize::MIN..=isize::MAX)
+    }
+
+    /// Returns the radius of the brush.
+    fn radius(&self) -> usize {
+        match *self {
+            Self::Square(radius) | Self::Round(radius) => radius,
+        }
+    }
+
+    /// Returns the offsets of [`Brush::offsets`] within `xs` and `ys`, without visiting the
+    /// offsets outside of them.
+    fn offsets_within(
+        &self,
+        xs: RangeInclusive<isize>,
+        ys: RangeInclusive<isize>,
+    ) -> impl Iterator<Item = (isize, isize)> {
+        let round = matches!(self, Self::Round(_));
+        let ellipse = Ellipse::new((self.radius(), self.radius()));
+        let (xs, ys) = ellipse.clamp(xs, ys);
+        ys.flat_map(move |dy| xs.clone().map(move |dx| (dx, dy)))
+            .filter(move |&offset| !round || ellipse.contains(offset))
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Sets every cell inside the axis-aligned ellipse at `center` to `value`.
+    ///
+    /// The ellipse extends `radii.0` cells left and right of `center`, and `radii.1` cells above
+    /// and below it; a radius of `0` is a single row or column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(7, 3, '.');
+    /// grid.fill_ellipse((3, 1), (3, 1), '#');
+    ///
+    /// assert_eq!(grid.to_string(), ".#####.\n#######\n.#####.\n");
+    /// ```
+    pub fn fill_ellipse(&mut self, center: impl Point, radii: (usize, usize), value: T) {
+        let (xs, ys) = self.clip_offsets(&center, 0);
+        let ellipse = Ellipse::new(radii);
+        self.plot_offsets(&center, ellipse.offsets(xs, ys), &value);
+    }
+
+    /// Sets every cell on the outline of the axis-aligned ellipse at `center` to `value`.
+    ///
+    /// The outline is every cell of [`Grid::fill_ellipse`] that is orthogonally adjacent to a cell
+    /// outside of the ellipse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(7, 5, '.');
+    /// grid.draw_ellipse((3, 2), (3, 2), '#');
+    ///
+    /// assert_eq!(grid.to_string(), "..###..\n##...##\n#.....#\n##...##\n..###..\n");
+    /// ```
+    pub fn draw_ellipse(&mut self, center: impl Point, radii: (usize, usize), value: T) {
+        self.stroke_ellipse(center, radii, Brush::Square(0), value);
+    }
+
+    /// Stamps `brush` on every cell of the outline of the axis-aligned ellipse at `center`.
+    ///
+    /// See [`Grid::draw_ellipse`] for the outline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, draw::Brush};
+    ///
+    /// let mut grid = Grid::new(7, 7, '.');
+    /// grid.stroke_ellipse((3, 3), (2, 2), Brush::Square(1), '#');
+    ///
+    /// // The outline passes through (3, 1), and the brush extends it inwards to (3, 2).
+    /// assert_eq!(grid[(3, 2)], '#');
+    /// assert_eq!(grid[(3, 3)], '.');
+    /// ```
+    pub fn stroke_ellipse(
+        &mut self,
+        center: impl Point,
+        radii: (usize, usize),
+        brush: Brush,
+        value: T,
+    ) {
+        let (xs, ys) = self.clip_offsets(&center, brush.radius());
+        let ellipse = Ellipse::new(radii);
+        let outline = ellipse
+            .offsets(xs, ys)
+            .filter(|&offset| ellipse.is_edge(offset));
+        self.stroke_offsets(&center, outline, brush, &value);
+    }
+
+    /// Sets every cell inside the sector (i.e. a pie slice) of the axis-aligned ellipse at
+    /// `center` between `angles` to `value`.
+    ///
+    /// The center cell is always included. If `angles` spans a full turn or more, this is the
+    /// same as [`Grid::fill_ellipse`].
+    ///
+    /// # Examples
+    ///
+    /// A cone facing east:
+    ///
+    /// ```
+    /// use std::f64::consts::FRAC_PI_4;
+    ///
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(5, 5, '.');
+    /// grid.fill_sector((0, 2), (4, 4), -FRAC_PI_4..FRAC_PI_4, '#');
+    ///
+    /// assert_eq!(grid.to_string(), "..##.\n.####\n#####\n.####\n..##.\n");
+    /// ```
+    pub fn fill_sector(
+        &mut self,
+        center: impl Point,
+        radii: (usize, usize),
+        angles: Range<f64>,
+        value: T,
+    ) {
+        let (xs, ys) = self.clip_offsets(&center, 0);
+        let ellipse = Ellipse::new(radii);
+        let sector = ellipse
+            .offsets(xs, ys)
+            .filter(|&offset| offset == (0, 0) || in_angles(offset, &angles));
+        self.plot_offsets(&center, sector, &value);
+    }
+
+    /// Sets every cell on the outline of the axis-aligned ellipse at `center` between `angles` to
+    /// `value`.
+    ///
+    /// This is the part of [`Grid::draw_ellipse`] between `angles`.
+    ///
+    /// # Examples
+    ///
+    /// The lower half of a circle:
+    ///
+    /// ```
+    /// use std::f64::consts::PI;
+    ///
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(7, 7, '.');
+    /// grid.draw_arc((3, 3), (3, 3), 0.0..PI, '#');
+    ///
+    /// assert_eq!(grid.to_string(), [
+    ///     ".......",
+    ///     ".......",
+    ///     ".......",
+    ///     "#.....#",
+    ///     "#.....#",
+    ///     ".#...#.",
+    ///     "..###..",
+    /// ].map(|row| format!("{row}\n")).concat());
+    /// ```
+    pub fn draw_arc(
+        &mut self,
+        center: impl Point,
+        radii: (usize, usize),
+        angles: Range<f64>,
+        value: T,
+    ) {
+        self.stroke_arc(center, radii, angles, Brush::Square(0), value);
+    }
+
+    /// Stamps `brush` on every cell of the outline of the axis-aligned ellipse at `center` between
+    /// `angles`.
+    ///
+    /// See [`Grid::draw_arc`] for the outline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::f64::consts::PI;
+    ///
+    /// use grud::{Grid, draw::Brush};
+    ///
+    /// let mut grid = Grid::new(7, 7, '.');
+    /// grid.stroke_arc((3, 3), (3, 3), 0.0..PI, Brush::Round(1), '#');
+    ///
+    /// assert_eq!(grid[(3, 5)], '#');
+    /// assert_eq!(grid[(3, 0)], '.');
+    /// ```
+    pub fn stroke_arc(
+        &mut self,
+        center: impl Point,
+        radii: (usize, usize),
+        angles: Range<f64>,
+        brush: Brush,
+        value: T,
+    ) {
+        let (xs, ys) = self.clip_offsets(&center, brush.radius());
+        let ellipse = Ellipse::new(radii);
+        let arc = ellipse
+            .offsets(xs, ys)
+            .filter(|&offset| ellipse.is_edge(offset) && in_angles(offset, &angles));
+        self.stroke_offsets(&center, arc, brush, &value);
+    }
+
+    /// Sets every cell of `brush` centered on the cell at `center` to `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, draw::Brush};
+    ///
+    /// let mut grid = Grid::new(3, 3, '.');
+    /// grid.stamp((1, 1), Brush::Round(1), '#');
+    ///
+    /// assert_eq!(grid.to_string(), ".#.\n###\n.#.\n");
+    /// ```
+    pub fn stamp(&mut self, center: impl Point, brush: Brush, value: T) {
+        let (xs, ys) = self.clip_offsets(&center, 0);
+        self.plot_offsets(&center, brush.offsets_within(xs, ys), &value);
+    }
+
+    /// Stamps `brush` on every point of `points`, i.e. a [`line`] or path.
+    ///
+    /// # Examples
+    ///
+    /// Painting along a path:
+    ///
+    /// ```
+    /// use grud::{Grid, draw::Brush};
+    ///
+    /// let mut grid = Grid::new(5, 3, '.');
+    /// grid.stroke([(0, 0), (1, 0), (2, 0)], Brush::Square(1), '#');
+    ///
+    /// assert_eq!(grid.to_string(), "####.\n####.\n.....\n");
+    /// ```
+    pub fn stroke<P>(&mut self, points: impl IntoIterator<Item = P>, brush: Brush, value: T)
+    where
+        P: Point,
+    {
+        for point in points {
+            self.stamp(point, brush, value.clone());
+        }
+    }
+
+    /// Sets every cell within `radius` of the line from `a` to `b` to `value`.
+    ///
+    /// This is a [`Grid::stroke`] with a [`Brush::Round`] along a [`line`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(6, 3, '.');
+    /// grid.draw_line_thick((1, 1), (4, 1), 1, '#');
+    ///
+    /// assert_eq!(grid.to_string(), ".####.\n######\n.####.\n");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If a coordinate of `a` or `b` is larger than [`isize::MAX`].
+    pub fn draw_line_thick(&mut self, a: impl Point, b: impl Point, radius: usize, value: T) {
+        self.stroke(line(a, b), Brush::Round(radius), value);
+    }
+
+    /// Stamps `brush` at each offset from `center`, ignoring cells outside of the grid.
+    fn stroke_offsets(
+        &mut self,
+        center: &impl Point,
+        offsets: impl IntoIterator<Item = (isize, isize)>,
+        brush: Brush,
+        value: &T,
+    ) {
+        let (xs, ys) = self.clip_offsets(center, 0);
+        let shift = |range: &RangeInclusive<isize>, by: isize| {
+            range.start().saturating_sub(by)..=range.end().saturating_sub(by)
+        };
+        let stamped = offsets.into_iter().flat_map(|(dx, dy)| {
+            brush
+                .offsets_within(shift(&xs, dx), shift(&ys, dy))
+                .map(move |(bx, by)| (dx.saturating_add(bx), dy.saturating_add(by)))
+        });
+        self.plot_offsets(center, stamped, value);
+    }
+
+    /// Returns the ranges of offsets from `center` that are within the grid, extended by `margin`
+    /// cells on every side.
+    ///
+    /// Shapes only visit these offsets, so their cost depends on the size of the grid rather than
+    /// the size of the shape.
+    fn clip_offsets(
+        &self,
+        center: &impl Point,
+        margin: usize,
+    ) -> (RangeInclusive<isize>, RangeInclusive<isize>) {
+        let signed = |n: usize| isize::try_from(n).unwrap_or(isize::MAX);
+        let clip = |center: usize, len: usize| {
+            let (center, margin) = (signed(center), signed(margin));
+            let start = center.saturating_add(margin).saturating_neg();
+            let end = signed(len)
+                .saturating_add(margin)
+                .saturating_sub(1)
+                .saturating_sub(center);
+            start..=end
+        };
+        let (width, height) = self.size();
+        (clip(center.x(), width), clip(center.y(), height))
+    }
+
+    /// Sets the cell at each offset from `center` to `value`, ignoring cells outside of the grid.
+    fn plot_offsets(
+        &mut self,
+        center: &impl Point,
+        offsets: impl IntoIterator<Item = (isize, isize)>,
+        value: &T,
+    ) {
+        let (width, height) = self.size();
+        for (dx, dy) in offsets {
+            let x = center.x().checked_add_signed(dx).filter(|&x| x < width);
+            let y = center.y().checked_add_signed(dy).filter(|&y| y < height);
+            if let (Some(x), Some(y)) = (x, y) {
+                self[(x, y)] = value.clone();
+            }
+        }
+    }
+}
+
+/// The offsets from the center of the cells inside an axis-aligned ellipse.
+struct Ellipse {
+    radii: (isize, isize),
+}
+
+impl Ellipse {
+    fn new(radii: (usize, usize)) -> Self {
+        let signed = |n: usize| isize::try_from(n).expect("Radius larger than isize::MAX");
+        Self {
+            radii: (signed(radii.0), signed(radii.1)),
+        }
+    }
+
+    /// Returns whether the cell at `offset` is inside the ellipse.
+    ///
+    /// The radii are extended by slightly less than half a cell, so cells along the axes are
+    /// inside up to the radius, but the corners of small ellipses are not.
+    fn contains(&self, (dx, dy): (isize, isize)) -> bool {
+        let rx = self.radii.0 as f64 + 0.4;
+        let ry = self.radii.1 as f64 + 0.4;
+        let (dx, dy) = (dx as f64 / rx, dy as f64 / ry);
+        dx * dx + dy * dy <= 1.0
+    }
+
+    /// Returns whether the cell at `offset` is inside the ellipse, next to a cell outside of it.
+    fn is_edge(&self, (dx, dy): (isize, isize)) -> bool {
+        [(dx - 1, dy), (dx + 1, dy), (dx, dy - 1), (dx, dy + 1)]
+            .into_iter()
+            .any(|neighbor| !self.contains(neighbor))
+    }
+
+    /// Returns the offsets of every cell inside the ellipse and within `xs` and `ys`, in row-major
+    /// order.
+    fn offsets(
+        &self,
+        xs: RangeInclusive<isize>,
+        ys: RangeInclusive<isize>,
+    ) -> impl Iterator<Item = (isize, isize)> + '_ {
+        let (xs, ys) = self.clamp(xs, ys);
+        ys.flat_map(move |dy| xs.clone().map(move |dx| (dx, dy)))
+            .filter(|&offset| self.contains(offset))
+    }
+
+    /// Returns `xs` and `ys` limited to the bounding box of the ellipse.
+    fn clamp(
+        &self,
+        xs: RangeInclusive<isize>,
+        ys: RangeInclusive<isize>,
+    ) -> (RangeInclusive<isize>, RangeInclusive<isize>) {
+        let (rx, ry) = self.radii;
+        (
+            (*xs.start()).max(-rx)..=(*xs.end()).min(rx),
+            (*ys.start()).max(-ry)..=(*ys.end()).min(ry),
+        )
+    }
+}
+
+/// Returns whether the angle of `offset` from the center is within `angles`.
+fn in_angles((dx, dy): (isize, isize), angles: &Range<f64>) -> bool {
+    let sweep = angles.end - angles.start;
+    if sweep >= TAU {
+        return true;
+    }
+    let angle = (dy as f64).atan2(dx as f64);
+    (angle - angles.start).rem_euclid(TAU) <= sweep
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn stroke_clipped_at_edges() {
+        let mut grid = Grid::new(3, 3, 0);
+        grid.stroke([(0, 0), (2, 2)], Brush::Square(2), 1);
+
+        assert_eq!(grid.count(|c| *c == 1), 9);
+    }
+
+    #[test]
+    fn huge_shapes_only_visit_the_grid() {
+        let mut grid = Grid::new(3, 3, 0);
+        grid.fill_ellipse((0, 0), (100_000, 100_000), 1);
+        assert_eq!(grid.count(|c| *c == 1), 9);
+
+        let mut grid = Grid::new(3, 3, 0);
+        grid.stamp((1, 1), Brush::Round(100_000), 1);
+        assert_eq!(grid.count(|c| *c == 1), 9);
+
+        let mut grid = Grid::new(3, 3, 0);
+        grid.fill_sector((1, 1), (100_000, 100_000), 0.0..1.0, 1);
+        assert_eq!(grid.as_vec(), &vec![0, 0, 0, 0, 1, 1, 0, 0, 1]);
+
+        let mut grid = Grid::new(3, 3, 0);
+        grid.stroke_ellipse((1, 50_000), (50_000, 50_000), Brush::Square(1), 1);
+        assert_eq!(grid.count(|c| *c == 1), 6);
+    }
+
+    #[test]
+    fn draw_line_thick_zero_radius_is_line() {
+        let mut grid = Grid::new(5, 3, 0);
+        grid.draw_line_thick((0, 0), (4, 2), 0, 1);
+
+        assert_eq!(grid.count(|c| *c == 1), line((0, 0), (4, 2)).count());
+    }
+
+    #[test]
+    fn fill_ellipse_zero_radii() {
+        let mut grid = Grid::new(3, 3, 0);
+        grid.fill_ellipse((1, 1), (0, 0), 1);
+
+        assert_eq!(grid.as_vec(), &vec![0, 0, 0, 0, 1, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fill_ellipse_clipped_at_corner() {
+        let mut grid = Grid::new(3, 3, 0);
+        grid.fill_ellipse((0, 0), (1, 1), 1);
+
+        assert_eq!(grid.as_vec(), &vec![1, 1, 0, 1, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fill_sector_full_turn_is_ellipse() {
+        let mut sector = Grid::new(9, 7, 0);
+        sector.fill_sector((4, 3), (4, 3), 1.0..1.0 + 2.0 * PI, 1);
+        let mut ellipse = Grid::new(9, 7, 0);
+        ellipse.fill_ellipse((4, 3), (4, 3), 1);
+
+        assert_eq!(sector.as_vec(), ellipse.as_vec());
+    }
+
+    #[test]
+    fn fill_sector_empty_range_is_center() {
+        let mut grid = Grid::new(3, 3, 0);
+        grid.fill_sector((1, 1), (1, 1), 1.0..0.0, 1);
+
+        assert_eq!(grid.count(|c| *c == 1), 1);
+    }
+}
@@ -0,0 +1,312 @@
+//! Storing data on the edges between cells, i.e. thin walls.
+//!
+//! See [`EdgeGrid`] for details.
+
+use crate::{grid::Grid, point::Point, view::GridMut};
+
+/// A grid that stores elements on the edges of a `width` by `height` grid of cells.
+///
+/// Edges are split into two grids:
+///
+/// - _Vertical_ edges separate horizontally adjacent cells (and the left and right borders), and
+///   are laid out as a `(width + 1)` by `height` grid, where the edge at `(x, y)` is to the left of
+///   cell `(x, y)`.
+/// - _Horizontal_ edges separate vertically adjacent cells (and the top and bottom borders), and
+///   are laid out as a `width` by `(height + 1)` grid, where the edge at `(x, y)` is above cell
+///   `(x, y)`.
+///
+/// # Examples
+///
+/// Blocking movement with walls instead of cell contents:
+///
+/// ```
+/// use grud::{Grid, edge::EdgeGrid, path};
+///
+/// let floor = Grid::new(2, 2, ());
+/// let mut walls = EdgeGrid::new(2, 2, false);
+/// *walls.between_mut((0, 0), (1, 0)).unwrap() = true;
+///
+/// let path = path::bfs_with_moves(&floor, (0, 0), (1, 0), |_| true, |a, b| {
+///     !walls.wall_between(a, b)
+/// });
+/// assert_eq!(path, Some(vec![(0, 0), (0, 1), (1, 1), (1, 0)]));
+/// ```
+///
+/// The same applies to the other searches, such as [`dijkstra_with_moves`] and
+/// [`AstarSearch::with_moves`].
+///
+/// [`dijkstra_with_moves`]: crate::path::dijkstra_with_moves
+/// [`AstarSearch::with_moves`]: crate::path::AstarSearch::with_moves
+#[derive(Clone, Debug)]
+pub struct EdgeGrid<T>
+where
+    T: Clone,
+{
+    vertical: Grid<T>,
+    horizontal: Grid<T>,
+}
+
+impl<T> EdgeGrid<T>
+where
+    T: Clone,
+{
+    /// Creates edges for a grid of `width` by `height` cells, filling with `default`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::edge::EdgeGrid;
+    ///
+    /// let edges = EdgeGrid::new(3, 2, 0);
+    /// assert_eq!(edges.vertical_edges().width(), 4);
+    /// assert_eq!(edges.horizontal_edges().height(), 3);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width` or `height` is `0`.
+    pub fn new(width: usize, height: usize, default: T) -> Self {
+        assert!(
+            width > 0 && height > 0,
+            "Cannot create edges for a {width}x{height} grid"
+        );
+        Self {
+            vertical: Grid::new(width + 1, height, default.clone()),
+            horizontal: Grid::new(width, height + 1, default),
+        }
+    }
+
+    /// Returns the width of the grid of cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::edge::EdgeGrid;
+    ///
+    /// assert_eq!(EdgeGrid::new(3, 2, 0).width(), 3);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.horizontal.width()
+    }
+
+    /// Returns the height of the grid of cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::edge::EdgeGrid;
+    ///
+    /// assert_eq!(EdgeGrid::new(3, 2, 0).height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.vertical.height()
+    }
+
+    /// Returns the vertical edges, where the edge at `(x, y)` is to the left of cell `(x, y)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::edge::EdgeGrid;
+    ///
+    /// let edges = EdgeGrid::new(3, 2, 0);
+    /// assert_eq!(edges.vertical_edges().area(), 4 * 2);
+    /// ```
+    pub fn vertical_edges(&self) -> &Grid<T> {
+        &self.vertical
+    }
+
+    /// Returns the vertical edges as mutable, where the edge at `(x, y)` is to the left of cell
+    /// `(x, y)`.
+    ///
+    /// The edges can be changed, but not resized.
+    ///
+    /// # Examples
+    ///
+    /// Walling off the left border:
+    ///
+    /// ```
+    /// use grud::edge::EdgeGrid;
+    ///
+    /// let mut edges = EdgeGrid::new(3, 2, false);
+    /// for y in 0..2 {
+    ///     edges.vertical_edges_mut()[(0, y)] = true;
+    /// }
+    /// ```
+    pub fn vertical_edges_mut(&mut self) -> GridMut<'_, T> {
+        GridMut::new(&mut self.vertical)
+    }
+
+    /// Returns the horizontal edges, where the edge at `(x, y)` is above cell `(x, y)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::edge::EdgeGrid;
+    ///
+    /// let edges = EdgeGrid::new(3, 2, 0);
+    /// assert_eq!(edges.horizontal_edges().area(), 3 * 3);
+    /// ```
+    pub fn horizontal_edges(&self) -> &Grid<T> {
+        &self.horizontal
+    }
+
+    /// Returns the horizontal edges as mutable, where the edge at `(x, y)` is above cell `(x, y)`.
+    ///
+    /// The edges can be changed, but not resized.
+    ///
+    /// # Examples
+    ///
+    /// Walling off the top border:
+    ///
+    /// ```
+    /// use grud::edge::EdgeGrid;
+    ///
+    /// let mut edges = EdgeGrid::new(3, 2, false);
+    /// for x in 0..3 {
+    ///     edges.horizontal_edges_mut()[(x, 0)] = true;
+    /// }
+    /// ```
+    pub fn horizontal_edges_mut(&mut self) -> GridMut<'_, T> {
+        GridMut::new(&mut self.horizontal)
+    }
+
+    /// Returns the edge between the cells at `a` and `b`.
+    ///
+    /// Returns `None` if either cell is out of bounds, or the cells are not orthogonally adjacent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::edge::EdgeGrid;
+    ///
+    /// let mut walls = EdgeGrid::new(2, 2, false);
+    /// walls.vertical_edges_mut()[(1, 0)] = true;
+    ///
+    /// assert_eq!(walls.between((0, 0), (1, 0)), Some(&true));
+    /// assert_eq!(walls.between((1, 0), (0, 0)), Some(&true));
+    /// assert_eq!(walls.between((0, 0), (0, 1)), Some(&false));
+    /// assert_eq!(walls.between((0, 0), (1, 1)), None);
+    /// ```
+    pub fn between(&self, a: impl Point, b: impl Point) -> Option<&T> {
+        match self.locate(a, b)? {
+            (true, point) => Some(&self.vertical[point]),
+            (false, point) => Some(&self.horizontal[point]),
+        }
+    }
+
+    /// Returns the edge between the cells at `a` and `b` as mutable.
+    ///
+    /// Returns `None` if either cell is out of bounds, or the cells are not orthogonally adjacent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::edge::EdgeGrid;
+    ///
+    /// let mut walls = EdgeGrid::new(2, 2, false);
+    /// *walls.between_mut((0, 1), (0, 0)).unwrap() = true;
+    ///
+    /// assert_eq!(walls.horizontal_edges()[(0, 1)], true);
+    /// ```
+    pub fn between_mut(&mut self, a: impl Point, b: impl Point) -> Option<&mut T> {
+        match self.locate(a, b)? {
+            (true, point) => Some(&mut self.vertical[point]),
+            (false, point) => Some(&mut self.horizontal[point]),
+        }
+    }
+
+    /// Returns whether the edge between `a` and `b` is vertical, and its position.
+    fn locate(&self, a: impl Point, b: impl Point) -> Option<(bool, (usize, usize))> {
+        let in_bounds = |x: usize, y: usize| x < self.width() && y < self.height();
+        if !in_bounds(a.x(), a.y()) || !in_bounds(b.x(), b.y()) {
+            return None;
+        }
+        let (min_x, max_x) = (a.x().min(b.x()), a.x().max(b.x()));
+        let (min_y, max_y) = (a.y().min(b.y()), a.y().max(b.y()));
+        match (max_x - min_x, max_y - min_y) {
+            (1, 0) => Some((true, (max_x, min_y))),
+            (0, 1) => Some((false, (min_x, max_y))),
+            _ => None,
+        }
+    }
+}
+
+impl EdgeGrid<bool> {
+    /// Returns whether a wall (a `true` edge) separates the cells at `a` and `b`.
+    ///
+    /// Cells that are out of bounds or not orthogonally adjacent are always separated, so this can
+    /// be used as is to block moves in pathfinding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::edge::EdgeGrid;
+    ///
+    /// let mut walls = EdgeGrid::new(2, 2, false);
+    /// *walls.between_mut((0, 0), (1, 0)).unwrap() = true;
+    ///
+    /// assert!(walls.wall_between((1, 0), (0, 0)));
+    /// assert!(!walls.wall_between((0, 0), (0, 1)));
+    /// assert!(walls.wall_between((0, 0), (1, 1)));
+    /// ```
+    pub fn wall_between(&self, a: impl Point, b: impl Point) -> bool {
+        self.between(a, b).copied().unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edge_grid_between_out_of_bounds() {
+        let edges = EdgeGrid::new(2, 2, 0);
+
+        assert_eq!(edges.between((1, 0), (2, 0)), None);
+        assert_eq!(edges.between((0, 1), (0, 2)), None);
+    }
+
+    #[test]
+    fn edge_grid_between_same_cell() {
+        let edges = EdgeGrid::new(2, 2, 0);
+
+        assert_eq!(edges.between((1, 1), (1, 1)), None);
+    }
+
+    #[test]
+    fn edge_grid_between_is_symmetric() {
+        let mut edges = EdgeGrid::new(3, 3, 0);
+        *edges.between_mut((1, 1), (2, 1)).unwrap() = 1;
+        *edges.between_mut((1, 2), (1, 1)).unwrap() = 2;
+
+        assert_eq!(edges.between((2, 1), (1, 1)), Some(&1));
+        assert_eq!(edges.between((1, 1), (1, 2)), Some(&2));
+        assert_eq!(edges.vertical_edges()[(2, 1)], 1);
+        assert_eq!(edges.horizontal_edges()[(1, 2)], 2);
+    }
+
+    #[test]
+    fn edge_grid_mut_keeps_size() {
+        let mut edges = EdgeGrid::new(2, 1, 0);
+        edges.vertical_edges_mut().fill(1);
+        edges.horizontal_edges_mut()[(1, 1)] = 2;
+
+        assert_eq!(edges.vertical_edges().as_vec(), &vec![1, 1, 1]);
+        assert_eq!(edges.between((1, 0), (1, 1)), None);
+        assert_eq!(edges.horizontal_edges()[(1, 1)], 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot replace 3x1 grid with 2x1 grid")]
+    fn edge_grid_mut_cannot_resize() {
+        let mut edges = EdgeGrid::new(2, 1, 0);
+        edges.vertical_edges_mut().replace(Grid::new(2, 1, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn edge_grid_empty() {
+        EdgeGrid::new(0, 1, 0);
+    }
+}
@@ -0,0 +1,194 @@
+//! Read-modify-write access to a single cell without repeated lookups.
+//!
+//! See [`Grid::entry`] for details.
+
+use std::mem;
+
+use crate::{grid::Grid, point::Point};
+
+/// A view into a single cell of a grid, created by [`Grid::entry`].
+///
+/// For grids of [`Option`], also see [`Entry::or_insert_with`].
+#[derive(Debug)]
+pub struct Entry<'a, T> {
+    point: (usize, usize),
+    cell: &'a mut T,
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns the entry for the cell at `point`, for in-place manipulation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 1);
+    /// let old = grid.entry((1, 0)).and_modify(|cell| *cell *= 10).replace(3);
+    ///
+    /// assert_eq!(old, 10);
+    /// assert_eq!(grid[(1, 0)], 3);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn entry(&mut self, point: impl Point) -> Entry<'_, T> {
+        let point = (point.x(), point.y());
+        let index = self.checked_index(point);
+        Entry {
+            point,
+            cell: &mut self[index],
+        }
+    }
+}
+
+impl<'a, T> Entry<'a, T> {
+    /// Returns the point of the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 0);
+    /// assert_eq!(grid.entry((1, 0)).point(), (1, 0));
+    /// ```
+    pub fn point(&self) -> (usize, usize) {
+        self.point
+    }
+
+    /// Returns a reference to the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 'a');
+    /// assert_eq!(grid.entry((0, 1)).get(), &'a');
+    /// ```
+    pub fn get(&self) -> &T {
+        self.cell
+    }
+
+    /// Returns a mutable reference to the cell, with the lifetime of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 0);
+    /// *grid.entry((0, 1)).into_mut() += 1;
+    ///
+    /// assert_eq!(grid[(0, 1)], 1);
+    /// ```
+    pub fn into_mut(self) -> &'a mut T {
+        self.cell
+    }
+
+    /// Calls `f` with the cell, returning the entry for further manipulation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 1);
+    /// grid.entry((1, 1)).and_modify(|cell| *cell += 1).and_modify(|cell| *cell *= 3);
+    ///
+    /// assert_eq!(grid[(1, 1)], 6);
+    /// ```
+    pub fn and_modify(self, f: impl FnOnce(&mut T)) -> Self {
+        f(self.cell);
+        self
+    }
+
+    /// Sets the cell to `value`, returning the previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 'a');
+    ///
+    /// assert_eq!(grid.entry((0, 0)).replace('b'), 'a');
+    /// assert_eq!(grid[(0, 0)], 'b');
+    /// ```
+    pub fn replace(self, value: T) -> T {
+        mem::replace(self.cell, value)
+    }
+
+    /// Takes the value of the cell, leaving the default value in its place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 1, String::from("hello"));
+    ///
+    /// assert_eq!(grid.entry((1, 0)).take(), "hello");
+    /// assert_eq!(grid[(1, 0)], "");
+    /// ```
+    pub fn take(self) -> T
+    where
+        T: Default,
+    {
+        mem::take(self.cell)
+    }
+}
+
+impl<'a, T> Entry<'a, Option<T>> {
+    /// Returns a mutable reference to the value in the cell, inserting `value` if empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 1, None);
+    /// *grid.entry((0, 0)).or_insert(0) += 1;
+    /// *grid.entry((0, 0)).or_insert(0) += 1;
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![Some(2), None]);
+    /// ```
+    pub fn or_insert(self, value: T) -> &'a mut T {
+        self.cell.get_or_insert(value)
+    }
+
+    /// Returns a mutable reference to the value in the cell, inserting the result of `f` if empty.
+    ///
+    /// # Examples
+    ///
+    /// Spawning units only in empty cells:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::with_width(2, vec![Some("orc"), None]);
+    /// grid.entry((0, 0)).or_insert_with(|| "elf");
+    /// grid.entry((1, 0)).or_insert_with(|| "elf");
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![Some("orc"), Some("elf")]);
+    /// ```
+    pub fn or_insert_with(self, f: impl FnOnce() -> T) -> &'a mut T {
+        self.cell.get_or_insert_with(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "Point (2, 0) out of bounds of 2x2 grid")]
+    fn entry_out_of_bounds() {
+        Grid::new(2, 2, 0).entry((2, 0));
+    }
+}
@@ -0,0 +1,137 @@
+//! Errors returned by fallible grid operations.
+//!
+//! See [`GridError`], [`OutOfBounds`], and [`SizeMismatch`] for details.
+
+use core::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+/// Error returned when a grid cannot be created with the requested dimensions or data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GridError {
+    /// The number of cells overflows a [`usize`], or the cells would take up more than
+    /// [`isize::MAX`] bytes.
+    TooLarge {
+        /// The requested width.
+        width: usize,
+
+        /// The requested height.
+        height: usize,
+    },
+
+    /// The number of cells is more than a user-provided limit.
+    LimitExceeded {
+        /// The requested width.
+        width: usize,
+
+        /// The requested height.
+        height: usize,
+
+        /// The maximum number of cells.
+        limit: usize,
+    },
+
+    /// The allocator could not provide memory for the cells.
+    AllocationFailed {
+        /// The requested width.
+        width: usize,
+
+        /// The requested height.
+        height: usize,
+    },
+
+    /// The number of cells is not a multiple of the requested width.
+    LengthNotDivisible {
+        /// The number of cells.
+        length: usize,
+
+        /// The requested width.
+        width: usize,
+    },
+
+    /// A row does not have the same number of cells as the first row.
+    RaggedRow {
+        /// The index of the row, starting at `0`.
+        row: usize,
+
+        /// The number of cells in the first row.
+        expected: usize,
+
+        /// The number of cells in the row.
+        found: usize,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooLarge { width, height } => {
+                write!(f, "Grid of {width}x{height} cells is too large")
+            }
+            Self::LimitExceeded {
+                width,
+                height,
+                limit,
+            } => write!(
+                f,
+                "Grid of {width}x{height} cells exceeds limit of {limit} cells"
+            ),
+            Self::AllocationFailed { width, height } => {
+                write!(f, "Could not allocate grid of {width}x{height} cells")
+            }
+            Self::LengthNotDivisible { length, width } => {
+                write!(f, "Data length {length} not divisible by {width}")
+            }
+            Self::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "Row {row} has {found} cells, expected {expected}"),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// Error returned when two grids that must be the same size are not.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SizeMismatch {
+    /// The width and height of the grid the operation was called on.
+    pub expected: (usize, usize),
+
+    /// The width and height of the other grid.
+    pub found: (usize, usize),
+}
+
+impl Display for SizeMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let (expected_width, expected_height) = self.expected;
+        let (found_width, found_height) = self.found;
+        write!(
+            f,
+            "Expected grid of {expected_width}x{expected_height} cells, found {found_width}x{found_height}"
+        )
+    }
+}
+
+impl Error for SizeMismatch {}
+
+/// Error returned when a point is outside of a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OutOfBounds {
+    /// The point that was out of bounds.
+    pub point: (usize, usize),
+
+    /// The width and height of the grid.
+    pub size: (usize, usize),
+}
+
+impl Display for OutOfBounds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let ((x, y), (width, height)) = (self.point, self.size);
+        write!(f, "Point ({x}, {y}) out of bounds of {width}x{height} grid")
+    }
+}
+
+impl Error for OutOfBounds {}
@@ -0,0 +1,378 @@
+//! Deterministic, seeded generation of grids from a pipeline of named stages.
+//!
+//! See [`Pipeline`] for details.
+
+use crate::{
+    grid::Grid,
+    progress::{Cancelled, Monitor},
+};
+
+/// A small, fast, deterministic pseudo-random number generator ([SplitMix64]).
+///
+/// This is not cryptographically secure; it exists so that generation is reproducible across
+/// platforms and versions without depending on an external crate.
+///
+/// [SplitMix64]: https://prng.di.unimi.it/splitmix64.c
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a new generator from a `seed`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::gen::Rng;
+    ///
+    /// let mut a = Rng::new(42);
+    /// let mut b = Rng::new(42);
+    /// assert_eq!(a.next_u64(), b.next_u64());
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next random `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::gen::Rng;
+    ///
+    /// let mut rng = Rng::new(0);
+    /// assert_ne!(rng.next_u64(), rng.next_u64());
+    /// ```
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random `f64` in the range `[0, 1)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::gen::Rng;
+    ///
+    /// let mut rng = Rng::new(0);
+    /// let n = rng.next_f64();
+    /// assert!((0.0..1.0).contains(&n));
+    /// ```
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random `usize` in the range `[0, bound)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::gen::Rng;
+    ///
+    /// let mut rng = Rng::new(0);
+    /// assert!(rng.below(6) < 6);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `bound` is `0`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "Bound must be greater than 0");
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Returns `true` with the given `probability`, in the range `[0, 1]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::gen::Rng;
+    ///
+    /// let mut rng = Rng::new(0);
+    /// assert!(rng.chance(1.0));
+    /// assert!(!rng.chance(0.0));
+    /// ```
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+}
+
+/// A named stage of a [`Pipeline`].
+type Stage<'a, T> = (String, Box<dyn FnMut(&mut Grid<T>, &mut Rng) + 'a>);
+
+/// A reproducible chain of generators and filters, driven by a single seed.
+///
+/// Each stage receives the grid produced by the previous stage, and its own [`Rng`] derived from
+/// the pipeline's seed and the stage's name. As a result, running the same pipeline with the same
+/// seed always produces the same grid, and adding, removing, or reordering _other_ stages does not
+/// change the random numbers a stage observes.
+///
+/// # Examples
+///
+/// ```
+/// use grud::gen::Pipeline;
+///
+/// let pipeline = || {
+///     Pipeline::new(8, 8, 0.0, 1234)
+///         .stage("noise", |grid, rng| {
+///             for cell in grid {
+///                 *cell = rng.next_f64();
+///             }
+///         })
+///         .stage("threshold", |grid, _| {
+///             for cell in grid {
+///                 *cell = if *cell > 0.5 { 1.0 } else { 0.0 };
+///             }
+///         })
+/// };
+///
+/// let a = pipeline().run();
+/// let b = pipeline().run();
+/// assert_eq!(a.as_vec(), b.as_vec());
+/// ```
+pub struct Pipeline<'a, T>
+where
+    T: Clone,
+{
+    width: usize,
+    height: usize,
+    initial: T,
+    seed: u64,
+    stages: Vec<Stage<'a, T>>,
+}
+
+impl<'a, T> Pipeline<'a, T>
+where
+    T: Clone,
+{
+    /// Creates a new pipeline producing grids of `width` and `height`, initially filled with
+    /// `initial`, and driven by `seed`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::gen::Pipeline;
+    ///
+    /// let grid = Pipeline::new(2, 3, '.', 0).run();
+    /// assert_eq!(grid.as_vec(), &vec!['.'; 6]);
+    /// ```
+    pub fn new(width: usize, height: usize, initial: T, seed: u64) -> Self {
+        Self {
+            width,
+            height,
+            initial,
+            seed,
+            stages: Vec::new(),
+        }
+    }
+
+    /// Appends a stage called `name` that transforms the grid in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::gen::Pipeline;
+    ///
+    /// let grid = Pipeline::new(2, 2, 0, 0)
+    ///     .stage("fill", |grid, _| grid[(1, 1)] = 1)
+    ///     .run();
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![0, 0, 0, 1]);
+    /// ```
+    pub fn stage(
+        mut self,
+        name: impl Into<String>,
+        stage: impl FnMut(&mut Grid<T>, &mut Rng) + 'a,
+    ) -> Self {
+        self.stages.push((name.into(), Box::new(stage)));
+        self
+    }
+
+    /// Returns the names of the stages, in the order they run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::gen::Pipeline;
+    ///
+    /// let pipeline = Pipeline::new(1, 1, 0, 0)
+    ///     .stage("noise", |_, _| {})
+    ///     .stage("smooth", |_, _| {});
+    ///
+    /// assert_eq!(pipeline.stage_names().collect::<Vec<_>>(), vec!["noise", "smooth"]);
+    /// ```
+    pub fn stage_names(&self) -> impl Iterator<Item = &str> {
+        self.stages.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Runs every stage in order, returning the final grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::gen::Pipeline;
+    ///
+    /// let grid = Pipeline::new(3, 1, 0, 7)
+    ///     .stage("count", |grid, _| {
+    ///         for (i, cell) in grid.into_iter().enumerate() {
+    ///             *cell = i;
+    ///         }
+    ///     })
+    ///     .run();
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![0, 1, 2]);
+    /// ```
+    pub fn run(self) -> Grid<T> {
+        match self.run_monitored(&mut Monitor::new()) {
+            Ok(grid) => grid,
+            Err(Cancelled) => unreachable!("Monitor without a token is never cancelled"),
+        }
+    }
+
+    /// Runs every stage in order, returning the final grid and a snapshot taken after each stage.
+    ///
+    /// Snapshots are useful for debugging, i.e. to see which stage introduced an artifact.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::gen::Pipeline;
+    ///
+    /// let (grid, snapshots) = Pipeline::new(1, 1, 0, 0)
+    ///     .stage("one", |grid, _| grid[0] = 1)
+    ///     .stage("two", |grid, _| grid[0] = 2)
+    ///     .run_with_snapshots();
+    ///
+    /// assert_eq!(grid[0], 2);
+    /// assert_eq!(snapshots[0].0, "one");
+    /// assert_eq!(snapshots[0].1[0], 1);
+    /// assert_eq!(snapshots[1].0, "two");
+    /// assert_eq!(snapshots[1].1[0], 2);
+    /// ```
+    pub fn run_with_snapshots(self) -> (Grid<T>, Vec<(String, Grid<T>)>) {
+        let mut snapshots = Vec::with_capacity(self.stages.len());
+        let grid = self.run_inner(
+            |name, grid| snapshots.push((name.to_string(), grid.clone())),
+            &mut Monitor::new(),
+        );
+        match grid {
+            Ok(grid) => (grid, snapshots),
+            Err(Cancelled) => unreachable!("Monitor without a token is never cancelled"),
+        }
+    }
+
+    /// Runs every stage in order, observed by a [`Monitor`], returning the final grid.
+    ///
+    /// Progress is reported as the number of stages completed, and cancellation is checked before
+    /// each stage runs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{gen::Pipeline, progress::Monitor};
+    ///
+    /// let mut stages = Vec::new();
+    /// let mut sink = |completed, total| stages.push((completed, total));
+    ///
+    /// let grid = Pipeline::new(1, 1, 0, 0)
+    ///     .stage("one", |grid, _| grid[0] += 1)
+    ///     .stage("two", |grid, _| grid[0] += 1)
+    ///     .run_monitored(&mut Monitor::new().with_sink(&mut sink))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(grid[0], 2);
+    /// assert_eq!(stages, vec![(0, 2), (1, 2), (2, 2)]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the monitor's [`CancelToken`](crate::progress::CancelToken) is cancelled.
+    pub fn run_monitored(self, monitor: &mut Monitor<'_>) -> Result<Grid<T>, Cancelled> {
+        self.run_inner(|_, _| {}, monitor)
+    }
+
+    /// Runs every stage in order, invoking `after` once each stage has completed.
+    fn run_inner(
+        self,
+        mut after: impl FnMut(&str, &Grid<T>),
+        monitor: &mut Monitor<'_>,
+    ) -> Result<Grid<T>, Cancelled> {
+        let total = self.stages.len();
+        let mut grid = Grid::new(self.width, self.height, self.initial);
+        for (i, (name, mut stage)) in self.stages.into_iter().enumerate() {
+            monitor.update(i, total)?;
+            let mut rng = Rng::new(self.seed ^ fnv1a(name.as_bytes()));
+            stage(&mut grid, &mut rng);
+            after(&name, &grid);
+        }
+        monitor.update(total, total)?;
+        Ok(grid)
+    }
+}
+
+/// Hashes `bytes` using [FNV-1a], which is stable across platforms and versions.
+///
+/// [FNV-1a]: https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xCBF2_9CE4_8422_2325, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noise(grid: &mut Grid<u64>, rng: &mut Rng) {
+        for cell in grid {
+            *cell = rng.next_u64();
+        }
+    }
+
+    #[test]
+    fn rng_is_stable() {
+        let mut rng = Rng::new(1234567);
+
+        assert_eq!(rng.next_u64(), 6457827717110365317);
+        assert_eq!(rng.next_u64(), 3203168211198807973);
+    }
+
+    #[test]
+    fn rng_below_in_range() {
+        let mut rng = Rng::new(0);
+
+        assert!((0..1000).all(|_| rng.below(3) < 3));
+    }
+
+    #[test]
+    fn pipeline_different_seeds() {
+        let a = Pipeline::new(4, 4, 0, 1).stage("noise", noise).run();
+        let b = Pipeline::new(4, 4, 0, 2).stage("noise", noise).run();
+
+        assert_ne!(a.as_vec(), b.as_vec());
+    }
+
+    #[test]
+    fn pipeline_stage_rng_independent_of_other_stages() {
+        let a = Pipeline::new(4, 4, 0, 1).stage("noise", noise).run();
+        let b = Pipeline::new(4, 4, 0, 1)
+            .stage("other", noise)
+            .stage("noise", noise)
+            .run();
+
+        assert_eq!(a.as_vec(), b.as_vec());
+    }
+
+    #[test]
+    fn pipeline_empty_snapshots() {
+        let (grid, snapshots) = Pipeline::new(1, 1, 0, 0).run_with_snapshots();
+
+        assert_eq!(grid.as_vec(), &vec![0]);
+        assert!(snapshots.is_empty());
+    }
+}
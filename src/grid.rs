@@ -2,13 +2,22 @@
 //!
 //! See [`Grid`] for details.
 
-use std::{
+use alloc::{vec, vec::Vec};
+use core::{
+    convert::Infallible,
     fmt::{Debug, Display},
     ops::{Index, IndexMut},
     slice::{Iter, IterMut},
 };
+#[cfg(feature = "std")]
+use std::{collections::HashMap, hash::Hash};
 
-use crate::point::Point;
+#[cfg(feature = "std")]
+use crate::progress::{Cancelled, Monitor};
+use crate::{
+    error::{GridError, OutOfBounds},
+    point::{Connectivity, Direction, Point},
+};
 
 /// A [dense] fixed-size grid that stores elements using a [`Vec`].
 ///
@@ -22,136 +31,1309 @@ where
     width: usize,
 }
 
-impl<T> Grid<T>
-where
-    T: Clone,
-{
-    /// Creates a new grid of the specified `width` and `height`, filling with `default`.
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Creates a new grid of the specified `width` and `height`, filling with `default`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let _ = Grid::new(3, 3, 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width * height` overflows a [`usize`].
+    pub fn new(width: usize, height: usize, default: T) -> Self {
+        let area = width
+            .checked_mul(height)
+            .unwrap_or_else(|| panic!("Area of {width}x{height} grid overflows usize"));
+        Self {
+            data: vec![default; area],
+            width,
+        }
+    }
+
+    /// Creates a new grid of the specified `width` and `height`, filling with `default`, or returns
+    /// an error if the grid is too large to allocate.
+    ///
+    /// Unlike [`Grid::new`], this never panics or aborts due to the size of the grid, so it is
+    /// suitable for dimensions from untrusted input; see [`Grid::try_new_with_limit`] to also
+    /// enforce a maximum number of cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, error::GridError};
+    ///
+    /// assert!(Grid::try_new(3, 3, 0).is_ok());
+    /// assert!(matches!(
+    ///     Grid::try_new(usize::MAX, 2, 0),
+    ///     Err(GridError::TooLarge { .. }),
+    /// ));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the grid would take up more than [`isize::MAX`] bytes, or allocation fails.
+    pub fn try_new(width: usize, height: usize, default: T) -> Result<Self, GridError> {
+        Self::try_new_with_limit(width, height, default, usize::MAX)
+    }
+
+    /// Creates a new grid of the specified `width` and `height`, filling with `default`, or returns
+    /// an error if the grid has more than `limit` cells or is too large to allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, error::GridError};
+    ///
+    /// assert!(Grid::try_new_with_limit(100, 100, 0, 10_000).is_ok());
+    /// assert_eq!(
+    ///     Grid::try_new_with_limit(100, 101, 0, 10_000).unwrap_err(),
+    ///     GridError::LimitExceeded { width: 100, height: 101, limit: 10_000 },
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the grid would have more than `limit` cells, would take up more than [`isize::MAX`]
+    /// bytes, or allocation fails.
+    pub fn try_new_with_limit(
+        width: usize,
+        height: usize,
+        default: T,
+        limit: usize,
+    ) -> Result<Self, GridError> {
+        let too_large = GridError::TooLarge { width, height };
+        let area = width.checked_mul(height).ok_or(too_large)?;
+        let bytes = area
+            .checked_mul(core::mem::size_of::<T>())
+            .ok_or(too_large)?;
+        if bytes > isize::MAX as usize {
+            return Err(too_large);
+        }
+        if area > limit {
+            return Err(GridError::LimitExceeded {
+                width,
+                height,
+                limit,
+            });
+        }
+        let mut data = Vec::new();
+        data.try_reserve_exact(area)
+            .map_err(|_| GridError::AllocationFailed { width, height })?;
+        data.resize(area, default);
+        Ok(Self { data, width })
+    }
+
+    /// Creates a new grid of the specified `width`, inferring height from the length of the `data`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(grid.width(), 2);
+    /// assert_eq!(grid.height(), 3);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `data.len()` is not evenly divisble by `width`; see [`Grid::try_with_width`] for a
+    /// non-panicking alternative.
+    pub fn with_width(width: usize, data: Vec<T>) -> Self {
+        assert_eq!(
+            data.len() % width,
+            0,
+            "Data length {} not divisible by {width}",
+            data.len()
+        );
+        Self { data, width }
+    }
+
+    /// Creates a new grid of the specified `width`, inferring height from the length of the `data`,
+    /// or returns an error if the length is not a multiple of `width`.
+    ///
+    /// This is the non-panicking equivalent of [`Grid::with_width`], i.e. for data read from a
+    /// file. A `width` of `0` is only allowed if `data` is empty, which creates an empty grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, error::GridError};
+    ///
+    /// assert!(Grid::try_with_width(2, vec![1, 2, 3, 4]).is_ok());
+    /// assert_eq!(
+    ///     Grid::try_with_width(2, vec![1, 2, 3]).unwrap_err(),
+    ///     GridError::LengthNotDivisible { length: 3, width: 2 },
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `data.len()` is not evenly divisible by `width`.
+    pub fn try_with_width(width: usize, data: Vec<T>) -> Result<Self, GridError> {
+        let length = data.len();
+        match length.checked_rem(width) {
+            Some(0) => Ok(Self { data, width }),
+            None if length == 0 => Ok(Self::from(Vec::new())),
+            _ => Err(GridError::LengthNotDivisible { length, width }),
+        }
+    }
+
+    /// Creates a new grid from an iterator of rows, each an iterator of cells.
+    ///
+    /// Unlike converting from a [`Vec`] of [`Vec`]s, cells are moved directly into the grid without
+    /// collecting each row first.
+    ///
+    /// # Examples
+    ///
+    /// Parsing a grid of digits:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let text = "123\n456";
+    /// let grid = Grid::from_rows(text.lines().map(|line| line.chars()));
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![vec!['1', '2', '3'], vec!['4', '5', '6']]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If every row is not the same length.
+    pub fn from_rows<R>(rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = T>,
+    {
+        Self::try_from_rows(rows).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Creates a new grid from an iterator of rows, each an iterator of cells, or returns an error
+    /// if every row is not the same length.
+    ///
+    /// This is the non-panicking equivalent of [`Grid::from_rows`], and also accepts a [`Vec`] of
+    /// [`Vec`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, error::GridError};
+    ///
+    /// assert!(Grid::try_from_rows(vec![vec![1, 2], vec![3, 4]]).is_ok());
+    /// assert_eq!(
+    ///     Grid::try_from_rows(vec![vec![1, 2], vec![3]]).unwrap_err(),
+    ///     GridError::RaggedRow { row: 1, expected: 2, found: 1 },
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If every row is not the same length.
+    pub fn try_from_rows<R>(rows: impl IntoIterator<Item = R>) -> Result<Self, GridError>
+    where
+        R: IntoIterator<Item = T>,
+    {
+        let mut data = Vec::new();
+        let mut width = None;
+        for (row, cells) in rows.into_iter().enumerate() {
+            let start = data.len();
+            data.extend(cells);
+            let found = data.len() - start;
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(GridError::RaggedRow {
+                    row,
+                    expected,
+                    found,
+                });
+            }
+        }
+        Ok(match width {
+            Some(width) if width > 0 => Self { data, width },
+            _ => Self::from(Vec::new()),
+        })
+    }
+
+    /// Creates a new grid from an iterator of columns, each an iterator of cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::from_cols([[1, 2, 3], [4, 5, 6]]);
+    /// assert_eq!(grid.to_matrix(), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If every column is not the same length.
+    pub fn from_cols<C>(cols: impl IntoIterator<Item = C>) -> Self
+    where
+        C: IntoIterator<Item = T>,
+    {
+        Self::from_rows(cols).transpose()
+    }
+
+    /// Returns the grid represnted as a flattened 2-dimensional vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::from(vec![
+    ///   vec![1, 2],
+    ///   vec![3, 4],
+    ///   vec![5, 6],
+    /// ]);
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![1, 2, 3, 4, 5, 6]);
+    /// ```
+    pub fn as_vec(&self) -> &Vec<T> {
+        &self.data
+    }
+
+    /// Returns the grid represented as a flattened 2-dimensional vector, consuming the grid.
+    ///
+    /// Unlike `as_vec().clone()`, no cells are cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// assert_eq!(grid.into_vec(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Returns the flattened 2-dimensional vector and width of the grid, consuming the grid.
+    ///
+    /// The grid can be recreated with [`Grid::with_width`], unless it was empty.
+    ///
+    /// # Examples
+    ///
+    /// Handing cells to an API that expects a buffer and a stride:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 2, 3, 4, 5, 6]);
+    /// let (data, width) = grid.into_raw_parts();
+    ///
+    /// assert_eq!(data, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(width, 3);
+    /// assert_eq!(Grid::with_width(width, data).to_matrix(), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    /// ```
+    pub fn into_raw_parts(self) -> (Vec<T>, usize) {
+        (self.data, self.width)
+    }
+
+    /// Returns the grid represnted by a multi-dimensional matrix (i.e. vector of vectors).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(grid.to_matrix(), vec![
+    ///     vec![1, 2],
+    ///     vec![3, 4],
+    ///     vec![5, 6],
+    /// ]);
+    /// ```
+    pub fn to_matrix(&self) -> Vec<Vec<T>> {
+        let mut data = Vec::<Vec<T>>::with_capacity(self.height());
+        for j in 0..self.height() {
+            let mut row = Vec::<T>::with_capacity(self.width());
+            for i in 0..self.width() {
+                row.push(self[(i, j)].clone());
+            }
+            data.push(row);
+        }
+        data
+    }
+
+    /// Returns the width of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::new(2, 3, 0);
+    /// assert_eq!(grid.width(), 2);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::new(2, 3, 0);
+    /// assert_eq!(grid.height(), 3);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.data.len() / self.width()
+    }
+
+    /// Returns the total size of the grid as represented by `width * height`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::new(2, 3, 0);
+    /// assert_eq!(grid.area(), 2 * 3);
+    /// ```
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    /// Returns a reference to the cell at `point`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    ///
+    /// assert_eq!(grid.get((1, 1)), Some(&4));
+    /// assert_eq!(grid.get((2, 0)), None);
+    /// ```
+    pub fn get(&self, point: impl Point) -> Option<&T> {
+        let index = self.bounded_index(point)?;
+        Some(&self.data[index])
+    }
+
+    /// Returns a reference to the cell at `point`, or an error describing the point and the size
+    /// of the grid if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, error::OutOfBounds};
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    ///
+    /// assert_eq!(grid.try_index((1, 1)), Ok(&4));
+    /// assert_eq!(
+    ///     grid.try_index((2, 0)),
+    ///     Err(OutOfBounds { point: (2, 0), size: (2, 2) }),
+    /// );
+    /// assert_eq!(
+    ///     grid.try_index((2, 0)).unwrap_err().to_string(),
+    ///     "Point (2, 0) out of bounds of 2x2 grid",
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `point` is out of bounds.
+    pub fn try_index(&self, point: impl Point) -> Result<&T, OutOfBounds> {
+        let index = self.try_checked_index(point)?;
+        Ok(&self.data[index])
+    }
+
+    /// Returns a mutable reference to the cell at `point`, or an error describing the point and
+    /// the size of the grid if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 0);
+    /// *grid.try_index_mut((0, 1)).unwrap() = 1;
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![0, 0, 1, 0]);
+    /// assert!(grid.try_index_mut((0, 2)).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `point` is out of bounds.
+    pub fn try_index_mut(&mut self, point: impl Point) -> Result<&mut T, OutOfBounds> {
+        let index = self.try_checked_index(point)?;
+        Ok(&mut self.data[index])
+    }
+
+    /// Returns a mutable reference to the cell at `point`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 0);
+    /// *grid.get_mut((0, 1)).unwrap() = 1;
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![0, 0, 1, 0]);
+    /// assert_eq!(grid.get_mut((0, 2)), None);
+    /// ```
+    pub fn get_mut(&mut self, point: impl Point) -> Option<&mut T> {
+        let index = self.bounded_index(point)?;
+        Some(&mut self.data[index])
+    }
+
+    /// Returns a reference to the cell `(dx, dy)` away from `point`, or `None` if it is out of
+    /// bounds.
+    ///
+    /// This avoids underflow when looking at cells to the left of or above `point`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    ///
+    /// assert_eq!(grid.get_offset((1, 1), -1, -1), Some(&1));
+    /// assert_eq!(grid.get_offset((0, 0), -1, 0), None);
+    /// ```
+    pub fn get_offset(&self, point: impl Point, dx: isize, dy: isize) -> Option<&T> {
+        self.get(point.offset(dx, dy)?)
+    }
+
+    /// Returns a mutable reference to the cell `(dx, dy)` away from `point`, or `None` if it is
+    /// out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 1, 0);
+    /// *grid.get_offset_mut((0, 0), 1, 0).unwrap() = 1;
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![0, 1]);
+    /// ```
+    pub fn get_offset_mut(&mut self, point: impl Point, dx: isize, dy: isize) -> Option<&mut T> {
+        self.get_mut(point.offset(dx, dy)?)
+    }
+
+    /// Returns a reference to the cell next to `point` in `direction`, or `None` if it is out of
+    /// bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Direction};
+    ///
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    ///
+    /// assert_eq!(grid.neighbor((0, 0), Direction::SouthEast), Some(&4));
+    /// assert_eq!(grid.neighbor((0, 0), Direction::West), None);
+    /// ```
+    pub fn neighbor(&self, point: impl Point, direction: Direction) -> Option<&T> {
+        self.get(point.step(direction)?)
+    }
+
+    /// Returns a mutable reference to the cell next to `point` in `direction`, or `None` if it is
+    /// out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Direction};
+    ///
+    /// let mut grid = Grid::new(2, 1, 0);
+    /// *grid.neighbor_mut((0, 0), Direction::East).unwrap() = 1;
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![0, 1]);
+    /// ```
+    pub fn neighbor_mut(&mut self, point: impl Point, direction: Direction) -> Option<&mut T> {
+        self.get_mut(point.step(direction)?)
+    }
+
+    /// Replaces the cells of row `y` with clones of `row`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(3, 2, 0);
+    /// grid.set_row(1, &[1, 2, 3]);
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![vec![0, 0, 0], vec![1, 2, 3]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `y` is out of bounds, or `row` is not the same length as the width of the grid.
+    pub fn set_row(&mut self, y: usize, row: &[T]) {
+        let (width, height) = self.size();
+        assert!(y < height, "Row {y} out of bounds of {width}x{height} grid");
+        assert_eq!(
+            row.len(),
+            width,
+            "Row has {} cells, expected {width}",
+            row.len()
+        );
+        self.data[y * width..(y + 1) * width].clone_from_slice(row);
+    }
+
+    /// Replaces the cells of column `x` with clones of `col`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 3, 0);
+    /// grid.set_col(0, &[1, 2, 3]);
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![vec![1, 0], vec![2, 0], vec![3, 0]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `x` is out of bounds, or `col` is not the same length as the height of the grid.
+    pub fn set_col(&mut self, x: usize, col: &[T]) {
+        let (width, height) = self.size();
+        assert!(
+            x < width,
+            "Column {x} out of bounds of {width}x{height} grid"
+        );
+        assert_eq!(
+            col.len(),
+            height,
+            "Column has {} cells, expected {height}",
+            col.len()
+        );
+        for (cell, value) in self.data[x..].iter_mut().step_by(width).zip(col) {
+            cell.clone_from(value);
+        }
+    }
+
+    /// Inserts `row` as row `y`, shifting later rows down.
+    ///
+    /// If the grid is empty, `row` becomes its only row (and sets its width).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::with_width(2, vec![1, 2, 5, 6]);
+    /// grid.insert_row(1, [3, 4]);
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `y` is greater than the height, or `row` is not the same length as the width of the
+    /// grid.
+    pub fn insert_row(&mut self, y: usize, row: impl IntoIterator<Item = T>) {
+        let (width, height) = self.size();
+        assert!(
+            y <= height,
+            "Cannot insert row {y} into {width}x{height} grid"
+        );
+        let row: Vec<T> = row.into_iter().collect();
+        if self.data.is_empty() {
+            if !row.is_empty() {
+                *self = Self::with_width(row.len(), row);
+            }
+            return;
+        }
+        assert_eq!(
+            row.len(),
+            width,
+            "Row has {} cells, expected {width}",
+            row.len()
+        );
+        self.data.splice(y * width..y * width, row);
+    }
+
+    /// Removes and returns row `y`, shifting later rows up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::with_width(2, vec![1, 2, 3, 4, 5, 6]);
+    ///
+    /// assert_eq!(grid.remove_row(0), vec![1, 2]);
+    /// assert_eq!(grid.to_matrix(), vec![vec![3, 4], vec![5, 6]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `y` is out of bounds.
+    pub fn remove_row(&mut self, y: usize) -> Vec<T> {
+        let (width, height) = self.size();
+        assert!(y < height, "Row {y} out of bounds of {width}x{height} grid");
+        self.data.drain(y * width..(y + 1) * width).collect()
+    }
+
+    /// Inserts `col` as column `x`, shifting later columns right.
+    ///
+    /// If the grid is empty, `col` becomes its only column (and sets its height).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::with_width(2, vec![1, 3, 4, 6]);
+    /// grid.insert_col(1, [2, 5]);
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `x` is greater than the width, or `col` is not the same length as the height of the
+    /// grid.
+    pub fn insert_col(&mut self, x: usize, col: impl IntoIterator<Item = T>) {
+        let (width, height) = self.size();
+        assert!(
+            x <= width,
+            "Cannot insert column {x} into {width}x{height} grid"
+        );
+        let col: Vec<T> = col.into_iter().collect();
+        if self.data.is_empty() {
+            if !col.is_empty() {
+                *self = Self::with_width(1, col);
+            }
+            return;
+        }
+        assert_eq!(
+            col.len(),
+            height,
+            "Column has {} cells, expected {height}",
+            col.len()
+        );
+        let mut data = Vec::with_capacity(self.data.len() + height);
+        let mut col = col.into_iter();
+        for (i, cell) in core::mem::take(&mut self.data).into_iter().enumerate() {
+            if i % width == x {
+                data.extend(col.next());
+            }
+            data.push(cell);
+            if x == width && i % width == width - 1 {
+                data.extend(col.next());
+            }
+        }
+        self.data = data;
+        self.width += 1;
+    }
+
+    /// Removes and returns column `x`, shifting later columns left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::with_width(3, vec![1, 2, 3, 4, 5, 6]);
+    ///
+    /// assert_eq!(grid.remove_col(2), vec![3, 6]);
+    /// assert_eq!(grid.to_matrix(), vec![vec![1, 2], vec![4, 5]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `x` is out of bounds.
+    pub fn remove_col(&mut self, x: usize) -> Vec<T> {
+        let (width, height) = self.size();
+        assert!(
+            x < width,
+            "Column {x} out of bounds of {width}x{height} grid"
+        );
+        let mut data = Vec::with_capacity(self.data.len() - height);
+        let mut removed = Vec::with_capacity(height);
+        for (i, cell) in core::mem::take(&mut self.data).into_iter().enumerate() {
+            if i % width == x {
+                removed.push(cell);
+            } else {
+                data.push(cell);
+            }
+        }
+        self.data = data;
+        self.width -= 1;
+        removed
+    }
+
+    /// Returns the point and cell of the first cell, in row-major order, that matches `predicate`.
+    ///
+    /// # Examples
+    ///
+    /// Locating the start of a parsed map:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, "#.#.S.#.#".chars().collect());
+    ///
+    /// assert_eq!(grid.find(|cell| *cell == 'S'), Some(((1, 1), &'S')));
+    /// assert_eq!(grid.find(|cell| *cell == 'E'), None);
+    /// ```
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<((usize, usize), &T)> {
+        let (index, cell) = self
+            .data
+            .iter()
+            .enumerate()
+            .find(|(_, cell)| predicate(cell))?;
+        let width = self.width();
+        Some(((index % width, index / width), cell))
+    }
+
+    /// Returns an iterator over the point and cell of every cell, in row-major order, that matches
+    /// `predicate`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 5, 2, 7, 3, 9]);
+    /// let large: Vec<_> = grid.find_all(|cell| *cell > 4).collect();
+    ///
+    /// assert_eq!(large, vec![((1, 0), &5), ((0, 1), &7), ((2, 1), &9)]);
+    /// ```
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
+        let width = self.width;
+        self.data
+            .iter()
+            .enumerate()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(move |(index, cell)| ((index % width, index / width), cell))
+    }
+
+    /// Returns an iterator over the point of every cell, in row-major order, that matches
+    /// `predicate`.
+    ///
+    /// # Examples
+    ///
+    /// Collecting all asteroid positions:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, ".#.#..#.#".chars().collect());
+    /// let asteroids: Vec<_> = grid.points_where(|cell| *cell == '#').collect();
+    ///
+    /// assert_eq!(asteroids, vec![(1, 0), (0, 1), (0, 2), (2, 2)]);
+    /// ```
+    pub fn points_where<'a>(
+        &'a self,
+        predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.find_all(predicate).map(|(point, _)| point)
+    }
+
+    /// Returns an iterator over the point and owned value of every cell, in row-major order,
+    /// consuming the grid.
+    ///
+    /// # Examples
+    ///
+    /// Moving entities out of a grid without cloning them:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![None, Some(String::from("orc")), None, None]);
+    /// let entities: Vec<_> = grid
+    ///     .into_iter_points()
+    ///     .filter_map(|(point, cell)| Some((point, cell?)))
+    ///     .collect();
+    ///
+    /// assert_eq!(entities, vec![((1, 0), String::from("orc"))]);
+    /// ```
+    pub fn into_iter_points(self) -> impl Iterator<Item = ((usize, usize), T)> {
+        let (width, _) = self.size();
+        self.data
+            .into_iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index % width, index / width), cell))
+    }
+
+    /// Returns the point of the first cell, in row-major order, that is equal to `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![0, 1, 1, 0]);
+    ///
+    /// assert_eq!(grid.position_of(&1), Some((1, 0)));
+    /// assert_eq!(grid.position_of(&2), None);
+    /// ```
+    pub fn position_of(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.find(|cell| cell == value).map(|(point, _)| point)
+    }
+
+    /// Returns the number of cells that match `predicate`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![Some(1), None, Some(3), None, None, Some(6)]);
+    ///
+    /// assert_eq!(grid.count(Option::is_some), 3);
+    /// ```
+    pub fn count(&self, mut predicate: impl FnMut(&T) -> bool) -> usize {
+        self.data.iter().filter(|cell| predicate(cell)).count()
+    }
+
+    /// Returns the number of cells with each distinct value.
+    ///
+    /// Each distinct value is cloned once.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::collections::HashMap;
+    ///
     /// use grud::Grid;
     ///
-    /// let _ = Grid::new(3, 3, 0);
+    /// let grid = Grid::with_width(3, "#.##..".chars().collect());
+    ///
+    /// assert_eq!(grid.value_counts(), HashMap::from([('#', 3), ('.', 3)]));
     /// ```
-    pub fn new(width: usize, height: usize, default: T) -> Self {
-        Self {
-            data: vec![default; width * height],
-            width,
+    #[cfg(feature = "std")]
+    pub fn value_counts(&self) -> HashMap<T, usize>
+    where
+        T: Eq + Hash,
+    {
+        let mut counts = HashMap::new();
+        for cell in &self.data {
+            match counts.get_mut(cell) {
+                Some(count) => *count += 1,
+                None => {
+                    counts.insert(cell.clone(), 1);
+                }
+            }
         }
+        counts
     }
 
-    /// Creates a new grid of the specified `width`, inferring height from the length of the `data`.
+    /// Returns clones of the cells at `points`, in order.
     ///
     /// # Examples
     ///
     /// ```
     /// use grud::Grid;
     ///
-    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4, 5, 6]);
-    /// assert_eq!(grid.width(), 2);
-    /// assert_eq!(grid.height(), 3);
+    /// let grid = Grid::with_width(3, (0..9).collect());
+    ///
+    /// assert_eq!(grid.gather([(0, 0), (2, 1), (0, 0)]), vec![0, 5, 0]);
     /// ```
     ///
     /// # Panics
     ///
-    /// If `data.len()` is not evenly divisble by `width`.
-    pub fn with_width(width: usize, data: Vec<T>) -> Self {
+    /// If any point is out of bounds.
+    pub fn gather<P>(&self, points: impl IntoIterator<Item = P>) -> Vec<T>
+    where
+        P: Point,
+    {
+        points
+            .into_iter()
+            .map(|point| self.data[self.checked_index(point)].clone())
+            .collect()
+    }
+
+    /// Sets the cell at each of `points` to the corresponding value in `values`, in order.
+    ///
+    /// Every point is checked before any cell is set, so the grid is unchanged if this panics. If a
+    /// point appears more than once, the last value is kept.
+    ///
+    /// # Examples
+    ///
+    /// Applying a turn's worth of changes computed elsewhere:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(3, 2, '.');
+    /// let changes = vec![((0, 0), '@'), ((2, 1), '$')];
+    /// grid.scatter(changes.iter().map(|(p, _)| *p), changes.iter().map(|(_, v)| *v));
+    ///
+    /// assert_eq!(grid.to_string(), "@..\n..$\n");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If any point is out of bounds, or there are not the same number of points and values.
+    pub fn scatter<P>(
+        &mut self,
+        points: impl IntoIterator<Item = P>,
+        values: impl IntoIterator<Item = T>,
+    ) where
+        P: Point,
+    {
+        let indices: Vec<_> = points
+            .into_iter()
+            .map(|point| self.checked_index(point))
+            .collect();
+        let values: Vec<_> = values.into_iter().collect();
         assert_eq!(
-            data.len() % width,
-            0,
-            "Data length {} not divisible by {width}",
-            data.len()
+            indices.len(),
+            values.len(),
+            "Scattering {} values to {} points",
+            values.len(),
+            indices.len()
         );
-        Self { data, width }
+        for (index, value) in indices.into_iter().zip(values) {
+            self.data[index] = value;
+        }
     }
 
-    /// Returns the grid represnted as a flattened 2-dimensional vector.
+    /// Returns every point in the connected region containing `start`, where cells match
+    /// `predicate` and are adjacent according to `connectivity`.
+    ///
+    /// Points are returned in the order they were visited. If the cell at `start` does not match
+    /// `predicate`, the region is empty.
     ///
     /// # Examples
     ///
+    /// Measuring an enclosed area:
+    ///
     /// ```
-    /// use grud::Grid;
+    /// use grud::{Grid, point::Connectivity};
     ///
     /// let grid = Grid::from(vec![
-    ///   vec![1, 2],
-    ///   vec![3, 4],
-    ///   vec![5, 6],
+    ///     "#####".chars().collect(),
+    ///     "#..##".chars().collect(),
+    ///     "#.#.#".chars().collect(),
+    ///     "#####".chars().collect(),
     /// ]);
     ///
-    /// assert_eq!(grid.as_vec(), &vec![1, 2, 3, 4, 5, 6]);
+    /// let four = grid.flood_region((1, 1), |c| *c == '.', Connectivity::Four);
+    /// assert_eq!(four.len(), 3);
+    ///
+    /// let eight = grid.flood_region((1, 1), |c| *c == '.', Connectivity::Eight);
+    /// assert_eq!(eight.len(), 4);
     /// ```
-    pub fn as_vec(&self) -> &Vec<T> {
-        &self.data
+    ///
+    /// # Panics
+    ///
+    /// If `start` is out of bounds.
+    pub fn flood_region(
+        &self,
+        start: impl Point,
+        predicate: impl FnMut(&T) -> bool,
+        connectivity: Connectivity,
+    ) -> Vec<(usize, usize)> {
+        match self.flood_region_with(start, predicate, connectivity, |_, _| {
+            Ok::<_, Infallible>(())
+        }) {
+            Ok(region) => region,
+            Err(never) => match never {},
+        }
     }
 
-    /// Returns the grid represnted by a multi-dimensional matrix (i.e. vector of vectors).
+    /// Returns every point in the connected region containing `start`, observed by a
+    /// [`Monitor`](crate::progress::Monitor).
+    ///
+    /// Progress is reported as the number of cells visited, out of the total number of cells. See
+    /// [`Grid::flood_region`] for details.
     ///
     /// # Examples
     ///
     /// ```
-    /// use grud::Grid;
+    /// use grud::{Grid, point::Connectivity, progress::{CancelToken, Monitor}};
     ///
-    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4, 5, 6]);
-    /// assert_eq!(grid.to_matrix(), vec![
-    ///     vec![1, 2],
-    ///     vec![3, 4],
-    ///     vec![5, 6],
-    /// ]);
+    /// let grid = Grid::new(10, 10, 0);
+    /// let token = CancelToken::new();
+    /// let mut monitor = Monitor::new().with_cancel(&token);
+    ///
+    /// let region = grid.flood_region_monitored((0, 0), |c| *c == 0, Connectivity::Four, &mut monitor);
+    /// assert_eq!(region.unwrap().len(), 100);
+    ///
+    /// token.cancel();
+    /// let region = grid.flood_region_monitored((0, 0), |c| *c == 0, Connectivity::Four, &mut monitor);
+    /// assert!(region.is_err());
     /// ```
-    pub fn to_matrix(&self) -> Vec<Vec<T>> {
-        let mut data = Vec::<Vec<T>>::with_capacity(self.height());
-        for j in 0..self.height() {
-            let mut row = Vec::<T>::with_capacity(self.width());
-            for i in 0..self.width() {
-                row.push(self[(i, j)].clone());
+    ///
+    /// # Errors
+    ///
+    /// If the monitor's [`CancelToken`](crate::progress::CancelToken) is cancelled.
+    ///
+    /// # Panics
+    ///
+    /// If `start` is out of bounds.
+    #[cfg(feature = "std")]
+    pub fn flood_region_monitored(
+        &self,
+        start: impl Point,
+        predicate: impl FnMut(&T) -> bool,
+        connectivity: Connectivity,
+        monitor: &mut Monitor<'_>,
+    ) -> Result<Vec<(usize, usize)>, Cancelled> {
+        self.flood_region_with(start, predicate, connectivity, |completed, total| {
+            monitor.update(completed, total)
+        })
+    }
+
+    /// Returns every point in the connected region containing `start`, calling
+    /// `update(visited, area)` after visiting each cell and stopping early if it fails.
+    fn flood_region_with<E>(
+        &self,
+        start: impl Point,
+        mut predicate: impl FnMut(&T) -> bool,
+        connectivity: Connectivity,
+        mut update: impl FnMut(usize, usize) -> Result<(), E>,
+    ) -> Result<Vec<(usize, usize)>, E> {
+        let index = self.checked_index(start);
+        if !predicate(&self.data[index]) {
+            return Ok(vec![]);
+        }
+        let mut visited = vec![false; self.data.len()];
+        let mut stack = vec![(start.x(), start.y())];
+        let mut region = Vec::new();
+        visited[index] = true;
+        while let Some(point) = stack.pop() {
+            region.push(point);
+            update(region.len(), self.data.len())?;
+            for next in connectivity.neighbors(point, self.width(), self.height()) {
+                let index = next.to_index(self.width());
+                if !visited[index] && predicate(&self.data[index]) {
+                    visited[index] = true;
+                    stack.push(next);
+                }
             }
-            data.push(row);
         }
-        data
+        Ok(region)
     }
 
-    /// Returns the width of the grid.
+    /// Replaces every cell in the connected region containing `start` with `value`, where cells
+    /// match `predicate` and are adjacent according to `connectivity`.
+    ///
+    /// Returns how many cells were replaced. See [`Grid::flood_region`] to visit the region
+    /// without replacing it.
+    ///
+    /// # Examples
+    ///
+    /// A paint-bucket tool:
+    ///
+    /// ```
+    /// use grud::{Grid, point::Connectivity};
+    ///
+    /// let mut grid = Grid::with_width(3, vec![
+    ///     0, 0, 1,
+    ///     1, 0, 1,
+    ///     0, 1, 0,
+    /// ]);
+    ///
+    /// let filled = grid.flood_fill((0, 0), |c| *c == 0, 2, Connectivity::Four);
+    /// assert_eq!(filled, 3);
+    /// assert_eq!(grid.as_vec(), &vec![
+    ///     2, 2, 1,
+    ///     1, 2, 1,
+    ///     0, 1, 0,
+    /// ]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `start` is out of bounds.
+    pub fn flood_fill(
+        &mut self,
+        start: impl Point,
+        predicate: impl FnMut(&T) -> bool,
+        value: T,
+        connectivity: Connectivity,
+    ) -> usize {
+        let region = self.flood_region(start, predicate, connectivity);
+        for point in &region {
+            self[*point] = value.clone();
+        }
+        region.len()
+    }
+
+    /// Replaces every cell in the connected region containing `start` with `value`, observed by a
+    /// [`Monitor`](crate::progress::Monitor).
+    ///
+    /// Progress is reported as the number of cells visited, out of the total number of cells. If
+    /// cancelled, no cells are replaced. See [`Grid::flood_fill`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Connectivity, progress::Monitor};
+    ///
+    /// let mut grid = Grid::new(4, 4, 0);
+    /// let mut reports = Vec::new();
+    /// let mut sink = |completed, total| reports.push((completed, total));
+    ///
+    /// let filled = grid.flood_fill_monitored(
+    ///     (0, 0),
+    ///     |c| *c == 0,
+    ///     1,
+    ///     Connectivity::Four,
+    ///     &mut Monitor::new().with_sink(&mut sink),
+    /// );
+    ///
+    /// assert_eq!(filled, Ok(16));
+    /// assert_eq!(reports.last(), Some(&(16, 16)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the monitor's [`CancelToken`](crate::progress::CancelToken) is cancelled.
+    ///
+    /// # Panics
+    ///
+    /// If `start` is out of bounds.
+    #[cfg(feature = "std")]
+    pub fn flood_fill_monitored(
+        &mut self,
+        start: impl Point,
+        predicate: impl FnMut(&T) -> bool,
+        value: T,
+        connectivity: Connectivity,
+        monitor: &mut Monitor<'_>,
+    ) -> Result<usize, Cancelled> {
+        let region = self.flood_region_monitored(start, predicate, connectivity, monitor)?;
+        for point in &region {
+            self[*point] = value.clone();
+        }
+        Ok(region.len())
+    }
+
+    /// Returns the index of `point` into the underlying data.
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub(crate) fn checked_index(&self, point: impl Point) -> usize {
+        self.try_checked_index(point)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Returns the index of `point` into the underlying data, or an error if it is out of bounds.
+    fn try_checked_index(&self, point: impl Point) -> Result<usize, OutOfBounds> {
+        let (width, height) = self.size();
+        if point.x() < width && point.y() < height {
+            Ok(point.to_index(width))
+        } else {
+            Err(OutOfBounds {
+                point: (point.x(), point.y()),
+                size: (width, height),
+            })
+        }
+    }
+
+    /// Returns the index of `point` into the underlying data, or `None` if it is out of bounds.
+    fn bounded_index(&self, point: impl Point) -> Option<usize> {
+        let (width, height) = self.size();
+        (point.x() < width && point.y() < height).then(|| point.to_index(width))
+    }
+
+    /// Returns the width and height of the grid, which are `(0, 0)` if the grid is empty.
+    pub(crate) fn size(&self) -> (usize, usize) {
+        if self.data.is_empty() {
+            (0, 0)
+        } else {
+            (self.width(), self.height())
+        }
+    }
+
+    /// Returns the number of cells the underlying data can hold without reallocating.
+    pub(crate) fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Returns the underlying data as a mutable slice, in row-major order.
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T> Grid<T>
+where
+    T: Clone + Send + Sync,
+{
+    /// Returns a parallel iterator that walks the grid in indexed order.
+    ///
+    /// Requires the `rayon` feature.
     ///
     /// # Examples
     ///
     /// ```
     /// use grud::Grid;
+    /// use rayon::prelude::*;
     ///
-    /// let grid = Grid::new(2, 3, 0);
-    /// assert_eq!(grid.width(), 2);
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// assert_eq!(grid.par_iter().sum::<i32>(), 10);
     /// ```
-    pub fn width(&self) -> usize {
-        self.width
+    pub fn par_iter(&self) -> rayon::slice::Iter<'_, T> {
+        use rayon::prelude::*;
+        self.data.par_iter()
     }
 
-    /// Returns the height of the grid.
+    /// Returns a parallel iterator that walks the grid in indexed order with mutable references.
+    ///
+    /// Requires the `rayon` feature.
     ///
     /// # Examples
     ///
     /// ```
     /// use grud::Grid;
+    /// use rayon::prelude::*;
     ///
-    /// let grid = Grid::new(2, 3, 0);
-    /// assert_eq!(grid.height(), 3);
+    /// let mut grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// grid.par_iter_mut().for_each(|i| *i *= 2);
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![2, 4, 6, 8]);
     /// ```
-    pub fn height(&self) -> usize {
-        self.data.len() / self.width()
+    pub fn par_iter_mut(&mut self) -> rayon::slice::IterMut<'_, T> {
+        use rayon::prelude::*;
+        self.data.par_iter_mut()
     }
 
-    /// Returns the total size of the grid as represented by `width * height`.
+    /// Returns a parallel iterator over the rows of the grid, each represented as a slice.
+    ///
+    /// Requires the `rayon` feature.
     ///
     /// # Examples
     ///
     /// ```
     /// use grud::Grid;
+    /// use rayon::prelude::*;
     ///
-    /// let grid = Grid::new(2, 3, 0);
-    /// assert_eq!(grid.area(), 2 * 3);
+    /// let grid = Grid::with_width(2, vec![1, 2, 3, 4, 5, 6]);
+    /// let sums: Vec<i32> = grid.par_rows().map(|row| row.iter().sum()).collect();
+    ///
+    /// assert_eq!(sums, vec![3, 7, 11]);
     /// ```
-    pub fn area(&self) -> usize {
-        self.width() * self.height()
+    pub fn par_rows(&self) -> rayon::slice::Chunks<'_, T> {
+        use rayon::prelude::*;
+        self.data.par_chunks(self.width().max(1))
     }
 }
 
@@ -160,7 +1342,7 @@ where
     T: Clone + Debug,
 {
     /// Formats the grid into string output for debugging.
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Grid")
             .field("data", &self.data)
             .field("width", &self.width())
@@ -188,7 +1370,7 @@ where
     /// // 789
     /// assert_eq!(format!("{}", grid), "123\n456\n789\n");
     /// ```
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         for j in 0..self.height() {
             for i in 0..self.width() {
                 write!(f, "{}", self[(i, j)])?;
@@ -199,6 +1381,29 @@ where
     }
 }
 
+impl<T> IntoIterator for Grid<T>
+where
+    T: Clone,
+{
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    /// Returns an iterator that walks the grid in indexed order, consuming the grid.
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![String::from("a"), String::from("b")]);
+    ///
+    /// let items: Vec<String> = grid.into_iter().collect();
+    ///
+    /// assert_eq!(items, vec!["a", "b"]);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
 impl<'a, T> IntoIterator for &'a Grid<T>
 where
     T: Clone,
@@ -213,7 +1418,7 @@ where
     ///
     /// let grid = Grid::with_width(2, vec!["a", "b", "c", "d"]);
     ///
-    /// let items: Vec<String> = grid.into_iter().map(|i| i.to_ascii_uppercase()).collect();
+    /// let items: Vec<String> = (&grid).into_iter().map(|i| i.to_ascii_uppercase()).collect();
     ///
     /// assert_eq!(items, vec!["A", "B", "C", "D"]);
     /// ```
@@ -270,7 +1475,8 @@ where
     ///
     /// # Panics
     ///
-    /// If the length of every inner vector is not the same.
+    /// If the length of every inner vector is not the same; see [`Grid::try_from_rows`] for a
+    /// non-panicking alternative.
     fn from(data: Vec<Vec<T>>) -> Self {
         let height = data.len();
         if height == 0 {
@@ -451,6 +1657,26 @@ mod tests {
         assert_eq!(grid.as_vec(), &vec![" ", " ", " ", " ", " ", " "]);
     }
 
+    #[test]
+    fn try_new_too_many_bytes() {
+        let error = Grid::try_new(isize::MAX as usize, 1, 0u16).unwrap_err();
+
+        assert_eq!(
+            error,
+            GridError::TooLarge {
+                width: isize::MAX as usize,
+                height: 1
+            }
+        );
+    }
+
+    #[test]
+    fn try_new_area_overflow() {
+        let error = Grid::try_new(usize::MAX, usize::MAX, ()).unwrap_err();
+
+        assert!(matches!(error, GridError::TooLarge { .. }));
+    }
+
     #[test]
     fn new_grid_of_width_to_matrix() {
         let grid = Grid::with_width(2, vec!["A", "B", "C", "D"]);
@@ -464,6 +1690,19 @@ mod tests {
         Grid::with_width(2, vec![1, 2, 3]);
     }
 
+    #[test]
+    #[should_panic(expected = "Row 1 has 1 cells, expected 2")]
+    fn grid_from_rows_ragged() {
+        Grid::from_rows(vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn grid_from_rows_empty() {
+        let grid = Grid::from_rows(vec![Vec::<i32>::new(), Vec::new()]);
+
+        assert!(grid.as_vec().is_empty());
+    }
+
     #[test]
     fn grid_from_matrix() {
         let grid: Grid<_> = vec![vec!["A", "B"], vec!["C", "D"]].into();
@@ -544,6 +1783,14 @@ mod tests {
         grid[0];
     }
 
+    #[test]
+    fn grid_get_offset_past_right_edge() {
+        let grid = Grid::new(2, 2, 0);
+
+        assert_eq!(grid.get_offset((1, 0), 1, 0), None);
+        assert_eq!(grid.get_offset((1, 0), 0, 1), Some(&0));
+    }
+
     #[test]
     fn grid_mut_index() {
         let mut grid: Grid<_> = vec![vec!["A", "B"], vec!["C", "D"]].into();
@@ -584,4 +1831,138 @@ mod tests {
 
         assert_eq!(grid.as_vec(), &vec!["a", "b", "c", "d"]);
     }
+
+    #[test]
+    fn grid_flood_fill_value_matches_predicate() {
+        let mut grid = Grid::new(3, 3, 0);
+        let filled = grid.flood_fill((1, 1), |c| *c < 5, 1, Connectivity::Four);
+
+        assert_eq!(filled, 9);
+        assert_eq!(grid.as_vec(), &vec![1; 9]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn grid_flood_fill_monitored_cancelled_leaves_cells() {
+        use crate::progress::CancelToken;
+
+        let mut grid = Grid::new(3, 3, 0);
+        let token = CancelToken::new();
+        token.cancel();
+        let mut monitor = Monitor::new().with_cancel(&token);
+
+        assert_eq!(
+            grid.flood_fill_monitored((0, 0), |c| *c == 0, 1, Connectivity::Four, &mut monitor),
+            Err(Cancelled)
+        );
+        assert_eq!(grid.as_vec(), &vec![0; 9]);
+    }
+
+    #[test]
+    fn grid_flood_region_start_not_matching() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert!(grid
+            .flood_region((1, 1), |c| *c == 1, Connectivity::Eight)
+            .is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_flood_fill_out_of_bounds() {
+        let mut grid = Grid::new(3, 3, 0);
+        grid.flood_fill((3, 0), |_| true, 1, Connectivity::Four);
+    }
+
+    #[test]
+    #[should_panic(expected = "Row has 2 cells, expected 3")]
+    fn grid_set_row_wrong_length() {
+        Grid::new(3, 2, 0).set_row(0, &[1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Column 2 out of bounds")]
+    fn grid_set_col_out_of_bounds() {
+        Grid::new(2, 2, 0).set_col(2, &[1, 2]);
+    }
+
+    #[test]
+    fn grid_scatter_validates_before_writing() {
+        let mut grid = Grid::new(2, 2, 0);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            grid.scatter([(0, 0), (2, 0)], [1, 2]);
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(grid.as_vec(), &vec![0; 4]);
+    }
+
+    #[test]
+    fn grid_try_index_empty() {
+        let grid: Grid<u8> = Grid::from(Vec::new());
+
+        assert_eq!(
+            grid.try_index((0, 0)),
+            Err(OutOfBounds {
+                point: (0, 0),
+                size: (0, 0)
+            })
+        );
+    }
+
+    #[test]
+    fn grid_try_with_width_zero() {
+        assert!(Grid::<u8>::try_with_width(0, Vec::new())
+            .unwrap()
+            .as_vec()
+            .is_empty());
+        assert_eq!(
+            Grid::try_with_width(0, vec![1]).unwrap_err(),
+            GridError::LengthNotDivisible {
+                length: 1,
+                width: 0
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Scattering 1 values to 2 points")]
+    fn grid_scatter_length_mismatch() {
+        Grid::new(2, 2, 0).scatter([(0, 0), (1, 0)], [1]);
+    }
+
+    #[test]
+    fn grid_insert_into_empty() {
+        let mut grid: Grid<i32> = Grid::from(Vec::new());
+        grid.insert_col(0, [1, 2]);
+        grid.insert_col(1, [3, 4]);
+
+        assert_eq!(grid.to_matrix(), vec![vec![1, 3], vec![2, 4]]);
+
+        let mut grid: Grid<i32> = Grid::from(Vec::new());
+        grid.insert_row(0, [1, 2, 3]);
+
+        assert_eq!(grid.to_matrix(), vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn grid_remove_last_row_and_col() {
+        let mut grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+        grid.remove_row(1);
+        grid.remove_col(0);
+
+        assert_eq!(grid.as_vec(), &vec![2]);
+        assert_eq!(grid.remove_row(0), vec![2]);
+        assert!(grid.as_vec().is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn grid_par_rows_empty() {
+        use rayon::prelude::*;
+
+        let grid: Grid<u8> = vec![].into();
+
+        assert_eq!(grid.par_rows().count(), 0);
+    }
 }
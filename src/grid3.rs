@@ -0,0 +1,688 @@
+//! A 3-dimensional grid of elements, i.e. for voxels or multi-layer puzzles.
+//!
+//! See [`Grid3`] for details.
+
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt::{Debug, Display},
+    ops::{Index, IndexMut},
+    slice::{Iter, IterMut},
+};
+
+use crate::grid::Grid;
+
+/// Represents a 3-dimensional coordinate in a [`Grid3`].
+///
+/// Implemented for `(x, y, z)` tuples and `[x, y, z]` arrays.
+pub trait Point3: Clone + Copy {
+    /// Returns the x-coordinate.
+    fn x(&self) -> usize;
+
+    /// Returns the y-coordinate.
+    fn y(&self) -> usize;
+
+    /// Returns the z-coordinate, i.e. the layer.
+    fn z(&self) -> usize;
+
+    /// Given the `width` and `height` of a grid, converts to an index into a 3-dimensional space
+    /// (e.g. [`Vec`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Point3;
+    ///
+    /// assert_eq!((1, 2, 1).to_index(3, 4), 19);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the index overflows a [`usize`], i.e. for large grids on 32-bit targets.
+    fn to_index(&self, width: usize, height: usize) -> usize {
+        self.checked_to_index(width, height).unwrap_or_else(|| {
+            panic!(
+                "Index of point ({}, {}, {}) in grid of {width}x{height} layers overflows usize",
+                self.x(),
+                self.y(),
+                self.z()
+            )
+        })
+    }
+
+    /// Given the `width` and `height` of a grid, converts to an index into a 3-dimensional space
+    /// (e.g. [`Vec`]), or returns `None` if the index overflows a [`usize`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Point3;
+    ///
+    /// assert_eq!((1, 2, 1).checked_to_index(3, 4), Some(19));
+    /// assert_eq!((0, 0, 2).checked_to_index(usize::MAX, 1), None);
+    /// ```
+    fn checked_to_index(&self, width: usize, height: usize) -> Option<usize> {
+        self.z()
+            .checked_mul(height)?
+            .checked_add(self.y())?
+            .checked_mul(width)?
+            .checked_add(self.x())
+    }
+}
+
+impl Point3 for (usize, usize, usize) {
+    fn x(&self) -> usize {
+        self.0
+    }
+
+    fn y(&self) -> usize {
+        self.1
+    }
+
+    fn z(&self) -> usize {
+        self.2
+    }
+}
+
+impl Point3 for [usize; 3] {
+    fn x(&self) -> usize {
+        self[0]
+    }
+
+    fn y(&self) -> usize {
+        self[1]
+    }
+
+    fn z(&self) -> usize {
+        self[2]
+    }
+}
+
+/// A [dense] fixed-size 3-dimensional grid that stores elements using a [`Vec`].
+///
+/// Cells are stored layer by layer, where each layer is in the same row-major order as [`Grid`].
+///
+/// [dense]: https://stackoverflow.com/questions/39030196/what-exactly-is-a-dense-array
+///
+/// # Examples
+///
+/// ```
+/// use grud::grid3::Grid3;
+///
+/// let mut voxels = Grid3::new(2, 2, 3, false);
+/// voxels[(1, 0, 2)] = true;
+///
+/// assert_eq!(voxels.layer(2).as_vec(), &vec![false, true, false, false]);
+/// ```
+#[derive(Clone)]
+pub struct Grid3<T>
+where
+    T: Clone,
+{
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid3<T>
+where
+    T: Clone,
+{
+    /// Creates a new grid of the specified `width`, `height`, and `depth`, filling with `default`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let grid = Grid3::new(2, 3, 4, 0);
+    /// assert_eq!(grid.volume(), 24);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width * height * depth` overflows a [`usize`].
+    pub fn new(width: usize, height: usize, depth: usize, default: T) -> Self {
+        let volume = width
+            .checked_mul(height)
+            .and_then(|area| area.checked_mul(depth))
+            .unwrap_or_else(|| panic!("Volume of {width}x{height}x{depth} grid overflows usize"));
+        Self {
+            data: vec![default; volume],
+            width,
+            height,
+        }
+    }
+
+    /// Creates a new grid of the specified `width` and `height`, inferring depth from the length of
+    /// the `data`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let grid = Grid3::with_size(2, 2, (0..8).collect());
+    /// assert_eq!(grid.depth(), 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `data.len()` is not evenly divisible by `width * height`.
+    pub fn with_size(width: usize, height: usize, data: Vec<T>) -> Self {
+        let area = width
+            .checked_mul(height)
+            .unwrap_or_else(|| panic!("Area of {width}x{height} layer overflows usize"));
+        assert!(
+            data.len().checked_rem(area).unwrap_or(data.len()) == 0,
+            "Data length {} not divisible by {width}x{height}",
+            data.len()
+        );
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a new grid by stacking `layers`, where the first layer has `z = 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, grid3::Grid3};
+    ///
+    /// let grid = Grid3::from_layers([
+    ///     Grid::with_width(2, vec![1, 2, 3, 4]),
+    ///     Grid::with_width(2, vec![5, 6, 7, 8]),
+    /// ]);
+    ///
+    /// assert_eq!(grid[(0, 1, 1)], 7);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the layers are not all the same size.
+    pub fn from_layers(layers: impl IntoIterator<Item = Grid<T>>) -> Self {
+        let mut layers = layers.into_iter();
+        let Some(first) = layers.next() else {
+            return Self::with_size(0, 0, Vec::new());
+        };
+        let (width, height) = first.size();
+        let mut data = first.into_vec();
+        for (z, layer) in layers.enumerate() {
+            let (layer_width, layer_height) = layer.size();
+            assert!(
+                (layer_width, layer_height) == (width, height),
+                "Layer {} is {layer_width}x{layer_height}, expected {width}x{height}",
+                z + 1
+            );
+            data.extend(layer);
+        }
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the underlying data, layer by layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let grid = Grid3::new(1, 1, 2, 'a');
+    /// assert_eq!(grid.as_vec(), &vec!['a', 'a']);
+    /// ```
+    pub fn as_vec(&self) -> &Vec<T> {
+        &self.data
+    }
+
+    /// Consumes the grid, returning the underlying data, layer by layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let grid = Grid3::with_size(1, 2, vec![1, 2, 3, 4]);
+    /// assert_eq!(grid.into_vec(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Returns the width of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// assert_eq!(Grid3::new(2, 3, 4, 0).width(), 2);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// assert_eq!(Grid3::new(2, 3, 4, 0).height(), 3);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the depth of the grid, i.e. the number of layers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// assert_eq!(Grid3::new(2, 3, 4, 0).depth(), 4);
+    /// ```
+    pub fn depth(&self) -> usize {
+        self.data
+            .len()
+            .checked_div(self.width * self.height)
+            .unwrap_or(0)
+    }
+
+    /// Returns the total size of the grid as represented by `width * height * depth`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// assert_eq!(Grid3::new(2, 3, 4, 0).volume(), 2 * 3 * 4);
+    /// ```
+    pub fn volume(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns a reference to the cell at `point`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let grid = Grid3::with_size(1, 1, vec![1, 2]);
+    ///
+    /// assert_eq!(grid.get((0, 0, 1)), Some(&2));
+    /// assert_eq!(grid.get((0, 0, 2)), None);
+    /// ```
+    pub fn get(&self, point: impl Point3) -> Option<&T> {
+        let index = self.bounded_index(point)?;
+        Some(&self.data[index])
+    }
+
+    /// Returns a mutable reference to the cell at `point`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let mut grid = Grid3::new(1, 1, 2, 0);
+    /// *grid.get_mut((0, 0, 1)).unwrap() = 5;
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![0, 5]);
+    /// assert!(grid.get_mut((1, 0, 0)).is_none());
+    /// ```
+    pub fn get_mut(&mut self, point: impl Point3) -> Option<&mut T> {
+        let index = self.bounded_index(point)?;
+        Some(&mut self.data[index])
+    }
+
+    /// Returns a copy of the layer at depth `z`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let grid = Grid3::with_size(2, 1, vec![1, 2, 3, 4]);
+    /// assert_eq!(grid.layer(1).as_vec(), &vec![3, 4]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `z` is out of bounds.
+    pub fn layer(&self, z: usize) -> Grid<T> {
+        Grid::with_width(self.width, self.layer_slice(z).to_vec())
+    }
+
+    /// Returns an iterator over each layer, from `z = 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let grid = Grid3::with_size(2, 1, vec![1, 2, 3, 4]);
+    /// let sums: Vec<i32> = grid.layers().map(|layer| layer.iter().sum()).collect();
+    ///
+    /// assert_eq!(sums, vec![3, 7]);
+    /// ```
+    pub fn layers(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks((self.width * self.height).max(1))
+    }
+
+    /// Replaces the layer at depth `z` with `layer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, grid3::Grid3};
+    ///
+    /// let mut grid = Grid3::new(2, 1, 2, 0);
+    /// grid.set_layer(1, &Grid::with_width(2, vec![7, 8]));
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![0, 0, 7, 8]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `z` is out of bounds, or `layer` is not the same width and height as the grid.
+    pub fn set_layer(&mut self, z: usize, layer: &Grid<T>) {
+        let (layer_width, layer_height) = layer.size();
+        assert!(
+            (layer_width, layer_height) == (self.width, self.height),
+            "Layer is {layer_width}x{layer_height}, expected {}x{}",
+            self.width,
+            self.height
+        );
+        self.layer_slice_mut(z).clone_from_slice(layer.as_vec());
+    }
+
+    /// Returns an iterator over every cell and its point, layer by layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let grid = Grid3::with_size(1, 2, vec!['a', 'b', 'c', 'd']);
+    /// let points: Vec<_> = grid.iter_points().filter(|(_, c)| **c > 'b').collect();
+    ///
+    /// assert_eq!(points, vec![((0, 0, 1), &'c'), ((0, 1, 1), &'d')]);
+    /// ```
+    pub fn iter_points(&self) -> impl Iterator<Item = ((usize, usize, usize), &T)> {
+        let (width, height) = (self.width, self.height);
+        self.data.iter().enumerate().map(move |(index, cell)| {
+            let (xy, z) = (index % (width * height), index / (width * height));
+            ((xy % width, xy / width, z), cell)
+        })
+    }
+
+    /// Returns the cells of layer `z` as a slice.
+    fn layer_slice(&self, z: usize) -> &[T] {
+        let area = self.width * self.height;
+        self.check_layer(z);
+        &self.data[z * area..(z + 1) * area]
+    }
+
+    /// Returns the cells of layer `z` as a mutable slice.
+    fn layer_slice_mut(&mut self, z: usize) -> &mut [T] {
+        let area = self.width * self.height;
+        self.check_layer(z);
+        &mut self.data[z * area..(z + 1) * area]
+    }
+
+    fn check_layer(&self, z: usize) {
+        let depth = self.depth();
+        assert!(
+            z < depth,
+            "Layer {z} is out of bounds of grid with depth {depth}"
+        );
+    }
+
+    /// Returns the index of `point` into the underlying data.
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    fn checked_index(&self, point: impl Point3) -> usize {
+        self.bounded_index(point).unwrap_or_else(|| {
+            panic!(
+                "Point ({}, {}, {}) is out of bounds of {}x{}x{} grid",
+                point.x(),
+                point.y(),
+                point.z(),
+                self.width,
+                self.height,
+                self.depth()
+            )
+        })
+    }
+
+    /// Returns the index of `point` into the underlying data, or `None` if it is out of bounds.
+    fn bounded_index(&self, point: impl Point3) -> Option<usize> {
+        let in_bounds =
+            point.x() < self.width && point.y() < self.height && point.z() < self.depth();
+        in_bounds.then(|| point.to_index(self.width, self.height))
+    }
+}
+
+impl<T> Debug for Grid3<T>
+where
+    T: Clone + Debug,
+{
+    /// Formats the grid into string output for debugging.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Grid3")
+            .field("data", &self.data)
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("depth", &self.depth())
+            .finish()
+    }
+}
+
+impl<T> Display for Grid3<T>
+where
+    T: Clone + Display,
+{
+    /// Formats the grid into a multi-line string output, one layer at a time.
+    ///
+    /// Each layer is formatted like [`Grid`], and layers are separated by an empty line:
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let grid = Grid3::with_size(2, 2, (1..=8).collect());
+    ///
+    /// assert_eq!(format!("{}", grid), "12\n34\n\n56\n78\n");
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (z, layer) in self.layers().enumerate() {
+            if z > 0 {
+                writeln!(f)?;
+            }
+            for row in layer.chunks(self.width) {
+                for cell in row {
+                    write!(f, "{cell}")?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> IntoIterator for Grid3<T>
+where
+    T: Clone,
+{
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    /// Returns an iterator that walks the grid layer by layer, consuming the grid.
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid3<T>
+where
+    T: Clone,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    /// Returns an iterator that walks the grid layer by layer.
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let grid = Grid3::with_size(1, 1, vec![1, 2, 3]);
+    /// assert_eq!((&grid).into_iter().sum::<i32>(), 6);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Grid3<T>
+where
+    T: Clone,
+{
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    /// Returns an iterator that walks the grid layer by layer with mutable references.
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let mut grid = Grid3::with_size(1, 1, vec![1, 2, 3]);
+    ///
+    /// for cell in &mut grid {
+    ///     *cell *= 2;
+    /// }
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![2, 4, 6]);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter_mut()
+    }
+}
+
+impl<T, I> Index<I> for Grid3<T>
+where
+    T: Clone,
+    I: Point3,
+{
+    type Output = T;
+
+    /// Given a three-dimensional coordinate [`Point3`], returns the underlying data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let grid = Grid3::with_size(1, 1, vec!["a", "b"]);
+    ///
+    /// assert_eq!(grid[(0, 0, 1)], "b");
+    /// assert_eq!(grid[[0, 0, 0]], "a");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index(&self, index: I) -> &Self::Output {
+        let index = self.checked_index(index);
+        &self.data[index]
+    }
+}
+
+impl<T, I> IndexMut<I> for Grid3<T>
+where
+    T: Clone,
+    I: Point3,
+{
+    /// Given a three-dimensional coordinate [`Point3`], sets the underlying data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::grid3::Grid3;
+    ///
+    /// let mut grid = Grid3::new(1, 1, 2, "a");
+    /// grid[(0, 0, 1)] = "b";
+    ///
+    /// assert_eq!(grid.as_vec(), &vec!["a", "b"]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        let index = self.checked_index(index);
+        &mut self.data[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid3_layers_round_trip() {
+        let layers = [
+            Grid::with_width(3, (0..6).collect()),
+            Grid::with_width(3, (6..12).collect()),
+        ];
+        let grid = Grid3::from_layers(layers.clone());
+
+        assert_eq!(grid.depth(), 2);
+        assert_eq!(grid[(2, 1, 1)], 11);
+        for (z, layer) in layers.iter().enumerate() {
+            assert_eq!(grid.layer(z).as_vec(), layer.as_vec());
+        }
+    }
+
+    #[test]
+    fn grid3_empty() {
+        let grid: Grid3<i32> = Grid3::from_layers([]);
+
+        assert_eq!(grid.depth(), 0);
+        assert_eq!(grid.get((0, 0, 0)), None);
+        assert_eq!(format!("{grid}"), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "Point (0, 0, 2) is out of bounds of 1x1x2 grid")]
+    fn grid3_index_out_of_bounds() {
+        let grid = Grid3::new(1, 1, 2, 0);
+        let _ = grid[(0, 0, 2)];
+    }
+
+    #[test]
+    #[should_panic(expected = "Layer 1 is 2x1, expected 1x2")]
+    fn grid3_from_layers_mismatch() {
+        Grid3::from_layers([Grid::new(1, 2, 0), Grid::new(2, 1, 0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Layer 1 is 0x0, expected 1x2")]
+    fn grid3_from_layers_empty_mismatch() {
+        Grid3::from_layers([Grid::new(1, 2, 0), Grid::from(Vec::new())]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Layer is 0x0, expected 1x1")]
+    fn grid3_set_layer_empty_mismatch() {
+        Grid3::new(1, 1, 1, 0).set_layer(0, &Grid::from(Vec::new()));
+    }
+}
@@ -0,0 +1,555 @@
+//! Hexagonal grids using axial coordinates, i.e. for strategy games.
+//!
+//! Hexagons are "pointy-top", and stored in a [`Grid`] using "odd-r" offset coordinates, where odd
+//! rows are shifted right by half a cell. See [`Hex`] and [`HexGrid`] for details, and [Red Blob
+//! Games] for an introduction to hexagonal coordinate systems.
+//!
+//! [Red Blob Games]: https://www.redblobgames.com/grids/hexagons/
+
+use std::ops::{Index, IndexMut};
+
+use crate::{grid::Grid, point::Point};
+
+/// One of the six directions to an adjacent [`Hex`], where north is towards `r = 0`.
+///
+/// # Examples
+///
+/// ```
+/// use grud::hex::{Hex, HexDirection};
+///
+/// assert_eq!(Hex::new(2, 2).neighbor(HexDirection::NorthEast), Hex::new(3, 1));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+impl HexDirection {
+    /// Every direction, counter-clockwise from east.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::NorthEast,
+        HexDirection::NorthWest,
+        HexDirection::West,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+
+    /// Returns the change in axial `(q, r)` when moving in this direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::HexDirection;
+    ///
+    /// assert_eq!(HexDirection::SouthWest.delta(), (-1, 1));
+    /// ```
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            HexDirection::East => (1, 0),
+            HexDirection::NorthEast => (1, -1),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::West => (-1, 0),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::SouthEast => (0, 1),
+        }
+    }
+}
+
+/// A hexagon in axial coordinates, where `q` increases towards the east and `r` towards the south.
+///
+/// Unlike [`Point`], coordinates may be negative, i.e. for hexagons outside of a [`HexGrid`].
+///
+/// # Examples
+///
+/// ```
+/// use grud::hex::Hex;
+///
+/// let a = Hex::new(0, 0);
+/// let b = Hex::new(2, -1);
+///
+/// assert_eq!(a.distance(b), 2);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+impl Hex {
+    /// Creates a hexagon at axial coordinates `(q, r)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::Hex;
+    ///
+    /// let hex = Hex::new(1, 2);
+    /// assert_eq!((hex.q, hex.r), (1, 2));
+    /// ```
+    pub fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+
+    /// Returns the third cube coordinate, `s`, where `q + r + s == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::Hex;
+    ///
+    /// assert_eq!(Hex::new(1, 2).s(), -3);
+    /// ```
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    /// Returns the adjacent hexagon in `direction`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::{Hex, HexDirection};
+    ///
+    /// assert_eq!(Hex::new(0, 0).neighbor(HexDirection::West), Hex::new(-1, 0));
+    /// ```
+    pub fn neighbor(&self, direction: HexDirection) -> Hex {
+        let (dq, dr) = direction.delta();
+        Hex::new(self.q + dq, self.r + dr)
+    }
+
+    /// Returns the six adjacent hexagons, counter-clockwise from east.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::Hex;
+    ///
+    /// let origin = Hex::new(0, 0);
+    /// assert!(origin.neighbors().iter().all(|hex| origin.distance(*hex) == 1));
+    /// ```
+    pub fn neighbors(&self) -> [Hex; 6] {
+        HexDirection::ALL.map(|direction| self.neighbor(direction))
+    }
+
+    /// Returns the number of steps between this hexagon and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::Hex;
+    ///
+    /// assert_eq!(Hex::new(0, 0).distance(Hex::new(3, -3)), 3);
+    /// assert_eq!(Hex::new(0, 0).distance(Hex::new(3, 3)), 6);
+    /// ```
+    pub fn distance(&self, other: Hex) -> usize {
+        let (dq, dr) = (self.q - other.q, self.r - other.r);
+        (dq.unsigned_abs() + dr.unsigned_abs() + (dq + dr).unsigned_abs()) / 2
+    }
+
+    /// Returns the "odd-r" offset `(column, row)` of the hexagon, or `None` if either would be
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::Hex;
+    ///
+    /// assert_eq!(Hex::new(1, 1).to_offset(), Some((1, 1)));
+    /// assert_eq!(Hex::new(0, 2).to_offset(), Some((1, 2)));
+    /// assert_eq!(Hex::new(-1, 0).to_offset(), None);
+    /// ```
+    pub fn to_offset(&self) -> Option<(usize, usize)> {
+        let column = self.q + (self.r - (self.r & 1)) / 2;
+        Some((usize::try_from(column).ok()?, usize::try_from(self.r).ok()?))
+    }
+
+    /// Returns the hexagon at the "odd-r" offset `(column, row)` of `point`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::Hex;
+    ///
+    /// assert_eq!(Hex::from_offset((1, 2)), Hex::new(0, 2));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If either coordinate overflows an [`isize`].
+    pub fn from_offset(point: impl Point) -> Self {
+        let (column, row) = (point.x() as isize, point.y() as isize);
+        assert!(
+            column >= 0 && row >= 0,
+            "Offset ({}, {}) overflows isize",
+            point.x(),
+            point.y()
+        );
+        Hex::new(column - (row - (row & 1)) / 2, row)
+    }
+
+    /// Returns the center of the hexagon in screen coordinates `(x, y)`, where `size` is the
+    /// distance from the center to each corner and the hexagon at `(0, 0)` is centered on the
+    /// origin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::Hex;
+    ///
+    /// let (x, y) = Hex::new(0, 2).to_pixel(10.0);
+    ///
+    /// assert!((x - 17.32).abs() < 0.01);
+    /// assert_eq!(y, 30.0);
+    /// ```
+    pub fn to_pixel(&self, size: f64) -> (f64, f64) {
+        let (q, r) = (self.q as f64, self.r as f64);
+        let x = size * (3f64.sqrt() * q + 3f64.sqrt() / 2.0 * r);
+        let y = size * 1.5 * r;
+        (x, y)
+    }
+
+    /// Returns the hexagon containing screen coordinates `(x, y)`; the inverse of
+    /// [`Hex::to_pixel`], i.e. for mouse picking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::Hex;
+    ///
+    /// assert_eq!(Hex::from_pixel(18.0, 28.0, 10.0), Hex::new(0, 2));
+    /// assert_eq!(Hex::from_pixel(-9.0, 0.0, 10.0), Hex::new(-1, 0));
+    /// ```
+    pub fn from_pixel(x: f64, y: f64, size: f64) -> Self {
+        let q = (3f64.sqrt() / 3.0 * x - y / 3.0) / size;
+        let r = (2.0 / 3.0 * y) / size;
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Hex::new(rq as isize, rr as isize)
+    }
+}
+
+/// A grid of hexagons, stored as a [`Grid`] in "odd-r" offset coordinates and accessed using axial
+/// [`Hex`] coordinates.
+///
+/// # Examples
+///
+/// ```
+/// use grud::hex::{Hex, HexGrid};
+///
+/// let mut map = HexGrid::new(4, 4, '.');
+/// map[Hex::new(1, 2)] = '#';
+///
+/// let walls = map.neighbors(Hex::new(1, 1)).filter(|(_, cell)| **cell == '#').count();
+/// assert_eq!(walls, 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HexGrid<T>
+where
+    T: Clone,
+{
+    grid: Grid<T>,
+}
+
+impl<T> HexGrid<T>
+where
+    T: Clone,
+{
+    /// Creates a grid of `width` columns and `height` rows of hexagons, filling with `default`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::HexGrid;
+    ///
+    /// let grid = HexGrid::new(3, 2, 0);
+    /// assert_eq!((grid.width(), grid.height()), (3, 2));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width * height` overflows a [`usize`].
+    pub fn new(width: usize, height: usize, default: T) -> Self {
+        Self {
+            grid: Grid::new(width, height, default),
+        }
+    }
+
+    /// Creates a hexagonal grid from `grid`, where each cell is at its "odd-r" offset coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, hex::{Hex, HexGrid}};
+    ///
+    /// let grid = HexGrid::from_grid(Grid::with_width(2, vec![1, 2, 3, 4]));
+    /// assert_eq!(grid[Hex::new(1, 1)], 4);
+    /// ```
+    pub fn from_grid(grid: Grid<T>) -> Self {
+        Self { grid }
+    }
+
+    /// Returns the underlying grid in "odd-r" offset coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::{Hex, HexGrid};
+    ///
+    /// let mut grid = HexGrid::new(2, 2, 0);
+    /// grid[Hex::new(0, 1)] = 1;
+    ///
+    /// assert_eq!(grid.as_grid().as_vec(), &vec![0, 0, 1, 0]);
+    /// ```
+    pub fn as_grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Consumes the hexagonal grid, returning the underlying grid in "odd-r" offset coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::HexGrid;
+    ///
+    /// assert_eq!(HexGrid::new(2, 1, 'a').into_grid().as_vec(), &vec!['a', 'a']);
+    /// ```
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Returns the number of columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::HexGrid;
+    ///
+    /// assert_eq!(HexGrid::new(3, 2, 0).width(), 3);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.grid.size().0
+    }
+
+    /// Returns the number of rows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::HexGrid;
+    ///
+    /// assert_eq!(HexGrid::new(3, 2, 0).height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.grid.size().1
+    }
+
+    /// Returns whether `hex` is within the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::{Hex, HexGrid};
+    ///
+    /// let grid = HexGrid::new(2, 2, 0);
+    ///
+    /// assert!(grid.contains(Hex::new(0, 1)));
+    /// assert!(!grid.contains(Hex::new(-1, 0)));
+    /// ```
+    pub fn contains(&self, hex: Hex) -> bool {
+        self.get(hex).is_some()
+    }
+
+    /// Returns a reference to the cell at `hex`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::{Hex, HexGrid};
+    ///
+    /// let grid = HexGrid::new(2, 2, 'x');
+    ///
+    /// assert_eq!(grid.get(Hex::new(1, 0)), Some(&'x'));
+    /// assert_eq!(grid.get(Hex::new(2, 0)), None);
+    /// ```
+    pub fn get(&self, hex: Hex) -> Option<&T> {
+        self.grid.get(hex.to_offset()?)
+    }
+
+    /// Returns a mutable reference to the cell at `hex`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::{Hex, HexGrid};
+    ///
+    /// let mut grid = HexGrid::new(2, 2, 0);
+    /// *grid.get_mut(Hex::new(1, 0)).unwrap() = 5;
+    ///
+    /// assert_eq!(grid[Hex::new(1, 0)], 5);
+    /// ```
+    pub fn get_mut(&mut self, hex: Hex) -> Option<&mut T> {
+        self.grid.get_mut(hex.to_offset()?)
+    }
+
+    /// Returns an iterator over the adjacent hexagons of `hex` within the grid, and their cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::{Hex, HexGrid};
+    ///
+    /// let grid = HexGrid::new(3, 3, 0);
+    ///
+    /// assert_eq!(grid.neighbors(Hex::new(1, 1)).count(), 6);
+    /// assert_eq!(grid.neighbors(Hex::new(0, 0)).count(), 2);
+    /// ```
+    pub fn neighbors(&self, hex: Hex) -> impl Iterator<Item = (Hex, &T)> {
+        hex.neighbors()
+            .into_iter()
+            .filter_map(|hex| Some((hex, self.get(hex)?)))
+    }
+
+    /// Returns an iterator over every hexagon in the grid, and its cell, row by row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::{Hex, HexGrid};
+    ///
+    /// let grid = HexGrid::new(2, 2, 0);
+    /// let hexes: Vec<Hex> = grid.iter().map(|(hex, _)| hex).collect();
+    ///
+    /// assert_eq!(hexes, vec![Hex::new(0, 0), Hex::new(1, 0), Hex::new(0, 1), Hex::new(1, 1)]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (Hex, &T)> {
+        let width = self.width();
+        self.grid
+            .as_vec()
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (Hex::from_offset((i % width, i / width)), cell))
+    }
+}
+
+impl<T> Index<Hex> for HexGrid<T>
+where
+    T: Clone,
+{
+    type Output = T;
+
+    /// Returns the cell at `hex`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::{Hex, HexGrid};
+    ///
+    /// let grid = HexGrid::new(1, 1, "X");
+    /// assert_eq!(grid[Hex::new(0, 0)], "X");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `hex` is out of bounds.
+    fn index(&self, hex: Hex) -> &Self::Output {
+        self.get(hex)
+            .unwrap_or_else(|| panic!("{}", out_of_bounds(hex, self.grid.size())))
+    }
+}
+
+impl<T> IndexMut<Hex> for HexGrid<T>
+where
+    T: Clone,
+{
+    /// Sets the cell at `hex`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::hex::{Hex, HexGrid};
+    ///
+    /// let mut grid = HexGrid::new(1, 1, "X");
+    /// grid[Hex::new(0, 0)] = "Y";
+    ///
+    /// assert_eq!(grid[Hex::new(0, 0)], "Y");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `hex` is out of bounds.
+    fn index_mut(&mut self, hex: Hex) -> &mut Self::Output {
+        let size = self.grid.size();
+        self.get_mut(hex)
+            .unwrap_or_else(|| panic!("{}", out_of_bounds(hex, size)))
+    }
+}
+
+/// Returns the panic message for `hex` being out of bounds of a grid of `(width, height)`.
+fn out_of_bounds(hex: Hex, (width, height): (usize, usize)) -> String {
+    format!(
+        "Hex ({}, {}) out of bounds of {width}x{height} grid",
+        hex.q, hex.r
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_offset_round_trip() {
+        for row in 0..5 {
+            for column in 0..5 {
+                let hex = Hex::from_offset((column, row));
+                assert_eq!(hex.to_offset(), Some((column, row)));
+            }
+        }
+    }
+
+    #[test]
+    fn hex_pixel_round_trip() {
+        for q in -3..3 {
+            for r in -3..3 {
+                let hex = Hex::new(q, r);
+                let (x, y) = hex.to_pixel(7.5);
+                assert_eq!(Hex::from_pixel(x, y, 7.5), hex);
+            }
+        }
+    }
+
+    #[test]
+    fn hex_neighbors_match_offset_layout() {
+        let grid = HexGrid::from_grid(Grid::with_width(3, (0..9).collect()));
+        let mut even: Vec<i32> = grid
+            .neighbors(Hex::from_offset((1, 0)))
+            .map(|(_, c)| *c)
+            .collect();
+        let mut odd: Vec<i32> = grid
+            .neighbors(Hex::from_offset((1, 1)))
+            .map(|(_, c)| *c)
+            .collect();
+        even.sort();
+        odd.sort();
+
+        assert_eq!(even, vec![0, 2, 3, 4]);
+        assert_eq!(odd, vec![1, 2, 3, 5, 7, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Hex (0, 2) out of bounds of 2x2 grid")]
+    fn hex_index_out_of_bounds() {
+        let _ = HexGrid::new(2, 2, 0)[Hex::new(0, 2)];
+    }
+}
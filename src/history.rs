@@ -0,0 +1,356 @@
+//! Undo and redo for edits to a grid, i.e. for level editors.
+//!
+//! See [`HistoryGrid`] for details.
+
+use std::{mem, ops::Index};
+
+use crate::{grid::Grid, point::Point};
+
+/// A change to a single cell, recorded by a [`HistoryGrid`].
+#[derive(Clone, Debug)]
+struct Change<T> {
+    index: usize,
+    before: T,
+    after: T,
+}
+
+/// A grid that records each change to a cell, so that edits can be undone and redone without
+/// copying the whole grid.
+///
+/// Changes are grouped into steps: every change since the last [`HistoryGrid::checkpoint`] is
+/// undone or redone together, i.e. a brush stroke that sets many cells is a single step.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, history::HistoryGrid};
+///
+/// let mut level = HistoryGrid::new(Grid::new(3, 1, '.'));
+///
+/// level.set((0, 0), '#');
+/// level.set((1, 0), '#');
+/// level.checkpoint();
+/// level.set((2, 0), '@');
+///
+/// level.undo();
+/// assert_eq!(level.as_grid().as_vec(), &vec!['#', '#', '.']);
+///
+/// level.undo();
+/// assert_eq!(level.as_grid().as_vec(), &vec!['.', '.', '.']);
+///
+/// level.redo();
+/// assert_eq!(level.as_grid().as_vec(), &vec!['#', '#', '.']);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HistoryGrid<T>
+where
+    T: Clone,
+{
+    grid: Grid<T>,
+    pending: Vec<Change<T>>,
+    undo: Vec<Vec<Change<T>>>,
+    redo: Vec<Vec<Change<T>>>,
+}
+
+impl<T> HistoryGrid<T>
+where
+    T: Clone,
+{
+    /// Creates a history for `grid`, with nothing to undo or redo.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let level = HistoryGrid::new(Grid::new(3, 1, 0));
+    /// assert!(!level.can_undo());
+    /// ```
+    pub fn new(grid: Grid<T>) -> Self {
+        Self {
+            grid,
+            pending: Vec::new(),
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    /// Returns the current state of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let level = HistoryGrid::new(Grid::new(2, 1, 0));
+    /// assert_eq!(level.as_grid().as_vec(), &vec![0, 0]);
+    /// ```
+    pub fn as_grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Consumes the history, returning the current state of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let mut level = HistoryGrid::new(Grid::new(2, 1, 0));
+    /// level.set((1, 0), 1);
+    ///
+    /// assert_eq!(level.into_grid().as_vec(), &vec![0, 1]);
+    /// ```
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Sets the cell at `point` to `value`, returning the previous value.
+    ///
+    /// The change is part of the current step, and discards anything that could be redone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let mut level = HistoryGrid::new(Grid::new(2, 1, 'a'));
+    ///
+    /// assert_eq!(level.set((0, 0), 'b'), 'a');
+    /// assert_eq!(level[(0, 0)], 'b');
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn set(&mut self, point: impl Point, value: T) -> T {
+        let index = self.grid.checked_index(point);
+        let before = mem::replace(&mut self.grid[index], value.clone());
+        self.pending.push(Change {
+            index,
+            before: before.clone(),
+            after: value,
+        });
+        self.redo.clear();
+        before
+    }
+
+    /// Replaces the cell at `point` with the result of `f`, as with [`HistoryGrid::set`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let mut level = HistoryGrid::new(Grid::new(1, 1, 1));
+    /// level.update((0, 0), |cell| cell * 10);
+    /// level.undo();
+    ///
+    /// assert_eq!(level[(0, 0)], 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn update(&mut self, point: impl Point, f: impl FnOnce(&T) -> T) {
+        let value = f(&self.grid[self.grid.checked_index(point)]);
+        self.set(point, value);
+    }
+
+    /// Ends the current step, so that later changes are undone separately.
+    ///
+    /// Does nothing if there were no changes since the last checkpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let mut level = HistoryGrid::new(Grid::new(1, 1, 0));
+    /// level.set((0, 0), 1);
+    /// level.checkpoint();
+    /// level.set((0, 0), 2);
+    /// level.undo();
+    ///
+    /// assert_eq!(level[(0, 0)], 1);
+    /// ```
+    pub fn checkpoint(&mut self) {
+        if !self.pending.is_empty() {
+            self.undo.push(mem::take(&mut self.pending));
+        }
+    }
+
+    /// Returns whether there is a step to undo, including changes since the last checkpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let mut level = HistoryGrid::new(Grid::new(1, 1, 0));
+    /// level.set((0, 0), 1);
+    ///
+    /// assert!(level.can_undo());
+    /// ```
+    pub fn can_undo(&self) -> bool {
+        !self.pending.is_empty() || !self.undo.is_empty()
+    }
+
+    /// Returns whether there is a step to redo.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let mut level = HistoryGrid::new(Grid::new(1, 1, 0));
+    /// level.set((0, 0), 1);
+    /// level.undo();
+    ///
+    /// assert!(level.can_redo());
+    /// ```
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Reverts the most recent step, returning whether there was a step to undo.
+    ///
+    /// Changes since the last checkpoint are a step of their own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let mut level = HistoryGrid::new(Grid::new(1, 1, 0));
+    /// level.set((0, 0), 1);
+    ///
+    /// assert!(level.undo());
+    /// assert!(!level.undo());
+    /// assert_eq!(level[(0, 0)], 0);
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        self.checkpoint();
+        let Some(step) = self.undo.pop() else {
+            return false;
+        };
+        for change in step.iter().rev() {
+            self.grid[change.index] = change.before.clone();
+        }
+        self.redo.push(step);
+        true
+    }
+
+    /// Re-applies the most recently undone step, returning whether there was a step to redo.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let mut level = HistoryGrid::new(Grid::new(1, 1, 0));
+    /// level.set((0, 0), 1);
+    /// level.undo();
+    ///
+    /// assert!(level.redo());
+    /// assert!(!level.redo());
+    /// assert_eq!(level[(0, 0)], 1);
+    /// ```
+    pub fn redo(&mut self) -> bool {
+        self.checkpoint();
+        let Some(step) = self.redo.pop() else {
+            return false;
+        };
+        for change in &step {
+            self.grid[change.index] = change.after.clone();
+        }
+        self.undo.push(step);
+        true
+    }
+
+    /// Forgets every step, so nothing can be undone or redone, keeping the current grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let mut level = HistoryGrid::new(Grid::new(1, 1, 0));
+    /// level.set((0, 0), 1);
+    /// level.clear_history();
+    ///
+    /// assert!(!level.undo());
+    /// assert_eq!(level[(0, 0)], 1);
+    /// ```
+    pub fn clear_history(&mut self) {
+        self.pending.clear();
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+impl<T, I> Index<I> for HistoryGrid<T>
+where
+    T: Clone,
+    I: Point,
+{
+    type Output = T;
+
+    /// Given a two-dimensional coordinate [`Point`], returns the current cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, history::HistoryGrid};
+    ///
+    /// let level = HistoryGrid::new(Grid::new(1, 1, 'x'));
+    /// assert_eq!(level[(0, 0)], 'x');
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index(&self, index: I) -> &Self::Output {
+        &self.grid[self.grid.checked_index(index)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_round_trip() {
+        let mut level = HistoryGrid::new(Grid::new(3, 3, 0));
+        let mut states = vec![level.as_grid().as_vec().clone()];
+        for i in 0..9 {
+            level.set((i % 3, i / 3), i);
+            level.set((0, 0), i * 10);
+            level.checkpoint();
+            states.push(level.as_grid().as_vec().clone());
+        }
+
+        for state in states.iter().rev().skip(1) {
+            assert!(level.undo());
+            assert_eq!(level.as_grid().as_vec(), state);
+        }
+        assert!(!level.undo());
+        for state in states.iter().skip(1) {
+            assert!(level.redo());
+            assert_eq!(level.as_grid().as_vec(), state);
+        }
+        assert!(!level.redo());
+    }
+
+    #[test]
+    fn history_set_discards_redo() {
+        let mut level = HistoryGrid::new(Grid::new(1, 1, 0));
+        level.set((0, 0), 1);
+        level.undo();
+        level.set((0, 0), 2);
+
+        assert!(!level.can_redo());
+        assert!(level.undo());
+        assert_eq!(level[(0, 0)], 0);
+    }
+}
@@ -0,0 +1,189 @@
+//! Converting grids to and from images, i.e. for pixel manipulation or dumping simulations.
+//!
+//! Requires the `image` feature.
+//!
+//! See [`Grid::from_image`] and [`Grid::to_image`] for details.
+
+use std::ops::Deref;
+
+use ::image::{GrayImage, ImageBuffer, Luma, Pixel};
+
+use crate::grid::Grid;
+
+impl<P> Grid<P>
+where
+    P: Pixel,
+{
+    /// Creates a grid with a cell for each pixel of `image`.
+    ///
+    /// Requires the `image` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    /// use image::{Rgb, RgbImage};
+    ///
+    /// let image = RgbImage::from_fn(2, 1, |x, _| Rgb([x as u8, 0, 0]));
+    /// let grid = Grid::from_image(&image);
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![Rgb([0, 0, 0]), Rgb([1, 0, 0])]);
+    /// ```
+    pub fn from_image<C>(image: &ImageBuffer<P, C>) -> Self
+    where
+        C: Deref<Target = [P::Subpixel]>,
+    {
+        if image.width() == 0 || image.height() == 0 {
+            return Self::from(Vec::new());
+        }
+        Self::with_width(image.width() as usize, image.pixels().copied().collect())
+    }
+
+    /// Creates an image with a pixel for each cell.
+    ///
+    /// Requires the `image` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    /// use image::Rgb;
+    ///
+    /// let grid = Grid::with_width(2, vec![Rgb([255, 0, 0]), Rgb([0, 0, 255])]);
+    /// let image = grid.to_image();
+    ///
+    /// assert_eq!(image.dimensions(), (2, 1));
+    /// assert_eq!(image[(1, 0)], Rgb([0, 0, 255]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the width or height of the grid does not fit in a [`u32`].
+    pub fn to_image(&self) -> ImageBuffer<P, Vec<P::Subpixel>> {
+        self.to_image_with(|pixel| *pixel)
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Creates an image where each pixel is `pixel(cell)`.
+    ///
+    /// Requires the `image` feature.
+    ///
+    /// # Examples
+    ///
+    /// Rendering a heat map:
+    ///
+    /// ```
+    /// use grud::Grid;
+    /// use image::Luma;
+    ///
+    /// let heat = Grid::with_width(2, vec![0.0, 0.5, 1.0, 0.25]);
+    /// let image = heat.to_image_with(|t: &f64| Luma([(t * 255.0) as u8]));
+    ///
+    /// assert_eq!(image.into_raw(), vec![0, 127, 255, 63]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the width or height of the grid does not fit in a [`u32`].
+    pub fn to_image_with<P>(
+        &self,
+        mut pixel: impl FnMut(&T) -> P,
+    ) -> ImageBuffer<P, Vec<P::Subpixel>>
+    where
+        P: Pixel,
+    {
+        let (width, height) = self.size();
+        let (width, height) = (
+            u32::try_from(width).expect("Grid is too wide for an image"),
+            u32::try_from(height).expect("Grid is too tall for an image"),
+        );
+        let mut image = ImageBuffer::new(width, height);
+        for (cell, out) in self.as_vec().iter().zip(image.pixels_mut()) {
+            *out = pixel(cell);
+        }
+        image
+    }
+}
+
+impl Grid<u8> {
+    /// Creates a grid with the brightness of each pixel of a grayscale `image`.
+    ///
+    /// Requires the `image` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    /// use image::GrayImage;
+    ///
+    /// let image = GrayImage::from_raw(2, 2, vec![0, 64, 128, 255]).unwrap();
+    /// let grid = Grid::from_gray_image(&image);
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![vec![0, 64], vec![128, 255]]);
+    /// ```
+    pub fn from_gray_image(image: &GrayImage) -> Self {
+        if image.width() == 0 || image.height() == 0 {
+            return Self::from(Vec::new());
+        }
+        // The raw buffer may be longer than the image, so only take the samples of its pixels.
+        Self::with_width(
+            image.width() as usize,
+            image.pixels().map(|pixel| pixel.0[0]).collect(),
+        )
+    }
+
+    /// Creates a grayscale image where each pixel's brightness is its cell.
+    ///
+    /// Requires the `image` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![0, 255]);
+    /// assert_eq!(grid.to_gray_image().into_raw(), vec![0, 255]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the width or height of the grid does not fit in a [`u32`].
+    pub fn to_gray_image(&self) -> GrayImage {
+        self.to_image_with(|value| Luma([*value]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::image::{Rgb, RgbImage};
+
+    use super::*;
+
+    #[test]
+    fn image_round_trip() {
+        let image = RgbImage::from_fn(3, 2, |x, y| Rgb([x as u8, y as u8, 7]));
+        let back = Grid::from_image(&image).to_image();
+
+        assert_eq!(back, image);
+    }
+
+    #[test]
+    fn image_empty() {
+        let grid = Grid::from_image(&GrayImage::new(0, 0));
+
+        assert!(grid.as_vec().is_empty());
+        assert_eq!(grid.to_image().dimensions(), (0, 0));
+    }
+
+    #[test]
+    fn image_gray_oversized_buffer() {
+        let image = GrayImage::from_raw(2, 2, vec![1, 2, 3, 4, 5, 6, 7]).unwrap();
+        let grid = Grid::from_gray_image(&image);
+
+        assert_eq!(grid.to_matrix(), vec![vec![1, 2], vec![3, 4]]);
+    }
+}
@@ -0,0 +1,423 @@
+//! Stacks of same-size grids, i.e. terrain, object, and overlay layers of a tile map.
+//!
+//! See [`LayeredGrid`] for details.
+
+use crate::{error::SizeMismatch, grid::Grid, point::Point, view::GridMut};
+
+/// A stack of grids with the same width and height, where each cell of a layer may be empty.
+///
+/// Layers are numbered from the bottom, starting at `0`. Reading the grid as a whole (i.e. with
+/// [`LayeredGrid::get`]) composites the layers, where the top-most non-empty cell wins.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, layered::LayeredGrid};
+///
+/// let mut map = LayeredGrid::new(3, 1);
+/// let terrain = map.push(Grid::new(3, 1, Some('.'))).unwrap();
+/// let objects = map.push_empty();
+///
+/// map.layer_mut(objects)[(1, 0)] = Some('@');
+///
+/// assert_eq!(map.get((0, 0)), Some(&'.'));
+/// assert_eq!(map.get((1, 0)), Some(&'@'));
+/// assert_eq!(map.layer(terrain)[(1, 0)], Some('.'));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LayeredGrid<T>
+where
+    T: Clone,
+{
+    width: usize,
+    height: usize,
+    layers: Vec<Grid<Option<T>>>,
+}
+
+impl<T> LayeredGrid<T>
+where
+    T: Clone,
+{
+    /// Creates a stack of `width` by `height` grids, without any layers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::layered::LayeredGrid;
+    ///
+    /// let map = LayeredGrid::<char>::new(3, 2);
+    ///
+    /// assert_eq!((map.width(), map.height()), (3, 2));
+    /// assert!(map.is_empty());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width * height` overflows a [`usize`].
+    pub fn new(width: usize, height: usize) -> Self {
+        let map = Self {
+            width,
+            height,
+            layers: Vec::new(),
+        };
+        map.area();
+        map
+    }
+
+    /// Returns the width of every layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::layered::LayeredGrid;
+    ///
+    /// assert_eq!(LayeredGrid::<u8>::new(3, 2).width(), 3);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of every layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::layered::LayeredGrid;
+    ///
+    /// assert_eq!(LayeredGrid::<u8>::new(3, 2).height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the number of layers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::layered::LayeredGrid;
+    ///
+    /// let mut map = LayeredGrid::<u8>::new(3, 2);
+    /// map.push_empty();
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns whether there are no layers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::layered::LayeredGrid;
+    ///
+    /// assert!(LayeredGrid::<u8>::new(3, 2).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Returns the layer at `index`, where `0` is the bottom layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::layered::LayeredGrid;
+    ///
+    /// let mut map = LayeredGrid::<u8>::new(2, 1);
+    /// let index = map.push_empty();
+    ///
+    /// assert_eq!(map.layer(index).as_vec(), &vec![None, None]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn layer(&self, index: usize) -> &Grid<Option<T>> {
+        &self.layers[self.checked_layer(index)]
+    }
+
+    /// Returns the layer at `index` as mutable, where `0` is the bottom layer.
+    ///
+    /// Cells can be changed, but the layer cannot be resized, so every layer keeps the size of the
+    /// stack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::layered::LayeredGrid;
+    ///
+    /// let mut map = LayeredGrid::new(2, 1);
+    /// let index = map.push_empty();
+    /// map.layer_mut(index)[(1, 0)] = Some(5);
+    ///
+    /// assert_eq!(map.get((1, 0)), Some(&5));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn layer_mut(&mut self, index: usize) -> GridMut<'_, Option<T>> {
+        let index = self.checked_layer(index);
+        GridMut::new(&mut self.layers[index])
+    }
+
+    /// Adds `layer` on top of the stack, returning its index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, layered::LayeredGrid};
+    ///
+    /// let mut map = LayeredGrid::new(2, 1);
+    ///
+    /// assert_eq!(map.push(Grid::new(2, 1, Some(1))), Ok(0));
+    /// assert!(map.push(Grid::new(1, 2, Some(1))).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `layer` is not the same size as the stack.
+    pub fn push(&mut self, layer: Grid<Option<T>>) -> Result<usize, SizeMismatch> {
+        self.insert(self.layers.len(), layer)?;
+        Ok(self.layers.len() - 1)
+    }
+
+    /// Adds a layer of empty cells on top of the stack, returning its index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::layered::LayeredGrid;
+    ///
+    /// let mut map = LayeredGrid::<u8>::new(2, 1);
+    ///
+    /// assert_eq!(map.push_empty(), 0);
+    /// assert_eq!(map.push_empty(), 1);
+    /// ```
+    pub fn push_empty(&mut self) -> usize {
+        self.layers.push(Grid::new(self.width, self.height, None));
+        self.layers.len() - 1
+    }
+
+    /// Inserts `layer` at `index`, moving the layers at and above `index` up by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, layered::LayeredGrid};
+    ///
+    /// let mut map = LayeredGrid::new(1, 1);
+    /// map.push(Grid::new(1, 1, Some('a'))).unwrap();
+    /// map.insert(0, Grid::new(1, 1, Some('b'))).unwrap();
+    ///
+    /// assert_eq!(map.layer(0)[(0, 0)], Some('b'));
+    /// assert_eq!(map.get((0, 0)), Some(&'a'));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `layer` is not the same size as the stack.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the number of layers.
+    pub fn insert(&mut self, index: usize, layer: Grid<Option<T>>) -> Result<(), SizeMismatch> {
+        let found = layer.size();
+        if found != (self.width, self.height) && !(layer.as_vec().is_empty() && self.area() == 0) {
+            return Err(SizeMismatch {
+                expected: (self.width, self.height),
+                found,
+            });
+        }
+        assert!(
+            index <= self.layers.len(),
+            "Cannot insert layer {index} into stack of {} layers",
+            self.layers.len()
+        );
+        self.layers.insert(index, layer);
+        Ok(())
+    }
+
+    /// Removes and returns the layer at `index`, moving the layers above `index` down by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, layered::LayeredGrid};
+    ///
+    /// let mut map = LayeredGrid::new(1, 1);
+    /// map.push(Grid::new(1, 1, Some('a'))).unwrap();
+    /// map.push(Grid::new(1, 1, Some('b'))).unwrap();
+    ///
+    /// assert_eq!(map.remove(1).as_vec(), &vec![Some('b')]);
+    /// assert_eq!(map.get((0, 0)), Some(&'a'));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Grid<Option<T>> {
+        let index = self.checked_layer(index);
+        self.layers.remove(index)
+    }
+
+    /// Returns the top-most non-empty cell at `point`, or `None` if every layer is empty there or
+    /// `point` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, layered::LayeredGrid};
+    ///
+    /// let mut map = LayeredGrid::new(2, 1);
+    /// map.push(Grid::with_width(2, vec![Some(1), None])).unwrap();
+    /// map.push(Grid::with_width(2, vec![None, None])).unwrap();
+    ///
+    /// assert_eq!(map.get((0, 0)), Some(&1));
+    /// assert_eq!(map.get((1, 0)), None);
+    /// ```
+    pub fn get(&self, point: impl Point) -> Option<&T> {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|layer| layer.get(point)?.as_ref())
+    }
+
+    /// Returns the index of the layer that the top-most non-empty cell at `point` is in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, layered::LayeredGrid};
+    ///
+    /// let mut map = LayeredGrid::new(2, 1);
+    /// map.push(Grid::new(2, 1, Some('.'))).unwrap();
+    /// map.push(Grid::with_width(2, vec![None, Some('@')])).unwrap();
+    ///
+    /// assert_eq!(map.top_layer((0, 0)), Some(0));
+    /// assert_eq!(map.top_layer((1, 0)), Some(1));
+    /// ```
+    pub fn top_layer(&self, point: impl Point) -> Option<usize> {
+        self.layers
+            .iter()
+            .rposition(|layer| matches!(layer.get(point), Some(Some(_))))
+    }
+
+    /// Returns an iterator over every point and its top-most non-empty cell, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, layered::LayeredGrid};
+    ///
+    /// let mut map = LayeredGrid::new(2, 1);
+    /// map.push(Grid::with_width(2, vec![Some('a'), None])).unwrap();
+    ///
+    /// let cells: Vec<_> = map.iter().collect();
+    /// assert_eq!(cells, vec![((0, 0), Some(&'a')), ((1, 0), None)]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), Option<&T>)> {
+        let width = self.width;
+        (0..self.area()).map(move |i| {
+            let point = (i % width, i / width);
+            (point, self.get(point))
+        })
+    }
+
+    /// Returns a grid of the top-most non-empty cell at every point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, layered::LayeredGrid};
+    ///
+    /// let mut map = LayeredGrid::new(3, 1);
+    /// map.push(Grid::new(3, 1, Some('.'))).unwrap();
+    /// map.push(Grid::with_width(3, vec![None, Some('@'), None])).unwrap();
+    ///
+    /// assert_eq!(map.flatten().as_vec(), &vec![Some('.'), Some('@'), Some('.')]);
+    /// ```
+    pub fn flatten(&self) -> Grid<Option<T>> {
+        let data: Vec<Option<T>> = self.iter().map(|(_, cell)| cell.cloned()).collect();
+        if data.is_empty() {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(self.width, data)
+        }
+    }
+
+    /// Returns the number of cells in each layer.
+    fn area(&self) -> usize {
+        let (width, height) = (self.width, self.height);
+        width
+            .checked_mul(height)
+            .unwrap_or_else(|| panic!("Area of {width}x{height} grid overflows usize"))
+    }
+
+    /// Returns `index`, panicking if it is not a layer.
+    fn checked_layer(&self, index: usize) -> usize {
+        assert!(
+            index < self.layers.len(),
+            "Layer {index} out of bounds of stack of {} layers",
+            self.layers.len()
+        );
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layered_composites_top_most() {
+        let mut map = LayeredGrid::new(3, 1);
+        map.push(Grid::new(3, 1, Some(0))).unwrap();
+        map.push(Grid::with_width(3, vec![Some(1), None, Some(1)]))
+            .unwrap();
+        map.push(Grid::with_width(3, vec![None, None, Some(2)]))
+            .unwrap();
+
+        assert_eq!(map.flatten().as_vec(), &vec![Some(1), Some(0), Some(2)]);
+
+        map.remove(2);
+        assert_eq!(map.get((2, 0)), Some(&1));
+    }
+
+    #[test]
+    fn layered_size_mismatch() {
+        let mut map = LayeredGrid::new(2, 2);
+
+        assert_eq!(
+            map.push(Grid::new(4, 1, Some(0))),
+            Err(SizeMismatch {
+                expected: (2, 2),
+                found: (4, 1),
+            })
+        );
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot replace 2x1 grid with 1x2 grid")]
+    fn layered_layer_mut_cannot_resize() {
+        let mut map = LayeredGrid::<u8>::new(2, 1);
+        let index = map.push_empty();
+        map.layer_mut(index).replace(Grid::new(1, 2, None));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows usize")]
+    fn layered_area_overflows() {
+        LayeredGrid::<u8>::new(usize::MAX, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Layer 0 out of bounds of stack of 0 layers")]
+    fn layered_layer_out_of_bounds() {
+        LayeredGrid::<u8>::new(1, 1).layer(0);
+    }
+}
@@ -9,9 +9,159 @@
 //! ```
 //!
 //! Other modules are included for additional functionality.
+//!
+//! # `no_std`
+//!
+//! [`Grid`] and the [`error`], [`grid3`], [`memory`], [`ops`], [`order`], [`point`],
+//! [`transform`], and [`view`] modules only require [`alloc`], and can be used without the
+//! standard library by disabling the default `std` feature. Every other module requires the `std`
+//! feature.
+//!
+//! # `strict`
+//!
+//! The `strict` feature enables extra runtime checks of invariants, such as views staying within
+//! their grid, patches being applicable, and encoded patterns reading back unchanged, which panic
+//! with a description of what was violated. Some checks are expensive, so it is meant for tests.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+/// Asserts that an invariant holds, like [`assert!`], but only if the `strict` feature is enabled.
+///
+/// Used for checks that are too expensive to always run, i.e. re-reading encoded output, but that
+/// catch bugs early in tests.
+macro_rules! strict_assert {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "strict") {
+            assert!($($arg)*);
+        }
+    };
+}
 
+#[cfg(feature = "std")]
+pub mod algo;
+#[cfg(feature = "std")]
+pub mod align;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "std")]
+pub mod automaton;
+#[cfg(feature = "std")]
+pub mod border;
+#[cfg(feature = "std")]
+pub mod chunk;
+#[cfg(feature = "std")]
+pub mod cli;
+#[cfg(feature = "std")]
+pub mod convolve;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "std")]
+pub mod damage;
+#[cfg(feature = "std")]
+pub mod delimited;
+#[cfg(feature = "std")]
+pub mod diagonal;
+#[cfg(feature = "std")]
+pub mod display;
+#[cfg(feature = "std")]
+pub mod dither;
+#[cfg(feature = "std")]
+pub mod dp;
+#[cfg(feature = "std")]
+pub mod draw;
+#[cfg(feature = "std")]
+pub mod edge;
+#[cfg(feature = "std")]
+pub mod entry;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod gen;
 pub mod grid;
+pub mod grid3;
+#[cfg(feature = "std")]
+pub mod hex;
+#[cfg(feature = "std")]
+pub mod history;
+#[cfg(feature = "image")]
+pub mod image;
+#[cfg(feature = "std")]
+pub mod layered;
+#[cfg(feature = "std")]
+pub mod lock;
+#[cfg(feature = "std")]
+pub mod lookup;
+#[cfg(feature = "std")]
+pub mod mask;
+#[cfg(feature = "rand")]
+pub mod maze;
+pub mod memory;
+#[cfg(feature = "std")]
+pub mod merge;
+#[cfg(feature = "ndarray")]
+pub mod ndarray;
+#[cfg(feature = "std")]
+pub mod observed;
+pub mod ops;
+pub mod order;
+#[cfg(feature = "std")]
+pub mod palette;
+#[cfg(feature = "std")]
+pub mod patch;
+#[cfg(feature = "std")]
+pub mod path;
+#[cfg(feature = "std")]
+pub mod pnm;
 pub mod point;
+#[cfg(feature = "std")]
+pub mod priority;
+#[cfg(feature = "std")]
+pub mod progress;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "std")]
+pub mod pyramid;
+#[cfg(feature = "std")]
+pub mod quadtree;
+#[cfg(feature = "query")]
+pub mod query;
+#[cfg(feature = "rand")]
+pub mod random;
+#[cfg(feature = "std")]
+pub mod raycast;
+#[cfg(feature = "std")]
+pub mod reduce;
+#[cfg(feature = "std")]
+pub mod region;
+#[cfg(feature = "std")]
+pub mod remap;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod rle;
+#[cfg(feature = "std")]
+pub mod scale;
+#[cfg(feature = "std")]
+pub mod seam;
+#[cfg(feature = "sim")]
+pub mod sim;
+#[cfg(feature = "std")]
+pub mod spiral;
+#[cfg(feature = "std")]
+pub mod stable;
+#[cfg(feature = "std")]
+pub mod testing;
+pub mod transform;
+#[cfg(feature = "std")]
+pub mod vertex;
+pub mod view;
+#[cfg(feature = "std")]
+pub mod viewport;
+#[cfg(feature = "std")]
+pub mod wrap;
+#[cfg(feature = "std")]
+pub mod zip;
 
 pub use grid::Grid;
 
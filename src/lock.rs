@@ -0,0 +1,399 @@
+//! Locking rectangular regions of a shared grid, i.e. for concurrent editors.
+//!
+//! See [`RegionLockGrid`] for details.
+
+use std::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{
+    grid::Grid,
+    point::{Point, Rect},
+};
+
+/// A grid that can be shared between threads, where each thread locks the region it edits.
+///
+/// Regions are [`Rect`]s. Any number of non-overlapping regions may be
+/// locked at once, so threads editing disjoint areas do not wait on each other; locking a region
+/// that overlaps a locked region waits (or fails, with [`RegionLockGrid::try_lock`]) until it is
+/// released.
+///
+/// Like [`Mutex`], locking a region while the same thread holds an overlapping region deadlocks.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, lock::RegionLockGrid, point::Rect};
+///
+/// let grid = RegionLockGrid::new(Grid::new(4, 2, 0));
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         let mut left = grid.lock(Rect::new(0, 0, 2, 2));
+///         left[(0, 0)] = 1;
+///     });
+///     s.spawn(|| {
+///         let mut right = grid.lock(Rect::new(2, 0, 2, 2));
+///         right[(3, 1)] = 2;
+///     });
+/// });
+///
+/// assert_eq!(grid.into_inner().as_vec(), &vec![1, 0, 0, 0, 0, 0, 0, 2]);
+/// ```
+pub struct RegionLockGrid<T>
+where
+    T: Clone,
+{
+    data: Box<[UnsafeCell<T>]>,
+    width: usize,
+    height: usize,
+    locked: Mutex<Vec<Rect>>,
+    released: Condvar,
+}
+
+// SAFETY: Cells are only accessed through a `RegionGuard`, and the regions of guards that exist
+// at the same time never overlap, so every cell is accessed by at most one thread at a time.
+unsafe impl<T> Sync for RegionLockGrid<T> where T: Clone + Send {}
+
+impl<T> RegionLockGrid<T>
+where
+    T: Clone,
+{
+    /// Creates a lockable grid from `grid`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, lock::RegionLockGrid};
+    ///
+    /// let grid = RegionLockGrid::new(Grid::new(3, 2, 0));
+    /// assert_eq!(grid.width(), 3);
+    /// ```
+    pub fn new(grid: Grid<T>) -> Self {
+        let (width, height) = grid.size();
+        Self {
+            data: grid.into_vec().into_iter().map(UnsafeCell::new).collect(),
+            width,
+            height,
+            locked: Mutex::new(Vec::new()),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Returns the width of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, lock::RegionLockGrid};
+    ///
+    /// assert_eq!(RegionLockGrid::new(Grid::new(3, 2, 0)).width(), 3);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, lock::RegionLockGrid};
+    ///
+    /// assert_eq!(RegionLockGrid::new(Grid::new(3, 2, 0)).height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Locks the cells inside `region`, waiting until no overlapping region is locked.
+    ///
+    /// The region is unlocked when the returned guard is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, lock::RegionLockGrid, point::Rect};
+    ///
+    /// let grid = RegionLockGrid::new(Grid::new(3, 3, 0));
+    /// let mut region = grid.lock(Rect::new(1, 1, 2, 2));
+    /// region[(2, 2)] = 5;
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the region is empty or not entirely within the grid.
+    pub fn lock(&self, region: Rect) -> RegionGuard<'_, T> {
+        self.check(region);
+        let mut locked = self.locked();
+        while locked.iter().any(|other| other.intersects(&region)) {
+            locked = self
+                .released
+                .wait(locked)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        locked.push(region);
+        RegionGuard {
+            grid: self,
+            region,
+            marker: PhantomData,
+        }
+    }
+
+    /// Locks the cells inside `region`.
+    ///
+    /// Returns `None` if an overlapping region is already locked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, lock::RegionLockGrid, point::Rect};
+    ///
+    /// let grid = RegionLockGrid::new(Grid::new(3, 3, 0));
+    /// let region = grid.try_lock(Rect::new(0, 0, 2, 2));
+    ///
+    /// assert!(region.is_some());
+    /// assert!(grid.try_lock(Rect::new(1, 1, 2, 2)).is_none());
+    /// assert!(grid.try_lock(Rect::new(2, 0, 1, 3)).is_some());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the region is empty or not entirely within the grid.
+    pub fn try_lock(&self, region: Rect) -> Option<RegionGuard<'_, T>> {
+        self.check(region);
+        let mut locked = self.locked();
+        if locked.iter().any(|other| other.intersects(&region)) {
+            return None;
+        }
+        locked.push(region);
+        Some(RegionGuard {
+            grid: self,
+            region,
+            marker: PhantomData,
+        })
+    }
+
+    /// Consumes the lockable grid, returning the underlying grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, lock::RegionLockGrid, point::Rect};
+    ///
+    /// let grid = RegionLockGrid::new(Grid::new(2, 1, 'a'));
+    /// grid.lock(Rect::new(1, 0, 1, 1))[(1, 0)] = 'b';
+    ///
+    /// assert_eq!(grid.into_inner().as_vec(), &vec!['a', 'b']);
+    /// ```
+    pub fn into_inner(self) -> Grid<T> {
+        let data: Vec<T> = self
+            .data
+            .into_vec()
+            .into_iter()
+            .map(UnsafeCell::into_inner)
+            .collect();
+        if data.is_empty() {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(self.width, data)
+        }
+    }
+
+    /// Returns the list of locked regions, ignoring poisoning (the list is always consistent).
+    fn locked(&self) -> MutexGuard<'_, Vec<Rect>> {
+        self.locked.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Asserts that a region is non-empty and within the grid.
+    fn check(&self, region: Rect) {
+        assert!(
+            !region.is_empty() && region.is_within(self.width, self.height),
+            "Region at ({}, {}) of {}x{} out of bounds of {}x{} grid",
+            region.x(),
+            region.y(),
+            region.width(),
+            region.height(),
+            self.width,
+            self.height
+        );
+    }
+}
+
+/// Exclusive access to a locked region of a [`RegionLockGrid`].
+///
+/// Cells are indexed by their position in the _grid_ (not relative to the region). The region is
+/// unlocked when the guard is dropped.
+///
+/// This struct is created by [`RegionLockGrid::lock`] and [`RegionLockGrid::try_lock`].
+pub struct RegionGuard<'a, T>
+where
+    T: Clone,
+{
+    grid: &'a RegionLockGrid<T>,
+    region: Rect,
+    /// Makes the guard [`Sync`] only if `T` is, as shared guards hand out `&T` on any thread.
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<T> RegionGuard<'_, T>
+where
+    T: Clone,
+{
+    /// Returns the locked region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, lock::RegionLockGrid, point::Rect};
+    ///
+    /// let grid = RegionLockGrid::new(Grid::new(3, 3, 0));
+    /// assert_eq!(grid.lock(Rect::new(1, 0, 2, 3)).region(), Rect::new(1, 0, 2, 3));
+    /// ```
+    pub fn region(&self) -> Rect {
+        self.region
+    }
+
+    /// Returns the index of `point` in the grid.
+    fn checked_index(&self, point: impl Point) -> usize {
+        let region = self.region;
+        assert!(
+            region.contains(point),
+            "Point ({}, {}) out of bounds of locked region at ({}, {}) of {}x{}",
+            point.x(),
+            point.y(),
+            region.x(),
+            region.y(),
+            region.width(),
+            region.height()
+        );
+        point.to_index(self.grid.width)
+    }
+}
+
+impl<T, I> Index<I> for RegionGuard<'_, T>
+where
+    T: Clone,
+    I: Point,
+{
+    type Output = T;
+
+    /// Given a two-dimensional coordinate [`Point`] in the grid, returns the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, lock::RegionLockGrid, point::Rect};
+    ///
+    /// let grid = RegionLockGrid::new(Grid::with_width(2, vec![1, 2, 3, 4]));
+    /// assert_eq!(grid.lock(Rect::new(1, 1, 1, 1))[(1, 1)], 4);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is outside of the locked region.
+    fn index(&self, index: I) -> &Self::Output {
+        let index = self.checked_index(index);
+        // SAFETY: The cell is within this guard's region, which no other guard overlaps.
+        unsafe { &*self.grid.data[index].get() }
+    }
+}
+
+impl<T, I> IndexMut<I> for RegionGuard<'_, T>
+where
+    T: Clone,
+    I: Point,
+{
+    /// Given a two-dimensional coordinate [`Point`] in the grid, sets the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, lock::RegionLockGrid, point::Rect};
+    ///
+    /// let grid = RegionLockGrid::new(Grid::new(2, 2, 0));
+    /// grid.lock(Rect::new(0, 1, 2, 1))[(1, 1)] = 7;
+    ///
+    /// assert_eq!(grid.into_inner()[(1, 1)], 7);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is outside of the locked region.
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        let index = self.checked_index(index);
+        // SAFETY: The cell is within this guard's region, which no other guard overlaps, and the
+        // guard is borrowed mutably, so no other reference into the region exists.
+        unsafe { &mut *self.grid.data[index].get() }
+    }
+}
+
+impl<T> Drop for RegionGuard<'_, T>
+where
+    T: Clone,
+{
+    fn drop(&mut self) {
+        let mut locked = self.grid.locked();
+        if let Some(i) = locked.iter().position(|other| *other == self.region) {
+            locked.swap_remove(i);
+        }
+        drop(locked);
+        self.grid.released.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_empty_grid() {
+        let grid = RegionLockGrid::new(Grid::<u8>::from(Vec::new()));
+
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert!(grid.into_inner().as_vec().is_empty());
+    }
+
+    #[test]
+    fn lock_released_on_drop() {
+        let grid = RegionLockGrid::new(Grid::new(2, 2, 0));
+        let guard = grid.try_lock(Rect::new(0, 0, 2, 2));
+        assert!(grid.try_lock(Rect::new(1, 1, 1, 1)).is_none());
+
+        drop(guard);
+        assert!(grid.try_lock(Rect::new(1, 1, 1, 1)).is_some());
+    }
+
+    #[test]
+    fn lock_waits_for_overlapping_region() {
+        let grid = RegionLockGrid::new(Grid::new(1, 1, 0));
+
+        std::thread::scope(|s| {
+            for _ in 0..8 {
+                s.spawn(|| {
+                    for _ in 0..100 {
+                        grid.lock(Rect::new(0, 0, 1, 1))[(0, 0)] += 1;
+                    }
+                });
+            }
+        });
+
+        assert_eq!(grid.into_inner()[(0, 0)], 800);
+    }
+
+    #[test]
+    #[should_panic]
+    fn lock_out_of_bounds() {
+        RegionLockGrid::new(Grid::new(2, 2, 0)).lock(Rect::new(1, 1, 2, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn lock_index_outside_region() {
+        let grid = RegionLockGrid::new(Grid::new(2, 2, 0));
+        let _ = grid.lock(Rect::new(0, 0, 1, 1))[(1, 0)];
+    }
+}
@@ -0,0 +1,186 @@
+//! Mapping cells through a table, i.e. palette-indexed tile maps.
+//!
+//! See [`Grid::lookup`] and [`Grid::lookup_palette`] for details.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+    hash::{BuildHasher, Hash},
+};
+
+use crate::grid::Grid;
+
+/// Error returned when a cell has no entry in a lookup table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MissingKey<K> {
+    point: (usize, usize),
+    key: K,
+}
+
+impl<K> MissingKey<K> {
+    /// Returns the point of the first cell (in row-major order) with no entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let error = Grid::with_width(2, vec![0, 1, 2, 0]).lookup_palette(&['a', 'b']).unwrap_err();
+    /// assert_eq!(error.point(), (0, 1));
+    /// ```
+    pub fn point(&self) -> (usize, usize) {
+        self.point
+    }
+
+    /// Returns the key of the cell with no entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let error = Grid::with_width(2, vec![0, 1, 2, 0]).lookup_palette(&['a', 'b']).unwrap_err();
+    /// assert_eq!(error.key(), &2);
+    /// ```
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K> Display for MissingKey<K>
+where
+    K: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (x, y) = self.point;
+        write!(f, "No entry for {:?} at ({x}, {y})", self.key)
+    }
+}
+
+impl<K> Error for MissingKey<K> where K: Debug {}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns a grid where each cell is the value for the cell's key in `table`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use grud::Grid;
+    ///
+    /// let names = HashMap::from([('#', "wall"), ('.', "floor")]);
+    /// let grid = Grid::with_width(2, vec!['#', '.']).lookup(&names).unwrap();
+    ///
+    /// assert_eq!(grid.as_vec(), &vec!["wall", "floor"]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If any cell has no entry in `table`.
+    pub fn lookup<U, S>(&self, table: &HashMap<T, U, S>) -> Result<Grid<U>, MissingKey<T>>
+    where
+        T: Eq + Hash,
+        U: Clone,
+        S: BuildHasher,
+    {
+        self.try_map_cells(|cell| table.get(cell).cloned())
+    }
+
+    /// Returns a grid with the same size, where each cell is `map(cell)`.
+    ///
+    /// Returns the point and key of the first cell (in row-major order) where `map` returns
+    /// `None`.
+    fn try_map_cells<U>(
+        &self,
+        mut map: impl FnMut(&T) -> Option<U>,
+    ) -> Result<Grid<U>, MissingKey<T>>
+    where
+        U: Clone,
+    {
+        let (width, _) = self.size();
+        let mut data = Vec::with_capacity(self.as_vec().len());
+        for (index, cell) in self.as_vec().iter().enumerate() {
+            match map(cell) {
+                Some(value) => data.push(value),
+                None => {
+                    return Err(MissingKey {
+                        point: (index % width, index / width),
+                        key: cell.clone(),
+                    })
+                }
+            }
+        }
+        Ok(if data.is_empty() {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(width, data)
+        })
+    }
+}
+
+impl Grid<usize> {
+    /// Returns a grid where each cell is the entry of `palette` at the cell's index.
+    ///
+    /// # Examples
+    ///
+    /// A palette-indexed tile map:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Tile {
+    ///     solid: bool,
+    /// }
+    ///
+    /// let palette = [Tile { solid: false }, Tile { solid: true }];
+    /// let tiles = Grid::with_width(2, vec![1, 0, 0, 1]).lookup_palette(&palette).unwrap();
+    ///
+    /// assert_eq!(tiles[(1, 0)], Tile { solid: false });
+    /// assert_eq!(tiles[(1, 1)], Tile { solid: true });
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If any cell is out of bounds of `palette`.
+    pub fn lookup_palette<U>(&self, palette: &[U]) -> Result<Grid<U>, MissingKey<usize>>
+    where
+        U: Clone,
+    {
+        self.try_map_cells(|index| palette.get(*index).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_missing_key() {
+        let table = HashMap::from([(1, 'a')]);
+        let error = Grid::with_width(2, vec![1, 1, 1, 5])
+            .lookup(&table)
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            MissingKey {
+                point: (1, 1),
+                key: 5
+            }
+        );
+        assert_eq!(error.to_string(), "No entry for 5 at (1, 1)");
+    }
+
+    #[test]
+    fn lookup_empty_grid() {
+        let grid: Grid<usize> = Grid::from(Vec::new());
+
+        assert!(grid.lookup_palette::<u8>(&[]).unwrap().as_vec().is_empty());
+    }
+}
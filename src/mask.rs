@@ -0,0 +1,338 @@
+//! Selective edits using a `Grid<bool>` mask, i.e. for image-editing style selections.
+//!
+//! See [`Grid::apply_mask`], [`Grid::select`], and [`Grid::magic_wand`] for details.
+
+use crate::{
+    error::SizeMismatch,
+    grid::Grid,
+    point::{Connectivity, Point},
+    progress::{Cancelled, Monitor},
+};
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Calls `f` with each cell where `mask` is `true`, leaving the other cells unchanged.
+    ///
+    /// # Examples
+    ///
+    /// Brightening a selection:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut image = Grid::with_width(2, vec![10, 20, 30, 40]);
+    /// let selection = Grid::with_width(2, vec![true, false, false, true]);
+    ///
+    /// image.apply_mask(&selection, |cell| *cell += 5).unwrap();
+    /// assert_eq!(image.as_vec(), &vec![15, 20, 30, 45]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `mask` is not the same size as this grid, in which case no cells are changed.
+    pub fn apply_mask(
+        &mut self,
+        mask: &Grid<bool>,
+        mut f: impl FnMut(&mut T),
+    ) -> Result<(), SizeMismatch> {
+        self.check_size(mask)?;
+        for (cell, selected) in self.as_mut_slice().iter_mut().zip(mask.as_vec()) {
+            if *selected {
+                f(cell);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a grid with the cells of `other` where `mask` is `true`, and the cells of this grid
+    /// elsewhere.
+    ///
+    /// # Examples
+    ///
+    /// Pasting a layer through a selection:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let background = Grid::new(3, 1, '.');
+    /// let layer = Grid::new(3, 1, '#');
+    /// let selection = Grid::with_width(3, vec![false, true, true]);
+    ///
+    /// let pasted = background.select(&selection, &layer).unwrap();
+    /// assert_eq!(pasted.as_vec(), &vec!['.', '#', '#']);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `mask` or `other` is not the same size as this grid.
+    pub fn select(&self, mask: &Grid<bool>, other: &Grid<T>) -> Result<Grid<T>, SizeMismatch> {
+        self.check_size(mask)?;
+        self.check_size(other)?;
+        let mut selected = self.clone();
+        let cells = selected.as_mut_slice().iter_mut().zip(mask.as_vec());
+        for ((cell, is_selected), theirs) in cells.zip(other.as_vec()) {
+            if *is_selected {
+                cell.clone_from(theirs);
+            }
+        }
+        Ok(selected)
+    }
+
+    /// Returns a mask of the connected region containing `seed`, where every cell is within
+    /// `tolerance` of the value at `seed` (i.e. a photo editor's magic wand).
+    ///
+    /// Cells are adjacent according to `connectivity`. See [`Grid::magic_wand_by`] to compare
+    /// cells that are not numbers, such as colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Connectivity};
+    ///
+    /// let heights = Grid::with_width(4, vec![
+    ///     10, 12, 30, 11,
+    ///     11, 14, 30, 10,
+    /// ]);
+    ///
+    /// let selected = heights.magic_wand((0, 0), 2.0, Connectivity::Four);
+    /// assert_eq!(selected.as_vec(), &vec![
+    ///     true, true, false, false,
+    ///     true, false, false, false,
+    /// ]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `seed` is out of bounds.
+    pub fn magic_wand(
+        &self,
+        seed: impl Point,
+        tolerance: f64,
+        connectivity: Connectivity,
+    ) -> Grid<bool>
+    where
+        T: Copy + Into<f64>,
+    {
+        self.magic_wand_by(seed, connectivity, |seed, cell| {
+            ((*cell).into() - (*seed).into()).abs() <= tolerance
+        })
+    }
+
+    /// Returns a mask of the connected region containing `seed`, where every cell is within
+    /// `tolerance` of the value at `seed`, observed by a [`Monitor`].
+    ///
+    /// Progress is reported as the number of cells visited, out of the total number of cells. See
+    /// [`Grid::magic_wand`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Connectivity, progress::{CancelToken, Monitor}};
+    ///
+    /// let heights = Grid::new(8, 8, 1);
+    /// let token = CancelToken::new();
+    /// token.cancel();
+    ///
+    /// let mut monitor = Monitor::new().with_cancel(&token);
+    /// assert!(heights.magic_wand_monitored((0, 0), 2.0, Connectivity::Four, &mut monitor).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the monitor's [`CancelToken`](crate::progress::CancelToken) is cancelled.
+    ///
+    /// # Panics
+    ///
+    /// If `seed` is out of bounds.
+    pub fn magic_wand_monitored(
+        &self,
+        seed: impl Point,
+        tolerance: f64,
+        connectivity: Connectivity,
+        monitor: &mut Monitor<'_>,
+    ) -> Result<Grid<bool>, Cancelled>
+    where
+        T: Copy + Into<f64>,
+    {
+        self.magic_wand_by_monitored(
+            seed,
+            connectivity,
+            |seed, cell| ((*cell).into() - (*seed).into()).abs() <= tolerance,
+            monitor,
+        )
+    }
+
+    /// Returns a mask of the connected region containing `seed`, where `similar(seed, cell)`
+    /// returns `true` for every cell, given the value at `seed`.
+    ///
+    /// Cells are adjacent according to `connectivity`. The cell at `seed` is always selected.
+    ///
+    /// # Examples
+    ///
+    /// Selecting similar colors:
+    ///
+    /// ```
+    /// use grud::{Grid, point::Connectivity};
+    ///
+    /// let image = Grid::with_width(3, vec![[250, 0, 0], [240, 10, 5], [0, 0, 255]]);
+    /// let reddish = image.magic_wand_by((0, 0), Connectivity::Four, |seed: &[u8; 3], cell| {
+    ///     seed.iter().zip(cell).all(|(a, b)| a.abs_diff(*b) <= 16)
+    /// });
+    ///
+    /// assert_eq!(reddish.as_vec(), &vec![true, true, false]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `seed` is out of bounds.
+    pub fn magic_wand_by(
+        &self,
+        seed: impl Point,
+        connectivity: Connectivity,
+        similar: impl FnMut(&T, &T) -> bool,
+    ) -> Grid<bool> {
+        match self.magic_wand_by_monitored(seed, connectivity, similar, &mut Monitor::new()) {
+            Ok(mask) => mask,
+            Err(Cancelled) => unreachable!("Monitor without a token is never cancelled"),
+        }
+    }
+
+    /// Returns a mask of the connected region containing `seed`, where `similar(seed, cell)`
+    /// returns `true` for every cell, observed by a [`Monitor`].
+    ///
+    /// Progress is reported as the number of cells visited, out of the total number of cells. See
+    /// [`Grid::magic_wand_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Connectivity, progress::Monitor};
+    ///
+    /// let image = Grid::with_width(3, vec!['a', 'a', 'b']);
+    /// let mut reports = Vec::new();
+    /// let mut sink = |completed, total| reports.push((completed, total));
+    ///
+    /// let mask = image.magic_wand_by_monitored(
+    ///     (0, 0),
+    ///     Connectivity::Four,
+    ///     |seed, cell| seed == cell,
+    ///     &mut Monitor::new().with_sink(&mut sink),
+    /// ).unwrap();
+    ///
+    /// assert_eq!(mask.as_vec(), &vec![true, true, false]);
+    /// assert_eq!(reports, vec![(1, 3), (2, 3)]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the monitor's [`CancelToken`](crate::progress::CancelToken) is cancelled.
+    ///
+    /// # Panics
+    ///
+    /// If `seed` is out of bounds.
+    pub fn magic_wand_by_monitored(
+        &self,
+        seed: impl Point,
+        connectivity: Connectivity,
+        mut similar: impl FnMut(&T, &T) -> bool,
+        monitor: &mut Monitor<'_>,
+    ) -> Result<Grid<bool>, Cancelled> {
+        let (width, height) = self.size();
+        let seed = (seed.x(), seed.y());
+        let value = &self[self.checked_index(seed)];
+        let region =
+            self.flood_region_monitored(seed, |cell| similar(value, cell), connectivity, monitor)?;
+        let mut mask = Grid::new(width, height, false);
+        mask[seed] = true;
+        for point in region {
+            mask[point] = true;
+        }
+        Ok(mask)
+    }
+
+    /// Returns an error if `other` is not the same size as this grid.
+    fn check_size<U>(&self, other: &Grid<U>) -> Result<(), SizeMismatch>
+    where
+        U: Clone,
+    {
+        if self.size() == other.size() {
+            Ok(())
+        } else {
+            Err(SizeMismatch {
+                expected: self.size(),
+                found: other.size(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::progress::CancelToken;
+
+    #[test]
+    fn apply_mask_size_mismatch_leaves_cells() {
+        let mut grid = Grid::new(2, 2, 0);
+        let error = grid
+            .apply_mask(&Grid::new(2, 1, true), |cell| *cell = 1)
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            SizeMismatch {
+                expected: (2, 2),
+                found: (2, 1)
+            }
+        );
+        assert_eq!(grid.as_vec(), &vec![0; 4]);
+    }
+
+    #[test]
+    fn magic_wand_eight_connected() {
+        let grid = Grid::with_width(3, vec![1.0, 9.0, 9.0, 9.0, 1.5, 9.0, 9.0, 9.0, 2.0]);
+        let eight = grid.magic_wand((1, 1), 0.5, Connectivity::Eight);
+        let four = grid.magic_wand((1, 1), 0.5, Connectivity::Four);
+
+        assert_eq!(
+            eight.points_where(|cell| *cell).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1), (2, 2)]
+        );
+        assert_eq!(
+            four.points_where(|cell| *cell).collect::<Vec<_>>(),
+            vec![(1, 1)]
+        );
+    }
+
+    #[test]
+    fn magic_wand_monitored_cancelled_mid_flood() {
+        let grid = Grid::new(10, 10, 1.0);
+        let token = CancelToken::new();
+        let mut cells = 0;
+        let mut sink = |completed, _| {
+            cells = completed;
+            if completed >= 50 {
+                token.cancel();
+            }
+        };
+        let mut monitor = Monitor::new().with_sink(&mut sink).with_cancel(&token);
+
+        assert_eq!(
+            grid.magic_wand_monitored((5, 5), 0.0, Connectivity::Four, &mut monitor)
+                .err(),
+            Some(Cancelled)
+        );
+        assert!((50..100).contains(&cells));
+    }
+
+    #[test]
+    fn select_other_size_mismatch() {
+        let grid = Grid::new(2, 2, 0);
+
+        assert!(grid
+            .select(&Grid::new(2, 2, true), &Grid::new(4, 1, 1))
+            .is_err());
+    }
+}
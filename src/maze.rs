@@ -0,0 +1,250 @@
+//! Generating perfect mazes using [`rand`], i.e. for procedural levels and pathfinding demos.
+//!
+//! Requires the `rand` feature.
+//!
+//! Each generator returns a `width` by `height` grid of [`Cell`]s, where rooms are the passages at
+//! odd coordinates (starting at `(1, 1)`), and the outer border is always a wall. Every room is
+//! reachable from every other room by exactly one route, so the result can be solved with the
+//! functions in the [`path`](crate::path) module:
+//!
+//! ```
+//! use grud::{maze::{self, Cell}, path};
+//! use rand::{rngs::SmallRng, SeedableRng};
+//!
+//! let grid = maze::recursive_backtracker(9, 7, &mut SmallRng::seed_from_u64(1));
+//!
+//! let route = path::bfs(&grid, (1, 1), (7, 5), |cell| *cell == Cell::Passage).unwrap();
+//! assert_eq!(route.first(), Some(&(1, 1)));
+//! assert_eq!(route.last(), Some(&(7, 5)));
+//! ```
+
+use core::fmt::{self, Display, Formatter};
+
+use ::rand::Rng;
+
+use crate::grid::Grid;
+
+/// A cell of a maze.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Cell {
+    /// A cell that cannot be walked through.
+    #[default]
+    Wall,
+
+    /// A cell that can be walked through.
+    Passage,
+}
+
+impl Display for Cell {
+    /// Formats a wall as `#` and a passage as a space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::maze::Cell;
+    ///
+    /// assert_eq!(format!("{}{}", Cell::Wall, Cell::Passage), "# ");
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Wall => "#",
+            Self::Passage => " ",
+        })
+    }
+}
+
+/// Generates a maze with a randomized depth-first search ([recursive backtracker]).
+///
+/// Mazes generated this way have long, winding corridors with few dead ends.
+///
+/// [recursive backtracker]: https://en.wikipedia.org/wiki/Maze_generation_algorithm#Randomized_depth-first_search
+///
+/// Requires the `rand` feature.
+///
+/// # Examples
+///
+/// ```
+/// use grud::maze;
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let grid = maze::recursive_backtracker(5, 5, &mut SmallRng::seed_from_u64(3));
+///
+/// assert_eq!(grid.width(), 5);
+/// assert_eq!(grid.height(), 5);
+/// ```
+///
+/// # Panics
+///
+/// If `width` or `height` is less than 3.
+pub fn recursive_backtracker<R>(width: usize, height: usize, rng: &mut R) -> Grid<Cell>
+where
+    R: Rng + ?Sized,
+{
+    let mut maze = Maze::new(width, height);
+    let start = maze.random_room(rng);
+    maze.carve(start);
+    let mut stack = vec![start];
+    while let Some(&room) = stack.last() {
+        let unvisited: Vec<_> = maze
+            .neighbors(room)
+            .filter(|next| !maze.is_carved(*next))
+            .collect();
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let next = unvisited[rng.random_range(..unvisited.len())];
+        maze.connect(room, next);
+        stack.push(next);
+    }
+    maze.grid
+}
+
+/// Generates a maze with a randomized version of [Prim's algorithm].
+///
+/// Mazes generated this way have short, branching corridors with many dead ends.
+///
+/// [Prim's algorithm]: https://en.wikipedia.org/wiki/Maze_generation_algorithm#Iterative_randomized_Prim's_algorithm_(without_stack,_without_sets)
+///
+/// Requires the `rand` feature.
+///
+/// # Examples
+///
+/// ```
+/// use grud::maze::{self, Cell};
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let grid = maze::prim(7, 5, &mut SmallRng::seed_from_u64(3));
+///
+/// // 6 rooms, connected by 5 openings.
+/// assert_eq!(grid.count(|cell| *cell == Cell::Passage), 11);
+/// ```
+///
+/// # Panics
+///
+/// If `width` or `height` is less than 3.
+pub fn prim<R>(width: usize, height: usize, rng: &mut R) -> Grid<Cell>
+where
+    R: Rng + ?Sized,
+{
+    let mut maze = Maze::new(width, height);
+    let start = maze.random_room(rng);
+    maze.carve(start);
+    let mut frontier: Vec<_> = maze.neighbors(start).map(|next| (start, next)).collect();
+    while !frontier.is_empty() {
+        let (room, next) = frontier.swap_remove(rng.random_range(..frontier.len()));
+        if maze.is_carved(next) {
+            continue;
+        }
+        maze.connect(room, next);
+        frontier.extend(
+            maze.neighbors(next)
+                .filter(|other| !maze.is_carved(*other))
+                .map(|other| (next, other)),
+        );
+    }
+    maze.grid
+}
+
+/// A maze being generated, where rooms are addressed by their grid coordinates.
+struct Maze {
+    grid: Grid<Cell>,
+}
+
+impl Maze {
+    fn new(width: usize, height: usize) -> Self {
+        assert!(
+            width >= 3 && height >= 3,
+            "Maze of {width}x{height} cells is too small; must be at least 3x3"
+        );
+        Self {
+            grid: Grid::new(width, height, Cell::Wall),
+        }
+    }
+
+    fn random_room<R>(&self, rng: &mut R) -> (usize, usize)
+    where
+        R: Rng + ?Sized,
+    {
+        let columns = (self.grid.width() - 1) / 2;
+        let rows = (self.grid.height() - 1) / 2;
+        (
+            rng.random_range(..columns) * 2 + 1,
+            rng.random_range(..rows) * 2 + 1,
+        )
+    }
+
+    fn is_carved(&self, room: (usize, usize)) -> bool {
+        self.grid[room] == Cell::Passage
+    }
+
+    fn carve(&mut self, room: (usize, usize)) {
+        self.grid[room] = Cell::Passage;
+    }
+
+    /// Carves `next` and the wall between it and the adjacent `room`.
+    fn connect(&mut self, room: (usize, usize), next: (usize, usize)) {
+        self.carve(((room.0 + next.0) / 2, (room.1 + next.1) / 2));
+        self.carve(next);
+    }
+
+    /// Returns the rooms two cells away in each cardinal direction, inside of the border.
+    fn neighbors(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        [
+            (x.checked_sub(2), Some(y)),
+            (Some(x + 2), Some(y)),
+            (Some(x), y.checked_sub(2)),
+            (Some(x), Some(y + 2)),
+        ]
+        .into_iter()
+        .filter_map(move |point| match point {
+            (Some(x), Some(y)) if x < width - 1 && y < height - 1 => Some((x, y)),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rand::{rngs::SmallRng, SeedableRng};
+
+    use super::*;
+    use crate::path::distance_map;
+
+    /// Asserts that every room is reachable and the passages form a tree.
+    fn assert_perfect(grid: &Grid<Cell>) {
+        let rooms = ((grid.width() - 1) / 2) * ((grid.height() - 1) / 2);
+        let distances = distance_map(grid, (1, 1), |cell| *cell == Cell::Passage);
+        let passages = grid.count(|cell| *cell == Cell::Passage);
+
+        assert_eq!(passages, rooms * 2 - 1);
+        assert_eq!(distances.count(Option::is_some), passages);
+        assert!(grid.border().all(|cell| *cell == Cell::Wall));
+    }
+
+    #[test]
+    fn generators_produce_perfect_mazes() {
+        for (width, height) in [(3, 3), (4, 3), (11, 7), (20, 16)] {
+            for seed in 0..4 {
+                let mut rng = SmallRng::seed_from_u64(seed);
+                assert_perfect(&recursive_backtracker(width, height, &mut rng));
+                assert_perfect(&prim(width, height, &mut rng));
+            }
+        }
+    }
+
+    #[test]
+    fn maze_is_deterministic_for_seed() {
+        let a = prim(15, 9, &mut SmallRng::seed_from_u64(5));
+        let b = prim(15, 9, &mut SmallRng::seed_from_u64(5));
+
+        assert_eq!(a.to_string(), b.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Maze of 2x5 cells is too small; must be at least 3x3")]
+    fn maze_too_small() {
+        recursive_backtracker(2, 5, &mut SmallRng::seed_from_u64(0));
+    }
+}
@@ -0,0 +1,146 @@
+//! Reporting how much memory a grid uses, i.e. for capacity planning.
+//!
+//! See [`Grid::memory_usage`] for details.
+
+use core::mem;
+
+use crate::grid::Grid;
+
+/// The memory used by the cells of a grid, returned by [`Grid::memory_usage`].
+///
+/// Only memory owned directly by the grid is counted; memory owned by the cells themselves (i.e.
+/// the contents of a [`String`]) is not.
+///
+/// [`String`]: alloc::string::String
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MemoryReport {
+    /// The number of cells.
+    pub len: usize,
+
+    /// The number of cells that fit in the allocation without reallocating.
+    pub capacity: usize,
+
+    /// The size of each cell in bytes, including any padding for alignment.
+    pub cell_size: usize,
+
+    /// The alignment of each cell in bytes.
+    pub cell_align: usize,
+
+    /// The size of the grid itself in bytes, excluding its allocation.
+    pub inline_bytes: usize,
+}
+
+impl MemoryReport {
+    /// Returns the size of the allocation in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let report = Grid::new(4, 4, 0_u32).memory_usage();
+    /// assert_eq!(report.heap_bytes(), 64);
+    /// ```
+    pub fn heap_bytes(&self) -> usize {
+        self.capacity * self.cell_size
+    }
+
+    /// Returns the number of bytes of the allocation holding cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let report = Grid::new(2, 2, 0_u16).memory_usage();
+    /// assert_eq!(report.used_bytes(), 8);
+    /// ```
+    pub fn used_bytes(&self) -> usize {
+        self.len * self.cell_size
+    }
+
+    /// Returns the number of bytes of the allocation reserved for cells that do not exist yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 0_u8);
+    /// grid.remove_row(0);
+    ///
+    /// assert_eq!(grid.memory_usage().unused_bytes(), 2);
+    /// ```
+    pub fn unused_bytes(&self) -> usize {
+        self.heap_bytes() - self.used_bytes()
+    }
+
+    /// Returns the total size of the grid and its allocation in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let report = Grid::new(4, 4, 0_u8).memory_usage();
+    /// assert_eq!(report.total_bytes(), report.inline_bytes + 16);
+    /// ```
+    pub fn total_bytes(&self) -> usize {
+        self.inline_bytes + self.heap_bytes()
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns how much memory the grid uses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let report = Grid::new(3, 2, (0_u8, 0_u32)).memory_usage();
+    ///
+    /// assert_eq!(report.len, 6);
+    /// assert_eq!(report.cell_size, 8);
+    /// assert_eq!(report.cell_align, 4);
+    /// assert_eq!(report.used_bytes(), 48);
+    /// ```
+    pub fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            len: self.as_vec().len(),
+            capacity: self.capacity(),
+            cell_size: mem::size_of::<T>(),
+            cell_align: mem::align_of::<T>(),
+            inline_bytes: mem::size_of::<Self>(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_usage_zero_sized_cells() {
+        let report = Grid::new(1000, 1000, ()).memory_usage();
+
+        assert_eq!(report.len, 1_000_000);
+        assert_eq!(report.heap_bytes(), 0);
+        assert_eq!(report.unused_bytes(), 0);
+    }
+
+    #[test]
+    fn memory_usage_empty_grid() {
+        let report = Grid::<u64>::from(alloc::vec::Vec::new()).memory_usage();
+
+        assert_eq!(report.len, 0);
+        assert_eq!(report.used_bytes(), 0);
+        assert_eq!(
+            report.total_bytes(),
+            report.inline_bytes + report.heap_bytes()
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Three-way merging of concurrently edited grids, i.e. for offline-capable editors.
+//!
+//! See [`Grid::merge`] for details.
+
+use crate::grid::Grid;
+
+/// A cell that was changed differently in both grids being merged.
+///
+/// Passed to the resolver of [`Grid::merge`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Conflict<'a, T> {
+    /// The position of the cell.
+    pub point: (usize, usize),
+
+    /// The cell in the common ancestor of both grids.
+    pub base: &'a T,
+
+    /// The cell in the grid being merged into.
+    pub ours: &'a T,
+
+    /// The cell in the grid being merged from.
+    pub theirs: &'a T,
+}
+
+/// Returns a resolver for [`Grid::merge`] that keeps whichever conflicting cell has the latest
+/// `timestamp`, preferring _ours_ if both are equal.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, merge::last_writer_wins};
+///
+/// // (value, timestamp)
+/// let base = Grid::new(2, 1, ('.', 0));
+/// let ours = Grid::with_width(2, vec![('a', 5), ('b', 1)]);
+/// let theirs = Grid::with_width(2, vec![('c', 3), ('d', 2)]);
+///
+/// let merged = ours.merge(&theirs, &base, last_writer_wins(|(_, time)| *time));
+/// assert_eq!(merged.as_vec(), &vec![('a', 5), ('d', 2)]);
+/// ```
+pub fn last_writer_wins<T, K>(
+    mut timestamp: impl FnMut(&T) -> K,
+) -> impl FnMut(Conflict<'_, T>) -> T
+where
+    T: Clone,
+    K: Ord,
+{
+    move |conflict| {
+        if timestamp(conflict.theirs) > timestamp(conflict.ours) {
+            conflict.theirs.clone()
+        } else {
+            conflict.ours.clone()
+        }
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone + PartialEq,
+{
+    /// Merges the changes made in `theirs` into this grid, where both were edited from `base`.
+    ///
+    /// For each cell:
+    ///
+    /// - If only one grid changed the cell (compared to `base`), the change is kept.
+    /// - If both grids changed the cell to the same value, that value is kept.
+    /// - Otherwise, the cell is a [`Conflict`] and is computed by `resolve`.
+    ///
+    /// Merging empty grids returns an empty grid. See [`last_writer_wins`] for a resolver using per-cell timestamps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let base = Grid::with_width(3, vec!['.', '.', '.']);
+    /// let ours = Grid::with_width(3, vec!['a', '.', 'x']);
+    /// let theirs = Grid::with_width(3, vec!['.', 'b', 'y']);
+    ///
+    /// let merged = ours.merge(&theirs, &base, |conflict| {
+    ///     assert_eq!(conflict.point, (2, 0));
+    ///     '?'
+    /// });
+    ///
+    /// assert_eq!(merged.as_vec(), &vec!['a', 'b', '?']);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the grids are not all the same size.
+    pub fn merge(
+        &self,
+        theirs: &Grid<T>,
+        base: &Grid<T>,
+        mut resolve: impl FnMut(Conflict<'_, T>) -> T,
+    ) -> Grid<T> {
+        assert!(
+            self.size() == theirs.size() && self.size() == base.size(),
+            "Cannot merge grids of different sizes"
+        );
+        let (width, _) = self.size();
+        let cells = self.as_vec().iter().zip(theirs.as_vec()).zip(base.as_vec());
+        let data = cells
+            .enumerate()
+            .map(|(i, ((ours, theirs), base))| {
+                if ours == theirs || theirs == base {
+                    ours.clone()
+                } else if ours == base {
+                    theirs.clone()
+                } else {
+                    resolve(Conflict {
+                        point: (i % width, i / width),
+                        base,
+                        ours,
+                        theirs,
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+        if data.is_empty() {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(width, data)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(data: &str) -> Grid<char> {
+        Grid::with_width(data.len(), data.chars().collect())
+    }
+
+    #[test]
+    fn merge_without_conflicts() {
+        let merged = row("ab..").merge(&row(".bc."), &row("...."), |_| unreachable!());
+
+        assert_eq!(merged.as_vec(), row("abc.").as_vec());
+    }
+
+    #[test]
+    fn merge_empty_grids() {
+        let empty = Grid::<char>::from(Vec::new());
+        let merged = empty.merge(&empty, &empty, |_| unreachable!());
+
+        assert!(merged.as_vec().is_empty());
+    }
+
+    #[test]
+    fn merge_both_reverted_is_not_a_conflict() {
+        let merged = row("a").merge(&row("a"), &row("b"), |_| unreachable!());
+
+        assert_eq!(merged.as_vec(), &vec!['a']);
+    }
+
+    #[test]
+    fn merge_last_writer_wins_tie_prefers_ours() {
+        let base = Grid::new(1, 1, (' ', 0));
+        let ours = Grid::new(1, 1, ('a', 1));
+        let theirs = Grid::new(1, 1, ('b', 1));
+        let merged = ours.merge(&theirs, &base, last_writer_wins(|(_, t)| *t));
+
+        assert_eq!(merged.as_vec(), &vec![('a', 1)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn merge_different_sizes() {
+        row("ab").merge(&row("abc"), &row("ab"), |c| *c.ours);
+    }
+}
@@ -0,0 +1,161 @@
+//! Converting grids to and from [`ndarray`] arrays, i.e. for linear algebra.
+//!
+//! Requires the `ndarray` feature.
+//!
+//! Grids are converted to arrays with the shape `(height, width)`, so an array is indexed by
+//! `[[y, x]]`. See [`Grid::as_array_view`] to borrow a grid as an array without copying.
+
+use ::ndarray::{Array2, ArrayView2, ArrayViewMut2};
+
+use crate::grid::Grid;
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns an array view of the grid, with the shape `(height, width)`.
+    ///
+    /// Requires the `ndarray` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![1.0, 2.0, 3.0, 4.0]);
+    /// let view = grid.as_array_view();
+    ///
+    /// assert_eq!(view.dim(), (2, 2));
+    /// assert_eq!(view[[1, 0]], 3.0);
+    /// assert_eq!(view.dot(&view)[[0, 0]], 7.0);
+    /// ```
+    pub fn as_array_view(&self) -> ArrayView2<'_, T> {
+        ArrayView2::from_shape(self.shape(), self.as_vec()).unwrap()
+    }
+
+    /// Returns a mutable array view of the grid, with the shape `(height, width)`.
+    ///
+    /// Requires the `ndarray` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// grid.as_array_view_mut().column_mut(1).fill(0);
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![1, 0, 3, 0]);
+    /// ```
+    pub fn as_array_view_mut(&mut self) -> ArrayViewMut2<'_, T> {
+        let shape = self.shape();
+        let data: &mut [T] = self.into_iter().into_slice();
+        ArrayViewMut2::from_shape(shape, data).unwrap()
+    }
+
+    /// Returns the shape of the grid as an array, which is `(0, 0)` if the grid is empty.
+    fn shape(&self) -> (usize, usize) {
+        let (width, height) = self.size();
+        (height, width)
+    }
+
+    /// Creates a grid from row-major `data`, or an empty grid if there is no data.
+    fn from_array_data(width: usize, data: Vec<T>) -> Self {
+        if data.is_empty() {
+            Self::from(Vec::new())
+        } else {
+            Self::with_width(width, data)
+        }
+    }
+}
+
+impl<T> From<Grid<T>> for Array2<T>
+where
+    T: Clone,
+{
+    /// Converts a grid into an array with the shape `(height, width)`, without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    /// use ndarray::{array, Array2};
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(Array2::from(grid), array![[1, 2, 3], [4, 5, 6]]);
+    /// ```
+    fn from(grid: Grid<T>) -> Self {
+        let shape = grid.shape();
+        Array2::from_shape_vec(shape, grid.into_vec()).unwrap()
+    }
+}
+
+impl<T> From<Array2<T>> for Grid<T>
+where
+    T: Clone,
+{
+    /// Converts an array with the shape `(height, width)` into a grid.
+    ///
+    /// Arrays with no elements are converted to an empty grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    /// use ndarray::array;
+    ///
+    /// let grid = Grid::from(array![[1, 2, 3], [4, 5, 6]]);
+    /// assert_eq!(grid.to_matrix(), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    /// ```
+    fn from(array: Array2<T>) -> Self {
+        let width = array.ncols();
+        Self::from_array_data(width, array.into_iter().collect())
+    }
+}
+
+impl<T> From<ArrayView2<'_, T>> for Grid<T>
+where
+    T: Clone,
+{
+    /// Copies an array view with the shape `(height, width)` into a grid.
+    ///
+    /// Array views with no elements are converted to an empty grid.
+    ///
+    /// # Examples
+    ///
+    /// Copying a transposed view:
+    ///
+    /// ```
+    /// use grud::Grid;
+    /// use ndarray::array;
+    ///
+    /// let array = array![[1, 2, 3], [4, 5, 6]];
+    /// let grid = Grid::from(array.t());
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    /// ```
+    fn from(view: ArrayView2<'_, T>) -> Self {
+        Self::from_array_data(view.ncols(), view.iter().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ndarray_round_trip() {
+        let grid = Grid::with_width(2, vec![1, 2, 3, 4, 5, 6]);
+        let back = Grid::from(Array2::from(grid.clone()));
+
+        assert_eq!(back.to_matrix(), grid.to_matrix());
+    }
+
+    #[test]
+    fn ndarray_empty() {
+        let grid: Grid<i32> = Grid::from(Array2::zeros((3, 0)));
+
+        assert!(grid.as_vec().is_empty());
+        assert_eq!(Array2::from(grid).dim(), (0, 0));
+    }
+}
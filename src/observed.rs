@@ -0,0 +1,376 @@
+//! Tracking which cells of a grid changed, i.e. for redrawing only the changed regions.
+//!
+//! See [`ObservedGrid`] for details.
+
+use std::ops::{Index, IndexMut};
+
+use crate::{
+    grid::Grid,
+    point::{Point, Rect},
+};
+
+/// A grid that records which cells were mutably accessed since the last [`ObservedGrid::take_dirty`].
+///
+/// Every mutation path marks cells as dirty, whether or not the value actually changed: `get_mut`
+/// and `index_mut` mark the cell when the reference is handed out, since what is done with it
+/// cannot be observed.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, observed::ObservedGrid, point::Rect};
+///
+/// let mut screen = ObservedGrid::new(Grid::new(80, 25, ' '));
+///
+/// screen[(3, 1)] = 'H';
+/// screen[(4, 1)] = 'i';
+/// screen.fill_rect(Rect::new(0, 24, 80, 1), '-');
+///
+/// assert_eq!(screen.take_dirty(), vec![Rect::new(3, 1, 2, 1), Rect::new(0, 24, 80, 1)]);
+/// assert!(screen.take_dirty().is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ObservedGrid<T>
+where
+    T: Clone,
+{
+    grid: Grid<T>,
+    dirty: Vec<bool>,
+    dirty_len: usize,
+}
+
+impl<T> ObservedGrid<T>
+where
+    T: Clone,
+{
+    /// Creates an observer for `grid`, with no dirty cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid};
+    ///
+    /// let screen = ObservedGrid::new(Grid::new(2, 2, 0));
+    /// assert!(screen.is_clean());
+    /// ```
+    pub fn new(grid: Grid<T>) -> Self {
+        let dirty = vec![false; grid.as_vec().len()];
+        Self {
+            grid,
+            dirty,
+            dirty_len: 0,
+        }
+    }
+
+    /// Returns the observed grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid};
+    ///
+    /// let screen = ObservedGrid::new(Grid::new(2, 1, 0));
+    /// assert_eq!(screen.as_grid().as_vec(), &vec![0, 0]);
+    /// ```
+    pub fn as_grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Consumes the observer, returning the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid};
+    ///
+    /// let mut screen = ObservedGrid::new(Grid::new(2, 1, 0));
+    /// screen[(1, 0)] = 1;
+    ///
+    /// assert_eq!(screen.into_grid().as_vec(), &vec![0, 1]);
+    /// ```
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Returns the cell at `point`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid};
+    ///
+    /// let screen = ObservedGrid::new(Grid::new(1, 1, 'x'));
+    ///
+    /// assert_eq!(screen.get((0, 0)), Some(&'x'));
+    /// assert_eq!(screen.get((1, 0)), None);
+    /// ```
+    pub fn get(&self, point: impl Point) -> Option<&T> {
+        self.grid.get(point)
+    }
+
+    /// Returns a mutable reference to the cell at `point`, marking it dirty, or `None` if it is out
+    /// of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid};
+    ///
+    /// let mut screen = ObservedGrid::new(Grid::new(2, 1, 0));
+    /// *screen.get_mut((1, 0)).unwrap() += 1;
+    ///
+    /// assert!(screen.is_dirty((1, 0)));
+    /// assert!(!screen.is_dirty((0, 0)));
+    /// ```
+    pub fn get_mut(&mut self, point: impl Point) -> Option<&mut T> {
+        let (width, height) = self.grid.size();
+        if !Rect::new(0, 0, width, height).contains(point) {
+            return None;
+        }
+        let index = point.to_index(width);
+        self.mark(index);
+        Some(&mut self.grid[index])
+    }
+
+    /// Sets the cell at `point` to `value`, marking it dirty, and returns the previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid};
+    ///
+    /// let mut screen = ObservedGrid::new(Grid::new(1, 1, 'a'));
+    ///
+    /// assert_eq!(screen.set((0, 0), 'b'), 'a');
+    /// assert!(screen.is_dirty((0, 0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn set(&mut self, point: impl Point, value: T) -> T {
+        std::mem::replace(&mut self[point], value)
+    }
+
+    /// Sets every cell within `rect` to `value`, marking them dirty; cells outside the grid are
+    /// ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid, point::Rect};
+    ///
+    /// let mut screen = ObservedGrid::new(Grid::new(3, 3, 0));
+    /// screen.fill_rect(Rect::new(2, 2, 5, 5), 1);
+    ///
+    /// assert_eq!(screen.take_dirty(), vec![Rect::new(2, 2, 1, 1)]);
+    /// ```
+    pub fn fill_rect(&mut self, rect: Rect, value: T) {
+        let (width, height) = self.grid.size();
+        let Some(rect) = rect.intersect(&Rect::new(0, 0, width, height)) else {
+            return;
+        };
+        for point in rect.iter_points() {
+            self[point] = value.clone();
+        }
+    }
+
+    /// Returns whether the cell at `point` is dirty; out of bounds cells are never dirty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid};
+    ///
+    /// let mut screen = ObservedGrid::new(Grid::new(2, 1, 0));
+    /// screen[(0, 0)] = 1;
+    ///
+    /// assert!(screen.is_dirty((0, 0)));
+    /// assert!(!screen.is_dirty((5, 5)));
+    /// ```
+    pub fn is_dirty(&self, point: impl Point) -> bool {
+        let (width, height) = self.grid.size();
+        Rect::new(0, 0, width, height).contains(point) && self.dirty[point.to_index(width)]
+    }
+
+    /// Returns whether no cells are dirty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid};
+    ///
+    /// let mut screen = ObservedGrid::new(Grid::new(2, 1, 0));
+    /// screen[(0, 0)] = 1;
+    ///
+    /// assert!(!screen.is_clean());
+    /// ```
+    pub fn is_clean(&self) -> bool {
+        self.dirty_len == 0
+    }
+
+    /// Returns the smallest rectangle containing every dirty cell, or `None` if there are none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid, point::Rect};
+    ///
+    /// let mut screen = ObservedGrid::new(Grid::new(5, 5, 0));
+    /// screen[(1, 3)] = 1;
+    /// screen[(3, 1)] = 1;
+    ///
+    /// assert_eq!(screen.dirty_bounds(), Some(Rect::new(1, 1, 3, 3)));
+    /// ```
+    pub fn dirty_bounds(&self) -> Option<Rect> {
+        if self.is_clean() {
+            return None;
+        }
+        let width = self.grid.width();
+        let (mut left, mut top, mut right, mut bottom) = (usize::MAX, usize::MAX, 0, 0);
+        for (i, _) in self.dirty.iter().enumerate().filter(|(_, dirty)| **dirty) {
+            let (x, y) = (i % width, i / width);
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x);
+            bottom = bottom.max(y);
+        }
+        Some(Rect::new(left, top, right - left + 1, bottom - top + 1))
+    }
+
+    /// Returns the dirty cells as horizontal runs, one row high, in row-major order, and marks every
+    /// cell clean.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid, point::Rect};
+    ///
+    /// let mut screen = ObservedGrid::new(Grid::new(4, 2, 0));
+    /// screen[(0, 0)] = 1;
+    /// screen[(2, 0)] = 1;
+    /// screen[(3, 0)] = 1;
+    /// screen[(0, 1)] = 1;
+    ///
+    /// assert_eq!(screen.take_dirty(), vec![
+    ///     Rect::new(0, 0, 1, 1),
+    ///     Rect::new(2, 0, 2, 1),
+    ///     Rect::new(0, 1, 1, 1),
+    /// ]);
+    /// assert!(screen.is_clean());
+    /// ```
+    pub fn take_dirty(&mut self) -> Vec<Rect> {
+        let mut runs = Vec::new();
+        if self.is_clean() {
+            return runs;
+        }
+        let width = self.grid.width();
+        for (y, row) in self.dirty.chunks_mut(width).enumerate() {
+            let mut x = 0;
+            while x < width {
+                if !row[x] {
+                    x += 1;
+                    continue;
+                }
+                let start = x;
+                while x < width && row[x] {
+                    row[x] = false;
+                    x += 1;
+                }
+                runs.push(Rect::new(start, y, x - start, 1));
+            }
+        }
+        self.dirty_len = 0;
+        runs
+    }
+
+    /// Marks the cell at `index` dirty.
+    fn mark(&mut self, index: usize) {
+        if !self.dirty[index] {
+            self.dirty[index] = true;
+            self.dirty_len += 1;
+        }
+    }
+}
+
+impl<T, I> Index<I> for ObservedGrid<T>
+where
+    T: Clone,
+    I: Point,
+{
+    type Output = T;
+
+    /// Given a two-dimensional coordinate [`Point`], returns the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid};
+    ///
+    /// let screen = ObservedGrid::new(Grid::new(1, 1, 'x'));
+    /// assert_eq!(screen[(0, 0)], 'x');
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index(&self, index: I) -> &Self::Output {
+        &self.grid[self.grid.checked_index(index)]
+    }
+}
+
+impl<T, I> IndexMut<I> for ObservedGrid<T>
+where
+    T: Clone,
+    I: Point,
+{
+    /// Given a two-dimensional coordinate [`Point`], returns a mutable reference to the cell and
+    /// marks it dirty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, observed::ObservedGrid};
+    ///
+    /// let mut screen = ObservedGrid::new(Grid::new(1, 1, 'x'));
+    /// screen[(0, 0)] = 'y';
+    ///
+    /// assert!(screen.is_dirty((0, 0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        let index = self.grid.checked_index(index);
+        self.mark(index);
+        &mut self.grid[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn observed_marks_each_cell_once() {
+        let mut screen = ObservedGrid::new(Grid::new(3, 3, 0));
+        for _ in 0..3 {
+            screen[(1, 1)] += 1;
+        }
+        screen.fill_rect(Rect::new(0, 1, 3, 1), 0);
+
+        assert_eq!(screen.dirty_bounds(), Some(Rect::new(0, 1, 3, 1)));
+        assert_eq!(screen.take_dirty(), vec![Rect::new(0, 1, 3, 1)]);
+        assert_eq!(screen.dirty_bounds(), None);
+    }
+
+    #[test]
+    fn observed_empty_grid() {
+        let mut screen = ObservedGrid::new(Grid::<u8>::from(Vec::new()));
+
+        assert_eq!(screen.get_mut((0, 0)), None);
+        assert!(!screen.is_dirty((0, 0)));
+        assert!(screen.take_dirty().is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+//! Element-wise arithmetic, i.e. for blending heightmaps and accumulation buffers.
+//!
+//! [`Add`], [`Sub`], and [`Mul`] (and their assigning variants) are implemented between two grids
+//! of the same size, applying the operator to each pair of cells, and between a grid and a
+//! scalar, applying the operator to each cell. The operators only require the same operator of
+//! the cell type, i.e. `Grid<T> + Grid<T>` requires `T: Add<Output = T>`, and `+=` requires
+//! `T: AddAssign`.
+//!
+//! # Examples
+//!
+//! ```
+//! use grud::Grid;
+//!
+//! let a = Grid::with_width(2, vec![1, 2, 3, 4]);
+//! let b = Grid::with_width(2, vec![10, 20, 30, 40]);
+//!
+//! assert_eq!((&a + &b).as_vec(), &vec![11, 22, 33, 44]);
+//! assert_eq!((&b - &a).as_vec(), &vec![9, 18, 27, 36]);
+//! assert_eq!((&a * 2).as_vec(), &vec![2, 4, 6, 8]);
+//! ```
+//!
+//! # Panics
+//!
+//! Operators between two grids panic if the grids are not the same size.
+
+use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+use alloc::vec::Vec;
+
+use crate::grid::Grid;
+
+macro_rules! impl_op {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident, $verb:literal) => {
+        impl<T> $assign<&Grid<T>> for Grid<T>
+        where
+            T: Clone + $assign,
+        {
+            fn $assign_method(&mut self, rhs: &Grid<T>) {
+                assert!(
+                    self.size() == rhs.size(),
+                    concat!("Cannot ", $verb, " grids of different sizes")
+                );
+                for (cell, other) in self.as_mut_slice().iter_mut().zip(rhs.as_vec()) {
+                    cell.$assign_method(other.clone());
+                }
+            }
+        }
+
+        impl<T> $assign<Grid<T>> for Grid<T>
+        where
+            T: Clone + $assign,
+        {
+            fn $assign_method(&mut self, rhs: Grid<T>) {
+                self.$assign_method(&rhs);
+            }
+        }
+
+        impl<T> $assign<T> for Grid<T>
+        where
+            T: Clone + $assign,
+        {
+            fn $assign_method(&mut self, rhs: T) {
+                for cell in self.as_mut_slice() {
+                    cell.$assign_method(rhs.clone());
+                }
+            }
+        }
+
+        impl<T> $op<&Grid<T>> for Grid<T>
+        where
+            T: Clone + $op<Output = T>,
+        {
+            type Output = Grid<T>;
+
+            fn $method(self, rhs: &Grid<T>) -> Grid<T> {
+                assert!(
+                    self.size() == rhs.size(),
+                    concat!("Cannot ", $verb, " grids of different sizes")
+                );
+                let mut others = rhs.as_vec().iter();
+                map_cells(self, |cell| {
+                    let other = others.next().expect("grids are the same size");
+                    cell.$method(other.clone())
+                })
+            }
+        }
+
+        impl<T> $op<Grid<T>> for Grid<T>
+        where
+            T: Clone + $op<Output = T>,
+        {
+            type Output = Grid<T>;
+
+            fn $method(self, rhs: Grid<T>) -> Grid<T> {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<T> $op<&Grid<T>> for &Grid<T>
+        where
+            T: Clone + $op<Output = T>,
+        {
+            type Output = Grid<T>;
+
+            fn $method(self, rhs: &Grid<T>) -> Grid<T> {
+                self.clone().$method(rhs)
+            }
+        }
+
+        impl<T> $op<T> for Grid<T>
+        where
+            T: Clone + $op<Output = T>,
+        {
+            type Output = Grid<T>;
+
+            fn $method(self, rhs: T) -> Grid<T> {
+                map_cells(self, |cell| cell.$method(rhs.clone()))
+            }
+        }
+
+        impl<T> $op<T> for &Grid<T>
+        where
+            T: Clone + $op<Output = T>,
+        {
+            type Output = Grid<T>;
+
+            fn $method(self, rhs: T) -> Grid<T> {
+                self.clone().$method(rhs)
+            }
+        }
+    };
+}
+
+/// Returns `grid` with `f` applied to each cell, in row-major order.
+fn map_cells<T>(grid: Grid<T>, f: impl FnMut(T) -> T) -> Grid<T>
+where
+    T: Clone,
+{
+    let (data, width) = grid.into_raw_parts();
+    let data: Vec<T> = data.into_iter().map(f).collect();
+    if data.is_empty() {
+        Grid::from(Vec::new())
+    } else {
+        Grid::with_width(width, data)
+    }
+}
+
+impl_op!(Add, add, AddAssign, add_assign, "add");
+impl_op!(Sub, sub, SubAssign, sub_assign, "subtract");
+impl_op!(Mul, mul, MulAssign, mul_assign, "multiply");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ops_assign_accumulates() {
+        let mut sum = Grid::new(2, 2, 0.0);
+        sum += &Grid::new(2, 2, 1.5);
+        sum += Grid::with_width(2, vec![1.0, 2.0, 3.0, 4.0]);
+        sum *= 2.0;
+
+        assert_eq!(sum.as_vec(), &vec![5.0, 7.0, 9.0, 11.0]);
+    }
+
+    /// A value that only implements the binary operators.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Meters(u32);
+
+    impl Add for Meters {
+        type Output = Self;
+
+        fn add(self, rhs: Self) -> Self {
+            Self(self.0 + rhs.0)
+        }
+    }
+
+    #[test]
+    fn ops_binary_without_assign() {
+        let a = Grid::new(2, 1, Meters(1));
+        let b = Grid::with_width(2, vec![Meters(2), Meters(3)]);
+
+        assert_eq!((&a + &b).as_vec(), &vec![Meters(3), Meters(4)]);
+        assert_eq!((a + Meters(5)).as_vec(), &vec![Meters(6), Meters(6)]);
+    }
+
+    #[test]
+    fn ops_empty_grids() {
+        let empty = Grid::<i32>::from(Vec::new());
+
+        assert!((&empty + &empty).as_vec().is_empty());
+        assert!((empty * 2).as_vec().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot subtract grids of different sizes")]
+    fn ops_size_mismatch() {
+        let _ = &Grid::new(2, 2, 0) - &Grid::new(4, 1, 0);
+    }
+}
@@ -0,0 +1,335 @@
+//! Traversal orders as types, i.e. so code can require a specific order at compile time.
+//!
+//! A [`Grid`] stores (and [`IntoIterator`] walks) its cells in [`RowMajor`] order. Code that
+//! depends on an order, such as a serializer and a hasher that must agree, can instead take an
+//! [`Ordered`] iterator with the order in its type, so passing cells in a different order does not
+//! compile:
+//!
+//! ```
+//! use grud::{Grid, order::{Ordered, RowMajor}};
+//!
+//! fn checksum(cells: Ordered<'_, u8, RowMajor>) -> u32 {
+//!     cells.fold(0, |sum, (_, cell)| sum.wrapping_mul(31).wrapping_add(u32::from(*cell)))
+//! }
+//!
+//! let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+//! assert_eq!(checksum(grid.iter_in()), 31_810);
+//! ```
+//!
+//! ```compile_fail
+//! # use grud::{Grid, order::{ColMajor, Ordered, RowMajor}};
+//! # fn checksum(cells: Ordered<'_, u8, RowMajor>) -> u32 { 0 }
+//! # let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+//! checksum(grid.iter_in::<ColMajor>());
+//! ```
+
+use core::{iter::FusedIterator, marker::PhantomData};
+
+use crate::grid::Grid;
+
+/// A traversal order over the points of a grid.
+///
+/// An order is a sequence of steps, each of which visits at most one point; steps that visit no
+/// point are skipped, so orders such as [`ZOrder`] can be defined over a larger area than the grid.
+/// Every point of the grid must be visited exactly once.
+pub trait Order {
+    /// Returns the number of steps in a traversal of a `width` by `height` grid.
+    fn steps(width: usize, height: usize) -> usize;
+
+    /// Returns the point visited at `step` of a traversal of a `width` by `height` grid, or `None`
+    /// if the step visits no point.
+    fn point(step: usize, width: usize, height: usize) -> Option<(usize, usize)>;
+
+    /// Returns the first step at or after `step` that may visit a point of a `width` by `height`
+    /// grid, so traversals can jump over steps that visit no point.
+    ///
+    /// The default implementation returns `step`, i.e. tries every step in turn.
+    fn seek(step: usize, _width: usize, _height: usize) -> usize {
+        step
+    }
+}
+
+/// Visits each row from top to bottom, and each row from left to right.
+///
+/// This is the order cells are stored in.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, order::RowMajor};
+///
+/// let grid = Grid::new(2, 2, ());
+/// let points: Vec<_> = grid.points_in::<RowMajor>().collect();
+///
+/// assert_eq!(points, vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RowMajor;
+
+impl Order for RowMajor {
+    fn steps(width: usize, height: usize) -> usize {
+        width * height
+    }
+
+    fn point(step: usize, width: usize, _: usize) -> Option<(usize, usize)> {
+        Some((step % width, step / width))
+    }
+}
+
+/// Visits each column from left to right, and each column from top to bottom.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, order::ColMajor};
+///
+/// let grid = Grid::new(2, 2, ());
+/// let points: Vec<_> = grid.points_in::<ColMajor>().collect();
+///
+/// assert_eq!(points, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ColMajor;
+
+impl Order for ColMajor {
+    fn steps(width: usize, height: usize) -> usize {
+        width * height
+    }
+
+    fn point(step: usize, _: usize, height: usize) -> Option<(usize, usize)> {
+        Some((step / height, step % height))
+    }
+}
+
+/// Visits points in [Z-order] (Morton order), which keeps nearby points close together.
+///
+/// Grids that are not square with a power of two side are traversed as part of the smallest such
+/// square containing them, jumping over the parts of that square outside of the grid.
+///
+/// [Z-order]: https://en.wikipedia.org/wiki/Z-order_curve
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, order::ZOrder};
+///
+/// let grid = Grid::new(4, 2, ());
+/// let points: Vec<_> = grid.points_in::<ZOrder>().collect();
+///
+/// assert_eq!(points, vec![
+///     (0, 0), (1, 0), (0, 1), (1, 1),
+///     (2, 0), (3, 0), (2, 1), (3, 1),
+/// ]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ZOrder;
+
+impl Order for ZOrder {
+    fn steps(width: usize, height: usize) -> usize {
+        if width == 0 || height == 0 {
+            return 0;
+        }
+        let side = width.max(height).next_power_of_two();
+        side.saturating_mul(side)
+    }
+
+    fn point(step: usize, width: usize, height: usize) -> Option<(usize, usize)> {
+        let (x, y) = (compact_bits(step), compact_bits(step >> 1));
+        (x < width && y < height).then_some((x, y))
+    }
+
+    fn seek(mut step: usize, width: usize, height: usize) -> usize {
+        let steps = Self::steps(width, height);
+        while step < steps {
+            let (x, y) = (compact_bits(step), compact_bits(step >> 1));
+            if x < width && y < height {
+                return step;
+            }
+            // The largest aligned square starting at `step` has `(x, y)` as its top-left corner, so
+            // it lies entirely outside of the grid as well.
+            let square = 1 << (step.trailing_zeros() & !1);
+            step = step.saturating_add(square);
+        }
+        steps
+    }
+}
+
+/// Returns the even bits of `bits`, packed together.
+fn compact_bits(bits: usize) -> usize {
+    let mut bits = bits as u64 & 0x5555_5555_5555_5555;
+    bits = (bits | (bits >> 1)) & 0x3333_3333_3333_3333;
+    bits = (bits | (bits >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+    bits = (bits | (bits >> 4)) & 0x00ff_00ff_00ff_00ff;
+    bits = (bits | (bits >> 8)) & 0x0000_ffff_0000_ffff;
+    bits = (bits | (bits >> 16)) & 0x0000_0000_ffff_ffff;
+    bits as usize
+}
+
+/// An iterator over the points of a grid in order `O`, created by [`Grid::points_in`].
+#[derive(Clone, Debug)]
+pub struct Points<O> {
+    step: usize,
+    steps: usize,
+    width: usize,
+    height: usize,
+    order: PhantomData<O>,
+}
+
+impl<O> Iterator for Points<O>
+where
+    O: Order,
+{
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.step < self.steps {
+            let step = O::seek(self.step, self.width, self.height);
+            if step >= self.steps {
+                break;
+            }
+            self.step = step + 1;
+            if let Some(point) = O::point(step, self.width, self.height) {
+                return Some(point);
+            }
+        }
+        self.step = self.steps;
+        None
+    }
+}
+
+impl<O> FusedIterator for Points<O> where O: Order {}
+
+/// An iterator over the points and cells of a grid in order `O`, created by [`Grid::iter_in`].
+#[derive(Clone, Debug)]
+pub struct Ordered<'a, T, O>
+where
+    T: Clone,
+{
+    grid: &'a Grid<T>,
+    points: Points<O>,
+}
+
+impl<'a, T, O> Iterator for Ordered<'a, T, O>
+where
+    T: Clone,
+    O: Order,
+{
+    type Item = ((usize, usize), &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.points.next()?;
+        Some((point, &self.grid[point]))
+    }
+}
+
+impl<T, O> FusedIterator for Ordered<'_, T, O>
+where
+    T: Clone,
+    O: Order,
+{
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns an iterator over every point of the grid, in order `O`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, order::ColMajor};
+    ///
+    /// let grid = Grid::new(3, 1, ());
+    /// assert_eq!(grid.points_in::<ColMajor>().collect::<Vec<_>>(), vec![(0, 0), (1, 0), (2, 0)]);
+    /// ```
+    pub fn points_in<O>(&self) -> Points<O>
+    where
+        O: Order,
+    {
+        let (width, height) = self.size();
+        Points {
+            step: 0,
+            steps: O::steps(width, height),
+            width,
+            height,
+            order: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over every point and cell of the grid, in order `O`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, order::ColMajor};
+    ///
+    /// let grid = Grid::with_width(2, vec!['a', 'b', 'c', 'd']);
+    /// let cells: String = grid.iter_in::<ColMajor>().map(|(_, cell)| cell).collect();
+    ///
+    /// assert_eq!(cells, "acbd");
+    /// ```
+    pub fn iter_in<O>(&self) -> Ordered<'_, T, O>
+    where
+        O: Order,
+    {
+        Ordered {
+            grid: self,
+            points: self.points_in(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    fn visits_each_point_once<O: Order>() {
+        for (width, height) in [(0, 0), (1, 1), (3, 5), (8, 8), (7, 2), (1, 9)] {
+            let grid = if width == 0 {
+                Grid::from(Vec::new())
+            } else {
+                Grid::new(width, height, ())
+            };
+            let mut points: Vec<_> = grid.points_in::<O>().collect();
+            points.sort_by_key(|(x, y)| (*y, *x));
+
+            assert_eq!(points, grid.points_in::<RowMajor>().collect::<Vec<_>>());
+            assert_eq!(points.len(), width * height);
+        }
+    }
+
+    #[test]
+    fn orders_visit_each_point_once() {
+        visits_each_point_once::<RowMajor>();
+        visits_each_point_once::<ColMajor>();
+        visits_each_point_once::<ZOrder>();
+    }
+
+    #[test]
+    fn z_order_skips_steps_outside_long_grids() {
+        let grid = Grid::new(1, 100_000, ());
+        let points: Vec<_> = grid.points_in::<ZOrder>().collect();
+
+        assert_eq!(points, grid.points_in::<RowMajor>().collect::<Vec<_>>());
+
+        let grid = Grid::new(100_000, 1, ());
+        assert_eq!(grid.points_in::<ZOrder>().count(), 100_000);
+    }
+
+    #[test]
+    fn z_order_compacts_bits() {
+        assert_eq!(compact_bits(0b0100_0111), 0b1011);
+        assert_eq!(compact_bits(usize::MAX), usize::MAX >> (usize::BITS / 2));
+    }
+
+    #[test]
+    fn row_major_matches_storage() {
+        let grid = Grid::with_width(3, (0..12).collect::<Vec<_>>());
+        let cells: Vec<_> = grid.iter_in::<RowMajor>().map(|(_, cell)| *cell).collect();
+
+        assert_eq!(&cells, grid.as_vec());
+    }
+}
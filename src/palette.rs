@@ -0,0 +1,549 @@
+//! Compact storage for grids with few distinct values, i.e. large tile maps.
+//!
+//! See [`PaletteGrid`] for details.
+
+use std::{collections::HashMap, hash::Hash, ops::Index};
+
+use crate::{grid::Grid, point::Point};
+
+/// A grid that stores each distinct value once, with each cell as an index into the palette.
+///
+/// Indices are stored in the smallest integer type that fits the palette: one byte per cell for
+/// up to 256 distinct values, two bytes for up to 65,536, and four bytes otherwise. The palette is
+/// managed automatically; new values are added as they are set, and [`PaletteGrid::compact`]
+/// removes values no longer in use.
+///
+/// Cells are read with [`Index`], and written with [`PaletteGrid::set`].
+///
+/// # Examples
+///
+/// ```
+/// use grud::palette::PaletteGrid;
+///
+/// let mut tiles = PaletteGrid::new(4096, 4096, "grass");
+/// tiles.set((10, 20), "water");
+///
+/// assert_eq!(tiles[(10, 20)], "water");
+/// assert_eq!(tiles.palette(), &["grass", "water"]);
+/// assert_eq!(tiles.bytes_per_cell(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PaletteGrid<T>
+where
+    T: Clone + Eq + Hash,
+{
+    width: usize,
+    indices: Indices,
+    palette: Vec<T>,
+    lookup: HashMap<T, usize>,
+}
+
+impl<T> PaletteGrid<T>
+where
+    T: Clone + Eq + Hash,
+{
+    /// Creates a `width` by `height` grid where every cell is `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::palette::PaletteGrid;
+    ///
+    /// let grid = PaletteGrid::new(3, 2, 'x');
+    ///
+    /// assert_eq!((grid.width(), grid.height()), (3, 2));
+    /// assert_eq!(grid[(2, 1)], 'x');
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width * height` overflows `usize`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        let area = width
+            .checked_mul(height)
+            .unwrap_or_else(|| panic!("Area of {width}x{height} grid overflows usize"));
+        Self {
+            width,
+            indices: Indices::U8(vec![0; area]),
+            palette: vec![value.clone()],
+            lookup: HashMap::from([(value, 0)]),
+        }
+    }
+
+    /// Creates a palette-compressed copy of `grid`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, palette::PaletteGrid};
+    ///
+    /// let grid = Grid::with_width(2, vec![7, 7, 9, 7]);
+    /// let compressed = PaletteGrid::from_grid(&grid);
+    ///
+    /// assert_eq!(compressed.palette(), &[7, 9]);
+    /// assert_eq!(compressed.to_grid().as_vec(), grid.as_vec());
+    /// ```
+    pub fn from_grid(grid: &Grid<T>) -> Self {
+        let (width, _) = grid.size();
+        let mut palette = Self {
+            width,
+            indices: Indices::U8(Vec::with_capacity(grid.as_vec().len())),
+            palette: Vec::new(),
+            lookup: HashMap::new(),
+        };
+        for cell in grid.as_vec() {
+            let index = palette.index_of(cell);
+            palette.indices.push(index);
+        }
+        palette
+    }
+
+    /// Returns an uncompressed copy of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::palette::PaletteGrid;
+    ///
+    /// let mut grid = PaletteGrid::new(2, 1, 0);
+    /// grid.set((1, 0), 5);
+    ///
+    /// assert_eq!(grid.to_grid().as_vec(), &vec![0, 5]);
+    /// ```
+    pub fn to_grid(&self) -> Grid<T> {
+        let data: Vec<T> = (0..self.indices.len())
+            .map(|i| self.palette[self.indices.get(i)].clone())
+            .collect();
+        if data.is_empty() {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(self.width, data)
+        }
+    }
+
+    /// Returns the width of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::palette::PaletteGrid;
+    ///
+    /// assert_eq!(PaletteGrid::new(3, 2, ()).width(), 3);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::palette::PaletteGrid;
+    ///
+    /// assert_eq!(PaletteGrid::new(3, 2, ()).height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.indices.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    /// Returns a reference to the cell at `point`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::palette::PaletteGrid;
+    ///
+    /// let grid = PaletteGrid::new(2, 2, 'a');
+    ///
+    /// assert_eq!(grid.get((1, 1)), Some(&'a'));
+    /// assert_eq!(grid.get((2, 0)), None);
+    /// ```
+    pub fn get(&self, point: impl Point) -> Option<&T> {
+        let in_bounds = point.x() < self.width() && point.y() < self.height();
+        in_bounds.then(|| &self.palette[self.indices.get(point.to_index(self.width))])
+    }
+
+    /// Sets the cell at `point` to `value`, adding `value` to the palette if it is new.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::palette::PaletteGrid;
+    ///
+    /// let mut grid = PaletteGrid::new(2, 1, '.');
+    /// grid.set((0, 0), '#');
+    ///
+    /// assert_eq!(grid.to_grid().as_vec(), &vec!['#', '.']);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn set(&mut self, point: impl Point, value: T) {
+        let index = self.checked_index(point);
+        let value = self.index_of(&value);
+        self.indices.set(index, value);
+    }
+
+    /// Returns the distinct values, in the order they were added.
+    ///
+    /// The palette may include values that are no longer used by any cell; see
+    /// [`PaletteGrid::compact`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::palette::PaletteGrid;
+    ///
+    /// let mut grid = PaletteGrid::new(1, 1, 'a');
+    /// grid.set((0, 0), 'b');
+    ///
+    /// assert_eq!(grid.palette(), &['a', 'b']);
+    /// ```
+    pub fn palette(&self) -> &[T] {
+        &self.palette
+    }
+
+    /// Returns the number of bytes used to store each cell's index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, palette::PaletteGrid};
+    ///
+    /// let grid = Grid::with_width(300, (0..300).collect());
+    ///
+    /// assert_eq!(PaletteGrid::from_grid(&grid).bytes_per_cell(), 2);
+    /// ```
+    pub fn bytes_per_cell(&self) -> usize {
+        match self.indices {
+            Indices::U8(_) => 1,
+            Indices::U16(_) => 2,
+            Indices::U32(_) => 4,
+        }
+    }
+
+    /// Removes values that are no longer used by any cell from the palette, shrinking indices to
+    /// the smallest integer type that fits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::palette::PaletteGrid;
+    ///
+    /// let mut grid = PaletteGrid::new(2, 1, 'a');
+    /// grid.set((0, 0), 'b');
+    /// grid.set((1, 0), 'c');
+    /// grid.compact();
+    ///
+    /// assert_eq!(grid.palette(), &['b', 'c']);
+    /// assert_eq!(grid[(1, 0)], 'c');
+    /// ```
+    pub fn compact(&mut self) {
+        let mut compacted = Self {
+            width: self.width,
+            indices: Indices::U8(Vec::with_capacity(self.indices.len())),
+            palette: Vec::new(),
+            lookup: HashMap::new(),
+        };
+        for i in 0..self.indices.len() {
+            let index = compacted.index_of(&self.palette[self.indices.get(i)]);
+            compacted.indices.push(index);
+        }
+        *self = compacted;
+    }
+
+    /// Returns the palette index of `value`, adding it to the palette if it is new.
+    fn index_of(&mut self, value: &T) -> usize {
+        if let Some(index) = self.lookup.get(value) {
+            return *index;
+        }
+        let index = self.palette.len();
+        self.palette.push(value.clone());
+        self.lookup.insert(value.clone(), index);
+        index
+    }
+
+    /// Returns the index of `point` into the indices, panicking if it is out of bounds.
+    fn checked_index(&self, point: impl Point) -> usize {
+        assert!(
+            point.x() < self.width() && point.y() < self.height(),
+            "Point ({}, {}) out of bounds of {}x{} grid",
+            point.x(),
+            point.y(),
+            self.width(),
+            self.height()
+        );
+        point.to_index(self.width)
+    }
+}
+
+impl PaletteGrid<[u8; 3]> {
+    /// Creates a palette-compressed copy of an RGB `grid`, reducing it to at most `colors`
+    /// distinct colors using [median cut].
+    ///
+    /// The colors are repeatedly split into two boxes at the median of the channel with the widest
+    /// range, until there are `colors` boxes or every box has a single color. Each cell is then
+    /// replaced by the average color of its box, weighted by how often each color occurs.
+    ///
+    /// [median cut]: https://en.wikipedia.org/wiki/Median_cut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, palette::PaletteGrid};
+    ///
+    /// let grid = Grid::with_width(2, vec![[0, 0, 0], [10, 0, 0], [250, 250, 250], [255, 255, 255]]);
+    /// let quantized = PaletteGrid::quantize(&grid, 2);
+    ///
+    /// assert_eq!(quantized.palette(), &[[5, 0, 0], [253, 253, 253]]);
+    /// assert_eq!(quantized[(1, 0)], [5, 0, 0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `colors` is `0`.
+    pub fn quantize(grid: &Grid<[u8; 3]>, colors: usize) -> Self {
+        assert!(colors > 0, "Cannot quantize to a palette of 0 colors");
+        let mut counts: HashMap<[u8; 3], usize> = HashMap::new();
+        for cell in grid.as_vec() {
+            *counts.entry(*cell).or_default() += 1;
+        }
+        let mut histogram: Vec<([u8; 3], usize)> = counts.into_iter().collect();
+        histogram.sort_unstable();
+
+        let mut boxes = vec![histogram];
+        while boxes.len() < colors {
+            let widest = boxes
+                .iter()
+                .enumerate()
+                .filter(|(_, colors)| colors.len() > 1)
+                .max_by_key(|(_, colors)| widest_channel(colors).1)
+                .map(|(i, _)| i);
+            let Some(widest) = widest else {
+                break;
+            };
+            let mut colors = boxes.swap_remove(widest);
+            let (channel, _) = widest_channel(&colors);
+            colors.sort_unstable_by_key(|(color, _)| color[channel]);
+            let upper = colors.split_off(median(&colors));
+            boxes.push(colors);
+            boxes.push(upper);
+        }
+
+        let mut replacements = HashMap::new();
+        for colors in boxes.iter().filter(|colors| !colors.is_empty()) {
+            let average = average(colors);
+            for (color, _) in colors {
+                replacements.insert(*color, average);
+            }
+        }
+        let mut palette = Self::from_grid(&Grid::from(Vec::new()));
+        palette.width = grid.size().0;
+        for cell in grid.as_vec() {
+            let index = palette.index_of(&replacements[cell]);
+            palette.indices.push(index);
+        }
+        palette
+    }
+}
+
+/// Returns the channel with the widest range of values in `colors`, and that range.
+fn widest_channel(colors: &[([u8; 3], usize)]) -> (usize, u8) {
+    (0..3)
+        .map(|channel| {
+            let values = colors.iter().map(|(color, _)| color[channel]);
+            let range = values.clone().max().unwrap_or(0) - values.min().unwrap_or(0);
+            (channel, range)
+        })
+        .max_by_key(|(channel, range)| (*range, core::cmp::Reverse(*channel)))
+        .expect("There are 3 channels")
+}
+
+/// Returns the index that splits `colors` (sorted, with at least 2 colors) into two halves of
+/// roughly equal weight, leaving at least one color in each half.
+fn median(colors: &[([u8; 3], usize)]) -> usize {
+    let total: usize = colors.iter().map(|(_, count)| count).sum();
+    let mut seen = 0;
+    for (i, (_, count)) in colors.iter().enumerate() {
+        seen += count;
+        if seen * 2 >= total {
+            return (i + 1).min(colors.len() - 1);
+        }
+    }
+    colors.len() - 1
+}
+
+/// Returns the average of `colors`, weighted by count and rounded to the nearest value.
+fn average(colors: &[([u8; 3], usize)]) -> [u8; 3] {
+    let total: usize = colors.iter().map(|(_, count)| count).sum();
+    let mut sums = [0; 3];
+    for (color, count) in colors {
+        for (sum, value) in sums.iter_mut().zip(color) {
+            *sum += usize::from(*value) * count;
+        }
+    }
+    sums.map(|sum| ((sum + total / 2) / total) as u8)
+}
+
+impl<T, I> Index<I> for PaletteGrid<T>
+where
+    T: Clone + Eq + Hash,
+    I: Point,
+{
+    type Output = T;
+
+    /// Given a two-dimensional coordinate [`Point`], returns the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::palette::PaletteGrid;
+    ///
+    /// let grid = PaletteGrid::new(2, 2, 1);
+    /// assert_eq!(grid[(1, 1)], 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index(&self, index: I) -> &Self::Output {
+        &self.palette[self.indices.get(self.checked_index(index))]
+    }
+}
+
+/// Palette indices, stored in the smallest integer type that fits every index.
+#[derive(Clone, Debug)]
+enum Indices {
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl Indices {
+    /// Returns the number of indices.
+    fn len(&self) -> usize {
+        match self {
+            Self::U8(indices) => indices.len(),
+            Self::U16(indices) => indices.len(),
+            Self::U32(indices) => indices.len(),
+        }
+    }
+
+    /// Returns the index at `i`.
+    fn get(&self, i: usize) -> usize {
+        match self {
+            Self::U8(indices) => usize::from(indices[i]),
+            Self::U16(indices) => usize::from(indices[i]),
+            Self::U32(indices) => indices[i] as usize,
+        }
+    }
+
+    /// Sets the index at `i` to `value`, widening the integer type if needed.
+    fn set(&mut self, i: usize, value: usize) {
+        self.widen(value);
+        match self {
+            Self::U8(indices) => indices[i] = value as u8,
+            Self::U16(indices) => indices[i] = value as u16,
+            Self::U32(indices) => indices[i] = value as u32,
+        }
+    }
+
+    /// Appends `value`, widening the integer type if needed.
+    fn push(&mut self, value: usize) {
+        self.widen(value);
+        match self {
+            Self::U8(indices) => indices.push(value as u8),
+            Self::U16(indices) => indices.push(value as u16),
+            Self::U32(indices) => indices.push(value as u32),
+        }
+    }
+
+    /// Converts to a wider integer type, if needed to store `value`.
+    fn widen(&mut self, value: usize) {
+        match self {
+            Self::U8(indices) if value > usize::from(u8::MAX) => {
+                *self = Self::U16(indices.iter().map(|i| u16::from(*i)).collect());
+                self.widen(value);
+            }
+            Self::U16(indices) if value > usize::from(u16::MAX) => {
+                *self = Self::U32(indices.iter().map(|i| u32::from(*i)).collect());
+            }
+            Self::U32(_) => {
+                assert!(
+                    u32::try_from(value).is_ok(),
+                    "Palette has more than {} values",
+                    u32::MAX
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_widens_indices() {
+        let mut grid = PaletteGrid::new(300, 1, 0);
+        for x in 0..300 {
+            grid.set((x, 0), x);
+        }
+
+        assert_eq!(grid.bytes_per_cell(), 2);
+        assert_eq!(grid[(299, 0)], 299);
+        assert_eq!(grid.palette().len(), 300);
+    }
+
+    #[test]
+    fn palette_compact_narrows_indices() {
+        let mut grid = PaletteGrid::from_grid(&Grid::with_width(300, (0..300).collect()));
+        for x in 0..300 {
+            grid.set((x, 0), 1);
+        }
+        grid.compact();
+
+        assert_eq!(grid.bytes_per_cell(), 1);
+        assert_eq!(grid.palette(), &[1]);
+    }
+
+    #[test]
+    fn palette_empty_grid() {
+        let grid = PaletteGrid::from_grid(&Grid::<u8>::from(Vec::new()));
+
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert!(grid.to_grid().as_vec().is_empty());
+    }
+
+    #[test]
+    fn quantize_limits_palette() {
+        let grid = Grid::with_width(
+            16,
+            (0..256)
+                .map(|i| [i as u8, (i * 7) as u8, 255 - i as u8])
+                .collect(),
+        );
+        let quantized = PaletteGrid::quantize(&grid, 8);
+
+        assert_eq!(quantized.palette().len(), 8);
+        assert_eq!((quantized.width(), quantized.height()), (16, 16));
+    }
+
+    #[test]
+    fn quantize_fewer_colors_than_palette() {
+        let grid = Grid::with_width(2, vec![[1, 2, 3], [4, 5, 6], [1, 2, 3], [1, 2, 3]]);
+        let quantized = PaletteGrid::quantize(&grid, 16);
+
+        assert_eq!(quantized.to_grid().as_vec(), grid.as_vec());
+    }
+
+    #[test]
+    fn quantize_empty_grid() {
+        let quantized = PaletteGrid::quantize(&Grid::from(Vec::new()), 4);
+
+        assert!(quantized.palette().is_empty());
+    }
+}
@@ -0,0 +1,252 @@
+//! Differences between two grids, i.e. for synchronizing state over a network.
+//!
+//! See [`Grid::diff`] and [`Grid::apply_patch`] for details.
+
+use crate::{error::SizeMismatch, grid::Grid, point::Rect};
+
+/// The cells that differ between two grids, and their sizes, created by [`Grid::diff`].
+///
+/// Applying the patch to the original grid with [`Grid::apply_patch`] produces the other grid. If
+/// the sizes differ, every cell outside of the original grid is included as a change.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GridPatch<T> {
+    from: (usize, usize),
+    to: (usize, usize),
+    changes: Vec<((usize, usize), T)>,
+}
+
+impl<T> GridPatch<T> {
+    /// Returns the width and height of the grid the patch applies to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let patch = Grid::new(2, 1, 0).diff(&Grid::new(3, 1, 0));
+    /// assert_eq!(patch.from_size(), (2, 1));
+    /// ```
+    pub fn from_size(&self) -> (usize, usize) {
+        self.from
+    }
+
+    /// Returns the width and height of the grid after the patch is applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let patch = Grid::new(2, 1, 0).diff(&Grid::new(3, 1, 0));
+    /// assert_eq!(patch.to_size(), (3, 1));
+    /// ```
+    pub fn to_size(&self) -> (usize, usize) {
+        self.to
+    }
+
+    /// Returns each changed point and its new value, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let before = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// let after = Grid::with_width(2, vec![1, 9, 3, 8]);
+    ///
+    /// assert_eq!(before.diff(&after).changes(), &[((1, 0), 9), ((1, 1), 8)]);
+    /// ```
+    pub fn changes(&self) -> &[((usize, usize), T)] {
+        &self.changes
+    }
+
+    /// Returns whether applying the patch would leave a grid unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::new(2, 2, 'a');
+    /// assert!(grid.diff(&grid).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.from == self.to
+    }
+
+    /// Panics if the changes are not in row-major order or within the new size, or do not include
+    /// every cell outside of the original grid, but only if the `strict` feature is enabled.
+    fn check(&self) {
+        if !cfg!(feature = "strict") {
+            return;
+        }
+        let (width, height) = self.to;
+        let (from_width, from_height) = self.from;
+        assert!(
+            self.changes
+                .windows(2)
+                .all(|pair| (pair[0].0 .1, pair[0].0 .0) < (pair[1].0 .1, pair[1].0 .0)),
+            "Patch changes are not in row-major order"
+        );
+        for ((x, y), _) in &self.changes {
+            assert!(
+                *x < width && *y < height,
+                "Patch change at ({x}, {y}) out of bounds of {width}x{height} grid"
+            );
+        }
+        let is_new = |(x, y): &(usize, usize)| *x >= from_width || *y >= from_height;
+        let added = Rect::new(0, 0, width, height)
+            .iter_points()
+            .filter(is_new)
+            .count();
+        let changed = self
+            .changes
+            .iter()
+            .filter(|(point, _)| is_new(point))
+            .count();
+        assert!(
+            added == changed,
+            "Patch from {from_width}x{from_height} to {width}x{height} sets {changed} of {added} new cells"
+        );
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone + PartialEq,
+{
+    /// Returns the cells that differ between this grid and `other`, as a patch that turns this grid
+    /// into `other`.
+    ///
+    /// Cells are compared by position; if the grids are different sizes, cells outside of this grid
+    /// are always included. See [`Grid::align_rows`] to compare grids where rows were inserted or
+    /// removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let before = Grid::with_width(2, vec!['.', '.', '.', '.']);
+    /// let after = Grid::with_width(3, vec!['.', '#', '.', '.', '.', '.']);
+    /// let patch = before.diff(&after);
+    ///
+    /// assert_eq!(patch.changes(), &[((1, 0), '#'), ((2, 0), '.'), ((2, 1), '.')]);
+    /// ```
+    pub fn diff(&self, other: &Grid<T>) -> GridPatch<T> {
+        let from = self.size();
+        let to = other.size();
+        let changes = Rect::new(0, 0, to.0, to.1)
+            .iter_points()
+            .filter(|point| self.get(*point) != Some(&other[*point]))
+            .map(|point| (point, other[point].clone()))
+            .collect();
+        GridPatch { from, to, changes }
+    }
+
+    /// Applies a patch created by [`Grid::diff`], resizing the grid if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let before = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// let after = Grid::with_width(1, vec![1, 3, 5]);
+    /// let patch = before.diff(&after);
+    ///
+    /// let mut grid = before.clone();
+    /// grid.apply_patch(&patch).unwrap();
+    ///
+    /// assert_eq!(grid.as_vec(), after.as_vec());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the grid is not the size the patch was created from; the grid is unchanged.
+    pub fn apply_patch(&mut self, patch: &GridPatch<T>) -> Result<(), SizeMismatch> {
+        if self.size() != patch.from {
+            return Err(SizeMismatch {
+                expected: patch.from,
+                found: self.size(),
+            });
+        }
+        patch.check();
+        if patch.from == patch.to {
+            for (point, value) in &patch.changes {
+                self[*point] = value.clone();
+            }
+            return Ok(());
+        }
+
+        let (width, height) = patch.to;
+        let mut changes = patch.changes.iter().peekable();
+        let data: Vec<T> = Rect::new(0, 0, width, height)
+            .iter_points()
+            .map(|point| match changes.next_if(|(at, _)| *at == point) {
+                Some((_, value)) => value.clone(),
+                None => self[point].clone(),
+            })
+            .collect();
+        *self = if data.is_empty() {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(width, data)
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patch_round_trip_resizes() {
+        let sizes = [(0, 0), (1, 3), (3, 1), (4, 4), (2, 5)];
+        for (w1, h1) in sizes {
+            for (w2, h2) in sizes {
+                let make = |w: usize, h: usize, seed: usize| {
+                    if w * h == 0 {
+                        return Grid::from(Vec::new());
+                    }
+                    Grid::with_width(w, (0..w * h).map(|i| (i * seed) % 3).collect())
+                };
+                let before = make(w1, h1, 5);
+                let after = make(w2, h2, 7);
+
+                let mut grid = before.clone();
+                grid.apply_patch(&before.diff(&after)).unwrap();
+                assert_eq!(grid.as_vec(), after.as_vec());
+                assert_eq!(grid.size(), after.size());
+            }
+        }
+    }
+
+    #[test]
+    fn patch_wrong_size() {
+        let patch = Grid::new(2, 2, 0).diff(&Grid::new(2, 2, 1));
+        let mut grid = Grid::new(3, 2, 0);
+
+        assert_eq!(
+            grid.apply_patch(&patch),
+            Err(SizeMismatch {
+                expected: (2, 2),
+                found: (3, 2),
+            })
+        );
+        assert_eq!(grid.as_vec(), &vec![0; 6]);
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    #[should_panic(expected = "Patch from 1x1 to 2x1 sets 0 of 1 new cells")]
+    fn patch_strict_missing_new_cell() {
+        let patch = GridPatch {
+            from: (1, 1),
+            to: (2, 1),
+            changes: Vec::new(),
+        };
+        Grid::new(1, 1, 0).apply_patch(&patch).unwrap();
+    }
+}
@@ -0,0 +1,1622 @@
+//! Pathfinding algorithms that operate on a [`Grid`].
+//!
+//! Movement is 4-connected (i.e. up, down, left, and right), except for [`theta_star`], which
+//! also moves diagonally and in straight lines at any angle. For weighted searches, the cost of a
+//! step is the cost of _entering_ a cell, as reported by a user-supplied closure that returns `None`
+//! for impassable cells; unweighted searches use a predicate that reports whether a cell is
+//! passable.
+//!
+//! ```
+//! use grud::{Grid, path};
+//!
+//! let grid = Grid::from(vec![
+//!     vec!['.', '#', '.'],
+//!     vec!['.', '#', '.'],
+//!     vec!['.', '.', '.'],
+//! ]);
+//!
+//! let path = path::astar(&grid, (0, 0), (2, 0), |c| (*c == '.').then_some(1)).unwrap();
+//! assert_eq!(path.len(), 7);
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+use crate::{
+    grid::Grid,
+    point::Point,
+    progress::{Cancelled, Monitor},
+};
+
+/// Returns the [Manhattan distance] between two points.
+///
+/// This is the default heuristic used by [`astar`].
+///
+/// [Manhattan distance]: https://en.wikipedia.org/wiki/Taxicab_geometry
+///
+/// # Examples
+///
+/// ```
+/// use grud::path::manhattan;
+///
+/// assert_eq!(manhattan((1, 1), (4, 3)), 5);
+/// ```
+pub fn manhattan<A, B>(a: A, b: B) -> usize
+where
+    A: Point,
+    B: Point,
+{
+    a.x().abs_diff(b.x()) + a.y().abs_diff(b.y())
+}
+
+/// Finds the cheapest path from `start` to `goal` using [A*] and the [`manhattan`] heuristic.
+///
+/// Returns every point along the path, including both `start` and `goal`, or `None` if `goal` is
+/// not reachable.
+///
+/// [A*]: https://en.wikipedia.org/wiki/A*_search_algorithm
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, path};
+///
+/// let grid = Grid::with_width(3, vec![
+///     1, 9, 1,
+///     1, 9, 1,
+///     1, 1, 1,
+/// ]);
+///
+/// // Walking through the expensive cells is shorter, but walking around them is cheaper.
+/// let path = path::astar(&grid, (0, 0), (2, 0), |c| Some(*c)).unwrap();
+/// assert_eq!(path, vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (2, 1), (2, 0)]);
+/// ```
+///
+/// # Panics
+///
+/// If `start` or `goal` is out of bounds.
+pub fn astar<T, C>(
+    grid: &Grid<T>,
+    start: impl Point,
+    goal: impl Point,
+    cost: C,
+) -> Option<Vec<(usize, usize)>>
+where
+    T: Clone,
+    C: FnMut(&T) -> Option<usize>,
+{
+    astar_with(grid, start, goal, cost, manhattan)
+}
+
+/// Finds the cheapest path from `start` to `goal` using [A*] and a custom `heuristic`.
+///
+/// The `heuristic` estimates the remaining cost from a point to the goal; for the path to be
+/// optimal, it must never overestimate that cost.
+///
+/// [A*]: https://en.wikipedia.org/wiki/A*_search_algorithm
+///
+/// # Examples
+///
+/// Using no heuristic at all, which is equivalent to [Dijkstra's algorithm]:
+///
+/// [Dijkstra's algorithm]: https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm
+///
+/// ```
+/// use grud::{Grid, path};
+///
+/// let grid = Grid::new(3, 3, ());
+///
+/// let path = path::astar_with(&grid, (0, 0), (2, 2), |_| Some(1), |_, _| 0).unwrap();
+/// assert_eq!(path.len(), 5);
+/// ```
+///
+/// # Panics
+///
+/// If `start` or `goal` is out of bounds.
+pub fn astar_with<T, C, H>(
+    grid: &Grid<T>,
+    start: impl Point,
+    goal: impl Point,
+    cost: C,
+    heuristic: H,
+) -> Option<Vec<(usize, usize)>>
+where
+    T: Clone,
+    C: FnMut(&T) -> Option<usize>,
+    H: FnMut((usize, usize), (usize, usize)) -> usize,
+{
+    match astar_monitored(grid, start, goal, cost, heuristic, &mut Monitor::new()) {
+        Ok(path) => path,
+        Err(Cancelled) => unreachable!("Monitor without a token is never cancelled"),
+    }
+}
+
+/// Finds the cheapest path from `start` to `goal` using [A*], observed by a [`Monitor`].
+///
+/// Progress is reported as the number of cells expanded, out of the total number of cells.
+///
+/// [A*]: https://en.wikipedia.org/wiki/A*_search_algorithm
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, path::{self, manhattan}, progress::{CancelToken, Cancelled, Monitor}};
+///
+/// let grid = Grid::new(3, 3, ());
+/// let token = CancelToken::new();
+/// token.cancel();
+///
+/// let mut monitor = Monitor::new().with_cancel(&token);
+/// let result = path::astar_monitored(&grid, (0, 0), (2, 2), |_| Some(1), manhattan, &mut monitor);
+/// assert_eq!(result, Err(Cancelled));
+/// ```
+///
+/// # Errors
+///
+/// If the monitor's [`CancelToken`](crate::progress::CancelToken) is cancelled.
+///
+/// # Panics
+///
+/// If `start` or `goal` is out of bounds.
+pub fn astar_monitored<T, C, H>(
+    grid: &Grid<T>,
+    start: impl Point,
+    goal: impl Point,
+    cost: C,
+    heuristic: H,
+    monitor: &mut Monitor<'_>,
+) -> Result<Option<Vec<(usize, usize)>>, Cancelled>
+where
+    T: Clone,
+    C: FnMut(&T) -> Option<usize>,
+    H: FnMut((usize, usize), (usize, usize)) -> usize,
+{
+    let mut search = AstarSearch::new(grid, start, goal, cost, heuristic);
+    loop {
+        let status = search.step(1);
+        monitor.update(search.expanded(), grid.area())?;
+        match status {
+            SearchStatus::Pending => continue,
+            SearchStatus::Found(path) => return Ok(Some(path)),
+            SearchStatus::Unreachable => return Ok(None),
+        }
+    }
+}
+
+/// Finds the cheapest path from `start` to the nearest of several `goals` using [Dijkstra's
+/// algorithm].
+///
+/// Returns the total cost of the path (the sum of the costs of entering each cell after `start`)
+/// and every point along it, including `start` and the goal that was reached, or `None` if no goal
+/// is reachable.
+///
+/// [Dijkstra's algorithm]: https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, path};
+///
+/// // Roads (1) are cheap, swamps (5) are expensive.
+/// let grid = Grid::with_width(4, vec![
+///     1, 5, 5, 1,
+///     1, 1, 1, 1,
+/// ]);
+///
+/// let (cost, path) = path::dijkstra(&grid, (0, 0), [(2, 0), (3, 0)], |c| Some(*c)).unwrap();
+/// assert_eq!(cost, 5);
+/// assert_eq!(path, vec![(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (3, 0)]);
+/// ```
+///
+/// # Panics
+///
+/// If `start` or any of the `goals` is out of bounds.
+pub fn dijkstra<T, C, P>(
+    grid: &Grid<T>,
+    start: impl Point,
+    goals: impl IntoIterator<Item = P>,
+    cost: C,
+) -> Option<(usize, Vec<(usize, usize)>)>
+where
+    T: Clone,
+    C: FnMut(&T) -> Option<usize>,
+    P: Point,
+{
+    match dijkstra_monitored(grid, start, goals, cost, &mut Monitor::new()) {
+        Ok(result) => result,
+        Err(Cancelled) => unreachable!("Monitor without a token is never cancelled"),
+    }
+}
+
+/// Finds the cheapest path from `start` to the nearest of several `goals`, like [`dijkstra`], only
+/// taking steps for which `can_move(from, to)` returns `true`.
+///
+/// This allows movement to be blocked by something other than the contents of the cell being
+/// entered, i.e. thin walls stored in an [`EdgeGrid`](crate::edge::EdgeGrid).
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, edge::EdgeGrid, path};
+///
+/// let grid = Grid::new(2, 2, 1);
+/// let mut walls = EdgeGrid::new(2, 2, false);
+/// *walls.between_mut((0, 0), (1, 0)).unwrap() = true;
+///
+/// let (cost, _) = path::dijkstra_with_moves(&grid, (0, 0), [(1, 0)], |c| Some(*c), |a, b| {
+///     !walls.wall_between(a, b)
+/// })
+/// .unwrap();
+/// assert_eq!(cost, 3);
+/// ```
+///
+/// # Panics
+///
+/// If `start` or any of the `goals` is out of bounds.
+pub fn dijkstra_with_moves<T, C, P, M>(
+    grid: &Grid<T>,
+    start: impl Point,
+    goals: impl IntoIterator<Item = P>,
+    cost: C,
+    can_move: M,
+) -> Option<(usize, Vec<(usize, usize)>)>
+where
+    T: Clone,
+    C: FnMut(&T) -> Option<usize>,
+    P: Point,
+    M: FnMut((usize, usize), (usize, usize)) -> bool,
+{
+    match dijkstra_with(grid, start, goals, cost, can_move, &mut Monitor::new()) {
+        Ok(result) => result,
+        Err(Cancelled) => unreachable!("Monitor without a token is never cancelled"),
+    }
+}
+
+/// Finds the cheapest path from `start` to the nearest of several `goals`, observed by a
+/// [`Monitor`].
+///
+/// Progress is reported as the number of cells expanded, out of the total number of cells.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, path, progress::Monitor};
+///
+/// let grid = Grid::new(3, 3, 1);
+///
+/// let result = path::dijkstra_monitored(&grid, (0, 0), [(2, 2)], |c| Some(*c), &mut Monitor::new());
+/// assert_eq!(result.unwrap().unwrap().0, 4);
+/// ```
+///
+/// # Errors
+///
+/// If the monitor's [`CancelToken`](crate::progress::CancelToken) is cancelled.
+///
+/// # Panics
+///
+/// If `start` or any of the `goals` is out of bounds.
+#[allow(clippy::type_complexity)]
+pub fn dijkstra_monitored<T, C, P>(
+    grid: &Grid<T>,
+    start: impl Point,
+    goals: impl IntoIterator<Item = P>,
+    cost: C,
+    monitor: &mut Monitor<'_>,
+) -> Result<Option<(usize, Vec<(usize, usize)>)>, Cancelled>
+where
+    T: Clone,
+    C: FnMut(&T) -> Option<usize>,
+    P: Point,
+{
+    dijkstra_with(grid, start, goals, cost, |_, _| true, monitor)
+}
+
+/// Runs [`dijkstra_monitored`], only taking steps for which `can_move(from, to)` returns `true`.
+#[allow(clippy::type_complexity)]
+fn dijkstra_with<T, C, P, M>(
+    grid: &Grid<T>,
+    start: impl Point,
+    goals: impl IntoIterator<Item = P>,
+    mut cost: C,
+    mut can_move: M,
+    monitor: &mut Monitor<'_>,
+) -> Result<Option<(usize, Vec<(usize, usize)>)>, Cancelled>
+where
+    T: Clone,
+    C: FnMut(&T) -> Option<usize>,
+    P: Point,
+    M: FnMut((usize, usize), (usize, usize)) -> bool,
+{
+    let start = grid.checked_index(start);
+    let mut is_goal = vec![false; grid.area()];
+    for goal in goals {
+        is_goal[grid.checked_index(goal)] = true;
+    }
+
+    let mut best = vec![usize::MAX; grid.area()];
+    let mut came_from = vec![usize::MAX; grid.area()];
+    let mut open = BinaryHeap::from([Reverse((0, start))]);
+    let mut expanded = 0;
+
+    best[start] = 0;
+    while let Some(Reverse((score, current))) = open.pop() {
+        if score > best[current] {
+            continue;
+        }
+        expanded += 1;
+        monitor.update(expanded, grid.area())?;
+        if is_goal[current] {
+            let path = reconstruct(&came_from, start, current, grid.width());
+            return Ok(Some((score, path)));
+        }
+        for next in neighbors(current, grid.width(), grid.height()) {
+            if !can_move(
+                to_point(current, grid.width()),
+                to_point(next, grid.width()),
+            ) {
+                continue;
+            }
+            let Some(step) = cost(&grid[next]) else {
+                continue;
+            };
+            let score = score.saturating_add(step);
+            if score < best[next] {
+                best[next] = score;
+                came_from[next] = current;
+                open.push(Reverse((score, next)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The state of an incremental search, such as [`AstarSearch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SearchStatus {
+    /// The search has not finished yet, and should be stepped again.
+    Pending,
+
+    /// The goal was found, with every point along the path (including both `start` and `goal`).
+    Found(Vec<(usize, usize)>),
+
+    /// Every reachable cell was expanded without finding the goal.
+    Unreachable,
+}
+
+/// An [A*] search that can be advanced incrementally, i.e. spread across several frames of a game.
+///
+/// Each call to [`AstarSearch::step`] expands at most a fixed number of cells, so the time spent
+/// per call is bounded regardless of the size of the grid.
+///
+/// [A*]: https://en.wikipedia.org/wiki/A*_search_algorithm
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, path::{manhattan, AstarSearch, SearchStatus}};
+///
+/// let grid = Grid::new(10, 10, ());
+/// let mut search = AstarSearch::new(&grid, (0, 0), (9, 9), |_| Some(1), manhattan);
+///
+/// let path = loop {
+///     // i.e. once per frame.
+///     match search.step(4) {
+///         SearchStatus::Pending => continue,
+///         SearchStatus::Found(path) => break Some(path),
+///         SearchStatus::Unreachable => break None,
+///     }
+/// };
+///
+/// assert_eq!(path.unwrap().len(), 19);
+/// ```
+pub struct AstarSearch<'a, T, C, H>
+where
+    T: Clone,
+{
+    grid: &'a Grid<T>,
+    cost: C,
+    heuristic: H,
+    start: usize,
+    goal: usize,
+    best: Vec<usize>,
+    came_from: Vec<usize>,
+    open: BinaryHeap<Reverse<(usize, usize, usize)>>,
+    expanded: usize,
+    status: SearchStatus,
+    can_move: Option<Box<MoveFilter<'a>>>,
+}
+
+/// Reports whether a step from one cell to an adjacent cell is allowed.
+type MoveFilter<'a> = dyn FnMut((usize, usize), (usize, usize)) -> bool + 'a;
+
+impl<'a, T, C, H> AstarSearch<'a, T, C, H>
+where
+    T: Clone,
+    C: FnMut(&T) -> Option<usize>,
+    H: FnMut((usize, usize), (usize, usize)) -> usize,
+{
+    /// Creates a search for the cheapest path from `start` to `goal`, without expanding any cells.
+    ///
+    /// See [`astar_with`] for a description of `cost` and `heuristic`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, path::{manhattan, AstarSearch}};
+    ///
+    /// let grid = Grid::new(3, 3, ());
+    /// let search = AstarSearch::new(&grid, (0, 0), (2, 2), |_| Some(1), manhattan);
+    /// assert_eq!(search.expanded(), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `start` or `goal` is out of bounds.
+    pub fn new(
+        grid: &'a Grid<T>,
+        start: impl Point,
+        goal: impl Point,
+        cost: C,
+        mut heuristic: H,
+    ) -> Self {
+        let width = grid.width();
+        let start = grid.checked_index(start);
+        let goal = grid.checked_index(goal);
+        let mut best = vec![usize::MAX; grid.area()];
+        let mut open = BinaryHeap::new();
+
+        best[start] = 0;
+        open.push(Reverse((
+            heuristic(to_point(start, width), to_point(goal, width)),
+            0,
+            start,
+        )));
+
+        Self {
+            grid,
+            cost,
+            heuristic,
+            start,
+            goal,
+            best,
+            came_from: vec![usize::MAX; grid.area()],
+            open,
+            expanded: 0,
+            status: SearchStatus::Pending,
+            can_move: None,
+        }
+    }
+
+    /// Restricts movement to steps for which `can_move(from, to)` returns `true`.
+    ///
+    /// This allows movement to be blocked by something other than the contents of the cell being
+    /// entered, i.e. thin walls stored in an [`EdgeGrid`](crate::edge::EdgeGrid).
+    ///
+    /// # Examples
+    ///
+    /// One-way movement to the right:
+    ///
+    /// ```
+    /// use grud::{Grid, path::{manhattan, AstarSearch, SearchStatus}};
+    ///
+    /// let grid = Grid::new(3, 1, ());
+    /// let mut search = AstarSearch::new(&grid, (2, 0), (0, 0), |_| Some(1), manhattan)
+    ///     .with_moves(|from, to| to.0 > from.0);
+    ///
+    /// assert_eq!(search.step(usize::MAX), SearchStatus::Unreachable);
+    /// ```
+    pub fn with_moves(
+        mut self,
+        can_move: impl FnMut((usize, usize), (usize, usize)) -> bool + 'a,
+    ) -> Self {
+        self.can_move = Some(Box::new(can_move));
+        self
+    }
+
+    /// Returns how many cells have been expanded so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, path::{manhattan, AstarSearch}};
+    ///
+    /// let grid = Grid::new(3, 3, ());
+    /// let mut search = AstarSearch::new(&grid, (0, 0), (2, 2), |_| Some(1), manhattan);
+    /// search.step(2);
+    /// assert_eq!(search.expanded(), 2);
+    /// ```
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+
+    /// Returns the current state of the search, without advancing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, path::{manhattan, AstarSearch, SearchStatus}};
+    ///
+    /// let grid = Grid::new(3, 3, ());
+    /// let search = AstarSearch::new(&grid, (0, 0), (2, 2), |_| Some(1), manhattan);
+    /// assert_eq!(search.status(), &SearchStatus::Pending);
+    /// ```
+    pub fn status(&self) -> &SearchStatus {
+        &self.status
+    }
+
+    /// Advances the search by expanding at most `budget` cells, returning the resulting state.
+    ///
+    /// Once the search has finished, further calls return the same result without doing any work.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, path::{manhattan, AstarSearch, SearchStatus}};
+    ///
+    /// let grid = Grid::new(3, 1, ());
+    /// let mut search = AstarSearch::new(&grid, (0, 0), (2, 0), |_| Some(1), manhattan);
+    ///
+    /// assert_eq!(search.step(2), SearchStatus::Pending);
+    /// assert_eq!(search.step(1), SearchStatus::Found(vec![(0, 0), (1, 0), (2, 0)]));
+    /// ```
+    pub fn step(&mut self, budget: usize) -> SearchStatus {
+        for _ in 0..budget {
+            if self.status != SearchStatus::Pending {
+                break;
+            }
+            self.expand();
+        }
+        self.status.clone()
+    }
+
+    /// Expands the next cell in the open set, updating the status if the search has finished.
+    fn expand(&mut self) {
+        let width = self.grid.width();
+        let goal_point = to_point(self.goal, width);
+        while let Some(Reverse((_, score, current))) = self.open.pop() {
+            if score > self.best[current] {
+                continue;
+            }
+            self.expanded += 1;
+            if current == self.goal {
+                let path = reconstruct(&self.came_from, self.start, self.goal, width);
+                self.status = SearchStatus::Found(path);
+                return;
+            }
+            for next in neighbors(current, width, self.grid.height()) {
+                if let Some(can_move) = self.can_move.as_mut() {
+                    if !can_move(to_point(current, width), to_point(next, width)) {
+                        continue;
+                    }
+                }
+                let Some(step) = (self.cost)(&self.grid[next]) else {
+                    continue;
+                };
+                let score = self.best[current].saturating_add(step);
+                if score < self.best[next] {
+                    self.best[next] = score;
+                    self.came_from[next] = current;
+                    let estimate =
+                        score.saturating_add((self.heuristic)(to_point(next, width), goal_point));
+                    self.open.push(Reverse((estimate, score, next)));
+                }
+            }
+            return;
+        }
+        self.status = SearchStatus::Unreachable;
+    }
+}
+
+/// A [D* Lite] planner, which repairs its path cheaply when the cost of a few cells changes.
+///
+/// The planner searches backwards from the goal, keeping the cost to reach the goal from every
+/// expanded cell. When a cell's cost changes (see [`DstarLite::set_cost`]) or the start moves
+/// (see [`DstarLite::set_start`]), only the cells affected by the change are expanded again, which
+/// is typically far fewer than an [`astar`] search from scratch.
+///
+/// The planner keeps its own copy of the cost of each cell, so the grid it was created from can be
+/// changed freely. Costs should be at least `1`, as the [`manhattan`] heuristic is used.
+///
+/// [D* Lite]: https://en.wikipedia.org/wiki/D*#D*_Lite
+///
+/// # Examples
+///
+/// An agent that discovers a wall while walking:
+///
+/// ```
+/// use grud::{Grid, path::DstarLite};
+///
+/// let grid = Grid::new(3, 3, 1);
+/// let mut planner = DstarLite::new(&grid, (0, 1), (2, 1), |c| Some(*c));
+/// assert_eq!(planner.path().unwrap(), vec![(0, 1), (1, 1), (2, 1)]);
+///
+/// // Walk one step, then discover that the next cell is impassable.
+/// planner.set_start((1, 1));
+/// planner.set_cost((2, 1), None);
+/// planner.set_cost((2, 0), None);
+///
+/// assert_eq!(planner.path(), None);
+///
+/// planner.set_cost((2, 1), Some(1));
+/// assert_eq!(planner.path().unwrap(), vec![(1, 1), (2, 1)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DstarLite {
+    width: usize,
+    height: usize,
+    start: usize,
+    goal: usize,
+    costs: Vec<Option<usize>>,
+    g: Vec<usize>,
+    rhs: Vec<usize>,
+    km: usize,
+    open: BinaryHeap<Reverse<(usize, usize, usize)>>,
+    queued: Vec<Option<(usize, usize)>>,
+    expanded: usize,
+}
+
+impl DstarLite {
+    /// Creates a planner for the cheapest path from `start` to `goal`, without expanding any cells.
+    ///
+    /// As with [`astar`], `cost` returns the cost of entering a cell, or `None` if impassable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, path::DstarLite};
+    ///
+    /// let grid = Grid::new(3, 3, ());
+    /// let planner = DstarLite::new(&grid, (0, 0), (2, 2), |_| Some(1));
+    /// assert_eq!(planner.expanded(), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `start` or `goal` is out of bounds.
+    pub fn new<T, C>(grid: &Grid<T>, start: impl Point, goal: impl Point, cost: C) -> Self
+    where
+        T: Clone,
+        C: FnMut(&T) -> Option<usize>,
+    {
+        let start = grid.checked_index(start);
+        let goal = grid.checked_index(goal);
+        let area = grid.area();
+        let mut planner = Self {
+            width: grid.width(),
+            height: grid.height(),
+            start,
+            goal,
+            costs: grid.as_vec().iter().map(cost).collect(),
+            g: vec![usize::MAX; area],
+            rhs: vec![usize::MAX; area],
+            km: 0,
+            open: BinaryHeap::new(),
+            queued: vec![None; area],
+            expanded: 0,
+        };
+        planner.rhs[goal] = 0;
+        planner.enqueue(goal);
+        planner
+    }
+
+    /// Returns how many cells have been expanded so far, across every call to
+    /// [`DstarLite::path`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, path::DstarLite};
+    ///
+    /// let grid = Grid::new(8, 8, 1);
+    /// let mut planner = DstarLite::new(&grid, (0, 0), (7, 7), |c| Some(*c));
+    /// planner.path();
+    /// let initial = planner.expanded();
+    ///
+    /// // Repairing after a small change expands fewer cells than the initial search.
+    /// planner.set_cost((7, 6), Some(5));
+    /// planner.path();
+    /// assert!(planner.expanded() - initial < initial);
+    /// ```
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+
+    /// Changes the cost of entering the cell at `point`, or makes it impassable if `None`.
+    ///
+    /// The path is repaired on the next call to [`DstarLite::path`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, path::DstarLite};
+    ///
+    /// let grid = Grid::new(3, 2, 1);
+    /// let mut planner = DstarLite::new(&grid, (0, 0), (2, 0), |c| Some(*c));
+    /// planner.set_cost((1, 0), Some(9));
+    ///
+    /// assert_eq!(planner.path().unwrap(), vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn set_cost(&mut self, point: impl Point, cost: Option<usize>) {
+        assert!(
+            point.x() < self.width && point.y() < self.height,
+            "Point ({}, {}) out of bounds of {}x{} grid",
+            point.x(),
+            point.y(),
+            self.width,
+            self.height
+        );
+        let index = point.to_index(self.width);
+        if self.costs[index] == cost {
+            return;
+        }
+        self.costs[index] = cost;
+        for neighbor in neighbors(index, self.width, self.height) {
+            self.update(neighbor);
+        }
+    }
+
+    /// Moves the start of the path to `point`, i.e. after the agent has taken a step.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, path::DstarLite};
+    ///
+    /// let grid = Grid::new(3, 1, 1);
+    /// let mut planner = DstarLite::new(&grid, (0, 0), (2, 0), |c| Some(*c));
+    /// planner.set_start((1, 0));
+    ///
+    /// assert_eq!(planner.path().unwrap(), vec![(1, 0), (2, 0)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn set_start(&mut self, point: impl Point) {
+        assert!(
+            point.x() < self.width && point.y() < self.height,
+            "Point ({}, {}) out of bounds of {}x{} grid",
+            point.x(),
+            point.y(),
+            self.width,
+            self.height
+        );
+        let start = point.to_index(self.width);
+        self.km = self.km.saturating_add(self.heuristic(self.start, start));
+        self.start = start;
+    }
+
+    /// Returns the cheapest path from the start to the goal, repairing it if anything changed.
+    ///
+    /// Returns every point along the path, including both the start and goal, or `None` if the
+    /// goal is not reachable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, path::DstarLite};
+    ///
+    /// let grid = Grid::with_width(3, vec![
+    ///     1, 9, 1,
+    ///     1, 1, 1,
+    /// ]);
+    /// let mut planner = DstarLite::new(&grid, (0, 0), (2, 0), |c| Some(*c));
+    ///
+    /// assert_eq!(planner.path().unwrap(), vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)]);
+    /// ```
+    pub fn path(&mut self) -> Option<Vec<(usize, usize)>> {
+        self.compute();
+        if self.g[self.start] == usize::MAX {
+            return None;
+        }
+        let mut path = vec![to_point(self.start, self.width)];
+        let mut current = self.start;
+        while current != self.goal {
+            let (score, next) = self.best_successor(current)?;
+            if score == usize::MAX || path.len() > self.costs.len() {
+                return None;
+            }
+            path.push(to_point(next, self.width));
+            current = next;
+        }
+        Some(path)
+    }
+
+    /// Expands cells until the cost from the start to the goal is known.
+    fn compute(&mut self) {
+        while let Some(&Reverse((k1, k2, current))) = self.open.peek() {
+            if self.queued[current] != Some((k1, k2)) {
+                self.open.pop();
+                continue;
+            }
+            if (k1, k2) >= self.key(self.start) && self.rhs[self.start] == self.g[self.start] {
+                break;
+            }
+            self.open.pop();
+            self.queued[current] = None;
+            self.expanded += 1;
+
+            if (k1, k2) < self.key(current) {
+                self.enqueue(current);
+            } else if self.g[current] > self.rhs[current] {
+                self.g[current] = self.rhs[current];
+                for neighbor in neighbors(current, self.width, self.height) {
+                    self.update(neighbor);
+                }
+            } else {
+                self.g[current] = usize::MAX;
+                self.update(current);
+                for neighbor in neighbors(current, self.width, self.height) {
+                    self.update(neighbor);
+                }
+            }
+        }
+    }
+
+    /// Returns the cheapest neighbor to step to from `index`, and the cost to the goal through it.
+    fn best_successor(&self, index: usize) -> Option<(usize, usize)> {
+        neighbors(index, self.width, self.height)
+            .filter_map(|next| Some((self.costs[next]?.saturating_add(self.g[next]), next)))
+            .min()
+    }
+
+    /// Recomputes the lookahead cost of `index`, queueing it if it has become inconsistent.
+    fn update(&mut self, index: usize) {
+        if index != self.goal {
+            self.rhs[index] = self
+                .best_successor(index)
+                .map_or(usize::MAX, |(score, _)| score);
+        }
+        if self.g[index] == self.rhs[index] {
+            self.queued[index] = None;
+        } else {
+            self.enqueue(index);
+        }
+    }
+
+    fn enqueue(&mut self, index: usize) {
+        let (k1, k2) = self.key(index);
+        self.queued[index] = Some((k1, k2));
+        self.open.push(Reverse((k1, k2, index)));
+    }
+
+    fn key(&self, index: usize) -> (usize, usize) {
+        let score = self.g[index].min(self.rhs[index]);
+        let estimate = score
+            .saturating_add(self.heuristic(self.start, index))
+            .saturating_add(self.km);
+        (estimate, score)
+    }
+
+    fn heuristic(&self, a: usize, b: usize) -> usize {
+        manhattan(to_point(a, self.width), to_point(b, self.width))
+    }
+}
+
+/// Finds the shortest path from `start` to `goal` using a [breadth-first search].
+///
+/// Every step has the same cost, and `passable` reports whether a cell may be entered. Returns
+/// every point along the path, including both `start` and `goal`, or `None` if `goal` is not
+/// reachable.
+///
+/// [breadth-first search]: https://en.wikipedia.org/wiki/Breadth-first_search
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, path};
+///
+/// let grid = Grid::from(vec![
+///     vec!['.', '#', '.'],
+///     vec!['.', '.', '.'],
+/// ]);
+///
+/// let path = path::bfs(&grid, (0, 0), (2, 0), |c| *c == '.').unwrap();
+/// assert_eq!(path, vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)]);
+/// ```
+///
+/// # Panics
+///
+/// If `start` or `goal` is out of bounds.
+pub fn bfs<T, P>(
+    grid: &Grid<T>,
+    start: impl Point,
+    goal: impl Point,
+    passable: P,
+) -> Option<Vec<(usize, usize)>>
+where
+    T: Clone,
+    P: FnMut(&T) -> bool,
+{
+    bfs_with_moves(grid, start, goal, passable, |_, _| true)
+}
+
+/// Finds the shortest path from `start` to `goal`, like [`bfs`], only taking steps for which
+/// `can_move(from, to)` returns `true`.
+///
+/// This allows movement to be blocked by something other than the contents of the cell being
+/// entered, i.e. thin walls stored in an [`EdgeGrid`](crate::edge::EdgeGrid).
+///
+/// # Examples
+///
+/// One-way movement to the right:
+///
+/// ```
+/// use grud::{Grid, path};
+///
+/// let grid = Grid::new(3, 1, ());
+///
+/// assert!(path::bfs_with_moves(&grid, (0, 0), (2, 0), |_| true, |a, b| b.0 > a.0).is_some());
+/// assert!(path::bfs_with_moves(&grid, (2, 0), (0, 0), |_| true, |a, b| b.0 > a.0).is_none());
+/// ```
+///
+/// # Panics
+///
+/// If `start` or `goal` is out of bounds.
+pub fn bfs_with_moves<T, P, M>(
+    grid: &Grid<T>,
+    start: impl Point,
+    goal: impl Point,
+    passable: P,
+    can_move: M,
+) -> Option<Vec<(usize, usize)>>
+where
+    T: Clone,
+    P: FnMut(&T) -> bool,
+    M: FnMut((usize, usize), (usize, usize)) -> bool,
+{
+    let start = grid.checked_index(start);
+    let goal = grid.checked_index(goal);
+    let mut monitor = Monitor::new();
+    let (distances, came_from) =
+        match breadth_first(grid, start, Some(goal), passable, can_move, &mut monitor) {
+            Ok(result) => result,
+            Err(Cancelled) => unreachable!("Monitor without a token is never cancelled"),
+        };
+    (distances[goal] != usize::MAX).then(|| reconstruct(&came_from, start, goal, grid.width()))
+}
+
+/// Returns the number of steps from `start` to every cell using a [breadth-first search].
+///
+/// Every step has the same cost, and `passable` reports whether a cell may be entered. Cells that
+/// are not reachable from `start` are `None`.
+///
+/// [breadth-first search]: https://en.wikipedia.org/wiki/Breadth-first_search
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, path};
+///
+/// let grid = Grid::from(vec![
+///     vec!['.', '#', '.'],
+///     vec!['.', '#', '.'],
+/// ]);
+///
+/// let distances = path::distance_map(&grid, (0, 0), |c| *c == '.');
+/// assert_eq!(distances.to_matrix(), vec![
+///     vec![Some(0), None, None],
+///     vec![Some(1), None, None],
+/// ]);
+/// ```
+///
+/// # Panics
+///
+/// If `start` is out of bounds.
+pub fn distance_map<T, P>(grid: &Grid<T>, start: impl Point, passable: P) -> Grid<Option<usize>>
+where
+    T: Clone,
+    P: FnMut(&T) -> bool,
+{
+    match distance_map_monitored(grid, start, passable, &mut Monitor::new()) {
+        Ok(distances) => distances,
+        Err(Cancelled) => unreachable!("Monitor without a token is never cancelled"),
+    }
+}
+
+/// Returns the number of steps from `start` to every cell, like [`distance_map`], only taking
+/// steps for which `can_move(from, to)` returns `true`.
+///
+/// This allows movement to be blocked by something other than the contents of the cell being
+/// entered, i.e. thin walls stored in an [`EdgeGrid`](crate::edge::EdgeGrid).
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, edge::EdgeGrid, path};
+///
+/// let grid = Grid::new(2, 1, ());
+/// let mut walls = EdgeGrid::new(2, 1, false);
+/// *walls.between_mut((0, 0), (1, 0)).unwrap() = true;
+///
+/// let distances = path::distance_map_with_moves(&grid, (0, 0), |_| true, |a, b| {
+///     !walls.wall_between(a, b)
+/// });
+/// assert_eq!(distances.as_vec(), &vec![Some(0), None]);
+/// ```
+///
+/// # Panics
+///
+/// If `start` is out of bounds.
+pub fn distance_map_with_moves<T, P, M>(
+    grid: &Grid<T>,
+    start: impl Point,
+    passable: P,
+    can_move: M,
+) -> Grid<Option<usize>>
+where
+    T: Clone,
+    P: FnMut(&T) -> bool,
+    M: FnMut((usize, usize), (usize, usize)) -> bool,
+{
+    let start = grid.checked_index(start);
+    match breadth_first(grid, start, None, passable, can_move, &mut Monitor::new()) {
+        Ok((distances, _)) => to_distance_map(grid, distances),
+        Err(Cancelled) => unreachable!("Monitor without a token is never cancelled"),
+    }
+}
+
+/// Returns the number of steps from `start` to every cell, observed by a [`Monitor`].
+///
+/// Progress is reported as the number of cells visited, out of the total number of cells.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, path, progress::Monitor};
+///
+/// let grid = Grid::new(3, 3, ());
+/// let mut reports = 0;
+/// let mut sink = |_, _| reports += 1;
+///
+/// let distances = path::distance_map_monitored(
+///     &grid,
+///     (0, 0),
+///     |_| true,
+///     &mut Monitor::new().with_sink(&mut sink),
+/// ).unwrap();
+///
+/// assert_eq!(distances[(2, 2)], Some(4));
+/// assert_eq!(reports, 9);
+/// ```
+///
+/// # Errors
+///
+/// If the monitor's [`CancelToken`](crate::progress::CancelToken) is cancelled.
+///
+/// # Panics
+///
+/// If `start` is out of bounds.
+pub fn distance_map_monitored<T, P>(
+    grid: &Grid<T>,
+    start: impl Point,
+    passable: P,
+    monitor: &mut Monitor<'_>,
+) -> Result<Grid<Option<usize>>, Cancelled>
+where
+    T: Clone,
+    P: FnMut(&T) -> bool,
+{
+    let start = grid.checked_index(start);
+    let (distances, _) = breadth_first(grid, start, None, passable, |_, _| true, monitor)?;
+    Ok(to_distance_map(grid, distances))
+}
+
+/// Converts the distances returned by [`breadth_first`] into a grid the size of `grid`.
+fn to_distance_map<T>(grid: &Grid<T>, distances: Vec<usize>) -> Grid<Option<usize>>
+where
+    T: Clone,
+{
+    Grid::with_width(
+        grid.width(),
+        distances
+            .into_iter()
+            .map(|d| (d != usize::MAX).then_some(d))
+            .collect(),
+    )
+}
+
+/// Runs a breadth-first search from `start`, stopping early once `goal` (if any) is reached, and
+/// only taking steps for which `can_move(from, to)` returns `true`.
+///
+/// Returns the distance to every cell (`usize::MAX` if not reached), and the cell each was
+/// reached from.
+fn breadth_first<T, P, M>(
+    grid: &Grid<T>,
+    start: usize,
+    goal: Option<usize>,
+    mut passable: P,
+    mut can_move: M,
+    monitor: &mut Monitor<'_>,
+) -> Result<(Vec<usize>, Vec<usize>), Cancelled>
+where
+    T: Clone,
+    P: FnMut(&T) -> bool,
+    M: FnMut((usize, usize), (usize, usize)) -> bool,
+{
+    let mut distances = vec![usize::MAX; grid.area()];
+    let mut came_from = vec![usize::MAX; grid.area()];
+    let mut queue = VecDeque::from([start]);
+    let mut visited = 0;
+
+    distances[start] = 0;
+    while let Some(current) = queue.pop_front() {
+        visited += 1;
+        monitor.update(visited, grid.area())?;
+        if Some(current) == goal {
+            break;
+        }
+        for next in neighbors(current, grid.width(), grid.height()) {
+            if distances[next] == usize::MAX
+                && can_move(
+                    to_point(current, grid.width()),
+                    to_point(next, grid.width()),
+                )
+                && passable(&grid[next])
+            {
+                distances[next] = distances[current] + 1;
+                came_from[next] = current;
+                queue.push_back(next);
+            }
+        }
+    }
+    Ok((distances, came_from))
+}
+
+/// Finds a short any-angle path from `start` to `goal` using [Theta*].
+///
+/// Unlike [`astar`], the path is not restricted to steps between adjacent cells: each point is
+/// connected to the next by a straight line through passable cells, so only the turning points
+/// are returned (including both `start` and `goal`), or `None` if `goal` is not reachable. The
+/// length of the path is measured with Euclidean distances between cells.
+///
+/// Movement is 8-connected. Every cell a line touches must be passable (see
+/// [`supercover_line`](crate::point::supercover_line)), so paths never cut through the corner of
+/// an impassable cell, including moving diagonally between two cells that touch at a corner. Like
+/// the other searches, `start` is never entered, so it is treated as passable.
+///
+/// [Theta*]: https://en.wikipedia.org/wiki/Theta*
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, path};
+///
+/// let grid = Grid::from(vec![
+///     "......".chars().collect(),
+///     "...#..".chars().collect(),
+///     "...#..".chars().collect(),
+///     "......".chars().collect(),
+/// ]);
+///
+/// let path = path::theta_star(&grid, (0, 0), (5, 2), |c| *c == '.').unwrap();
+/// assert_eq!(path, vec![(0, 0), (4, 0), (5, 2)]);
+/// ```
+///
+/// # Panics
+///
+/// If `start` or `goal` is out of bounds.
+pub fn theta_star<T, P>(
+    grid: &Grid<T>,
+    start: impl Point,
+    goal: impl Point,
+    passable: P,
+) -> Option<Vec<(usize, usize)>>
+where
+    T: Clone,
+    P: FnMut(&T) -> bool,
+{
+    let width = grid.width();
+    let start = grid.checked_index(start);
+    let goal = grid.checked_index(goal);
+    let mut passable: Vec<bool> = grid.as_vec().iter().map(passable).collect();
+    passable[start] = true;
+    let distance = |a: usize, b: usize| {
+        let ((ax, ay), (bx, by)) = (to_point(a, width), to_point(b, width));
+        (ax.abs_diff(bx) as f64).hypot(ay.abs_diff(by) as f64)
+    };
+    let visible = |a: usize, b: usize| {
+        crate::point::supercover_line(to_point(a, width), to_point(b, width))
+            .all(|point| passable[point.to_index(width)])
+    };
+
+    let mut best = vec![f64::INFINITY; grid.area()];
+    let mut parent = vec![usize::MAX; grid.area()];
+    let mut closed = vec![false; grid.area()];
+    // Scores are never negative, so the order of their bits is the same as their numeric order.
+    let mut open = BinaryHeap::from([Reverse((distance(start, goal).to_bits(), start))]);
+
+    best[start] = 0.0;
+    parent[start] = start;
+    while let Some(Reverse((_, current))) = open.pop() {
+        if closed[current] {
+            continue;
+        }
+        closed[current] = true;
+        if current == goal {
+            return Some(reconstruct(&parent, start, goal, width));
+        }
+        for next in neighbors_8(current, width, grid.height()) {
+            if closed[next] || !passable[next] {
+                continue;
+            }
+            let grandparent = parent[current];
+            let (from, score) = if visible(grandparent, next) {
+                (grandparent, best[grandparent] + distance(grandparent, next))
+            } else if visible(current, next) {
+                (current, best[current] + distance(current, next))
+            } else {
+                continue;
+            };
+            if score < best[next] {
+                best[next] = score;
+                parent[next] = from;
+                let estimate = score + distance(next, goal);
+                open.push(Reverse((estimate.to_bits(), next)));
+            }
+        }
+    }
+    None
+}
+
+/// Converts an index back into a point, given the `width` of a grid.
+fn to_point(index: usize, width: usize) -> (usize, usize) {
+    (index % width, index / width)
+}
+
+/// Returns the indices of the 4-connected neighbors of `index`.
+fn neighbors(index: usize, width: usize, height: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = to_point(index, width);
+    [
+        (y > 0).then(|| index - width),
+        (x > 0).then(|| index - 1),
+        (x + 1 < width).then(|| index + 1),
+        (y + 1 < height).then(|| index + width),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Returns the indices of the 8-connected neighbors of `index`.
+fn neighbors_8(index: usize, width: usize, height: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = to_point(index, width);
+    (y.saturating_sub(1)..(y + 2).min(height))
+        .flat_map(move |ny| (x.saturating_sub(1)..(x + 2).min(width)).map(move |nx| (nx, ny)))
+        .filter(move |&point| point != (x, y))
+        .map(move |(nx, ny)| ny * width + nx)
+}
+
+/// Walks `came_from` backwards from `goal` to `start`, returning the path in forward order.
+fn reconstruct(
+    came_from: &[usize],
+    start: usize,
+    goal: usize,
+    width: usize,
+) -> Vec<(usize, usize)> {
+    let mut path = vec![to_point(goal, width)];
+    let mut current = goal;
+    while current != start {
+        current = came_from[current];
+        path.push(to_point(current, width));
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{edge::EdgeGrid, progress::CancelToken};
+
+    fn maze() -> Grid<char> {
+        Grid::from(vec![
+            "..#....".chars().collect(),
+            ".##.##.".chars().collect(),
+            "....#..".chars().collect(),
+        ])
+    }
+
+    fn open(c: &char) -> Option<usize> {
+        (*c == '.').then_some(1)
+    }
+
+    #[test]
+    fn astar_start_is_goal() {
+        let grid = maze();
+
+        assert_eq!(astar(&grid, (0, 0), (0, 0), open), Some(vec![(0, 0)]));
+    }
+
+    #[test]
+    fn astar_through_maze() {
+        let grid = maze();
+        let path = astar(&grid, (0, 0), (6, 0), open).unwrap();
+
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(6, 0)));
+        assert_eq!(path.len(), 11);
+        assert!(path.iter().all(|p| grid[*p] == '.'));
+        assert!(path.windows(2).all(|w| manhattan(w[0], w[1]) == 1));
+    }
+
+    #[test]
+    fn astar_unreachable() {
+        let grid = Grid::from(vec![vec!['.', '#', '.']]);
+
+        assert_eq!(astar(&grid, (0, 0), (2, 0), open), None);
+    }
+
+    #[test]
+    fn astar_impassable_goal() {
+        let grid = maze();
+
+        assert_eq!(astar(&grid, (0, 0), (2, 0), open), None);
+    }
+
+    #[test]
+    fn astar_with_matches_dijkstra_cost() {
+        let grid = Grid::with_width(3, vec![1, 5, 1, 1, 5, 1, 1, 1, 1]);
+        let path = astar_with(&grid, (0, 0), (2, 0), |c| Some(*c), |_, _| 0).unwrap();
+        let cost: usize = path[1..].iter().map(|p| grid[*p]).sum();
+
+        assert_eq!(cost, 6);
+    }
+
+    #[test]
+    fn bfs_through_maze() {
+        let grid = maze();
+        let path = bfs(&grid, (0, 0), (6, 0), |c| *c == '.').unwrap();
+
+        assert_eq!(path.len(), 11);
+        assert!(path.windows(2).all(|w| manhattan(w[0], w[1]) == 1));
+    }
+
+    #[test]
+    fn bfs_start_is_goal() {
+        let grid = maze();
+
+        assert_eq!(bfs(&grid, (0, 0), (0, 0), |_| false), Some(vec![(0, 0)]));
+    }
+
+    #[test]
+    fn bfs_unreachable() {
+        let grid = maze();
+
+        assert_eq!(bfs(&grid, (0, 0), (6, 0), |_| false), None);
+    }
+
+    #[test]
+    fn distance_map_through_maze() {
+        let grid = maze();
+        let distances = distance_map(&grid, (0, 0), |c| *c == '.');
+
+        assert_eq!(distances[(6, 0)], Some(10));
+        assert_eq!(distances[(6, 2)], Some(12));
+        assert_eq!(distances[(2, 0)], None);
+    }
+
+    #[test]
+    fn distance_map_monitored_cancelled() {
+        let token = CancelToken::new();
+        token.cancel();
+        let mut monitor = Monitor::new().with_cancel(&token);
+
+        assert_eq!(
+            distance_map_monitored(&maze(), (0, 0), |_| true, &mut monitor).err(),
+            Some(Cancelled)
+        );
+    }
+
+    #[test]
+    fn astar_monitored_reports_expanded() {
+        let grid = Grid::new(10, 10, ());
+        let mut last = (0, 0);
+        let mut sink = |completed, total| last = (completed, total);
+        let path = astar_monitored(
+            &grid,
+            (0, 0),
+            (9, 0),
+            |_| Some(1),
+            manhattan,
+            &mut Monitor::new().with_sink(&mut sink),
+        );
+
+        assert_eq!(path.unwrap().unwrap().len(), 10);
+        assert_eq!(last, (10, 100));
+    }
+
+    #[test]
+    fn astar_search_unreachable_is_sticky() {
+        let grid = Grid::from(vec![vec!['.', '#', '.']]);
+        let mut search = AstarSearch::new(&grid, (0, 0), (2, 0), open, manhattan);
+
+        assert_eq!(search.step(10), SearchStatus::Unreachable);
+        assert_eq!(search.expanded(), 1);
+        assert_eq!(search.step(10), SearchStatus::Unreachable);
+        assert_eq!(search.expanded(), 1);
+    }
+
+    #[test]
+    fn astar_search_zero_budget() {
+        let grid = maze();
+        let mut search = AstarSearch::new(&grid, (0, 0), (6, 0), open, manhattan);
+
+        assert_eq!(search.step(0), SearchStatus::Pending);
+        assert_eq!(search.expanded(), 0);
+    }
+
+    #[test]
+    fn astar_search_with_moves_detours() {
+        let grid = Grid::new(3, 2, '.');
+        let mut search = AstarSearch::new(&grid, (0, 0), (2, 0), open, manhattan)
+            .with_moves(|from, to| from != (1, 0) || to != (2, 0));
+
+        assert_eq!(
+            search.step(usize::MAX),
+            SearchStatus::Found(vec![(0, 0), (1, 0), (1, 1), (2, 1), (2, 0)])
+        );
+    }
+
+    #[test]
+    fn searches_with_moves_respect_walls() {
+        let grid = Grid::new(3, 2, '.');
+        let mut walls = EdgeGrid::new(3, 2, false);
+        *walls.between_mut((1, 0), (2, 0)).unwrap() = true;
+        *walls.between_mut((1, 1), (2, 1)).unwrap() = true;
+        let can_move = |a, b| !walls.wall_between(a, b);
+
+        assert_eq!(
+            bfs_with_moves(&grid, (0, 0), (2, 0), |c| *c == '.', can_move),
+            None
+        );
+        assert_eq!(
+            dijkstra_with_moves(&grid, (0, 0), [(2, 1)], open, can_move),
+            None
+        );
+        let distances = distance_map_with_moves(&grid, (0, 0), |c| *c == '.', can_move);
+        assert_eq!(distances.count(Option::is_some), 4);
+    }
+
+    #[test]
+    fn dijkstra_nearest_goal() {
+        let grid = maze();
+        let (cost, path) = dijkstra(&grid, (0, 0), [(6, 0), (0, 2)], open).unwrap();
+
+        assert_eq!(cost, 2);
+        assert_eq!(path, vec![(0, 0), (0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn dijkstra_start_is_goal() {
+        let grid = maze();
+
+        assert_eq!(
+            dijkstra(&grid, (0, 0), [(0, 0)], open),
+            Some((0, vec![(0, 0)]))
+        );
+    }
+
+    #[test]
+    fn dijkstra_no_goals() {
+        let grid = maze();
+
+        assert_eq!(
+            dijkstra(&grid, (0, 0), Vec::<(usize, usize)>::new(), open),
+            None
+        );
+    }
+
+    #[test]
+    fn dijkstra_unreachable() {
+        let grid = maze();
+
+        assert_eq!(dijkstra(&grid, (0, 0), [(2, 0)], open), None);
+    }
+
+    #[test]
+    fn dstar_lite_matches_dijkstra_after_changes() {
+        let mut grid = Grid::new(9, 7, 1);
+        let mut planner = DstarLite::new(&grid, (0, 0), (8, 6), |c| Some(*c));
+        let cost_of = |grid: &Grid<usize>, path: &[(usize, usize)]| -> usize {
+            path[1..].iter().map(|&point| grid[point]).sum()
+        };
+
+        let mut seed = 17_usize;
+        let mut start = (0, 0);
+        for round in 0..30 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            let point = ((seed >> 33) % 9, (seed >> 45) % 7);
+            if point != (8, 6) {
+                grid[point] = 1 + (seed >> 20) % 9;
+                planner.set_cost(point, Some(grid[point]));
+            }
+            if round % 5 == 4 {
+                start = ((start.0 + 1).min(8), start.1);
+                planner.set_start(start);
+            }
+
+            let expected = dijkstra(&grid, start, [(8, 6)], |c| Some(*c)).unwrap().0;
+            let path = planner.path().unwrap();
+            assert_eq!(path.first(), Some(&start));
+            assert_eq!(cost_of(&grid, &path), expected, "round {round}");
+        }
+    }
+
+    #[test]
+    fn dstar_lite_start_is_goal() {
+        let grid = Grid::new(2, 2, ());
+        let mut planner = DstarLite::new(&grid, (1, 1), (1, 1), |_| None);
+
+        assert_eq!(planner.path(), Some(vec![(1, 1)]));
+    }
+
+    #[test]
+    fn theta_star_straight_line() {
+        let grid = Grid::new(10, 10, ());
+
+        assert_eq!(
+            theta_star(&grid, (0, 0), (9, 4), |_| true),
+            Some(vec![(0, 0), (9, 4)])
+        );
+    }
+
+    #[test]
+    fn theta_star_through_maze() {
+        let path = theta_star(&maze(), (0, 0), (6, 0), |c| *c == '.').unwrap();
+
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(6, 0)));
+        for pair in path.windows(2) {
+            assert!(crate::point::supercover_line(pair[0], pair[1]).all(|p| maze()[p] == '.'));
+        }
+    }
+
+    #[test]
+    fn theta_star_does_not_cut_corners() {
+        let grid = Grid::from(vec![
+            "..#".chars().collect(),
+            ".#.".chars().collect(),
+            "...".chars().collect(),
+        ]);
+        let path = theta_star(&grid, (0, 0), (2, 1), |c| *c == '.').unwrap();
+
+        assert_eq!(path, vec![(0, 0), (0, 2), (2, 2), (2, 1)]);
+        assert_eq!(
+            theta_star(&grid, (1, 0), (2, 1), |c| *c == '.'),
+            Some(vec![(1, 0), (0, 0), (0, 2), (2, 2), (2, 1)])
+        );
+    }
+
+    #[test]
+    fn theta_star_unreachable() {
+        let grid = Grid::from(vec![vec!['.', '#', '.']]);
+
+        assert_eq!(theta_star(&grid, (0, 0), (2, 0), |c| *c == '.'), None);
+    }
+
+    #[test]
+    fn theta_star_impassable_start() {
+        let grid = Grid::from(vec![vec!['#', '.', '.']]);
+
+        assert_eq!(
+            theta_star(&grid, (0, 0), (2, 0), |c| *c == '.'),
+            Some(vec![(0, 0), (2, 0)])
+        );
+        assert_eq!(
+            astar(&grid, (0, 0), (2, 0), open).map(|path| path.len()),
+            Some(3)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn astar_out_of_bounds() {
+        astar(&maze(), (0, 0), (7, 0), open);
+    }
+}
@@ -0,0 +1,142 @@
+//! Writing grids as [Netpbm] images (PGM and PPM), i.e. for visually debugging heightmaps.
+//!
+//! Both formats are simple enough to write without dependencies, and are widely supported by image
+//! viewers. See [`Grid::write_pgm`] and [`Grid::write_ppm`] for details.
+//!
+//! [Netpbm]: https://netpbm.sourceforge.net/doc/pbm.html
+
+use std::io::{self, Write};
+
+use crate::grid::Grid;
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Writes the grid as a binary grayscale PGM image, where each pixel is `brightness(cell)`.
+    ///
+    /// # Examples
+    ///
+    /// Rendering a distance field:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let distances = Grid::with_width(2, vec![Some(0), Some(4), None, Some(8)]);
+    /// let mut pgm = Vec::new();
+    /// distances.write_pgm_with(&mut pgm, |d| d.map_or(0, |d| 255 - d * 16)).unwrap();
+    ///
+    /// assert_eq!(pgm, b"P5\n2 2\n255\n\xff\xbf\x00\x7f");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_pgm_with(
+        &self,
+        mut writer: impl Write,
+        mut brightness: impl FnMut(&T) -> u8,
+    ) -> io::Result<()> {
+        let (width, height) = self.size();
+        write!(writer, "P5\n{width} {height}\n255\n")?;
+        let data: Vec<u8> = self.as_vec().iter().map(&mut brightness).collect();
+        writer.write_all(&data)
+    }
+
+    /// Writes the grid as a binary color PPM image, where each pixel is the `[r, g, b]` color
+    /// returned by `color(cell)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![true, false]);
+    /// let mut ppm = Vec::new();
+    /// grid.write_ppm_with(&mut ppm, |on| if *on { [255, 0, 0] } else { [0, 0, 0] }).unwrap();
+    ///
+    /// assert_eq!(ppm, b"P6\n2 1\n255\n\xff\x00\x00\x00\x00\x00");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_ppm_with(
+        &self,
+        mut writer: impl Write,
+        mut color: impl FnMut(&T) -> [u8; 3],
+    ) -> io::Result<()> {
+        let (width, height) = self.size();
+        write!(writer, "P6\n{width} {height}\n255\n")?;
+        let data: Vec<u8> = self.as_vec().iter().flat_map(&mut color).collect();
+        writer.write_all(&data)
+    }
+}
+
+impl Grid<u8> {
+    /// Writes the grid as a binary grayscale PGM image, where each cell is the brightness of a
+    /// pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut pgm = Vec::new();
+    /// Grid::with_width(2, vec![0, 255]).write_pgm(&mut pgm).unwrap();
+    ///
+    /// assert_eq!(pgm, b"P5\n2 1\n255\n\x00\xff");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_pgm(&self, writer: impl Write) -> io::Result<()> {
+        self.write_pgm_with(writer, |value| *value)
+    }
+}
+
+impl Grid<[u8; 3]> {
+    /// Writes the grid as a binary color PPM image, where each cell is the `[r, g, b]` color of a
+    /// pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut ppm = Vec::new();
+    /// Grid::with_width(1, vec![[1, 2, 3]]).write_ppm(&mut ppm).unwrap();
+    ///
+    /// assert_eq!(ppm, b"P6\n1 1\n255\n\x01\x02\x03");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_ppm(&self, writer: impl Write) -> io::Result<()> {
+        self.write_ppm_with(writer, |color| *color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pgm_empty_grid() {
+        let mut pgm = Vec::new();
+        Grid::<u8>::from(Vec::new()).write_pgm(&mut pgm).unwrap();
+
+        assert_eq!(pgm, b"P5\n0 0\n255\n");
+    }
+
+    #[test]
+    fn ppm_rows_in_order() {
+        let mut ppm = Vec::new();
+        let grid = Grid::with_width(1, vec![[1, 1, 1], [2, 2, 2]]);
+        grid.write_ppm(&mut ppm).unwrap();
+
+        assert_eq!(ppm, b"P6\n1 2\n255\n\x01\x01\x01\x02\x02\x02");
+    }
+}
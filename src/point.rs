@@ -1,5 +1,9 @@
 //! Utilities for defining and using 2-dimensional points in space (i.e. `(x, y)` coordinates).
 
+use core::{cmp::Ordering, iter::FusedIterator};
+
+use crate::grid::Grid;
+
 /// A 2-dimensional point.
 pub trait Point: Clone + Copy {
     /// Returns the x-coordinate.
@@ -9,8 +13,75 @@ pub trait Point: Clone + Copy {
     fn y(&self) -> usize;
 
     /// Given the `width` of a grid, converts to an index into a 2-dimensional space (e.g. [`Vec`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Point;
+    ///
+    /// assert_eq!((1, 2).to_index(3), 7);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the index overflows a [`usize`], i.e. for large grids on 32-bit targets.
     fn to_index(&self, width: usize) -> usize {
-        self.y() * width + self.x()
+        self.checked_to_index(width).unwrap_or_else(|| {
+            panic!(
+                "Index of point ({}, {}) in grid of width {width} overflows usize",
+                self.x(),
+                self.y()
+            )
+        })
+    }
+
+    /// Given the `width` of a grid, converts to an index into a 2-dimensional space (e.g. [`Vec`]),
+    /// or returns `None` if the index overflows a [`usize`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Point;
+    ///
+    /// assert_eq!((1, 2).checked_to_index(3), Some(7));
+    /// assert_eq!((0, 2).checked_to_index(usize::MAX), None);
+    /// ```
+    fn checked_to_index(&self, width: usize) -> Option<usize> {
+        self.y().checked_mul(width)?.checked_add(self.x())
+    }
+
+    /// Returns the point moved by `(dx, dy)`, or `None` if either coordinate would be negative
+    /// (or overflow a [`usize`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Point;
+    ///
+    /// assert_eq!((1, 1).offset(-1, 2), Some((0, 3)));
+    /// assert_eq!((0, 1).offset(-1, 0), None);
+    /// ```
+    fn offset(&self, dx: isize, dy: isize) -> Option<(usize, usize)> {
+        Some((
+            self.x().checked_add_signed(dx)?,
+            self.y().checked_add_signed(dy)?,
+        ))
+    }
+
+    /// Returns the adjacent point in `direction`, or `None` if either coordinate would be negative
+    /// (or overflow a [`usize`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::{Direction, Point};
+    ///
+    /// assert_eq!((1, 1).step(Direction::NorthEast), Some((2, 0)));
+    /// assert_eq!((1, 0).step(Direction::North), None);
+    /// ```
+    fn step(&self, direction: Direction) -> Option<(usize, usize)> {
+        let (dx, dy) = direction.delta();
+        self.offset(dx, dy)
     }
 }
 
@@ -34,6 +105,706 @@ impl Point for [usize; 2] {
     }
 }
 
+/// A compass direction, where north is towards `y = 0` and west is towards `x = 0`.
+///
+/// # Examples
+///
+/// ```
+/// use grud::point::{Direction, Point};
+///
+/// let mut position = (2, 2);
+/// for direction in [Direction::North, Direction::North, Direction::West] {
+///     position = position.step(direction).unwrap();
+/// }
+///
+/// assert_eq!(position, (1, 0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    /// Up, towards `y = 0`.
+    North,
+
+    /// Up and to the right.
+    NorthEast,
+
+    /// Right, away from `x = 0`.
+    East,
+
+    /// Down and to the right.
+    SouthEast,
+
+    /// Down, away from `y = 0`.
+    South,
+
+    /// Down and to the left.
+    SouthWest,
+
+    /// Left, towards `x = 0`.
+    West,
+
+    /// Up and to the left.
+    NorthWest,
+}
+
+impl Direction {
+    /// Every direction, clockwise starting from [`Direction::North`].
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// The four cardinal directions, clockwise starting from [`Direction::North`].
+    pub const CARDINAL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// Returns the `(dx, dy)` offset of a single step in this direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Direction;
+    ///
+    /// assert_eq!(Direction::North.delta(), (0, -1));
+    /// assert_eq!(Direction::SouthEast.delta(), (1, 1));
+    /// ```
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Self::North => (0, -1),
+            Self::NorthEast => (1, -1),
+            Self::East => (1, 0),
+            Self::SouthEast => (1, 1),
+            Self::South => (0, 1),
+            Self::SouthWest => (-1, 1),
+            Self::West => (-1, 0),
+            Self::NorthWest => (-1, -1),
+        }
+    }
+
+    /// Returns whether this is one of the four [`Direction::CARDINAL`] directions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Direction;
+    ///
+    /// assert!(Direction::East.is_cardinal());
+    /// assert!(!Direction::NorthEast.is_cardinal());
+    /// ```
+    pub fn is_cardinal(self) -> bool {
+        matches!(self, Self::North | Self::East | Self::South | Self::West)
+    }
+
+    /// Returns the direction pointing the opposite way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Direction;
+    ///
+    /// assert_eq!(Direction::North.opposite(), Direction::South);
+    /// assert_eq!(Direction::SouthWest.opposite(), Direction::NorthEast);
+    /// ```
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// Returns the direction a quarter turn clockwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Direction;
+    ///
+    /// assert_eq!(Direction::North.turn_right(), Direction::East);
+    /// assert_eq!(Direction::NorthWest.turn_right(), Direction::NorthEast);
+    /// ```
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Returns the direction a quarter turn counter-clockwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Direction;
+    ///
+    /// assert_eq!(Direction::North.turn_left(), Direction::West);
+    /// assert_eq!(Direction::SouthEast.turn_left(), Direction::NorthEast);
+    /// ```
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    /// Returns the direction `eighths` eighth-turns clockwise.
+    fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+}
+
+/// Which surrounding points are considered adjacent to a point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// The 4 orthogonally adjacent points (i.e. the [von Neumann neighborhood]).
+    ///
+    /// [von Neumann neighborhood]: https://en.wikipedia.org/wiki/Von_Neumann_neighborhood
+    Four,
+
+    /// The 8 orthogonally and diagonally adjacent points (i.e. the [Moore neighborhood]).
+    ///
+    /// [Moore neighborhood]: https://en.wikipedia.org/wiki/Moore_neighborhood
+    Eight,
+}
+
+impl Connectivity {
+    /// Returns the `(dx, dy)` offsets of every adjacent point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Connectivity;
+    ///
+    /// assert_eq!(Connectivity::Four.offsets().len(), 4);
+    /// assert_eq!(Connectivity::Eight.offsets().len(), 8);
+    /// ```
+    pub fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Four => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Self::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+
+    /// Returns every point adjacent to `point` that is within a `width` by `height` area.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Connectivity;
+    ///
+    /// let neighbors: Vec<_> = Connectivity::Four.neighbors((0, 0), 2, 2).collect();
+    /// assert_eq!(neighbors, vec![(1, 0), (0, 1)]);
+    /// ```
+    pub fn neighbors(
+        self,
+        point: impl Point,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (x, y) = (point.x(), point.y());
+        self.offsets().iter().filter_map(move |(dx, dy)| {
+            let (x, y) = (x, y).offset(*dx, *dy)?;
+            (x < width && y < height).then_some((x, y))
+        })
+    }
+
+    /// Returns every point adjacent to `point` within a `width` by `height` area whose edges wrap
+    /// around (i.e. a torus), so points on an edge are adjacent to points on the opposite edge.
+    ///
+    /// Every point has the same number of neighbors; in areas narrower or shorter than 3, some
+    /// neighbors are the same point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Connectivity;
+    ///
+    /// let neighbors: Vec<_> = Connectivity::Four.neighbors_wrapping((0, 0), 3, 3).collect();
+    /// assert_eq!(neighbors, vec![(0, 2), (2, 0), (1, 0), (0, 1)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is not within the area.
+    pub fn neighbors_wrapping(
+        self,
+        point: impl Point,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (x, y) = (point.x(), point.y());
+        assert!(
+            x < width && y < height,
+            "Point ({x}, {y}) out of bounds of {width}x{height} area"
+        );
+        self.offsets().iter().map(move |(dx, dy)| {
+            let x = x.checked_add_signed(*dx).unwrap_or(width - 1) % width;
+            let y = y.checked_add_signed(*dy).unwrap_or(height - 1) % height;
+            (x, y)
+        })
+    }
+}
+
+/// An axis-aligned rectangle of points, with its top-left corner at `(x, y)`.
+///
+/// A rectangle that would extend past [`usize::MAX`] is treated as ending at [`usize::MAX`].
+///
+/// # Examples
+///
+/// ```
+/// use grud::point::Rect;
+///
+/// let rect = Rect::new(1, 2, 3, 4);
+/// assert!(rect.contains((3, 5)));
+/// assert!(!rect.contains((4, 5)));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Rect {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Rect {
+    /// Creates a `width` by `height` rectangle with its top-left corner at `(x, y)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Rect;
+    ///
+    /// let rect = Rect::new(1, 2, 3, 4);
+    /// assert_eq!((rect.x(), rect.y(), rect.width(), rect.height()), (1, 2, 3, 4));
+    /// ```
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the x-coordinate of the left edge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Rect;
+    ///
+    /// assert_eq!(Rect::new(1, 2, 3, 4).x(), 1);
+    /// ```
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    /// Returns the y-coordinate of the top edge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Rect;
+    ///
+    /// assert_eq!(Rect::new(1, 2, 3, 4).y(), 2);
+    /// ```
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
+    /// Returns the width.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Rect;
+    ///
+    /// assert_eq!(Rect::new(1, 2, 3, 4).width(), 3);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Rect;
+    ///
+    /// assert_eq!(Rect::new(1, 2, 3, 4).height(), 4);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns whether the rectangle has no area.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Rect;
+    ///
+    /// assert!(Rect::new(1, 2, 0, 4).is_empty());
+    /// assert!(!Rect::new(1, 2, 3, 4).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Returns whether `point` is inside the rectangle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Rect;
+    ///
+    /// let rect = Rect::new(1, 1, 2, 2);
+    /// assert!(rect.contains((1, 1)));
+    /// assert!(rect.contains((2, 2)));
+    /// assert!(!rect.contains((3, 1)));
+    /// ```
+    pub fn contains(&self, point: impl Point) -> bool {
+        (self.x..self.right()).contains(&point.x()) && (self.y..self.bottom()).contains(&point.y())
+    }
+
+    /// Returns whether the rectangle shares at least one point with `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Rect;
+    ///
+    /// let rect = Rect::new(0, 0, 2, 2);
+    /// assert!(rect.intersects(&Rect::new(1, 1, 2, 2)));
+    /// assert!(!rect.intersects(&Rect::new(2, 0, 2, 2)));
+    /// ```
+    pub fn intersects(&self, other: &Rect) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.x < other.right()
+            && other.x < self.right()
+            && self.y < other.bottom()
+            && other.y < self.bottom()
+    }
+
+    /// Returns the rectangle of points inside both this rectangle and `other`.
+    ///
+    /// Returns `None` if the rectangles do not intersect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Rect;
+    ///
+    /// let rect = Rect::new(0, 0, 3, 3);
+    /// assert_eq!(rect.intersect(&Rect::new(2, 1, 4, 4)), Some(Rect::new(2, 1, 1, 2)));
+    /// assert_eq!(rect.intersect(&Rect::new(3, 0, 1, 1)), None);
+    /// ```
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        if !self.intersects(other) {
+            return None;
+        }
+        let (x, y) = (self.x.max(other.x), self.y.max(other.y));
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        Some(Rect::new(x, y, right - x, bottom - y))
+    }
+
+    /// Returns whether the rectangle shares at least one cell with `grid`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let grid = Grid::new(3, 2, 0);
+    /// assert!(Rect::new(2, 1, 5, 5).intersects_grid(&grid));
+    /// assert!(!Rect::new(3, 0, 1, 1).intersects_grid(&grid));
+    /// ```
+    pub fn intersects_grid<T>(&self, grid: &Grid<T>) -> bool
+    where
+        T: Clone,
+    {
+        let (width, height) = grid.size();
+        self.intersects(&Rect::new(0, 0, width, height))
+    }
+
+    /// Returns an iterator over every point inside the rectangle, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::point::Rect;
+    ///
+    /// let points: Vec<_> = Rect::new(1, 2, 2, 2).iter_points().collect();
+    /// assert_eq!(points, vec![(1, 2), (2, 2), (1, 3), (2, 3)]);
+    /// ```
+    pub fn iter_points(&self) -> impl Iterator<Item = (usize, usize)> {
+        let Rect {
+            x,
+            y,
+            width,
+            height,
+        } = *self;
+        (y..y.saturating_add(height))
+            .flat_map(move |j| (x..x.saturating_add(width)).map(move |i| (i, j)))
+    }
+
+    /// Returns the x-coordinate just past the right edge, saturating at [`usize::MAX`].
+    fn right(&self) -> usize {
+        self.x.saturating_add(self.width)
+    }
+
+    /// Returns the y-coordinate just past the bottom edge, saturating at [`usize::MAX`].
+    fn bottom(&self) -> usize {
+        self.y.saturating_add(self.height)
+    }
+
+    /// Returns whether the rectangle is entirely within a `width` by `height` area at the origin.
+    pub(crate) fn is_within(&self, width: usize, height: usize) -> bool {
+        self.x
+            .checked_add(self.width)
+            .is_some_and(|right| right <= width)
+            && self
+                .y
+                .checked_add(self.height)
+                .is_some_and(|bottom| bottom <= height)
+    }
+}
+
+/// Returns an iterator over every point on the rasterized line from `a` to `b`, including both.
+///
+/// Points are yielded in order from `a` to `b` using [Bresenham's line algorithm], so each point
+/// is adjacent (possibly diagonally) to the previous one. This is useful for drawing lines, or for
+/// simple line-of-sight checks; see [`supercover_line`] for every cell the line touches.
+///
+/// [Bresenham's line algorithm]: https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm
+///
+/// # Examples
+///
+/// ```
+/// use grud::point::line;
+///
+/// let points: Vec<_> = line((0, 0), (4, 2)).collect();
+/// assert_eq!(points, vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]);
+/// ```
+///
+/// Checking line of sight:
+///
+/// ```
+/// use grud::{Grid, point::line};
+///
+/// let grid = Grid::from_rows(["....", ".#..", "...."].map(str::chars));
+/// let visible = |a, b| line(a, b).all(|p| grid[p] != '#');
+///
+/// assert!(visible((0, 0), (3, 0)));
+/// assert!(!visible((0, 0), (2, 2)));
+/// ```
+///
+/// # Panics
+///
+/// If a coordinate of `a` or `b` is larger than [`isize::MAX`].
+pub fn line(a: impl Point, b: impl Point) -> Line {
+    let signed = |n: usize| isize::try_from(n).expect("Coordinate larger than isize::MAX");
+    let (x0, y0, x1, y1) = (signed(a.x()), signed(a.y()), signed(b.x()), signed(b.y()));
+    let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+    Line {
+        x: x0,
+        y: y0,
+        dx,
+        dy,
+        sx: if x0 < x1 { 1 } else { -1 },
+        sy: if y0 < y1 { 1 } else { -1 },
+        error: dx + dy,
+        remaining: dx.max(-dy) as usize + 1,
+    }
+}
+
+/// An iterator over the points on a rasterized line.
+///
+/// This struct is created by [`line`].
+#[derive(Clone, Debug)]
+pub struct Line {
+    x: isize,
+    y: isize,
+    dx: isize,
+    dy: isize,
+    sx: isize,
+    sy: isize,
+    error: isize,
+    remaining: usize,
+}
+
+impl Iterator for Line {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let point = (self.x as usize, self.y as usize);
+        let twice = 2 * self.error;
+        if twice >= self.dy {
+            self.error += self.dy;
+            self.x += self.sx;
+        }
+        if twice <= self.dx {
+            self.error += self.dx;
+            self.y += self.sy;
+        }
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Line {}
+
+impl FusedIterator for Line {}
+
+/// Returns an iterator over every point whose cell is touched by the segment from the center of
+/// `a` to the center of `b`, including both.
+///
+/// Unlike [`line`], which may step diagonally past cells the segment clips, this is a
+/// _supercover_ line: each point is orthogonally adjacent to the previous one, except where the
+/// segment passes exactly through the corner of a cell, in which case every cell sharing that
+/// corner is included. Use this for conservative checks such as collision sweeps, and [`line`]
+/// for drawing.
+///
+/// # Examples
+///
+/// ```
+/// use grud::point::{line, supercover_line};
+///
+/// let points: Vec<_> = supercover_line((0, 0), (4, 1)).collect();
+/// assert_eq!(points, vec![(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (4, 1)]);
+///
+/// // Bresenham skips (2, 0), which the segment passes through.
+/// let points: Vec<_> = line((0, 0), (4, 1)).collect();
+/// assert_eq!(points, vec![(0, 0), (1, 0), (2, 1), (3, 1), (4, 1)]);
+/// ```
+///
+/// Passing through a corner:
+///
+/// ```
+/// use grud::point::supercover_line;
+///
+/// let points: Vec<_> = supercover_line((0, 0), (1, 1)).collect();
+/// assert_eq!(points, vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+/// ```
+///
+/// # Panics
+///
+/// If a coordinate of `a` or `b` is larger than [`isize::MAX`].
+pub fn supercover_line(a: impl Point, b: impl Point) -> SupercoverLine {
+    let signed = |n: usize| isize::try_from(n).expect("Coordinate larger than isize::MAX");
+    let (x0, y0, x1, y1) = (signed(a.x()), signed(a.y()), signed(b.x()), signed(b.y()));
+    SupercoverLine {
+        x: x0,
+        y: y0,
+        sx: (x1 - x0).signum(),
+        sy: (y1 - y0).signum(),
+        nx: (x1 - x0).unsigned_abs(),
+        ny: (y1 - y0).unsigned_abs(),
+        ix: 0,
+        iy: 0,
+        corner: Corner::None,
+        started: false,
+    }
+}
+
+/// An iterator over the points touched by a line segment.
+///
+/// This struct is created by [`supercover_line`].
+#[derive(Clone, Debug)]
+pub struct SupercoverLine {
+    x: isize,
+    y: isize,
+    sx: isize,
+    sy: isize,
+    nx: usize,
+    ny: usize,
+    ix: usize,
+    iy: usize,
+    corner: Corner,
+    started: bool,
+}
+
+/// Progress through the cells around a corner the segment passes through exactly.
+#[derive(Clone, Copy, Debug)]
+enum Corner {
+    None,
+    Horizontal,
+    Vertical,
+}
+
+impl Iterator for SupercoverLine {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            return Some((self.x as usize, self.y as usize));
+        }
+        match self.corner {
+            Corner::Horizontal => {
+                self.corner = Corner::Vertical;
+                return Some((self.x as usize, (self.y + self.sy) as usize));
+            }
+            Corner::Vertical => {
+                self.corner = Corner::None;
+                self.x += self.sx;
+                self.y += self.sy;
+                self.ix += 1;
+                self.iy += 1;
+                return Some((self.x as usize, self.y as usize));
+            }
+            Corner::None => {}
+        }
+        if self.ix >= self.nx && self.iy >= self.ny {
+            return None;
+        }
+        // Compares where the segment crosses the next vertical and horizontal cell boundaries.
+        let decision = ((1 + 2 * self.ix) * self.ny).cmp(&((1 + 2 * self.iy) * self.nx));
+        match decision {
+            Ordering::Equal => {
+                self.corner = Corner::Horizontal;
+                return Some(((self.x + self.sx) as usize, self.y as usize));
+            }
+            Ordering::Less => {
+                self.x += self.sx;
+                self.ix += 1;
+            }
+            Ordering::Greater => {
+                self.y += self.sy;
+                self.iy += 1;
+            }
+        }
+        Some((self.x as usize, self.y as usize))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every step yields one point, and every corner yields one extra point; a corner only
+        // advances `ix` and `iy` once its last point is yielded.
+        let (rx, ry) = (self.nx - self.ix, self.ny - self.iy);
+        let (lower, corners) = match self.corner {
+            Corner::None => (rx + ry + usize::from(!self.started), rx.min(ry)),
+            Corner::Horizontal => (rx + ry, (rx - 1).min(ry - 1)),
+            Corner::Vertical => (rx + ry - 1, (rx - 1).min(ry - 1)),
+        };
+        (lower, Some(lower + corners))
+    }
+}
+
+impl FusedIterator for SupercoverLine {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,4 +824,172 @@ mod tests {
         assert_eq!(point.y(), 2);
         assert_eq!(point.to_index(2), 5);
     }
+
+    #[test]
+    fn rect_empty_contains_nothing() {
+        let rect = Rect::new(1, 1, 0, 3);
+
+        assert!(!rect.contains((1, 1)));
+        assert!(!rect.intersects(&Rect::new(0, 0, 5, 5)));
+    }
+
+    #[test]
+    fn rect_past_usize_max_does_not_overflow() {
+        let rect = Rect::new(usize::MAX, 0, 2, 1);
+        let far = Rect::new(usize::MAX - 1, usize::MAX - 1, 5, 5);
+
+        assert!(!rect.contains((0, 0)));
+        assert!(far.contains((usize::MAX - 1, usize::MAX - 1)));
+        assert!(!rect.intersects(&Rect::new(0, 0, 5, 5)));
+        assert_eq!(
+            far.intersect(&Rect::new(usize::MAX - 2, usize::MAX - 2, 2, 2)),
+            Some(Rect::new(usize::MAX - 1, usize::MAX - 1, 1, 1))
+        );
+        assert_eq!(far.iter_points().count(), 1);
+    }
+
+    #[test]
+    fn rect_intersect_contained() {
+        let inner = Rect::new(1, 1, 2, 2);
+
+        assert_eq!(Rect::new(0, 0, 5, 5).intersect(&inner), Some(inner));
+        assert_eq!(inner.intersect(&Rect::new(2, 2, 0, 0)), None);
+        assert_eq!(Rect::new(1, 1, 0, 3).iter_points().count(), 0);
+    }
+
+    #[test]
+    fn connectivity_eight_neighbors_corner() {
+        let neighbors: Vec<_> = Connectivity::Eight.neighbors((2, 2), 3, 3).collect();
+
+        assert_eq!(neighbors, vec![(1, 1), (2, 1), (1, 2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows usize")]
+    fn to_index_overflow() {
+        (1, usize::MAX).to_index(2);
+    }
+
+    #[test]
+    fn direction_delta_matches_all_order() {
+        let deltas: Vec<_> = Direction::ALL.iter().map(|d| d.delta()).collect();
+        let clockwise = [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ];
+
+        assert_eq!(deltas, clockwise);
+    }
+
+    #[test]
+    fn direction_turns_round_trip() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.opposite().opposite(), direction);
+        }
+    }
+
+    #[test]
+    fn line_every_octant_reaches_end() {
+        let center = (5, 5);
+        for end in [
+            (9, 7),
+            (7, 9),
+            (3, 9),
+            (1, 7),
+            (1, 3),
+            (3, 1),
+            (7, 1),
+            (9, 3),
+        ] {
+            let points: Vec<_> = line(center, end).collect();
+
+            assert_eq!(points.first(), Some(&center));
+            assert_eq!(points.last(), Some(&end));
+            assert_eq!(points.len(), 5);
+        }
+    }
+
+    #[test]
+    fn supercover_line_octants() {
+        assert_eq!(
+            supercover_line((5, 5), (9, 7)).collect::<Vec<_>>(),
+            vec![(5, 5), (6, 5), (6, 6), (7, 6), (8, 6), (8, 7), (9, 7)]
+        );
+        assert_eq!(
+            supercover_line((5, 5), (7, 9)).collect::<Vec<_>>(),
+            vec![(5, 5), (5, 6), (6, 6), (6, 7), (6, 8), (7, 8), (7, 9)]
+        );
+        assert_eq!(
+            supercover_line((5, 5), (1, 3)).collect::<Vec<_>>(),
+            vec![(5, 5), (4, 5), (4, 4), (3, 4), (2, 4), (2, 3), (1, 3)]
+        );
+        assert_eq!(
+            supercover_line((5, 5), (3, 9)).collect::<Vec<_>>(),
+            vec![(5, 5), (5, 6), (4, 6), (4, 7), (4, 8), (3, 8), (3, 9)]
+        );
+    }
+
+    #[test]
+    fn supercover_line_corners() {
+        assert_eq!(
+            supercover_line((0, 0), (2, 2)).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (2, 2)]
+        );
+        assert_eq!(
+            supercover_line((0, 0), (3, 1)).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (2, 0), (1, 1), (2, 1), (3, 1)]
+        );
+    }
+
+    #[test]
+    fn supercover_line_size_hint() {
+        for end in [
+            (1, 1),
+            (2, 2),
+            (3, 1),
+            (4, 2),
+            (6, 3),
+            (5, 0),
+            (0, 4),
+            (7, 3),
+        ] {
+            let mut line = supercover_line((0, 0), end);
+            loop {
+                let (lower, upper) = line.size_hint();
+                let remaining = line.clone().count();
+                assert!(lower <= remaining, "{end:?}: {lower} > {remaining}");
+                assert!(
+                    upper.unwrap() >= remaining,
+                    "{end:?}: {upper:?} < {remaining}"
+                );
+                if line.next().is_none() {
+                    break;
+                }
+            }
+        }
+
+        let mut line = supercover_line((0, 0), (1, 1));
+        line.nth(2);
+        assert_eq!(line.size_hint(), (1, Some(1)));
+    }
+
+    #[test]
+    fn supercover_line_single_point() {
+        assert_eq!(
+            supercover_line((2, 3), (2, 3)).collect::<Vec<_>>(),
+            vec![(2, 3)]
+        );
+    }
+
+    #[test]
+    fn line_single_point() {
+        assert_eq!(line((2, 3), (2, 3)).collect::<Vec<_>>(), vec![(2, 3)]);
+    }
 }
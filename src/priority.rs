@@ -0,0 +1,387 @@
+//! A priority queue keyed by cell, i.e. for Dijkstra or field D* searches.
+//!
+//! See [`PriorityField`] for details.
+
+use crate::{grid::Grid, point::Point};
+
+/// Position in the heap of a cell that is not queued.
+const NOT_QUEUED: usize = usize::MAX;
+
+/// A grid of priorities, combined with a min-heap of the cells that are queued.
+///
+/// Unlike [`BinaryHeap`](std::collections::BinaryHeap), each cell is queued at most once, and
+/// the priority of a queued cell can be changed in `O(log n)` time (i.e. decrease-key), so there
+/// are no stale entries to skip when popping.
+///
+/// Every cell starts with a priority of [`f64::INFINITY`], and keeps its last priority after being
+/// popped; [`PriorityField::priorities`] is therefore the distance field once a search completes.
+///
+/// # Examples
+///
+/// Dijkstra's algorithm on a grid of step costs:
+///
+/// ```
+/// use grud::{Grid, priority::PriorityField};
+///
+/// let costs = Grid::with_width(3, vec![
+///     1.0, 9.0, 1.0,
+///     1.0, 1.0, 1.0,
+/// ]);
+///
+/// let mut field = PriorityField::new(3, 2);
+/// field.push((0, 0), 0.0);
+///
+/// while let Some(((x, y), distance)) = field.pop() {
+///     let neighbors = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+///     for next in neighbors {
+///         if let Some(cost) = costs.get(next) {
+///             field.decrease(next, distance + cost);
+///         }
+///     }
+/// }
+///
+/// assert_eq!(field.priority((2, 0)), 4.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PriorityField {
+    priorities: Grid<f64>,
+    heap: Vec<usize>,
+    positions: Vec<usize>,
+}
+
+impl PriorityField {
+    /// Creates a field of `width` by `height` cells, where no cells are queued.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::priority::PriorityField;
+    ///
+    /// let field = PriorityField::new(3, 2);
+    ///
+    /// assert!(field.is_empty());
+    /// assert_eq!(field.priority((2, 1)), f64::INFINITY);
+    /// ```
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            priorities: Grid::new(width, height, f64::INFINITY),
+            heap: Vec::new(),
+            positions: vec![NOT_QUEUED; width * height],
+        }
+    }
+
+    /// Returns the priority of every cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::priority::PriorityField;
+    ///
+    /// let mut field = PriorityField::new(2, 1);
+    /// field.push((1, 0), 3.0);
+    ///
+    /// assert_eq!(field.priorities().as_vec(), &vec![f64::INFINITY, 3.0]);
+    /// ```
+    pub fn priorities(&self) -> &Grid<f64> {
+        &self.priorities
+    }
+
+    /// Returns the priority of the cell at `point`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::priority::PriorityField;
+    ///
+    /// let mut field = PriorityField::new(2, 2);
+    /// field.push((1, 1), 0.5);
+    ///
+    /// assert_eq!(field.priority((1, 1)), 0.5);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn priority(&self, point: impl Point) -> f64 {
+        self.priorities[point]
+    }
+
+    /// Returns the number of queued cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::priority::PriorityField;
+    ///
+    /// let mut field = PriorityField::new(2, 2);
+    /// field.push((0, 0), 1.0);
+    /// field.push((0, 0), 2.0);
+    ///
+    /// assert_eq!(field.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns whether no cells are queued.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::priority::PriorityField;
+    ///
+    /// assert!(PriorityField::new(2, 2).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns whether the cell at `point` is queued.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::priority::PriorityField;
+    ///
+    /// let mut field = PriorityField::new(2, 2);
+    /// field.push((1, 0), 1.0);
+    ///
+    /// assert!(field.contains((1, 0)));
+    /// assert!(!field.contains((0, 1)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn contains(&self, point: impl Point) -> bool {
+        self.positions[self.priorities.checked_index(point)] != NOT_QUEUED
+    }
+
+    /// Queues the cell at `point` with `priority`, or changes its priority if already queued.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::priority::PriorityField;
+    ///
+    /// let mut field = PriorityField::new(2, 1);
+    /// field.push((0, 0), 1.0);
+    /// field.push((1, 0), 2.0);
+    /// field.push((0, 0), 3.0);
+    ///
+    /// assert_eq!(field.pop(), Some(((1, 0), 2.0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds, or `priority` is NaN.
+    pub fn push(&mut self, point: impl Point, priority: f64) {
+        assert!(!priority.is_nan(), "Priority cannot be NaN");
+        let cell = self.priorities.checked_index(point);
+        let previous = std::mem::replace(&mut self.priorities[cell], priority);
+        let position = self.positions[cell];
+        if position == NOT_QUEUED {
+            self.heap.push(cell);
+            self.positions[cell] = self.heap.len() - 1;
+            self.sift_up(self.heap.len() - 1);
+        } else if priority < previous {
+            self.sift_up(position);
+        } else {
+            self.sift_down(position);
+        }
+    }
+
+    /// Queues the cell at `point` with `priority` if lower than its current priority.
+    ///
+    /// Returns whether the priority was changed.
+    ///
+    /// A cell that was popped is queued again if `priority` is lower than the one it was popped
+    /// with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::priority::PriorityField;
+    ///
+    /// let mut field = PriorityField::new(2, 1);
+    ///
+    /// assert!(field.decrease((0, 0), 5.0));
+    /// assert!(!field.decrease((0, 0), 7.0));
+    /// assert_eq!(field.priority((0, 0)), 5.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds, or `priority` is NaN.
+    pub fn decrease(&mut self, point: impl Point, priority: f64) -> bool {
+        assert!(!priority.is_nan(), "Priority cannot be NaN");
+        let cell = self.priorities.checked_index(point);
+        if priority < self.priorities[cell] {
+            self.push(cell_point(cell, self.priorities.width()), priority);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the queued cell with the lowest priority, without removing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::priority::PriorityField;
+    ///
+    /// let mut field = PriorityField::new(2, 1);
+    /// field.push((1, 0), 2.0);
+    ///
+    /// assert_eq!(field.peek(), Some(((1, 0), 2.0)));
+    /// assert_eq!(field.len(), 1);
+    /// ```
+    pub fn peek(&self) -> Option<((usize, usize), f64)> {
+        let cell = *self.heap.first()?;
+        Some((
+            cell_point(cell, self.priorities.width()),
+            self.priorities[cell],
+        ))
+    }
+
+    /// Removes and returns the queued cell with the lowest priority.
+    ///
+    /// Ties are broken arbitrarily. The cell keeps its priority.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::priority::PriorityField;
+    ///
+    /// let mut field = PriorityField::new(2, 1);
+    /// field.push((0, 0), 2.0);
+    /// field.push((1, 0), 1.0);
+    ///
+    /// assert_eq!(field.pop(), Some(((1, 0), 1.0)));
+    /// assert_eq!(field.pop(), Some(((0, 0), 2.0)));
+    /// assert_eq!(field.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<((usize, usize), f64)> {
+        let top = self.peek()?;
+        self.remove_at(0);
+        Some(top)
+    }
+
+    /// Removes the cell at `point` from the queue, returning whether it was queued.
+    ///
+    /// The cell keeps its priority.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::priority::PriorityField;
+    ///
+    /// let mut field = PriorityField::new(2, 1);
+    /// field.push((0, 0), 1.0);
+    ///
+    /// assert!(field.remove((0, 0)));
+    /// assert!(!field.remove((0, 0)));
+    /// assert_eq!(field.priority((0, 0)), 1.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn remove(&mut self, point: impl Point) -> bool {
+        let position = self.positions[self.priorities.checked_index(point)];
+        if position == NOT_QUEUED {
+            return false;
+        }
+        self.remove_at(position);
+        true
+    }
+
+    /// Removes the cell at `position` in the heap, restoring the heap order.
+    fn remove_at(&mut self, position: usize) {
+        let last = self.heap.len() - 1;
+        self.swap(position, last);
+        let cell = self.heap.pop().expect("Heap is not empty");
+        self.positions[cell] = NOT_QUEUED;
+        if position < self.heap.len() {
+            self.sift_down(position);
+            self.sift_up(position);
+        }
+    }
+
+    /// Returns whether the cell at heap `a` has a lower priority than the cell at heap `b`.
+    fn less(&self, a: usize, b: usize) -> bool {
+        self.priorities[self.heap[a]] < self.priorities[self.heap[b]]
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions[self.heap[a]] = a;
+        self.positions[self.heap[b]] = b;
+    }
+
+    fn sift_up(&mut self, mut position: usize) {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if !self.less(position, parent) {
+                break;
+            }
+            self.swap(position, parent);
+            position = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut position: usize) {
+        loop {
+            let mut smallest = position;
+            for child in [2 * position + 1, 2 * position + 2] {
+                if child < self.heap.len() && self.less(child, smallest) {
+                    smallest = child;
+                }
+            }
+            if smallest == position {
+                break;
+            }
+            self.swap(position, smallest);
+            position = smallest;
+        }
+    }
+}
+
+/// Returns the point of the cell at `index` in a grid of `width`.
+fn cell_point(index: usize, width: usize) -> (usize, usize) {
+    (index % width, index / width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Rect;
+
+    #[test]
+    fn priority_pops_in_order_after_updates() {
+        let mut field = PriorityField::new(4, 4);
+        for (i, point) in Rect::new(0, 0, 4, 4).iter_points().enumerate() {
+            field.push(point, ((i * 7) % 16) as f64);
+        }
+        field.push((3, 3), -1.0);
+        field.push((0, 0), 100.0);
+        field.remove((1, 0));
+
+        let mut popped = Vec::new();
+        while let Some((_, priority)) = field.pop() {
+            popped.push(priority);
+        }
+
+        assert_eq!(popped.len(), 15);
+        assert_eq!(popped.first(), Some(&-1.0));
+        assert_eq!(popped.last(), Some(&100.0));
+        assert!(popped.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Priority cannot be NaN")]
+    fn priority_nan() {
+        PriorityField::new(1, 1).push((0, 0), f64::NAN);
+    }
+}
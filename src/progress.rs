@@ -0,0 +1,245 @@
+//! Progress reporting and cancellation for long-running algorithms.
+//!
+//! Algorithms that may take a long time on large grids offer a `_monitored` variant that accepts a
+//! [`Monitor`], which forwards progress to a [`ProgressSink`] and stops early when a
+//! [`CancelToken`] is cancelled:
+//!
+//! ```
+//! use grud::{Grid, path, progress::{CancelToken, Monitor}};
+//!
+//! let grid = Grid::new(100, 100, ());
+//! let token = CancelToken::new();
+//! let mut reports = Vec::new();
+//! let mut sink = |completed, total| reports.push((completed, total));
+//!
+//! let mut monitor = Monitor::new().with_sink(&mut sink).with_cancel(&token);
+//! let distances = path::distance_map_monitored(&grid, (0, 0), |_| true, &mut monitor).unwrap();
+//!
+//! assert_eq!(distances[(99, 99)], Some(198));
+//! assert_eq!(reports.last(), Some(&(10_000, 10_000)));
+//! ```
+
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Receives progress updates from a long-running algorithm.
+///
+/// Implemented for any `FnMut(usize, usize)` closure.
+pub trait ProgressSink {
+    /// Reports that `completed` units of work are done out of an estimated `total`.
+    ///
+    /// The `total` is an upper bound; an algorithm may finish before `completed` reaches it.
+    fn report(&mut self, completed: usize, total: usize);
+}
+
+impl<F> ProgressSink for F
+where
+    F: FnMut(usize, usize),
+{
+    fn report(&mut self, completed: usize, total: usize) {
+        self(completed, total)
+    }
+}
+
+/// A shared flag used to request that a long-running algorithm stop early.
+///
+/// Clones share the same flag, so a token can be handed to another thread (i.e. a UI) that cancels
+/// work running elsewhere.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// Creates a new token that is not cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::progress::CancelToken;
+    ///
+    /// let token = CancelToken::new();
+    /// assert!(!token.is_cancelled());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of any algorithm observing this token (or a clone of it).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::progress::CancelToken;
+    ///
+    /// let token = CancelToken::new();
+    /// token.clone().cancel();
+    /// assert!(token.is_cancelled());
+    /// ```
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether cancellation has been requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::progress::CancelToken;
+    ///
+    /// let token = CancelToken::new();
+    /// assert!(!token.is_cancelled());
+    /// ```
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Error returned by a monitored algorithm that stopped because its [`CancelToken`] was cancelled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Operation was cancelled")
+    }
+}
+
+impl Error for Cancelled {}
+
+/// Observes a long-running algorithm, with an optional [`ProgressSink`] and [`CancelToken`].
+///
+/// Progress is reported at most once per percent of the estimated total, so a sink can be
+/// relatively expensive (i.e. redraw a progress bar) without slowing the algorithm down.
+#[derive(Default)]
+pub struct Monitor<'a> {
+    sink: Option<&'a mut dyn ProgressSink>,
+    token: Option<&'a CancelToken>,
+    reported: Option<(usize, usize)>,
+}
+
+impl<'a> Monitor<'a> {
+    /// Creates a monitor that neither reports progress nor can be cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::progress::Monitor;
+    ///
+    /// let _ = Monitor::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reports progress to `sink`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::progress::Monitor;
+    ///
+    /// let mut sink = |completed, total| println!("{completed}/{total}");
+    /// let _ = Monitor::new().with_sink(&mut sink);
+    /// ```
+    pub fn with_sink(mut self, sink: &'a mut dyn ProgressSink) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// Stops the algorithm early once `token` is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::progress::{CancelToken, Monitor};
+    ///
+    /// let token = CancelToken::new();
+    /// let _ = Monitor::new().with_cancel(&token);
+    /// ```
+    pub fn with_cancel(mut self, token: &'a CancelToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Records that `completed` out of `total` units of work are done.
+    ///
+    /// Returns an error if cancellation was requested, in which case the algorithm should stop.
+    pub(crate) fn update(&mut self, completed: usize, total: usize) -> Result<(), Cancelled> {
+        if self.token.is_some_and(CancelToken::is_cancelled) {
+            return Err(Cancelled);
+        }
+        if let Some(sink) = self.sink.as_mut() {
+            let percent = completed.saturating_mul(100) / total.max(1);
+            let report = match self.reported {
+                Some((p, c)) => percent > p || (completed == total && completed != c),
+                None => true,
+            };
+            if report {
+                self.reported = Some((percent, completed));
+                sink.report(completed, total);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Monitor<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Monitor")
+            .field("sink", &self.sink.is_some())
+            .field("token", &self.token)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monitor_reports_each_percent() {
+        let mut reports = Vec::new();
+        let mut sink = |completed, _| reports.push(completed);
+        let mut monitor = Monitor::new().with_sink(&mut sink);
+        for i in 1..=1000 {
+            monitor.update(i, 1000).unwrap();
+        }
+
+        assert_eq!(reports.len(), 101);
+        assert_eq!(reports[0], 1);
+        assert_eq!(reports[100], 1000);
+    }
+
+    #[test]
+    fn monitor_cancelled() {
+        let token = CancelToken::new();
+        let mut monitor = Monitor::new().with_cancel(&token);
+
+        assert_eq!(monitor.update(1, 2), Ok(()));
+        token.cancel();
+        assert_eq!(monitor.update(2, 2), Err(Cancelled));
+    }
+
+    #[test]
+    fn monitor_empty_total() {
+        let mut reports = Vec::new();
+        let mut sink = |completed, total| reports.push((completed, total));
+        let mut monitor = Monitor::new().with_sink(&mut sink);
+        monitor.update(0, 0).unwrap();
+        monitor.update(0, 0).unwrap();
+
+        assert_eq!(reports, vec![(0, 0)]);
+    }
+
+    #[test]
+    fn cancelled_display() {
+        assert_eq!(Cancelled.to_string(), "Operation was cancelled");
+    }
+}
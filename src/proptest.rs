@@ -0,0 +1,119 @@
+//! Strategies for generating grids with [`proptest`], i.e. for property-based testing.
+//!
+//! Requires the `proptest` feature.
+//!
+//! See [`grid`] to choose the size and cells, or use [`any`] for grids up to 16 by 16 cells.
+//!
+//! [`any`]: ::proptest::prelude::any
+
+use std::fmt::Debug;
+
+use ::proptest::{
+    arbitrary::Arbitrary,
+    collection::vec,
+    strategy::{BoxedStrategy, Strategy},
+};
+
+use crate::grid::Grid;
+
+/// Returns a strategy for grids with a width from `widths` and a height from `heights`, where each
+/// cell is generated by `cell`.
+///
+/// Shrinking reduces the size of the grid, and shrinks each cell with `cell`.
+///
+/// Requires the `proptest` feature.
+///
+/// # Examples
+///
+/// ```
+/// use grud::proptest::grid;
+/// use proptest::prelude::*;
+///
+/// proptest! {
+///     fn transpose_twice_is_identity(g in grid(0..8_usize, 0..8_usize, any::<u8>())) {
+///         let twice = g.transpose().transpose();
+///         prop_assert_eq!(twice.as_vec(), g.as_vec());
+///     }
+/// }
+/// transpose_twice_is_identity();
+/// ```
+pub fn grid<S>(
+    widths: impl Strategy<Value = usize>,
+    heights: impl Strategy<Value = usize>,
+    cell: S,
+) -> impl Strategy<Value = Grid<S::Value>>
+where
+    S: Strategy + Clone,
+    S::Value: Clone + Debug,
+{
+    (widths, heights).prop_flat_map(move |(width, height)| {
+        vec(cell.clone(), width * height).prop_map(move |data| {
+            if data.is_empty() {
+                Grid::from(Vec::new())
+            } else {
+                Grid::with_width(width, data)
+            }
+        })
+    })
+}
+
+impl<T> Arbitrary for Grid<T>
+where
+    T: Arbitrary + Clone,
+    T::Strategy: Clone + 'static,
+{
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Returns a strategy for grids up to 16 by 16 cells, where each cell is generated with `args`.
+    ///
+    /// Requires the `proptest` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    /// use proptest::prelude::*;
+    ///
+    /// proptest! {
+    ///     fn transpose_keeps_cells(g in any::<Grid<bool>>()) {
+    ///         prop_assert_eq!(g.transpose().as_vec().len(), g.as_vec().len());
+    ///     }
+    /// }
+    /// transpose_keeps_cells();
+    /// ```
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        grid(0..=16_usize, 0..=16_usize, T::arbitrary_with(args)).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::{prelude::any, strategy::ValueTree, test_runner::TestRunner};
+
+    use super::*;
+
+    #[test]
+    fn grid_respects_size_ranges() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = grid(2..=3_usize, 4..=4_usize, any::<u8>());
+        for _ in 0..32 {
+            let grid = strategy.new_tree(&mut runner).unwrap().current();
+
+            assert!((2..=3).contains(&grid.width()));
+            assert_eq!(grid.height(), 4);
+        }
+    }
+
+    #[test]
+    fn grid_shrinks_to_smaller_grid() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = grid(1..=8_usize, 1..=8_usize, any::<u8>())
+            .new_tree(&mut runner)
+            .unwrap();
+        let area = tree.current().as_vec().len();
+        while tree.simplify() {}
+
+        assert!(tree.current().as_vec().len() <= area);
+    }
+}
@@ -0,0 +1,321 @@
+//! Successively downsampled copies of a grid, i.e. for level-of-detail or coarse-to-fine search.
+//!
+//! See [`GridPyramid`] for details.
+
+use crate::{grid::Grid, point::Point, scale::Scale, view::GridMut};
+
+/// A stack of grids, where each level is half the size (rounded up) of the level below it.
+///
+/// Level `0` is the original (finest) grid, and the last level is the coarsest. Each cell of a
+/// coarser level covers a block of cells in the level below it, as described by [`Scale`].
+///
+/// # Examples
+///
+/// Finding which coarse regions contain a wall:
+///
+/// ```
+/// use grud::{Grid, pyramid::GridPyramid};
+///
+/// let grid = Grid::from(vec![
+///     "....".chars().collect(),
+///     "..#.".chars().collect(),
+///     "....".chars().collect(),
+///     "....".chars().collect(),
+/// ]);
+///
+/// let pyramid = GridPyramid::new(&grid, 3, |block| {
+///     if block.iter().any(|c| **c == '#') { '#' } else { '.' }
+/// });
+///
+/// assert_eq!(pyramid.len(), 3);
+/// assert_eq!(pyramid.level(1).to_matrix(), vec![vec!['.', '#'], vec!['.', '.']]);
+/// assert_eq!(pyramid.level(2).to_matrix(), vec![vec!['#']]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct GridPyramid<T>
+where
+    T: Clone,
+{
+    levels: Vec<Grid<T>>,
+}
+
+impl<T> GridPyramid<T>
+where
+    T: Clone,
+{
+    /// Creates a pyramid of up to `levels` levels (including `grid` itself), where each cell of a
+    /// coarser level is computed by `reducer` from the block of cells it covers in the level
+    /// below it (in row-major order).
+    ///
+    /// Stops early once a level is `1x1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, pyramid::GridPyramid};
+    ///
+    /// let grid = Grid::new(5, 3, 1);
+    /// let pyramid = GridPyramid::new(&grid, 10, |block| block.iter().copied().sum());
+    ///
+    /// assert_eq!(pyramid.len(), 4);
+    /// assert_eq!(pyramid.coarsest().as_vec(), &vec![15]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `grid` is empty, or `levels` is `0`.
+    pub fn new(grid: &Grid<T>, levels: usize, reducer: impl FnMut(&[&T]) -> T) -> Self {
+        assert!(
+            !grid.as_vec().is_empty(),
+            "Cannot create a pyramid of an empty grid"
+        );
+        assert!(levels > 0, "Pyramid must have at least 1 level");
+        let mut pyramid = Self {
+            levels: vec![grid.clone()],
+        };
+        pyramid.build(levels, reducer);
+        pyramid
+    }
+
+    /// Returns the number of levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, pyramid::GridPyramid};
+    ///
+    /// let pyramid = GridPyramid::new(&Grid::new(4, 4, 0), 2, |block| *block[0]);
+    /// assert_eq!(pyramid.len(), 2);
+    /// ```
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns the grid at `level`, where `0` is the finest level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, pyramid::GridPyramid};
+    ///
+    /// let pyramid = GridPyramid::new(&Grid::new(4, 4, 0), 2, |block| *block[0]);
+    /// assert_eq!(pyramid.level(1).width(), 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `level` is out of bounds.
+    pub fn level(&self, level: usize) -> &Grid<T> {
+        &self.levels[level]
+    }
+
+    /// Returns the grid at `level` as mutable, where `0` is the finest level.
+    ///
+    /// Cells can be changed, but the level cannot be resized, so every level stays half the size of
+    /// the level below it. Changes are not reflected in other levels until
+    /// [`GridPyramid::propagate_up`] or [`GridPyramid::propagate_down`] is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, pyramid::GridPyramid};
+    ///
+    /// let mut pyramid = GridPyramid::new(&Grid::new(4, 4, 0), 2, |block| *block[0]);
+    /// pyramid.level_mut(0)[(0, 0)] = 1;
+    /// assert_eq!(pyramid.level(1)[(0, 0)], 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `level` is out of bounds.
+    pub fn level_mut(&mut self, level: usize) -> GridMut<'_, T> {
+        GridMut::new(&mut self.levels[level])
+    }
+
+    /// Returns the coarsest (last) level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, pyramid::GridPyramid};
+    ///
+    /// let pyramid = GridPyramid::new(&Grid::new(4, 4, 0), 2, |block| *block[0]);
+    /// assert_eq!(pyramid.coarsest().width(), 2);
+    /// ```
+    pub fn coarsest(&self) -> &Grid<T> {
+        self.levels.last().expect("pyramid has at least 1 level")
+    }
+
+    /// Returns the [`Scale`] that maps cells of `level` onto cells of `level + 1`.
+    ///
+    /// # Examples
+    ///
+    /// Coarse-to-fine, finding the cells under a coarse cell:
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect, pyramid::GridPyramid};
+    ///
+    /// let pyramid = GridPyramid::new(&Grid::new(5, 5, 0), 2, |block| *block[0]);
+    /// let scale = pyramid.scale(0);
+    ///
+    /// assert_eq!(scale.to_source((2, 2)), Rect::new(4, 4, 1, 1));
+    /// assert_eq!(scale.to_target((3, 1)), (1, 0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `level` is not below the coarsest level.
+    pub fn scale(&self, level: usize) -> Scale {
+        let (finer, coarser) = (&self.levels[level], &self.levels[level + 1]);
+        Scale::new(
+            (finer.width(), finer.height()),
+            (coarser.width(), coarser.height()),
+        )
+    }
+
+    /// Recomputes every coarser level from the finest level, using `reducer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, pyramid::GridPyramid};
+    ///
+    /// let max = |block: &[&i32]| **block.iter().max().unwrap();
+    /// let mut pyramid = GridPyramid::new(&Grid::new(4, 4, 0), 3, max);
+    ///
+    /// pyramid.level_mut(0)[(3, 3)] = 9;
+    /// pyramid.propagate_up(max);
+    ///
+    /// assert_eq!(pyramid.level(1)[(1, 1)], 9);
+    /// assert_eq!(pyramid.coarsest()[(0, 0)], 9);
+    /// ```
+    pub fn propagate_up(&mut self, reducer: impl FnMut(&[&T]) -> T) {
+        let levels = self.levels.len();
+        self.levels.truncate(1);
+        self.build(levels, reducer);
+    }
+
+    /// Pushes values from the coarsest level down to the finest level, calling
+    /// `apply(coarse, fine)` for every cell of each finer level with the coarser cell covering it.
+    ///
+    /// # Examples
+    ///
+    /// Marking every fine cell under a coarse cell:
+    ///
+    /// ```
+    /// use grud::{Grid, pyramid::GridPyramid};
+    ///
+    /// let mut pyramid = GridPyramid::new(&Grid::new(4, 2, false), 2, |_| false);
+    /// pyramid.level_mut(1)[(1, 0)] = true;
+    /// pyramid.propagate_down(|coarse, fine| *fine |= *coarse);
+    ///
+    /// assert_eq!(
+    ///     pyramid.level(0).as_vec(),
+    ///     &vec![false, false, true, true, false, false, true, true],
+    /// );
+    /// ```
+    pub fn propagate_down(&mut self, mut apply: impl FnMut(&T, &mut T)) {
+        for level in (0..self.levels.len() - 1).rev() {
+            let scale = self.scale(level);
+            let (finer, coarser) = self.levels.split_at_mut(level + 1);
+            let (finer, coarser) = (&mut finer[level], &coarser[0]);
+            for y in 0..finer.height() {
+                for x in 0..finer.width() {
+                    apply(&coarser[scale.to_target((x, y))], &mut finer[(x, y)]);
+                }
+            }
+        }
+    }
+
+    /// Returns the cell at `level` that covers `point` in the finest level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, pyramid::GridPyramid};
+    ///
+    /// let pyramid = GridPyramid::new(&Grid::new(8, 8, 0), 4, |block| *block[0]);
+    /// assert_eq!(pyramid.locate((7, 2), 2), (1, 0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds of the finest level, or `level` is out of bounds.
+    pub fn locate(&self, point: impl Point, level: usize) -> (usize, usize) {
+        assert!(
+            level < self.levels.len(),
+            "Level {level} out of bounds of {} levels",
+            self.levels.len()
+        );
+        let mut point = (point.x(), point.y());
+        for i in 0..level {
+            point = self.scale(i).to_target(point);
+        }
+        point
+    }
+
+    /// Adds coarser levels (until there are `levels` levels) from the current coarsest level.
+    fn build(&mut self, levels: usize, mut reducer: impl FnMut(&[&T]) -> T) {
+        while self.levels.len() < levels {
+            let last = self.coarsest();
+            if last.width() == 1 && last.height() == 1 {
+                break;
+            }
+            let (width, height) = (last.width().div_ceil(2), last.height().div_ceil(2));
+            let (next, _) = last.minimap(width, height, &mut reducer);
+            self.levels.push(next);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum(block: &[&usize]) -> usize {
+        block.iter().copied().sum()
+    }
+
+    #[test]
+    fn pyramid_level_sizes() {
+        let pyramid = GridPyramid::new(&Grid::new(7, 2, 1), 10, sum);
+        let sizes: Vec<_> = (0..pyramid.len())
+            .map(|i| (pyramid.level(i).width(), pyramid.level(i).height()))
+            .collect();
+
+        assert_eq!(sizes, vec![(7, 2), (4, 1), (2, 1), (1, 1)]);
+        assert_eq!(pyramid.coarsest()[(0, 0)], 14);
+    }
+
+    #[test]
+    fn pyramid_single_level() {
+        let pyramid = GridPyramid::new(&Grid::new(4, 4, 1), 1, sum);
+
+        assert_eq!(pyramid.len(), 1);
+        assert_eq!(pyramid.locate((3, 3), 0), (3, 3));
+    }
+
+    #[test]
+    fn pyramid_propagate_up_keeps_level_count() {
+        let mut pyramid = GridPyramid::new(&Grid::new(4, 4, 1), 2, sum);
+        pyramid.level_mut(0)[(0, 0)] = 5;
+        pyramid.propagate_up(sum);
+
+        assert_eq!(pyramid.len(), 2);
+        assert_eq!(pyramid.level(1).as_vec(), &vec![8, 4, 4, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot replace 4x4 grid with 0x0 grid")]
+    fn pyramid_level_mut_cannot_resize() {
+        let mut pyramid = GridPyramid::new(&Grid::new(4, 4, 1), 2, sum);
+        pyramid.level_mut(0).replace(Grid::from(Vec::new()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pyramid_zero_levels() {
+        GridPyramid::new(&Grid::new(4, 4, 1), 0, sum);
+    }
+}
@@ -0,0 +1,294 @@
+//! Region quadtrees, i.e. for compressing large maps with big uniform areas.
+//!
+//! See [`Quadtree`] for details.
+
+use crate::{
+    grid::Grid,
+    point::{Point, Rect},
+};
+
+/// A region quadtree, where each uniform region of a grid is stored as a single leaf.
+///
+/// The grid is recursively split into four quadrants (rounding the top-left quadrant up, so
+/// non-square and non-power-of-two grids are supported), and any quadrants whose cells are all
+/// equal are merged into a single leaf. Reading a cell is `O(log n)`, and querying a region visits
+/// only the leaves that intersect it.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, point::Rect, quadtree::Quadtree};
+///
+/// let mut grid = Grid::new(64, 64, '~');
+/// grid[(40, 8)] = '#';
+///
+/// let tree = Quadtree::from_grid(&grid);
+///
+/// assert_eq!(tree.get((40, 8)), Some(&'#'));
+/// assert_eq!(tree.leaves().len(), 19);
+/// assert!(tree.query(Rect::new(0, 32, 64, 32)).iter().all(|(_, c)| **c == '~'));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Quadtree<T>
+where
+    T: Clone + PartialEq,
+{
+    width: usize,
+    height: usize,
+    root: Option<Node<T>>,
+}
+
+/// A node of a [`Quadtree`], covering a rectangle that is implied by its position in the tree.
+#[derive(Clone, Debug)]
+enum Node<T> {
+    /// Every cell in the rectangle is the same value.
+    Leaf(T),
+
+    /// The non-empty quadrants of the rectangle, in the order returned by [`quadrants`].
+    Branch(Vec<Node<T>>),
+}
+
+impl<T> Quadtree<T>
+where
+    T: Clone + PartialEq,
+{
+    /// Creates a quadtree from the cells of `grid`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, quadtree::Quadtree};
+    ///
+    /// let tree = Quadtree::from_grid(&Grid::new(100, 50, 0));
+    /// assert_eq!(tree.leaves().len(), 1);
+    /// ```
+    pub fn from_grid(grid: &Grid<T>) -> Self {
+        let (width, height) = grid.size();
+        let bounds = Rect::new(0, 0, width, height);
+        Self {
+            width,
+            height,
+            root: (!bounds.is_empty()).then(|| build(grid, bounds)),
+        }
+    }
+
+    /// Returns the width of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, quadtree::Quadtree};
+    ///
+    /// assert_eq!(Quadtree::from_grid(&Grid::new(3, 2, 0)).width(), 3);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, quadtree::Quadtree};
+    ///
+    /// assert_eq!(Quadtree::from_grid(&Grid::new(3, 2, 0)).height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the cell at `point`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, quadtree::Quadtree};
+    ///
+    /// let tree = Quadtree::from_grid(&Grid::with_width(2, vec![1, 2, 3, 4]));
+    ///
+    /// assert_eq!(tree.get((1, 1)), Some(&4));
+    /// assert_eq!(tree.get((2, 0)), None);
+    /// ```
+    pub fn get(&self, point: impl Point) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        let mut rect = self.bounds();
+        if !rect.contains(point) {
+            return None;
+        }
+        loop {
+            match node {
+                Node::Leaf(value) => return Some(value),
+                Node::Branch(children) => {
+                    let (child, quadrant) = children
+                        .iter()
+                        .zip(quadrants(rect))
+                        .find(|(_, quadrant)| quadrant.contains(point))
+                        .expect("Point is in a quadrant");
+                    node = child;
+                    rect = quadrant;
+                }
+            }
+        }
+    }
+
+    /// Returns each uniform region that intersects `rect`, clipped to `rect`, and its value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect, quadtree::Quadtree};
+    ///
+    /// let grid = Grid::with_width(4, vec![
+    ///     0, 0, 1, 1,
+    ///     0, 0, 1, 1,
+    ///     0, 0, 0, 0,
+    ///     0, 0, 0, 0,
+    /// ]);
+    /// let tree = Quadtree::from_grid(&grid);
+    ///
+    /// assert_eq!(tree.query(Rect::new(1, 0, 2, 1)), vec![
+    ///     (Rect::new(1, 0, 1, 1), &0),
+    ///     (Rect::new(2, 0, 1, 1), &1),
+    /// ]);
+    /// ```
+    pub fn query(&self, rect: Rect) -> Vec<(Rect, &T)> {
+        let mut regions = Vec::new();
+        if let Some(root) = &self.root {
+            query(root, self.bounds(), &rect, &mut regions);
+        }
+        regions
+    }
+
+    /// Returns every uniform region of the grid, and its value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect, quadtree::Quadtree};
+    ///
+    /// let tree = Quadtree::from_grid(&Grid::with_width(2, vec![1, 1, 1, 2]));
+    ///
+    /// assert_eq!(tree.leaves(), vec![
+    ///     (Rect::new(0, 0, 1, 1), &1),
+    ///     (Rect::new(1, 0, 1, 1), &1),
+    ///     (Rect::new(0, 1, 1, 1), &1),
+    ///     (Rect::new(1, 1, 1, 1), &2),
+    /// ]);
+    /// ```
+    pub fn leaves(&self) -> Vec<(Rect, &T)> {
+        self.query(self.bounds())
+    }
+
+    /// Returns a dense copy of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, quadtree::Quadtree};
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 1, 2, 1, 1, 2]);
+    /// assert_eq!(Quadtree::from_grid(&grid).to_grid().as_vec(), grid.as_vec());
+    /// ```
+    pub fn to_grid(&self) -> Grid<T> {
+        let leaves = self.leaves();
+        let Some((_, first)) = leaves.first() else {
+            return Grid::from(Vec::new());
+        };
+        let mut grid = Grid::new(self.width, self.height, (*first).clone());
+        for (rect, value) in leaves {
+            grid.fill_rect(rect, value.clone());
+        }
+        grid
+    }
+
+    /// Returns the rectangle covering the whole grid.
+    fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width, self.height)
+    }
+}
+
+/// Returns the node covering `rect` of `grid`, merging uniform quadrants into a single leaf.
+fn build<T>(grid: &Grid<T>, rect: Rect) -> Node<T>
+where
+    T: Clone + PartialEq,
+{
+    if rect.width() == 1 && rect.height() == 1 {
+        return Node::Leaf(grid[(rect.x(), rect.y())].clone());
+    }
+    let children: Vec<Node<T>> = quadrants(rect)
+        .map(|quadrant| build(grid, quadrant))
+        .collect();
+    let Node::Leaf(first) = &children[0] else {
+        return Node::Branch(children);
+    };
+    let uniform = children
+        .iter()
+        .all(|child| matches!(child, Node::Leaf(value) if value == first));
+    if uniform {
+        Node::Leaf(first.clone())
+    } else {
+        Node::Branch(children)
+    }
+}
+
+/// Appends the leaves of `node` (covering `bounds`) that intersect `rect` to `regions`.
+fn query<'a, T>(node: &'a Node<T>, bounds: Rect, rect: &Rect, regions: &mut Vec<(Rect, &'a T)>) {
+    let Some(clipped) = bounds.intersect(rect) else {
+        return;
+    };
+    match node {
+        Node::Leaf(value) => regions.push((clipped, value)),
+        Node::Branch(children) => {
+            for (child, quadrant) in children.iter().zip(quadrants(bounds)) {
+                query(child, quadrant, rect, regions);
+            }
+        }
+    }
+}
+
+/// Returns the non-empty quadrants of `rect`: top-left, top-right, bottom-left, and bottom-right.
+fn quadrants(rect: Rect) -> impl Iterator<Item = Rect> {
+    let (left, top) = (rect.width().div_ceil(2), rect.height().div_ceil(2));
+    let (right, bottom) = (rect.width() - left, rect.height() - top);
+    let (x, y) = (rect.x(), rect.y());
+    [
+        Rect::new(x, y, left, top),
+        Rect::new(x + left, y, right, top),
+        Rect::new(x, y + top, left, bottom),
+        Rect::new(x + left, y + top, right, bottom),
+    ]
+    .into_iter()
+    .filter(|quadrant| !quadrant.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quadtree_round_trip_odd_size() {
+        let grid = Grid::with_width(7, (0..35).map(|i| (i % 7) / 3 + i / 21).collect::<Vec<_>>());
+        let tree = Quadtree::from_grid(&grid);
+
+        assert_eq!(tree.to_grid().as_vec(), grid.as_vec());
+        for point in Rect::new(0, 0, 7, 5).iter_points() {
+            assert_eq!(tree.get(point), Some(&grid[point]));
+        }
+        let area: usize = tree
+            .leaves()
+            .iter()
+            .map(|(r, _)| r.width() * r.height())
+            .sum();
+        assert_eq!(area, 35);
+    }
+
+    #[test]
+    fn quadtree_empty_grid() {
+        let tree = Quadtree::from_grid(&Grid::<u8>::from(Vec::new()));
+
+        assert_eq!(tree.get((0, 0)), None);
+        assert!(tree.leaves().is_empty());
+        assert!(tree.to_grid().as_vec().is_empty());
+    }
+}
@@ -0,0 +1,584 @@
+//! Filtering numeric grids with textual queries, i.e. `value > 3 && x < 10`.
+//!
+//! Requires the `query` feature.
+//!
+//! See [`Query`] for the syntax, and [`Grid::query`] for details.
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::grid::Grid;
+
+/// A parsed query that is evaluated against each cell of a numeric grid.
+///
+/// Queries are parsed from text using [`FromStr`], and are made up of:
+///
+/// - Variables: `value` (the cell, as an [`f64`]; see [`QueryValue`]), `x`, and `y`.
+/// - Numbers: i.e. `3`, `-1`, and `0.5`.
+/// - Arithmetic: `+`, `-`, `*`, `/`, and `%`.
+/// - Comparisons: `<`, `<=`, `>`, `>=`, `==`, and `!=`.
+/// - Logic: `&&`, `||`, and `!`.
+/// - Parentheses for grouping, nested at most 64 deep (counting `!` and unary `-`).
+///
+/// A query must evaluate to a condition (i.e. `value > 3`), not a number (i.e. `value + 3`).
+///
+/// # Examples
+///
+/// ```
+/// use grud::query::Query;
+///
+/// let query: Query = "value > 3 && x < 10".parse().unwrap();
+///
+/// assert!(query.matches(4.0, 0, 0));
+/// assert!(!query.matches(4.0, 10, 0));
+/// assert!(!query.matches(3.0, 0, 0));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    expr: Expr,
+}
+
+impl Query {
+    /// Returns whether the query matches a cell with `value` at (`x`, `y`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::query::Query;
+    ///
+    /// let checkerboard: Query = "(x + y) % 2 == 0".parse().unwrap();
+    ///
+    /// assert!(checkerboard.matches(0.0, 1, 1));
+    /// assert!(!checkerboard.matches(0.0, 1, 0));
+    /// ```
+    pub fn matches(&self, value: f64, x: usize, y: usize) -> bool {
+        self.expr.eval(value, x, y) != 0.0
+    }
+}
+
+impl FromStr for Query {
+    type Err = ParseQueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            next: 0,
+            end: s.len(),
+            depth: 0,
+        };
+        let (expr, kind) = parser.or()?;
+        if let Some(&(_, position)) = parser.tokens.get(parser.next) {
+            return Err(ParseQueryError::new(position, "Unexpected token"));
+        }
+        if kind != Kind::Condition {
+            return Err(ParseQueryError::new(0, "Expected a condition"));
+        }
+        Ok(Self { expr })
+    }
+}
+
+/// Error returned when parsing an invalid [`Query`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseQueryError {
+    position: usize,
+    message: &'static str,
+}
+
+impl ParseQueryError {
+    fn new(position: usize, message: &'static str) -> Self {
+        Self { position, message }
+    }
+
+    /// Returns the byte offset in the query where the error was found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::query::Query;
+    ///
+    /// let error = "value > > 3".parse::<Query>().unwrap_err();
+    /// assert_eq!(error.position(), 8);
+    /// ```
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for ParseQueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl Error for ParseQueryError {}
+
+/// A numeric cell type that can be queried, converted to an [`f64`] as `value`.
+///
+/// Implemented for all primitive integer and floating point types; 64-bit and wider integers
+/// are converted lossily, as with `as`.
+///
+/// # Examples
+///
+/// ```
+/// use grud::query::QueryValue;
+///
+/// assert_eq!(3_u8.to_f64(), 3.0);
+/// assert_eq!((-2_i64).to_f64(), -2.0);
+/// ```
+pub trait QueryValue {
+    /// Returns the value as an [`f64`].
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_query_value {
+    ($($t:ty),*) => {
+        $(
+            impl QueryValue for $t {
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_query_value!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl<T> Grid<T>
+where
+    T: Clone + QueryValue,
+{
+    /// Returns a mask of which cells match `query`.
+    ///
+    /// Requires the `query` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 5, 9, 2, 6, 3]);
+    /// let mask = grid.query(&"value > 4 && x > 0".parse().unwrap());
+    ///
+    /// assert_eq!(mask.to_matrix(), vec![vec![false, true, true], vec![false, true, false]]);
+    /// ```
+    pub fn query(&self, query: &Query) -> Grid<bool> {
+        let width = self.width();
+        let data = self
+            .as_vec()
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| query.matches(cell.to_f64(), i % width, i / width))
+            .collect::<Vec<_>>();
+        if data.is_empty() {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(width, data)
+        }
+    }
+}
+
+/// Whether an expression evaluates to a number or a condition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Number,
+    Condition,
+}
+
+/// A parsed expression, where conditions evaluate to `1.0` (true) or `0.0` (false).
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Number(f64),
+    Value,
+    X,
+    Y,
+    Not(Box<Expr>),
+    Negate(Box<Expr>),
+    /// A left-associative chain of operators, stored flat so long chains do not nest.
+    Chain(Box<Expr>, Vec<(Op, Expr)>),
+}
+
+impl Expr {
+    fn eval(&self, value: f64, x: usize, y: usize) -> f64 {
+        match self {
+            Self::Number(n) => *n,
+            Self::Value => value,
+            Self::X => x as f64,
+            Self::Y => y as f64,
+            Self::Not(e) => f64::from(e.eval(value, x, y) == 0.0),
+            Self::Negate(e) => -e.eval(value, x, y),
+            Self::Chain(first, rest) => {
+                let mut a = first.eval(value, x, y);
+                for (op, b) in rest {
+                    // Short-circuit, as `b` can be skipped.
+                    a = match op {
+                        Op::And if a == 0.0 => 0.0,
+                        Op::Or if a != 0.0 => 1.0,
+                        _ => op.apply(a, b.eval(value, x, y)),
+                    };
+                }
+                a
+            }
+        }
+    }
+}
+
+/// A binary operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+    And,
+    Or,
+}
+
+impl Op {
+    fn apply(self, a: f64, b: f64) -> f64 {
+        match self {
+            Self::Add => a + b,
+            Self::Sub => a - b,
+            Self::Mul => a * b,
+            Self::Div => a / b,
+            Self::Rem => a % b,
+            Self::Lt => f64::from(a < b),
+            Self::Le => f64::from(a <= b),
+            Self::Gt => f64::from(a > b),
+            Self::Ge => f64::from(a >= b),
+            Self::Eq => f64::from(a == b),
+            Self::Ne => f64::from(a != b),
+            Self::And | Self::Or => f64::from(b != 0.0),
+        }
+    }
+}
+
+/// A token, i.e. a number, identifier, or symbol.
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Symbol(&'static str),
+}
+
+/// Symbols, with longer symbols first so they are matched before their prefixes.
+const SYMBOLS: [&str; 16] = [
+    "&&", "||", "<=", ">=", "==", "!=", "<", ">", "!", "+", "-", "*", "/", "%", "(", ")",
+];
+
+/// Splits `s` into tokens, each with its byte offset.
+fn tokenize(s: &str) -> Result<Vec<(Token, usize)>, ParseQueryError> {
+    let mut tokens = Vec::new();
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        let position = s.len() - rest.len();
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c.is_ascii_digit() || c == '.' {
+            let len = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(rest.len());
+            let number = rest[..len]
+                .parse()
+                .map_err(|_| ParseQueryError::new(position, "Invalid number"))?;
+            tokens.push((Token::Number(number), position));
+            rest = &rest[len..];
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            tokens.push((Token::Ident(rest[..len].to_string()), position));
+            rest = &rest[len..];
+        } else {
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| rest.starts_with(**symbol))
+                .ok_or(ParseQueryError::new(position, "Unexpected character"))?;
+            tokens.push((Token::Symbol(symbol), position));
+            rest = &rest[symbol.len()..];
+        }
+    }
+    Ok(tokens)
+}
+
+/// The maximum nesting of parentheses and unary operators, so that parsing and evaluating
+/// untrusted queries cannot overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+/// A recursive descent parser, with one method per level of precedence (lowest first).
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+    end: usize,
+    depth: usize,
+}
+
+type Parsed = Result<(Expr, Kind), ParseQueryError>;
+
+impl Parser {
+    /// Returns the byte offset of the next token, or the end of the query.
+    fn position(&self) -> usize {
+        self.tokens.get(self.next).map_or(self.end, |(_, p)| *p)
+    }
+
+    /// Enters one more level of nesting at `position`, failing if the query is nested too deeply.
+    fn enter(&mut self, position: usize) -> Result<(), ParseQueryError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(ParseQueryError::new(position, "Query is nested too deeply"));
+        }
+        Ok(())
+    }
+
+    /// Consumes the next token if it is one of `symbols`, returning it.
+    fn eat(&mut self, symbols: &[&'static str]) -> Option<&'static str> {
+        match self.tokens.get(self.next) {
+            Some((Token::Symbol(s), _)) if symbols.contains(s) => {
+                self.next += 1;
+                Some(s)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses an operand of `kind` using `parse`, failing if it is the wrong kind.
+    fn operand(
+        &mut self,
+        kind: Kind,
+        parse: fn(&mut Self) -> Parsed,
+    ) -> Result<Expr, ParseQueryError> {
+        let position = self.position();
+        let (expr, found) = parse(self)?;
+        if found != kind {
+            return Err(ParseQueryError::new(
+                position,
+                match kind {
+                    Kind::Number => "Expected a number",
+                    Kind::Condition => "Expected a condition",
+                },
+            ));
+        }
+        Ok(expr)
+    }
+
+    /// Parses a left-associative chain of `operand`s joined by `ops`.
+    fn chain(
+        &mut self,
+        ops: &[(&'static str, Op)],
+        operands: Kind,
+        result: Kind,
+        parse: fn(&mut Self) -> Parsed,
+    ) -> Parsed {
+        let (first, mut kind) = parse(self)?;
+        let symbols: Vec<_> = ops.iter().map(|(s, _)| *s).collect();
+        let mut rest = Vec::new();
+        while let Some(symbol) = self.eat(&symbols) {
+            if kind != operands {
+                return Err(ParseQueryError::new(
+                    self.tokens[self.next - 1].1,
+                    "Operator cannot be applied here",
+                ));
+            }
+            let op = ops.iter().find(|(s, _)| *s == symbol).unwrap().1;
+            rest.push((op, self.operand(operands, parse)?));
+            kind = result;
+        }
+        if rest.is_empty() {
+            Ok((first, kind))
+        } else {
+            Ok((Expr::Chain(Box::new(first), rest), kind))
+        }
+    }
+
+    fn or(&mut self) -> Parsed {
+        self.chain(
+            &[("||", Op::Or)],
+            Kind::Condition,
+            Kind::Condition,
+            Self::and,
+        )
+    }
+
+    fn and(&mut self) -> Parsed {
+        self.chain(
+            &[("&&", Op::And)],
+            Kind::Condition,
+            Kind::Condition,
+            Self::comparison,
+        )
+    }
+
+    fn comparison(&mut self) -> Parsed {
+        let (lhs, kind) = self.sum()?;
+        let ops = [
+            ("<=", Op::Le),
+            (">=", Op::Ge),
+            ("==", Op::Eq),
+            ("!=", Op::Ne),
+            ("<", Op::Lt),
+            (">", Op::Gt),
+        ];
+        let symbols: Vec<_> = ops.iter().map(|(s, _)| *s).collect();
+        let position = self.position();
+        let Some(symbol) = self.eat(&symbols) else {
+            return Ok((lhs, kind));
+        };
+        if kind != Kind::Number {
+            return Err(ParseQueryError::new(
+                position,
+                "Operator cannot be applied here",
+            ));
+        }
+        let op = ops.iter().find(|(s, _)| *s == symbol).unwrap().1;
+        let rhs = self.operand(Kind::Number, Self::sum)?;
+        Ok((Expr::Chain(Box::new(lhs), vec![(op, rhs)]), Kind::Condition))
+    }
+
+    fn sum(&mut self) -> Parsed {
+        let ops = [("+", Op::Add), ("-", Op::Sub)];
+        self.chain(&ops, Kind::Number, Kind::Number, Self::product)
+    }
+
+    fn product(&mut self) -> Parsed {
+        let ops = [("*", Op::Mul), ("/", Op::Div), ("%", Op::Rem)];
+        self.chain(&ops, Kind::Number, Kind::Number, Self::unary)
+    }
+
+    fn unary(&mut self) -> Parsed {
+        let position = self.position();
+        if self.eat(&["!"]).is_some() {
+            self.enter(position)?;
+            let expr = self.operand(Kind::Condition, Self::unary)?;
+            self.depth -= 1;
+            return Ok((Expr::Not(Box::new(expr)), Kind::Condition));
+        }
+        if self.eat(&["-"]).is_some() {
+            self.enter(position)?;
+            let expr = self.operand(Kind::Number, Self::unary)?;
+            self.depth -= 1;
+            return Ok((Expr::Negate(Box::new(expr)), Kind::Number));
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Parsed {
+        let position = self.position();
+        let Some((token, _)) = self.tokens.get(self.next).cloned() else {
+            return Err(ParseQueryError::new(position, "Unexpected end of query"));
+        };
+        self.next += 1;
+        match token {
+            Token::Number(n) => Ok((Expr::Number(n), Kind::Number)),
+            Token::Ident(name) => match name.as_str() {
+                "value" => Ok((Expr::Value, Kind::Number)),
+                "x" => Ok((Expr::X, Kind::Number)),
+                "y" => Ok((Expr::Y, Kind::Number)),
+                _ => Err(ParseQueryError::new(position, "Unknown variable")),
+            },
+            Token::Symbol("(") => {
+                self.enter(position)?;
+                let inner = self.or()?;
+                if self.eat(&[")"]).is_none() {
+                    return Err(ParseQueryError::new(self.position(), "Expected `)`"));
+                }
+                self.depth -= 1;
+                Ok(inner)
+            }
+            Token::Symbol(_) => Err(ParseQueryError::new(position, "Unexpected token")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(query: &str) -> ParseQueryError {
+        query.parse::<Query>().unwrap_err()
+    }
+
+    #[test]
+    fn query_wide_integers() {
+        let query: Query = "value > 4".parse().unwrap();
+
+        let grid = Grid::with_width(2, vec![1_i64, 5, -9, 6]);
+        assert_eq!(grid.query(&query).into_vec(), vec![false, true, false, true]);
+
+        let grid = Grid::with_width(2, vec![1_usize, 5, 9, 2]);
+        assert_eq!(grid.query(&query).into_vec(), vec![false, true, true, false]);
+    }
+
+    #[test]
+    fn query_precedence() {
+        let query: Query = "value == 1 + 2 * 3 || !(x < 1) && y >= -1".parse().unwrap();
+
+        assert!(query.matches(7.0, 0, 0));
+        assert!(query.matches(0.0, 1, 0));
+        assert!(!query.matches(0.0, 0, 0));
+    }
+
+    #[test]
+    fn query_must_be_a_condition() {
+        assert_eq!(
+            error("value + 1"),
+            ParseQueryError::new(0, "Expected a condition")
+        );
+    }
+
+    #[test]
+    fn query_type_errors() {
+        assert_eq!(error("x && y > 1").position(), 2);
+        assert_eq!(error("x < 1 + (y > 1)").position(), 8);
+    }
+
+    #[test]
+    fn query_syntax_errors() {
+        assert_eq!(
+            error("value = 1").to_string(),
+            "Unexpected character at position 6"
+        );
+        assert_eq!(error("(x > 1").to_string(), "Expected `)` at position 6");
+        assert_eq!(error("z > 1").to_string(), "Unknown variable at position 0");
+        assert_eq!(
+            error("x > 1 2").to_string(),
+            "Unexpected token at position 6"
+        );
+    }
+
+    #[test]
+    fn query_nested_too_deeply() {
+        let parens = format!("{}x > 1{}", "(".repeat(200_000), ")".repeat(200_000));
+        let negations = format!("{}x > 1", "-".repeat(200_000));
+
+        for query in [parens, negations] {
+            assert_eq!(error(&query).message, "Query is nested too deeply");
+        }
+        let nested = format!("{}x > 1{}", "(".repeat(60), ")".repeat(60));
+        assert!(nested.parse::<Query>().is_ok());
+    }
+
+    #[test]
+    fn query_long_chain() {
+        let sum: Query = format!("x{} > 1", " + x".repeat(200_000)).parse().unwrap();
+
+        assert!(sum.matches(0.0, 1, 0));
+        assert!(!sum.matches(0.0, 0, 0));
+    }
+
+    #[test]
+    fn query_empty_grid() {
+        let grid: Grid<u8> = Grid::from(Vec::new());
+
+        assert!(grid.query(&"x > 1".parse().unwrap()).as_vec().is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+//! Random grids using [`rand`], i.e. for procedural generation and test fixtures.
+//!
+//! Requires the `rand` feature.
+
+use ::rand::{distr::Distribution, seq::SliceRandom, Rng};
+
+use crate::grid::Grid;
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Creates a new grid of the specified `width` and `height`, where each cell is sampled from
+    /// `distribution` in row-major order.
+    ///
+    /// Requires the `rand` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    /// use rand::{distr::Uniform, rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(42);
+    /// let grid = Grid::random(4, 3, &mut rng, Uniform::new(0, 10).unwrap());
+    ///
+    /// assert_eq!(grid.width(), 4);
+    /// assert!(grid.as_vec().iter().all(|cell| *cell < 10));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width * height` overflows a [`usize`].
+    pub fn random<R, D>(width: usize, height: usize, rng: &mut R, distribution: D) -> Self
+    where
+        R: Rng + ?Sized,
+        D: Distribution<T>,
+    {
+        let area = width
+            .checked_mul(height)
+            .unwrap_or_else(|| panic!("Area of {width}x{height} grid overflows usize"));
+        let data: Vec<T> = distribution.sample_iter(rng).take(area).collect();
+        if data.is_empty() {
+            return Grid::from(Vec::new());
+        }
+        Grid::with_width(width, data)
+    }
+
+    /// Replaces every cell with a value sampled from `distribution`, in row-major order.
+    ///
+    /// Requires the `rand` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    /// use rand::{distr::Bernoulli, rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    /// let mut grid = Grid::new(16, 16, false);
+    /// grid.fill_random(&mut rng, Bernoulli::new(0.5).unwrap());
+    ///
+    /// assert!(grid.count(|alive| *alive) > 0);
+    /// ```
+    pub fn fill_random<R, D>(&mut self, rng: &mut R, distribution: D)
+    where
+        R: Rng + ?Sized,
+        D: Distribution<T>,
+    {
+        for (cell, value) in self
+            .as_mut_slice()
+            .iter_mut()
+            .zip(distribution.sample_iter(rng))
+        {
+            *cell = value;
+        }
+    }
+
+    /// Randomly permutes the cells of the grid, so each arrangement is equally likely.
+    ///
+    /// Requires the `rand` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(1);
+    /// let mut grid = Grid::with_width(3, (0..9).collect());
+    /// grid.shuffle(&mut rng);
+    ///
+    /// let mut cells = grid.as_vec().clone();
+    /// cells.sort();
+    /// assert_eq!(cells, (0..9).collect::<Vec<_>>());
+    /// ```
+    pub fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        self.as_mut_slice().shuffle(rng);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rand::{distr::StandardUniform, rngs::SmallRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn random_is_deterministic_for_seed() {
+        let a: Grid<u32> = Grid::random(5, 5, &mut SmallRng::seed_from_u64(3), StandardUniform);
+        let b: Grid<u32> = Grid::random(5, 5, &mut SmallRng::seed_from_u64(3), StandardUniform);
+
+        assert_eq!(a.as_vec(), b.as_vec());
+    }
+
+    #[test]
+    fn random_empty() {
+        let grid: Grid<u8> = Grid::random(0, 5, &mut SmallRng::seed_from_u64(0), StandardUniform);
+        assert!(grid.as_vec().is_empty());
+    }
+}
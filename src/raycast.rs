@@ -0,0 +1,193 @@
+//! Casting rays through a grid with sub-cell precision, i.e. for raycasting renderers.
+//!
+//! See [`Grid::raycast`] for details.
+
+use crate::{grid::Grid, point::Direction};
+
+/// Where a ray cast by [`Grid::raycast`] hit a cell.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// The cell that was hit.
+    pub cell: (usize, usize),
+
+    /// The exact point where the ray entered the cell, where cell `(x, y)` covers the area from
+    /// `(x, y)` to `(x + 1, y + 1)`.
+    pub point: (f64, f64),
+
+    /// The side of the cell the ray entered through, or `None` if the ray started inside the cell.
+    ///
+    /// This is always [`Direction::North`], [`Direction::East`], [`Direction::South`], or
+    /// [`Direction::West`].
+    pub normal: Option<Direction>,
+
+    /// The distance travelled by the ray from its origin to `point`.
+    pub distance: f64,
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Casts a ray from `origin` in direction `dir`, returning the first cell for which `hit`
+    /// returns `true`, or `None` if no cell is hit within `max_dist`.
+    ///
+    /// Cell `(x, y)` covers the area from `(x, y)` to `(x + 1, y + 1)`, so the center of cell
+    /// `(0, 0)` is `(0.5, 0.5)`. Every cell the ray passes through is visited, using a [DDA], and
+    /// the ray may start outside of the grid.
+    ///
+    /// [DDA]: https://en.wikipedia.org/wiki/Digital_differential_analyzer_(graphics_algorithm)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Direction};
+    ///
+    /// let grid = Grid::from(vec![
+    ///     "...#".chars().collect(),
+    ///     "....".chars().collect(),
+    /// ]);
+    ///
+    /// let hit = grid.raycast((0.5, 0.5), (1.0, 0.0), 10.0, |c| *c == '#').unwrap();
+    ///
+    /// assert_eq!(hit.cell, (3, 0));
+    /// assert_eq!(hit.point, (3.0, 0.5));
+    /// assert_eq!(hit.normal, Some(Direction::West));
+    /// assert_eq!(hit.distance, 2.5);
+    ///
+    /// assert_eq!(grid.raycast((0.5, 0.5), (1.0, 0.0), 2.0, |c| *c == '#'), None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `dir` is zero or not finite, or `origin` is not finite.
+    pub fn raycast(
+        &self,
+        origin: (f64, f64),
+        dir: (f64, f64),
+        max_dist: f64,
+        mut hit: impl FnMut(&T) -> bool,
+    ) -> Option<RayHit> {
+        let length = dir.0.hypot(dir.1);
+        assert!(
+            length > 0.0 && length.is_finite(),
+            "Ray direction must be non-zero and finite"
+        );
+        assert!(
+            origin.0.is_finite() && origin.1.is_finite(),
+            "Ray origin must be finite"
+        );
+        let (dx, dy) = (dir.0 / length, dir.1 / length);
+        let (width, height) = self.size();
+        let (width, height) = (width as f64, height as f64);
+
+        let mut x = origin.0.floor();
+        let mut y = origin.1.floor();
+        let (step_x, mut next_x, delta_x) = axis(origin.0, x, dx);
+        let (step_y, mut next_y, delta_y) = axis(origin.1, y, dy);
+        let mut distance = 0.0;
+        let mut normal = None;
+
+        while distance <= max_dist {
+            let inside_x = x >= 0.0 && x < width;
+            let inside_y = y >= 0.0 && y < height;
+            if inside_x && inside_y {
+                let cell = (x as usize, y as usize);
+                if hit(&self[cell]) {
+                    return Some(RayHit {
+                        cell,
+                        point: (origin.0 + dx * distance, origin.1 + dy * distance),
+                        normal,
+                        distance,
+                    });
+                }
+            }
+            if (!inside_x && !approaching(x, step_x)) || (!inside_y && !approaching(y, step_y)) {
+                return None;
+            }
+            if next_x < next_y {
+                x += step_x;
+                distance = next_x;
+                next_x += delta_x;
+                normal = Some(if step_x > 0.0 {
+                    Direction::West
+                } else {
+                    Direction::East
+                });
+            } else {
+                y += step_y;
+                distance = next_y;
+                next_y += delta_y;
+                normal = Some(if step_y > 0.0 {
+                    Direction::North
+                } else {
+                    Direction::South
+                });
+            }
+        }
+        None
+    }
+}
+
+/// Returns whether a ray outside of the grid along one axis, at cell `cell`, is moving towards it.
+fn approaching(cell: f64, step: f64) -> bool {
+    if cell < 0.0 {
+        step > 0.0
+    } else {
+        step < 0.0
+    }
+}
+
+/// Returns the step direction along one axis, the distance to the first cell boundary, and the
+/// distance between cell boundaries, for a ray starting at `origin` in cell `cell` with direction
+/// `dir`.
+fn axis(origin: f64, cell: f64, dir: f64) -> (f64, f64, f64) {
+    if dir > 0.0 {
+        (1.0, (cell + 1.0 - origin) / dir, 1.0 / dir)
+    } else if dir < 0.0 {
+        (-1.0, (origin - cell) / -dir, -1.0 / dir)
+    } else {
+        (0.0, f64::INFINITY, f64::INFINITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raycast_diagonal_from_outside() {
+        let mut grid = Grid::new(4, 4, false);
+        grid[(2, 2)] = true;
+
+        let hit = grid
+            .raycast((-1.0, -1.0), (1.0, 1.0), f64::INFINITY, |c| *c)
+            .unwrap();
+
+        assert_eq!(hit.cell, (2, 2));
+        assert!((hit.distance - 3.0 * 2.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn raycast_starts_inside_hit() {
+        let grid = Grid::new(2, 2, true);
+        let hit = grid.raycast((1.5, 0.25), (0.0, -1.0), 0.0, |c| *c).unwrap();
+
+        assert_eq!(hit.cell, (1, 0));
+        assert_eq!(hit.normal, None);
+        assert_eq!(hit.distance, 0.0);
+    }
+
+    #[test]
+    fn raycast_leaves_grid() {
+        let grid = Grid::new(2, 2, false);
+
+        assert_eq!(
+            grid.raycast((0.5, 0.5), (-1.0, 0.3), f64::INFINITY, |c| *c),
+            None
+        );
+        assert_eq!(
+            grid.raycast((5.5, 0.5), (0.0, 1.0), f64::INFINITY, |c| *c),
+            None
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Reducing grids along rows and columns, i.e. for spreadsheet totals or projection profiles.
+//!
+//! See [`Grid::fold_rows`] and [`Grid::fold_columns`] for details.
+
+use std::ops::Add;
+
+use crate::grid::Grid;
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns the result of folding each row, from left to right, starting with `init`.
+    ///
+    /// # Examples
+    ///
+    /// Finding the maximum of each row:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![3, 1, 2, 4, 6, 5]);
+    ///
+    /// assert_eq!(grid.fold_rows(i32::MIN, |max, cell| max.max(*cell)), vec![3, 6]);
+    /// ```
+    pub fn fold_rows<A>(&self, init: A, mut f: impl FnMut(A, &T) -> A) -> Vec<A>
+    where
+        A: Clone,
+    {
+        let (width, _) = self.size();
+        if width == 0 {
+            return Vec::new();
+        }
+        self.as_vec()
+            .chunks(width)
+            .map(|row| row.iter().fold(init.clone(), &mut f))
+            .collect()
+    }
+
+    /// Returns the result of folding each column, from top to bottom, starting with `init`.
+    ///
+    /// Cells are visited in row-major order (the order they are stored in), so every column is
+    /// folded in a single pass over the grid.
+    ///
+    /// # Examples
+    ///
+    /// Counting the filled cells in each column:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![true, false, true, true, false, false]);
+    ///
+    /// assert_eq!(grid.fold_columns(0, |n, cell| n + usize::from(*cell)), vec![2, 0, 1]);
+    /// ```
+    pub fn fold_columns<A>(&self, init: A, mut f: impl FnMut(A, &T) -> A) -> Vec<A>
+    where
+        A: Clone,
+    {
+        let (width, _) = self.size();
+        if width == 0 {
+            return Vec::new();
+        }
+        let mut columns = vec![Some(init); width];
+        for row in self.as_vec().chunks(width) {
+            for (column, cell) in columns.iter_mut().zip(row) {
+                *column = column.take().map(|acc| f(acc, cell));
+            }
+        }
+        columns.into_iter().flatten().collect()
+    }
+
+    /// Returns the sum of each row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 2, 3, 4, 5, 6]);
+    ///
+    /// assert_eq!(grid.row_sums(), vec![6, 15]);
+    /// ```
+    pub fn row_sums(&self) -> Vec<T>
+    where
+        T: Default + Add<Output = T>,
+    {
+        self.fold_rows(T::default(), |sum, cell| sum + cell.clone())
+    }
+
+    /// Returns the sum of each column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    ///
+    /// assert_eq!(grid.column_sums(), vec![5.0, 7.0, 9.0]);
+    /// ```
+    pub fn column_sums(&self) -> Vec<T>
+    where
+        T: Default + Add<Output = T>,
+    {
+        self.fold_columns(T::default(), |sum, cell| sum + cell.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fold_columns_in_order() {
+        let grid = Grid::with_width(2, vec!['a', 'b', 'c', 'd', 'e', 'f']);
+        let columns = grid.fold_columns(String::new(), |mut s, c| {
+            s.push(*c);
+            s
+        });
+
+        assert_eq!(columns, vec!["ace", "bdf"]);
+    }
+
+    #[test]
+    fn fold_empty_grid() {
+        let grid: Grid<i32> = Grid::from(Vec::new());
+
+        assert!(grid.row_sums().is_empty());
+        assert!(grid.column_sums().is_empty());
+    }
+}
@@ -0,0 +1,316 @@
+//! Named rectangular regions of a grid, i.e. for level annotations like spawn zones.
+//!
+//! See [`RegionMap`] for details.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::point::{Point, Rect};
+
+/// A set of named [`Rect`]s, i.e. annotations that travel alongside a grid.
+///
+/// Regions may overlap, and are iterated in order of their names.
+///
+/// A region map can be converted to and from text, with one region per line formatted as
+/// `x y width height name`:
+///
+/// ```
+/// use grud::{point::Rect, region::RegionMap};
+///
+/// let mut regions = RegionMap::new();
+/// regions.insert("spawn zone", Rect::new(0, 0, 4, 4));
+/// regions.insert("shop", Rect::new(10, 2, 3, 3));
+///
+/// let text = regions.to_string();
+/// assert_eq!(text, "10 2 3 3 shop\n0 0 4 4 spawn zone\n");
+///
+/// let parsed: RegionMap = text.parse().unwrap();
+/// assert_eq!(parsed, regions);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RegionMap {
+    regions: BTreeMap<String, Rect>,
+}
+
+impl RegionMap {
+    /// Creates an empty region map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::region::RegionMap;
+    ///
+    /// assert!(RegionMap::new().is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of regions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{point::Rect, region::RegionMap};
+    ///
+    /// let mut regions = RegionMap::new();
+    /// regions.insert("a", Rect::new(0, 0, 1, 1));
+    /// assert_eq!(regions.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Returns whether there are no regions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::region::RegionMap;
+    ///
+    /// assert!(RegionMap::new().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Sets the region named `name`, returning the previous region with that name, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{point::Rect, region::RegionMap};
+    ///
+    /// let mut regions = RegionMap::new();
+    /// assert_eq!(regions.insert("a", Rect::new(0, 0, 1, 1)), None);
+    /// assert_eq!(regions.insert("a", Rect::new(1, 1, 1, 1)), Some(Rect::new(0, 0, 1, 1)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `name` is empty, contains a line break, or starts or ends with whitespace.
+    pub fn insert(&mut self, name: impl Into<String>, rect: Rect) -> Option<Rect> {
+        let name = name.into();
+        assert!(
+            !name.is_empty() && !name.contains(['\n', '\r']) && name.trim() == name,
+            "Invalid region name {name:?}"
+        );
+        self.regions.insert(name, rect)
+    }
+
+    /// Returns the region named `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{point::Rect, region::RegionMap};
+    ///
+    /// let mut regions = RegionMap::new();
+    /// regions.insert("a", Rect::new(0, 0, 1, 1));
+    ///
+    /// assert_eq!(regions.get("a"), Some(Rect::new(0, 0, 1, 1)));
+    /// assert_eq!(regions.get("b"), None);
+    /// ```
+    pub fn get(&self, name: &str) -> Option<Rect> {
+        self.regions.get(name).copied()
+    }
+
+    /// Removes and returns the region named `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{point::Rect, region::RegionMap};
+    ///
+    /// let mut regions = RegionMap::new();
+    /// regions.insert("a", Rect::new(0, 0, 1, 1));
+    ///
+    /// assert_eq!(regions.remove("a"), Some(Rect::new(0, 0, 1, 1)));
+    /// assert!(regions.is_empty());
+    /// ```
+    pub fn remove(&mut self, name: &str) -> Option<Rect> {
+        self.regions.remove(name)
+    }
+
+    /// Returns an iterator over every region and its name, in order of name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{point::Rect, region::RegionMap};
+    ///
+    /// let mut regions = RegionMap::new();
+    /// regions.insert("b", Rect::new(1, 1, 1, 1));
+    /// regions.insert("a", Rect::new(0, 0, 1, 1));
+    ///
+    /// let names: Vec<_> = regions.iter().map(|(name, _)| name).collect();
+    /// assert_eq!(names, vec!["a", "b"]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Rect)> {
+        self.regions
+            .iter()
+            .map(|(name, rect)| (name.as_str(), *rect))
+    }
+
+    /// Returns an iterator over every region that contains `point`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{point::Rect, region::RegionMap};
+    ///
+    /// let mut regions = RegionMap::new();
+    /// regions.insert("town", Rect::new(0, 0, 10, 10));
+    /// regions.insert("shop", Rect::new(2, 2, 3, 3));
+    ///
+    /// let names: Vec<_> = regions.at((3, 3)).map(|(name, _)| name).collect();
+    /// assert_eq!(names, vec!["shop", "town"]);
+    /// ```
+    pub fn at(&self, point: impl Point) -> impl Iterator<Item = (&str, Rect)> {
+        self.iter().filter(move |(_, rect)| rect.contains(point))
+    }
+
+    /// Returns an iterator over every region that overlaps `rect`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{point::Rect, region::RegionMap};
+    ///
+    /// let mut regions = RegionMap::new();
+    /// regions.insert("left", Rect::new(0, 0, 5, 10));
+    /// regions.insert("right", Rect::new(5, 0, 5, 10));
+    ///
+    /// let names: Vec<_> = regions
+    ///     .overlapping(Rect::new(4, 4, 2, 2))
+    ///     .map(|(name, _)| name)
+    ///     .collect();
+    /// assert_eq!(names, vec!["left", "right"]);
+    /// ```
+    pub fn overlapping(&self, rect: Rect) -> impl Iterator<Item = (&str, Rect)> {
+        self.iter()
+            .filter(move |(_, other)| other.intersects(&rect))
+    }
+}
+
+impl Display for RegionMap {
+    /// Formats the regions as text, with one region per line formatted as `x y width height name`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (name, rect) in self.iter() {
+            writeln!(
+                f,
+                "{} {} {} {} {name}",
+                rect.x(),
+                rect.y(),
+                rect.width(),
+                rect.height()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RegionMap {
+    type Err = ParseRegionMapError;
+
+    /// Parses regions from text, with one region per line formatted as `x y width height name`.
+    ///
+    /// Blank lines are ignored, and if a name appears more than once, the last region wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{point::Rect, region::RegionMap};
+    ///
+    /// let regions: RegionMap = "1 2 3 4 exit\n".parse().unwrap();
+    /// assert_eq!(regions.get("exit"), Some(Rect::new(1, 2, 3, 4)));
+    ///
+    /// let error = "1 2 three 4 exit".parse::<RegionMap>().unwrap_err();
+    /// assert_eq!(error.line(), 1);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut regions = Self::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = ParseRegionMapError { line: i + 1 };
+            let mut parts = line.splitn(5, ' ');
+            let mut number = || -> Result<usize, ParseRegionMapError> {
+                parts.next().and_then(|n| n.parse().ok()).ok_or(error)
+            };
+            let rect = Rect::new(number()?, number()?, number()?, number()?);
+            let name = parts.next().map(str::trim).ok_or(error)?;
+            regions.insert(name, rect);
+        }
+        Ok(regions)
+    }
+}
+
+/// Error returned when parsing a [`RegionMap`] from invalid text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseRegionMapError {
+    line: usize,
+}
+
+impl ParseRegionMapError {
+    /// Returns the line (starting at `1`) that could not be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::region::RegionMap;
+    ///
+    /// let error = "0 0 1 1 a\n0 0 1".parse::<RegionMap>().unwrap_err();
+    /// assert_eq!(error.line(), 2);
+    /// ```
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl Display for ParseRegionMapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid region on line {}", self.line)
+    }
+}
+
+impl Error for ParseRegionMapError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn region_map_parse_ignores_blank_lines() {
+        let regions: RegionMap = "\n0 0 1 1 a\n\n".parse().unwrap();
+
+        assert_eq!(regions.len(), 1);
+    }
+
+    #[test]
+    fn region_map_parse_missing_name() {
+        let error = "0 0 1 1".parse::<RegionMap>().unwrap_err();
+
+        assert_eq!(error, ParseRegionMapError { line: 1 });
+    }
+
+    #[test]
+    fn region_map_round_trip_name_with_spaces() {
+        let mut regions = RegionMap::new();
+        regions.insert("a  b", Rect::new(1, 2, 3, 4));
+
+        assert_eq!(regions.to_string().parse::<RegionMap>(), Ok(regions));
+    }
+
+    #[test]
+    #[should_panic]
+    fn region_map_insert_name_with_newline() {
+        RegionMap::new().insert("a\nb", Rect::default());
+    }
+}
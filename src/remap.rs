@@ -0,0 +1,438 @@
+//! Structural edits that report how coordinates moved, i.e. for keeping entity positions in sync.
+//!
+//! See [`Grid::edit_structure`] for details.
+
+use std::collections::HashMap;
+
+use crate::{grid::Grid, point::Point};
+
+/// A row or column inserted into or removed from a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StructuralEdit {
+    /// A row was inserted at `y`, shifting later rows down.
+    InsertRow(usize),
+
+    /// Row `y` was removed, shifting later rows up.
+    RemoveRow(usize),
+
+    /// A column was inserted at `x`, shifting later columns right.
+    InsertCol(usize),
+
+    /// Column `x` was removed, shifting later columns left.
+    RemoveCol(usize),
+}
+
+impl StructuralEdit {
+    /// Returns where the cell at `point` moved to, or `None` if it was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::remap::StructuralEdit;
+    ///
+    /// assert_eq!(StructuralEdit::InsertRow(1).remap((4, 2)), Some((4, 3)));
+    /// assert_eq!(StructuralEdit::RemoveCol(4).remap((4, 2)), None);
+    /// ```
+    pub fn remap(&self, point: impl Point) -> Option<(usize, usize)> {
+        let (x, y) = (point.x(), point.y());
+        match *self {
+            StructuralEdit::InsertRow(row) => Some((x, if y >= row { y + 1 } else { y })),
+            StructuralEdit::RemoveRow(row) => shift_removed(y, row).map(|y| (x, y)),
+            StructuralEdit::InsertCol(col) => Some((if x >= col { x + 1 } else { x }, y)),
+            StructuralEdit::RemoveCol(col) => shift_removed(x, col).map(|x| (x, y)),
+        }
+    }
+}
+
+/// Returns where coordinate `at` moved to after `removed` was removed.
+fn shift_removed(at: usize, removed: usize) -> Option<usize> {
+    match at.cmp(&removed) {
+        std::cmp::Ordering::Less => Some(at),
+        std::cmp::Ordering::Equal => None,
+        std::cmp::Ordering::Greater => Some(at - 1),
+    }
+}
+
+/// How the coordinates of a grid moved after a sequence of [`StructuralEdit`]s.
+///
+/// Created by [`StructuralTransaction::commit`]; external data keyed by cell position can be
+/// updated with [`CoordinateRemap::apply`] or [`CoordinateRemap::apply_keys`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CoordinateRemap {
+    edits: Vec<StructuralEdit>,
+}
+
+impl CoordinateRemap {
+    /// Creates a remap that leaves every coordinate unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::remap::CoordinateRemap;
+    ///
+    /// let remap = CoordinateRemap::new();
+    ///
+    /// assert!(remap.is_identity());
+    /// assert_eq!(remap.apply((1, 2)), Some((1, 2)));
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `edit`, applied after every edit already in the remap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::remap::{CoordinateRemap, StructuralEdit};
+    ///
+    /// let mut remap = CoordinateRemap::new();
+    /// remap.push(StructuralEdit::InsertCol(0));
+    ///
+    /// assert_eq!(remap.apply((0, 0)), Some((1, 0)));
+    /// ```
+    pub fn push(&mut self, edit: StructuralEdit) {
+        self.edits.push(edit);
+    }
+
+    /// Returns the edits, in the order they were applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::remap::{CoordinateRemap, StructuralEdit};
+    ///
+    /// let mut remap = CoordinateRemap::new();
+    /// remap.push(StructuralEdit::RemoveRow(3));
+    ///
+    /// assert_eq!(remap.edits(), &[StructuralEdit::RemoveRow(3)]);
+    /// ```
+    pub fn edits(&self) -> &[StructuralEdit] {
+        &self.edits
+    }
+
+    /// Returns whether the remap has no edits, so every coordinate is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::remap::{CoordinateRemap, StructuralEdit};
+    ///
+    /// let mut remap = CoordinateRemap::new();
+    /// remap.push(StructuralEdit::InsertRow(0));
+    ///
+    /// assert!(!remap.is_identity());
+    /// ```
+    pub fn is_identity(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Returns a remap that applies the edits of `self`, followed by the edits of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::remap::{CoordinateRemap, StructuralEdit};
+    ///
+    /// let mut first = CoordinateRemap::new();
+    /// first.push(StructuralEdit::InsertRow(0));
+    /// let mut second = CoordinateRemap::new();
+    /// second.push(StructuralEdit::InsertCol(0));
+    ///
+    /// assert_eq!(first.then(second).apply((0, 0)), Some((1, 1)));
+    /// ```
+    pub fn then(mut self, other: CoordinateRemap) -> Self {
+        self.edits.extend(other.edits);
+        self
+    }
+
+    /// Returns where the cell at `point` moved to, or `None` if it was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::remap::{CoordinateRemap, StructuralEdit};
+    ///
+    /// let mut remap = CoordinateRemap::new();
+    /// remap.push(StructuralEdit::RemoveRow(0));
+    /// remap.push(StructuralEdit::InsertCol(1));
+    ///
+    /// assert_eq!(remap.apply((0, 0)), None);
+    /// assert_eq!(remap.apply((1, 1)), Some((2, 0)));
+    /// ```
+    pub fn apply(&self, point: impl Point) -> Option<(usize, usize)> {
+        self.edits
+            .iter()
+            .try_fold((point.x(), point.y()), |point, edit| edit.remap(point))
+    }
+
+    /// Moves each entry of `entries` to its new position, dropping entries whose cell was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use grud::remap::{CoordinateRemap, StructuralEdit};
+    ///
+    /// let mut remap = CoordinateRemap::new();
+    /// remap.push(StructuralEdit::RemoveCol(0));
+    ///
+    /// let entities = HashMap::from([((0, 0), "goblin"), ((2, 0), "chest")]);
+    ///
+    /// assert_eq!(remap.apply_keys(entities), HashMap::from([((1, 0), "chest")]));
+    /// ```
+    pub fn apply_keys<V>(&self, entries: HashMap<(usize, usize), V>) -> HashMap<(usize, usize), V> {
+        entries
+            .into_iter()
+            .filter_map(|(point, value)| Some((self.apply(point)?, value)))
+            .collect()
+    }
+}
+
+/// A sequence of structural edits to a grid, started by [`Grid::edit_structure`].
+///
+/// Edits are applied immediately. [`StructuralTransaction::commit`] returns the resulting
+/// [`CoordinateRemap`], while [`StructuralTransaction::rollback`] reverts every edit; dropping the
+/// transaction keeps the edits, without reporting them.
+#[must_use = "commit the transaction to receive the coordinate remap"]
+#[derive(Debug)]
+pub struct StructuralTransaction<'a, T>
+where
+    T: Clone,
+{
+    grid: &'a mut Grid<T>,
+    remap: CoordinateRemap,
+    removed: Vec<Vec<T>>,
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Starts a sequence of row and column insertions and removals, which reports how coordinates
+    /// moved when committed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::with_width(2, vec!['a', 'b', 'c', 'd']);
+    ///
+    /// let mut edit = grid.edit_structure();
+    /// edit.insert_row(0, ['x', 'x']);
+    /// edit.remove_col(0);
+    /// let remap = edit.commit();
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![vec!['x'], vec!['b'], vec!['d']]);
+    /// assert_eq!(remap.apply((1, 1)), Some((0, 2)));
+    /// assert_eq!(remap.apply((0, 1)), None);
+    /// ```
+    pub fn edit_structure(&mut self) -> StructuralTransaction<'_, T> {
+        StructuralTransaction {
+            grid: self,
+            remap: CoordinateRemap::new(),
+            removed: Vec::new(),
+        }
+    }
+}
+
+impl<T> StructuralTransaction<'_, T>
+where
+    T: Clone,
+{
+    /// Returns the grid, as edited so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 0);
+    /// let mut edit = grid.edit_structure();
+    /// edit.remove_row(0);
+    ///
+    /// assert_eq!(edit.grid().height(), 1);
+    /// ```
+    pub fn grid(&self) -> &Grid<T> {
+        self.grid
+    }
+
+    /// Inserts `row` as row `y`, as with [`Grid::insert_row`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(1, 1, 0);
+    /// let mut edit = grid.edit_structure();
+    /// edit.insert_row(0, [1]);
+    ///
+    /// assert_eq!(edit.commit().apply((0, 0)), Some((0, 1)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `y` is greater than the height, or `row` is not the same length as the width.
+    pub fn insert_row(&mut self, y: usize, row: impl IntoIterator<Item = T>) {
+        self.grid.insert_row(y, row);
+        self.remap.push(StructuralEdit::InsertRow(y));
+    }
+
+    /// Removes and returns row `y`, as with [`Grid::remove_row`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::with_width(1, vec![1, 2]);
+    /// let mut edit = grid.edit_structure();
+    ///
+    /// assert_eq!(edit.remove_row(0), vec![1]);
+    /// assert_eq!(edit.commit().apply((0, 1)), Some((0, 0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `y` is out of bounds.
+    pub fn remove_row(&mut self, y: usize) -> Vec<T> {
+        let row = self.grid.remove_row(y);
+        self.removed.push(row.clone());
+        self.remap.push(StructuralEdit::RemoveRow(y));
+        row
+    }
+
+    /// Inserts `col` as column `x`, as with [`Grid::insert_col`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(1, 1, 0);
+    /// let mut edit = grid.edit_structure();
+    /// edit.insert_col(0, [1]);
+    ///
+    /// assert_eq!(edit.commit().apply((0, 0)), Some((1, 0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `x` is greater than the width, or `col` is not the same length as the height.
+    pub fn insert_col(&mut self, x: usize, col: impl IntoIterator<Item = T>) {
+        self.grid.insert_col(x, col);
+        self.remap.push(StructuralEdit::InsertCol(x));
+    }
+
+    /// Removes and returns column `x`, as with [`Grid::remove_col`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::with_width(2, vec![1, 2]);
+    /// let mut edit = grid.edit_structure();
+    ///
+    /// assert_eq!(edit.remove_col(0), vec![1]);
+    /// assert_eq!(edit.commit().apply((1, 0)), Some((0, 0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `x` is out of bounds.
+    pub fn remove_col(&mut self, x: usize) -> Vec<T> {
+        let col = self.grid.remove_col(x);
+        self.removed.push(col.clone());
+        self.remap.push(StructuralEdit::RemoveCol(x));
+        col
+    }
+
+    /// Ends the transaction, keeping the edits and returning how coordinates moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 0);
+    /// assert!(grid.edit_structure().commit().is_identity());
+    /// ```
+    pub fn commit(self) -> CoordinateRemap {
+        self.remap
+    }
+
+    /// Ends the transaction, reverting every edit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+    ///
+    /// let mut edit = grid.edit_structure();
+    /// edit.remove_col(1);
+    /// edit.insert_row(2, [5]);
+    /// edit.rollback();
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![vec![1, 2], vec![3, 4]]);
+    /// ```
+    pub fn rollback(mut self) {
+        for edit in self.remap.edits.iter().rev() {
+            match *edit {
+                StructuralEdit::InsertRow(y) => {
+                    self.grid.remove_row(y);
+                }
+                StructuralEdit::InsertCol(x) => {
+                    self.grid.remove_col(x);
+                }
+                StructuralEdit::RemoveRow(y) => {
+                    let row = self.removed.pop().expect("Removed row was recorded");
+                    self.grid.insert_row(y, row);
+                }
+                StructuralEdit::RemoveCol(x) => {
+                    let col = self.removed.pop().expect("Removed column was recorded");
+                    self.grid.insert_col(x, col);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remap_follows_every_cell() {
+        let original = Grid::with_width(4, (0..12).collect::<Vec<_>>());
+        let mut grid = original.clone();
+        let mut edit = grid.edit_structure();
+        edit.remove_row(1);
+        edit.insert_col(4, [20, 21]);
+        edit.remove_col(0);
+        edit.insert_row(0, [30, 31, 32, 33]);
+        let remap = edit.commit();
+
+        for point in crate::point::Rect::new(0, 0, 4, 3).iter_points() {
+            match remap.apply(point) {
+                Some(moved) => assert_eq!(grid[moved], original[point]),
+                None => assert!(point.0 == 0 || point.1 == 1),
+            }
+        }
+    }
+
+    #[test]
+    fn rollback_through_empty_grid() {
+        let mut grid = Grid::with_width(2, vec![1, 2]);
+        let mut edit = grid.edit_structure();
+        edit.remove_row(0);
+        edit.insert_col(0, [7, 8, 9]);
+        edit.rollback();
+
+        assert_eq!(grid.to_matrix(), vec![vec![1, 2]]);
+    }
+}
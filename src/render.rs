@@ -0,0 +1,412 @@
+//! Rendering grids for visualization, i.e. with transient annotations for debugging.
+//!
+//! See [`Overlay`] and [`draw_path`] for details.
+
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Display, Formatter},
+};
+
+use crate::{
+    grid::Grid,
+    point::{line, Point},
+};
+
+/// A layer of an [`Overlay`], returning the value (if any) to draw over a cell.
+type Layer<'a, T> = dyn Fn((usize, usize), &T) -> Option<T> + 'a;
+
+/// A grid with annotation layers that are composited when read, leaving the grid unchanged.
+///
+/// Each layer is given the position of a cell and the value below it (the grid, composited with
+/// any earlier layers), and returns `Some(value)` to draw over it, or `None` to leave it as is.
+/// Later layers are drawn on top of earlier ones.
+///
+/// # Examples
+///
+/// Highlighting a region and marking a point of interest:
+///
+/// ```
+/// use grud::{Grid, render::Overlay};
+///
+/// let grid = Grid::new(4, 3, '.');
+/// let overlay = Overlay::new(&grid)
+///     .layer(|(x, _), _| (x >= 2).then_some('~'))
+///     .markers([(3, 1)], '@');
+///
+/// assert_eq!(overlay.to_string(), "..~~\n..~@\n..~~\n");
+/// assert_eq!(grid.to_string(), "....\n....\n....\n");
+/// ```
+pub struct Overlay<'a, T>
+where
+    T: Clone,
+{
+    base: &'a Grid<T>,
+    layers: Vec<Box<Layer<'a, T>>>,
+}
+
+impl<'a, T> Overlay<'a, T>
+where
+    T: Clone + 'a,
+{
+    /// Creates an overlay over `base`, with no layers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, render::Overlay};
+    ///
+    /// let grid = Grid::new(2, 1, '.');
+    /// assert_eq!(Overlay::new(&grid).to_string(), "..\n");
+    /// ```
+    pub fn new(base: &'a Grid<T>) -> Self {
+        Self {
+            base,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Adds a layer on top, where `draw(point, below)` returns the value to draw at `point`, if
+    /// any.
+    ///
+    /// # Examples
+    ///
+    /// Highlighting every cell that matches a predicate:
+    ///
+    /// ```
+    /// use grud::{Grid, render::Overlay};
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 5, 9]);
+    /// let overlay = Overlay::new(&grid).layer(|_, n| (*n > 4).then_some(0));
+    ///
+    /// assert_eq!(overlay.to_grid().as_vec(), &vec![1, 0, 0]);
+    /// ```
+    pub fn layer(mut self, draw: impl Fn((usize, usize), &T) -> Option<T> + 'a) -> Self {
+        self.layers.push(Box::new(draw));
+        self
+    }
+
+    /// Adds a layer on top that draws `value` at every point in `points`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, render::Overlay};
+    ///
+    /// let grid = Grid::new(3, 1, '.');
+    /// let overlay = Overlay::new(&grid).markers([(0, 0), (2, 0)], '*');
+    ///
+    /// assert_eq!(overlay.to_string(), "*.*\n");
+    /// ```
+    pub fn markers<P>(self, points: impl IntoIterator<Item = P>, value: T) -> Self
+    where
+        P: Point,
+    {
+        let points: HashSet<_> = points.into_iter().map(|p| (p.x(), p.y())).collect();
+        self.layer(move |point, _| points.contains(&point).then(|| value.clone()))
+    }
+
+    /// Returns the composited value at `point`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, render::Overlay};
+    ///
+    /// let grid = Grid::new(2, 1, '.');
+    /// let overlay = Overlay::new(&grid).markers([(1, 0)], '*');
+    ///
+    /// assert_eq!(overlay.get((0, 0)), '.');
+    /// assert_eq!(overlay.get((1, 0)), '*');
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn get(&self, point: impl Point) -> T {
+        let index = self.base.checked_index(point);
+        self.composite((point.x(), point.y()), &self.base[index])
+    }
+
+    /// Composites every layer into a new grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, render::Overlay};
+    ///
+    /// let grid = Grid::new(2, 1, 0);
+    /// let overlay = Overlay::new(&grid).layer(|(x, _), n| Some(n + x));
+    ///
+    /// assert_eq!(overlay.to_grid().as_vec(), &vec![0, 1]);
+    /// ```
+    pub fn to_grid(&self) -> Grid<T> {
+        let (width, _) = self.base.size();
+        if width == 0 {
+            return Grid::from(Vec::new());
+        }
+        let data = self
+            .base
+            .into_iter()
+            .enumerate()
+            .map(|(i, cell)| self.composite((i % width, i / width), cell))
+            .collect();
+        Grid::with_width(width, data)
+    }
+
+    /// Returns `cell` at `point` after drawing every layer over it.
+    fn composite(&self, point: (usize, usize), cell: &T) -> T {
+        let mut cell = cell.clone();
+        for layer in &self.layers {
+            if let Some(value) = layer(point, &cell) {
+                cell = value;
+            }
+        }
+        cell
+    }
+}
+
+impl<T> Debug for Overlay<'_, T>
+where
+    T: Clone + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Overlay")
+            .field("base", &self.base)
+            .field("layers", &self.layers.len())
+            .finish()
+    }
+}
+
+impl<T> Display for Overlay<'_, T>
+where
+    T: Clone + Display,
+{
+    /// Formats the composited grid the same way as [`Grid`]'s [`Display`].
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (width, height) = self.base.size();
+        for y in 0..height {
+            for x in 0..width {
+                write!(f, "{}", self.composite((x, y), &self.base[(x, y)]))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// How [`draw_path`] draws each step of a path.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PathStyle {
+    /// Box-drawing lines, with corners where the path turns (i.e. `─`, `│`, and `└`).
+    #[default]
+    Lines,
+
+    /// Arrows pointing to the next step (i.e. `→` and `↓`), with the last step pointing in the
+    /// direction it was entered from.
+    Arrows,
+}
+
+/// Draws `path` onto `grid`, joining each point to the previous one with a [`line`].
+///
+/// Points do not need to be next to each other, so paths from any-angle searches such as
+/// [`theta_star`](crate::path::theta_star) can be drawn as is. Diagonal steps are drawn as `╲` and
+/// `╱` (or diagonal arrows). Paths with a single point are not drawn, as they have no direction.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, render::{draw_path, PathStyle}};
+///
+/// let path = [(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (3, 2)];
+///
+/// let mut grid = Grid::new(4, 3, '.');
+/// draw_path(&mut grid, path, PathStyle::Lines);
+/// assert_eq!(grid.to_string(), "──┐.\n..│.\n..└─\n");
+///
+/// let mut grid = Grid::new(4, 3, '.');
+/// draw_path(&mut grid, path, PathStyle::Arrows);
+/// assert_eq!(grid.to_string(), "→→↓.\n..↓.\n..→→\n");
+/// ```
+///
+/// Drawing a path with diagonal and any-angle segments:
+///
+/// ```
+/// use grud::{Grid, render::{draw_path, PathStyle}};
+///
+/// let mut grid = Grid::new(4, 3, '.');
+/// draw_path(&mut grid, [(0, 0), (2, 2), (3, 2)], PathStyle::Arrows);
+/// assert_eq!(grid.to_string(), "↘...\n.↘..\n..→→\n");
+/// ```
+///
+/// # Panics
+///
+/// If a point is out of bounds, in which case nothing is drawn.
+pub fn draw_path<P>(grid: &mut Grid<char>, path: impl IntoIterator<Item = P>, style: PathStyle)
+where
+    P: Point,
+{
+    let mut cells: Vec<(usize, usize)> = Vec::new();
+    for point in path {
+        let point = (point.x(), point.y());
+        match cells.last() {
+            Some(&last) => cells.extend(line(last, point).skip(1)),
+            None => cells.push(point),
+        }
+    }
+    if cells.len() < 2 {
+        return;
+    }
+    let indices: Vec<_> = cells
+        .iter()
+        .map(|&point| grid.checked_index(point))
+        .collect();
+    for (i, index) in indices.into_iter().enumerate() {
+        let before = i.checked_sub(1).map(|j| step(cells[j], cells[i]));
+        let after = cells.get(i + 1).map(|&next| step(cells[i], next));
+        grid[index] = match style {
+            PathStyle::Lines => line_glyph(before, after),
+            PathStyle::Arrows => arrow_glyph(after.or(before)),
+        };
+    }
+}
+
+/// Returns the direction `(dx, dy)` of the step from `a` to its neighbor `b`.
+fn step(a: (usize, usize), b: (usize, usize)) -> (isize, isize) {
+    let delta = |from: usize, to: usize| {
+        if to > from {
+            1
+        } else {
+            -isize::from(to < from)
+        }
+    };
+    (delta(a.0, b.0), delta(a.1, b.1))
+}
+
+/// Returns the line joining the step entering a point (`before`) and the step leaving it (`after`).
+///
+/// Diagonal steps are drawn as a diagonal line, preferring the step leaving the point.
+fn line_glyph(before: Option<(isize, isize)>, after: Option<(isize, isize)>) -> char {
+    let diagonal = |&(dx, dy): &(isize, isize)| dx != 0 && dy != 0;
+    if let Some((dx, dy)) = after.filter(diagonal).or(before.filter(diagonal)) {
+        return if dx == dy { '╲' } else { '╱' };
+    }
+    // The sides of the cell the path passes through: the side it entered, and the side it left.
+    let entered = before.map(|(dx, dy)| (-dx, -dy));
+    let sides = [entered, after];
+    let has = |side| sides.contains(&Some(side));
+    let (left, right, up, down) = (has((-1, 0)), has((1, 0)), has((0, -1)), has((0, 1)));
+    match (left, right, up, down) {
+        (_, _, false, false) => '─',
+        (false, false, _, _) => '│',
+        (true, _, true, _) => '┘',
+        (true, _, _, true) => '┐',
+        (_, true, true, _) => '└',
+        _ => '┌',
+    }
+}
+
+/// Returns the arrow pointing in `direction`.
+fn arrow_glyph(direction: Option<(isize, isize)>) -> char {
+    match direction {
+        Some((1, 0)) => '→',
+        Some((-1, 0)) => '←',
+        Some((0, -1)) => '↑',
+        Some((1, 1)) => '↘',
+        Some((-1, -1)) => '↖',
+        Some((1, -1)) => '↗',
+        Some((-1, 1)) => '↙',
+        _ => '↓',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlay_later_layers_draw_on_top() {
+        let grid = Grid::new(1, 1, 'a');
+        let overlay = Overlay::new(&grid)
+            .markers([(0, 0)], 'b')
+            .markers([[0, 0]], 'c');
+
+        assert_eq!(overlay.get((0, 0)), 'c');
+    }
+
+    #[test]
+    fn overlay_layers_see_value_below() {
+        let grid = Grid::new(1, 1, 1);
+        let overlay = Overlay::new(&grid)
+            .layer(|_, n| Some(n * 10))
+            .layer(|_, n| Some(n + 1));
+
+        assert_eq!(overlay.get((0, 0)), 11);
+    }
+
+    #[test]
+    fn overlay_empty_base() {
+        let grid = Grid::<char>::from(Vec::new());
+        let overlay = Overlay::new(&grid).markers([(0, 0)], '*');
+
+        assert!(overlay.to_grid().as_vec().is_empty());
+        assert_eq!(overlay.to_string(), "");
+    }
+
+    #[test]
+    #[should_panic]
+    fn overlay_get_out_of_bounds() {
+        let grid = Grid::new(2, 2, 0);
+        Overlay::new(&grid).get((2, 0));
+    }
+
+    #[test]
+    fn draw_path_every_corner() {
+        let mut grid = Grid::new(3, 3, '.');
+        let path = [(0, 1), (0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (1, 2)];
+        draw_path(&mut grid, path, PathStyle::Lines);
+
+        assert_eq!(grid.to_string(), "┌┐.\n│└┐\n.─┘\n");
+    }
+
+    #[test]
+    fn draw_path_single_point_is_not_drawn() {
+        let mut grid = Grid::new(1, 1, '.');
+        draw_path(&mut grid, [(0, 0)], PathStyle::Arrows);
+
+        assert_eq!(grid.to_string(), ".\n");
+    }
+
+    #[test]
+    fn draw_path_diagonal_steps() {
+        let mut grid = Grid::new(3, 3, '.');
+        draw_path(&mut grid, [(0, 2), (2, 0)], PathStyle::Lines);
+        assert_eq!(grid.to_string(), "..╱\n.╱.\n╱..\n");
+
+        let mut grid = Grid::new(3, 3, '.');
+        draw_path(&mut grid, [(2, 2), (0, 0), (0, 1)], PathStyle::Arrows);
+        assert_eq!(grid.to_string(), "↓..\n↓↖.\n..↖\n");
+    }
+
+    #[test]
+    fn draw_path_any_angle_segments() {
+        let mut grid = Grid::new(5, 3, '.');
+        draw_path(
+            &mut grid,
+            [(0, 0), (4, 2), (4, 2), (4, 0)],
+            PathStyle::Lines,
+        );
+
+        assert_eq!(grid.to_string(), "╲...│\n.╲╲.│\n...╲┘\n");
+    }
+
+    #[test]
+    fn draw_path_out_of_bounds_draws_nothing() {
+        let mut grid = Grid::new(2, 2, '.');
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            draw_path(&mut grid, [(0, 0), (1, 0), (1, 2)], PathStyle::Lines);
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(grid.to_string(), "..\n..\n");
+    }
+}
@@ -0,0 +1,456 @@
+//! Run-length encoding, i.e. for compactly storing large sparse patterns.
+//!
+//! [`Grid::encode_runs`] and [`Grid::decode_runs`] work with any cell type, while
+//! [`Grid::read_life_rle`] and [`Grid::write_life_rle`] support the [Life RLE] pattern format.
+//!
+//! [Life RLE]: https://conwaylife.com/wiki/Run_Length_Encoded
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    io::{self, Read, Write},
+};
+
+use crate::{grid::Grid, point::Rect};
+
+/// The maximum length of a line of pattern data written by [`Grid::write_life_rle`].
+const LINE_LENGTH: usize = 70;
+
+/// The maximum number of cells in a pattern read by [`Grid::read_life_rle`].
+///
+/// This prevents a header such as `x = 100000, y = 100000` from allocating gigabytes of memory; use
+/// [`Grid::read_life_rle_with_limit`] to read larger patterns.
+pub const DEFAULT_CELL_LIMIT: usize = 1 << 24;
+
+impl<T> Grid<T>
+where
+    T: Clone + PartialEq,
+{
+    /// Returns the cells in row-major order as runs of equal cells, each a count and a value.
+    ///
+    /// Runs continue from the end of one row to the start of the next.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec!['.', '.', '#', '#', '.', '.']);
+    ///
+    /// assert_eq!(grid.encode_runs(), vec![(2, '.'), (2, '#'), (2, '.')]);
+    /// ```
+    pub fn encode_runs(&self) -> Vec<(usize, T)> {
+        let runs = runs(self.as_vec());
+        strict_assert!(
+            runs.iter().map(|(count, _)| count).sum::<usize>() == self.as_vec().len(),
+            "Runs do not cover every cell"
+        );
+        runs
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Creates a grid of the specified `width` from runs of cells in row-major order, as returned
+    /// by [`Grid::encode_runs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::decode_runs(2, [(3, 0), (1, 1)]);
+    /// assert_eq!(grid.to_matrix(), vec![vec![0, 0], vec![0, 1]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the total number of cells is not evenly divisible by `width`, including if `width` is `0`
+    /// and the runs are not empty.
+    pub fn decode_runs(width: usize, runs: impl IntoIterator<Item = (usize, T)>) -> Self {
+        let mut data = Vec::new();
+        for (count, value) in runs {
+            data.resize(data.len() + count, value);
+        }
+        if data.is_empty() {
+            return Grid::from(Vec::new());
+        }
+        assert_ne!(width, 0, "Width of 0 for {} decoded cells", data.len());
+        Grid::with_width(width, data)
+    }
+}
+
+/// Error returned when reading a pattern in the Life RLE format.
+#[derive(Debug)]
+pub enum ReadRleError {
+    /// Reading failed, i.e. due to I/O or invalid UTF-8.
+    Io(io::Error),
+
+    /// The `x = .., y = ..` header line is missing or invalid.
+    Header {
+        /// The line (starting at `1`), or `0` if the header is missing.
+        line: usize,
+
+        /// A description of why the header is invalid.
+        message: String,
+    },
+
+    /// The pattern data is invalid, or does not fit within the size in the header.
+    Pattern {
+        /// The line (starting at `1`).
+        line: usize,
+
+        /// A description of why the pattern is invalid.
+        message: String,
+    },
+}
+
+impl Display for ReadRleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Header { line: 0, message } => write!(f, "Missing header: {message}"),
+            Self::Header { line, message } => write!(f, "Invalid header at line {line}: {message}"),
+            Self::Pattern { line, message } => {
+                write!(f, "Invalid pattern at line {line}: {message}")
+            }
+        }
+    }
+}
+
+impl Error for ReadRleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadRleError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl Grid<bool> {
+    /// Reads a pattern in the Life RLE format, where `o` cells are `true` and `b` cells are `false`.
+    ///
+    /// Lines starting with `#` are ignored, and the rule in the header (if any) is not checked.
+    ///
+    /// # Examples
+    ///
+    /// A glider:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let text = "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+    /// let grid = Grid::read_life_rle(text.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![
+    ///     vec![false, true, false],
+    ///     vec![false, false, true],
+    ///     vec![true, true, true],
+    /// ]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, the header is missing or invalid (including a size of more than
+    /// [`DEFAULT_CELL_LIMIT`] cells), or the pattern data is invalid or does not fit within the size
+    /// in the header.
+    pub fn read_life_rle(reader: impl Read) -> Result<Self, ReadRleError> {
+        Self::read_life_rle_with_limit(reader, DEFAULT_CELL_LIMIT)
+    }
+
+    /// Reads a pattern in the Life RLE format, like [`Grid::read_life_rle`], with a size of at most
+    /// `limit` cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let text = "x = 3, y = 3\nbo$2bo$3o!\n";
+    ///
+    /// assert!(Grid::read_life_rle_with_limit(text.as_bytes(), 9).is_ok());
+    /// assert!(Grid::read_life_rle_with_limit(text.as_bytes(), 8).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, the header is missing or invalid (including a size of more than `limit`
+    /// cells or too large to allocate), or the pattern data is invalid or does not fit within the
+    /// size in the header.
+    pub fn read_life_rle_with_limit(
+        mut reader: impl Read,
+        limit: usize,
+    ) -> Result<Self, ReadRleError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let (header_line, header) = lines.next().ok_or_else(|| ReadRleError::Header {
+            line: 0,
+            message: String::from("expected `x = .., y = ..`"),
+        })?;
+        let (width, height) = parse_header(header).map_err(|message| ReadRleError::Header {
+            line: header_line,
+            message,
+        })?;
+        if width == 0 || height == 0 {
+            return Ok(Grid::from(Vec::new()));
+        }
+
+        let mut grid = Grid::try_new_with_limit(width, height, false, limit).map_err(|error| {
+            ReadRleError::Header {
+                line: header_line,
+                message: error.to_string(),
+            }
+        })?;
+        let (mut x, mut y) = (0_usize, 0_usize);
+        for (line, data) in lines {
+            let error = |message: String| ReadRleError::Pattern { line, message };
+            let mut count: Option<usize> = None;
+            for tag in data.chars() {
+                if let Some(digit) = tag.to_digit(10) {
+                    let next = count.unwrap_or(0).checked_mul(10);
+                    count = next.and_then(|count| count.checked_add(digit as usize));
+                    if count.is_none() {
+                        return Err(error(String::from("run count overflows")));
+                    }
+                    continue;
+                }
+                if tag.is_whitespace() {
+                    continue;
+                }
+                let run = count.take().unwrap_or(1);
+                match tag {
+                    'b' | 'o' => {
+                        if y >= height || x.checked_add(run).is_none_or(|end| end > width) {
+                            return Err(error(format!(
+                                "cells extend past the {width}x{height} pattern"
+                            )));
+                        }
+                        if tag == 'o' {
+                            grid.fill_rect(Rect::new(x, y, run, 1), true);
+                        }
+                        x += run;
+                    }
+                    '$' => {
+                        y = y.saturating_add(run);
+                        x = 0;
+                    }
+                    '!' => return Ok(grid),
+                    _ => return Err(error(format!("unexpected {tag:?}"))),
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Writes the grid as a pattern in the Life RLE format, where `true` cells are `o` and `false`
+    /// cells are `b`.
+    ///
+    /// Trailing dead cells in each row are omitted, and lines of pattern data are at most 70
+    /// characters long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![
+    ///     false, true, false,
+    ///     false, false, true,
+    ///     true, true, true,
+    /// ]);
+    ///
+    /// let mut rle = Vec::new();
+    /// grid.write_life_rle(&mut rle).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(rle).unwrap(), "x = 3, y = 3\nbo$2bo$3o!\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    pub fn write_life_rle(&self, mut writer: impl Write) -> io::Result<()> {
+        let (width, height) = self.size();
+        let mut text = format!("x = {width}, y = {height}\n");
+
+        let mut tokens = Vec::new();
+        let mut pending_rows = 0;
+        if width > 0 {
+            for row in self.as_vec().chunks(width) {
+                let mut runs = runs(row);
+                if runs.last().is_some_and(|(_, alive)| !alive) {
+                    runs.pop();
+                }
+                if runs.is_empty() {
+                    pending_rows += 1;
+                    continue;
+                }
+                if !tokens.is_empty() || pending_rows > 0 {
+                    tokens.push(token(pending_rows + usize::from(!tokens.is_empty()), '$'));
+                }
+                pending_rows = 0;
+                tokens.extend(
+                    runs.into_iter()
+                        .map(|(count, alive)| token(count, if alive { 'o' } else { 'b' })),
+                );
+            }
+        }
+        tokens.push(String::from("!"));
+
+        let mut line_start = text.len();
+        for token in tokens {
+            if text.len() - line_start + token.len() > LINE_LENGTH {
+                text.push('\n');
+                line_start = text.len();
+            }
+            text.push_str(&token);
+        }
+        text.push('\n');
+
+        if cfg!(feature = "strict") {
+            let read = Grid::read_life_rle(text.as_bytes())
+                .unwrap_or_else(|error| panic!("Written pattern could not be read: {error}"));
+            assert!(
+                read.size() == self.size() && read.as_vec() == self.as_vec(),
+                "Written pattern does not read back as the same grid"
+            );
+        }
+        writer.write_all(text.as_bytes())
+    }
+}
+
+/// Returns the width and height from an RLE header line, i.e. `x = 3, y = 3, rule = B3/S23`.
+fn parse_header(header: &str) -> Result<(usize, usize), String> {
+    let (mut width, mut height) = (None, None);
+    for field in header.split(',') {
+        let (key, value) = field
+            .split_once('=')
+            .ok_or_else(|| format!("expected `key = value`, found {:?}", field.trim()))?;
+        let parse = || {
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|error| format!("{:?}: {error}", value.trim()))
+        };
+        match key.trim() {
+            "x" => width = Some(parse()?),
+            "y" => height = Some(parse()?),
+            _ => {}
+        }
+    }
+    match (width, height) {
+        (Some(width), Some(height)) => Ok((width, height)),
+        _ => Err(String::from("expected `x = .., y = ..`")),
+    }
+}
+
+/// Returns `cells` as runs of equal cells, each a count and a value.
+fn runs<T>(cells: &[T]) -> Vec<(usize, T)>
+where
+    T: Clone + PartialEq,
+{
+    let mut runs: Vec<(usize, T)> = Vec::new();
+    for cell in cells {
+        match runs.last_mut() {
+            Some((count, value)) if value == cell => *count += 1,
+            _ => runs.push((1, cell.clone())),
+        }
+    }
+    runs
+}
+
+/// Returns an RLE token for a run of `count` tags, omitting a count of `1`.
+fn token(count: usize, tag: char) -> String {
+    if count == 1 {
+        tag.to_string()
+    } else {
+        format!("{count}{tag}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn life_rle_round_trip_sparse() {
+        let mut grid = Grid::new(200, 100, false);
+        for (x, y) in [(0, 0), (199, 0), (100, 50), (101, 50), (0, 99), (150, 99)] {
+            grid[(x, y)] = true;
+        }
+        for x in 0..150 {
+            grid[(x, 70)] = x % 2 == 0;
+        }
+
+        let mut rle = Vec::new();
+        grid.write_life_rle(&mut rle).unwrap();
+        let text = String::from_utf8(rle).unwrap();
+
+        assert!(text.lines().all(|line| line.len() <= LINE_LENGTH));
+        assert!(text.len() < 400);
+        let read = Grid::read_life_rle(text.as_bytes()).unwrap();
+        assert_eq!(read.as_vec(), grid.as_vec());
+    }
+
+    #[test]
+    fn life_rle_errors() {
+        let error = |text: &str| {
+            Grid::read_life_rle(text.as_bytes())
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            error("#C only comments\n"),
+            "Missing header: expected `x = .., y = ..`"
+        );
+        assert_eq!(
+            error("x = 2\n"),
+            "Invalid header at line 1: expected `x = .., y = ..`"
+        );
+        assert_eq!(
+            error("x = 2, y = 1\n3o!\n"),
+            "Invalid pattern at line 2: cells extend past the 2x1 pattern"
+        );
+        assert_eq!(
+            error("x = 2, y = 1\n\n2z!\n"),
+            "Invalid pattern at line 3: unexpected 'z'"
+        );
+        assert_eq!(
+            error("x = 99999999999, y = 99999999999\n!\n"),
+            "Invalid header at line 1: Grid of 99999999999x99999999999 cells is too large"
+        );
+        assert_eq!(
+            error("x = 100000, y = 100000\n!\n"),
+            "Invalid header at line 1: Grid of 100000x100000 cells exceeds limit of 16777216 cells"
+        );
+    }
+
+    #[test]
+    fn decode_runs_round_trip() {
+        let grid = Grid::with_width(4, (0..12).map(|i| i / 5).collect::<Vec<_>>());
+        let decoded = Grid::decode_runs(4, grid.encode_runs());
+
+        assert_eq!(decoded.as_vec(), grid.as_vec());
+        assert!(Grid::<u8>::decode_runs(0, []).as_vec().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Width of 0 for 3 decoded cells")]
+    fn decode_runs_zero_width() {
+        Grid::decode_runs(0, [(3, 0)]);
+    }
+}
@@ -0,0 +1,440 @@
+//! Resampling grids to a different size, i.e. for mini-maps and thumbnails.
+//!
+//! See [`Grid::minimap`] and [`Grid::area_average`] for details.
+
+use crate::{
+    grid::Grid,
+    point::{Point, Rect},
+};
+
+/// Describes how cells of a _source_ grid map onto cells of a (usually smaller) _target_ grid.
+///
+/// Each target cell covers a block of source cells. Block edges are placed at `ceil(i * S / T)`,
+/// where `S` and `T` are the source and target sizes in that axis, so when downsampling, blocks
+/// differ in size by at most one cell and every source cell belongs to exactly one block. When
+/// the target is _larger_ than the source in an axis, each target cell covers the single nearest
+/// source cell instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Scale {
+    source: (usize, usize),
+    target: (usize, usize),
+}
+
+impl Scale {
+    /// Creates a mapping from a `source` grid size to a `target` grid size, as `(width, height)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::scale::Scale;
+    ///
+    /// let scale = Scale::new((10, 10), (5, 5));
+    /// assert_eq!(scale.source(), (10, 10));
+    /// assert_eq!(scale.target(), (5, 5));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If any dimension is `0`.
+    pub fn new(source: (usize, usize), target: (usize, usize)) -> Self {
+        assert!(
+            source.0 > 0 && source.1 > 0 && target.0 > 0 && target.1 > 0,
+            "Cannot scale from {}x{} to {}x{}",
+            source.0,
+            source.1,
+            target.0,
+            target.1
+        );
+        Self { source, target }
+    }
+
+    /// Returns the size of the source grid, as `(width, height)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::scale::Scale;
+    ///
+    /// assert_eq!(Scale::new((10, 8), (5, 4)).source(), (10, 8));
+    /// ```
+    pub fn source(&self) -> (usize, usize) {
+        self.source
+    }
+
+    /// Returns the size of the target grid, as `(width, height)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::scale::Scale;
+    ///
+    /// assert_eq!(Scale::new((10, 8), (5, 4)).target(), (5, 4));
+    /// ```
+    pub fn target(&self) -> (usize, usize) {
+        self.target
+    }
+
+    /// Returns the block of source cells covered by the target cell at `point`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{point::Rect, scale::Scale};
+    ///
+    /// let scale = Scale::new((5, 5), (3, 3));
+    /// assert_eq!(scale.to_source((0, 0)), Rect::new(0, 0, 2, 2));
+    /// assert_eq!(scale.to_source((2, 1)), Rect::new(4, 2, 1, 2));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is outside of the target size.
+    pub fn to_source(&self, point: impl Point) -> Rect {
+        assert!(
+            point.x() < self.target.0 && point.y() < self.target.1,
+            "Point ({}, {}) out of bounds of {}x{} target",
+            point.x(),
+            point.y(),
+            self.target.0,
+            self.target.1
+        );
+        let (x, width) = block(point.x(), self.source.0, self.target.0);
+        let (y, height) = block(point.y(), self.source.1, self.target.1);
+        Rect::new(x, y, width, height)
+    }
+
+    /// Returns the target cell that covers the source cell at `point`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::scale::Scale;
+    ///
+    /// let scale = Scale::new((5, 5), (3, 3));
+    /// assert_eq!(scale.to_target((1, 1)), (0, 0));
+    /// assert_eq!(scale.to_target((4, 2)), (2, 1));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is outside of the source size.
+    pub fn to_target(&self, point: impl Point) -> (usize, usize) {
+        assert!(
+            point.x() < self.source.0 && point.y() < self.source.1,
+            "Point ({}, {}) out of bounds of {}x{} source",
+            point.x(),
+            point.y(),
+            self.source.0,
+            self.source.1
+        );
+        (
+            point.x() * self.target.0 / self.source.0,
+            point.y() * self.target.1 / self.source.1,
+        )
+    }
+}
+
+/// Returns the start and length of the block of `source` cells covered by target cell `i`.
+fn block(i: usize, source: usize, target: usize) -> (usize, usize) {
+    let start = (i * source).div_ceil(target);
+    let end = ((i + 1) * source).div_ceil(target);
+    if end > start {
+        (start, end - start)
+    } else {
+        ((i * source / target).min(source - 1), 1)
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Creates a `target_width` by `target_height` grid, where each cell is computed by `reducer`
+    /// from the block of cells it covers in this grid (in row-major order).
+    ///
+    /// Returns the new grid and the [`Scale`] that maps between the two.
+    ///
+    /// # Examples
+    ///
+    /// Most important tile wins:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::from(vec![
+    ///     "..~~".chars().collect(),
+    ///     ".@~~".chars().collect(),
+    ///     "....".chars().collect(),
+    ///     "....".chars().collect(),
+    /// ]);
+    ///
+    /// let priority = |c: &char| "~.@".find(*c).unwrap();
+    /// let (minimap, scale) = grid.minimap(2, 2, |block| {
+    ///     **block.iter().max_by_key(|c| priority(c)).unwrap()
+    /// });
+    ///
+    /// assert_eq!(minimap.to_matrix(), vec![vec!['@', '~'], vec!['.', '.']]);
+    /// assert_eq!(scale.to_target((1, 1)), (0, 0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty, or either target dimension is `0`.
+    pub fn minimap<U>(
+        &self,
+        target_width: usize,
+        target_height: usize,
+        mut reducer: impl FnMut(&[&T]) -> U,
+    ) -> (Grid<U>, Scale)
+    where
+        U: Clone,
+    {
+        let scale = Scale::new((self.width(), self.height()), (target_width, target_height));
+        let mut data = Vec::with_capacity(target_width * target_height);
+        let mut block = Vec::new();
+        for ty in 0..target_height {
+            for tx in 0..target_width {
+                block.clear();
+                block.extend(
+                    scale
+                        .to_source((tx, ty))
+                        .iter_points()
+                        .map(|point| &self[point]),
+                );
+                data.push(reducer(&block));
+            }
+        }
+        (Grid::with_width(target_width, data), scale)
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Creates a `target_width` by `target_height` grid, where each cell is the average of
+    /// `value(cell)` over the area of this grid it covers.
+    ///
+    /// Unlike [`Grid::minimap`], blocks may partially cover source cells, which contribute in
+    /// proportion to the area covered (i.e. a box filter), so downsampling by a non-integer factor
+    /// is smooth instead of aliased.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![true, false, false]);
+    /// let averaged = grid.area_average_with(2, 1, |cell| if *cell { 1.0 } else { 0.0 });
+    ///
+    /// assert_eq!(averaged.as_vec(), &vec![2.0 / 3.0, 0.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty, or either target dimension is `0`.
+    pub fn area_average_with(
+        &self,
+        target_width: usize,
+        target_height: usize,
+        mut value: impl FnMut(&T) -> f64,
+    ) -> Grid<f64> {
+        let data = self
+            .area_average_channels(target_width, target_height, |cell| [value(cell)])
+            .into_iter()
+            .map(|[value]| value)
+            .collect();
+        Grid::with_width(target_width, data)
+    }
+
+    /// Returns the area-weighted average of each channel of `channels(cell)`, for each cell of a
+    /// `target_width` by `target_height` grid, in row-major order.
+    fn area_average_channels<const N: usize>(
+        &self,
+        target_width: usize,
+        target_height: usize,
+        mut channels: impl FnMut(&T) -> [f64; N],
+    ) -> Vec<[f64; N]> {
+        let scale = Scale::new((self.width(), self.height()), (target_width, target_height));
+        let columns = coverage(scale.source.0, scale.target.0);
+        let rows = coverage(scale.source.1, scale.target.1);
+        let values: Vec<[f64; N]> = self.as_vec().iter().map(&mut channels).collect();
+        let mut data = Vec::with_capacity(target_width * target_height);
+        for row in &rows {
+            for column in &columns {
+                let mut sums = [0.0; N];
+                let mut total = 0.0;
+                for (y, height) in row {
+                    for (x, width) in column {
+                        let weight = height * width;
+                        let value = values[(*x, *y).to_index(scale.source.0)];
+                        for (sum, channel) in sums.iter_mut().zip(value) {
+                            *sum += channel * weight;
+                        }
+                        total += weight;
+                    }
+                }
+                data.push(sums.map(|sum| sum / total));
+            }
+        }
+        data
+    }
+}
+
+impl Grid<f64> {
+    /// Creates a `target_width` by `target_height` grid, where each cell is the average of the
+    /// area of this grid it covers.
+    ///
+    /// See [`Grid::area_average_with`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(4, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+    /// assert_eq!(grid.area_average(2, 1).as_vec(), &vec![2.5, 4.5]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty, or either target dimension is `0`.
+    pub fn area_average(&self, target_width: usize, target_height: usize) -> Grid<f64> {
+        self.area_average_with(target_width, target_height, |cell| *cell)
+    }
+}
+
+impl Grid<u8> {
+    /// Creates a `target_width` by `target_height` grid, where each cell is the average of the
+    /// area of this grid it covers, rounded to the nearest value.
+    ///
+    /// See [`Grid::area_average_with`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![0, 255, 255]);
+    /// assert_eq!(grid.area_average(2, 1).as_vec(), &vec![85, 255]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty, or either target dimension is `0`.
+    pub fn area_average(&self, target_width: usize, target_height: usize) -> Grid<u8> {
+        let data = self
+            .area_average_channels(target_width, target_height, |cell| [f64::from(*cell)])
+            .into_iter()
+            .map(|[value]| value.round() as u8)
+            .collect();
+        Grid::with_width(target_width, data)
+    }
+}
+
+impl Grid<[u8; 3]> {
+    /// Creates a `target_width` by `target_height` grid, where each cell is the average RGB color of
+    /// the area of this grid it covers, rounded to the nearest value.
+    ///
+    /// See [`Grid::area_average_with`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(2, vec![[255, 0, 0], [0, 0, 255]]);
+    /// assert_eq!(grid.area_average(1, 1).as_vec(), &vec![[128, 0, 128]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty, or either target dimension is `0`.
+    pub fn area_average(&self, target_width: usize, target_height: usize) -> Grid<[u8; 3]> {
+        let data = self
+            .area_average_channels(target_width, target_height, |cell| cell.map(f64::from))
+            .into_iter()
+            .map(|color| color.map(|channel| channel.round() as u8))
+            .collect();
+        Grid::with_width(target_width, data)
+    }
+}
+
+/// Returns, for each of `target` cells, the `source` cells it overlaps and the length of each
+/// overlap, where each target cell spans `source / target` source cells.
+///
+/// Overlaps are measured in units of `1 / target` source cells, so that they are exact.
+fn coverage(source: usize, target: usize) -> Vec<Vec<(usize, f64)>> {
+    (0..target)
+        .map(|i| {
+            let (start, end) = (i * source, (i + 1) * source);
+            (start / target..end.div_ceil(target))
+                .map(|cell| {
+                    let overlap = end.min((cell + 1) * target) - start.max(cell * target);
+                    (cell, overlap as f64)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_blocks_partition_source() {
+        let scale = Scale::new((7, 5), (3, 2));
+        let mut covered = Grid::new(7, 5, 0);
+        for ty in 0..2 {
+            for tx in 0..3 {
+                for point in scale.to_source((tx, ty)).iter_points() {
+                    covered[point] += 1;
+                    assert_eq!(scale.to_target(point), (tx, ty));
+                }
+            }
+        }
+
+        assert!(covered.as_vec().iter().all(|c| *c == 1));
+    }
+
+    #[test]
+    fn scale_upsampling_uses_nearest() {
+        let scale = Scale::new((2, 1), (3, 1));
+
+        assert_eq!(scale.to_source((0, 0)), Rect::new(0, 0, 1, 1));
+        assert_eq!(scale.to_source((1, 0)), Rect::new(1, 0, 1, 1));
+        assert_eq!(scale.to_source((2, 0)), Rect::new(1, 0, 1, 1));
+    }
+
+    #[test]
+    fn minimap_block_sizes() {
+        let grid = Grid::new(5, 5, 1);
+        let (minimap, _) = grid.minimap(3, 3, |block| block.len());
+
+        assert_eq!(minimap.as_vec(), &vec![4, 4, 2, 4, 4, 2, 2, 2, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn minimap_zero_target() {
+        Grid::new(5, 5, 1).minimap(0, 3, |block| block.len());
+    }
+
+    #[test]
+    fn area_average_preserves_mean() {
+        let grid = Grid::with_width(7, (0..35).map(f64::from).collect());
+        let averaged = grid.area_average(3, 2);
+        let mean = |grid: &Grid<f64>| grid.as_vec().iter().sum::<f64>() / grid.area() as f64;
+
+        assert!((mean(&averaged) - mean(&grid)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn area_average_upsampling() {
+        let grid = Grid::with_width(2, vec![0.0, 4.0]);
+
+        assert_eq!(grid.area_average(4, 1).as_vec(), &vec![0.0, 0.0, 4.0, 4.0]);
+        assert_eq!(grid.area_average(3, 1).as_vec(), &vec![0.0, 2.0, 4.0]);
+    }
+}
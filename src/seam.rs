@@ -0,0 +1,213 @@
+//! Content-aware resizing using [seam carving], i.e. for shrinking image-like grids.
+//!
+//! See [`seam_carve`] for details.
+//!
+//! [seam carving]: https://en.wikipedia.org/wiki/Seam_carving
+
+use crate::{grid::Grid, point::Direction};
+
+/// Returns the energy of each cell of `grid`, as the sum of the absolute differences between the
+/// cell and each of its (up to four) cardinal neighbors.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, seam::energy};
+///
+/// let grid = Grid::with_width(3, vec![0.0, 0.0, 6.0]);
+/// assert_eq!(energy(&grid).as_vec(), &vec![0.0, 6.0, 6.0]);
+/// ```
+pub fn energy(grid: &Grid<f64>) -> Grid<f64> {
+    let (width, height) = grid.size();
+    if width == 0 {
+        return Grid::from(Vec::new());
+    }
+    let data = (0..width * height)
+        .map(|i| {
+            let (x, y) = (i % width, i / width);
+            let cell = grid[(x, y)];
+            Direction::CARDINAL
+                .iter()
+                .filter_map(|direction| grid.neighbor((x, y), *direction))
+                .map(|neighbor| (cell - neighbor).abs())
+                .sum()
+        })
+        .collect();
+    Grid::with_width(width, data)
+}
+
+/// Returns the vertical seam with the lowest total `energy`, as the x-coordinate of the seam in
+/// each row from top to bottom.
+///
+/// A seam is 8-connected, so the x-coordinate changes by at most one between rows. Ties are
+/// broken towards the left.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, seam::find_seam};
+///
+/// let energy = Grid::with_width(3, vec![
+///     5.0, 1.0, 5.0,
+///     5.0, 5.0, 1.0,
+///     5.0, 1.0, 5.0,
+/// ]);
+///
+/// assert_eq!(find_seam(&energy), vec![1, 2, 1]);
+/// ```
+pub fn find_seam(energy: &Grid<f64>) -> Vec<usize> {
+    let (width, height) = energy.size();
+    if width == 0 {
+        return Vec::new();
+    }
+
+    // Cheapest total energy of a seam from the top row to each cell.
+    let mut totals = energy.clone();
+    for y in 1..height {
+        for x in 0..width {
+            let (_, above) = cheapest_above(&totals, x, y);
+            totals[(x, y)] += above;
+        }
+    }
+
+    let last = (0..width)
+        .map(|x| totals[(x, height - 1)])
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(x, _)| x)
+        .expect("Grid is not empty");
+    let mut seam = vec![last; height];
+    for y in (1..height).rev() {
+        seam[y - 1] = cheapest_above(&totals, seam[y], y).0;
+    }
+    seam
+}
+
+/// Returns the x-coordinate and total of the cheapest cell in row `y - 1` adjacent to `x`.
+fn cheapest_above(totals: &Grid<f64>, x: usize, y: usize) -> (usize, f64) {
+    (x.saturating_sub(1)..=(x + 1).min(totals.width() - 1))
+        .map(|ax| (ax, totals[(ax, y - 1)]))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .expect("At least one cell is above")
+}
+
+/// Returns a copy of `grid` narrowed to `target_width`, by repeatedly removing the vertical seam
+/// with the lowest [`energy`].
+///
+/// Unlike scaling, seam carving removes cells from low-detail areas first, preserving edges and
+/// other high-contrast features. See [`seam_carve_height`] for the horizontal variant.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, seam::seam_carve};
+///
+/// let grid = Grid::with_width(4, vec![
+///     0.0, 0.0, 9.0, 0.0,
+///     0.0, 0.0, 9.0, 0.0,
+/// ]);
+///
+/// assert_eq!(seam_carve(&grid, 3).to_matrix(), vec![
+///     vec![0.0, 9.0, 0.0],
+///     vec![0.0, 9.0, 0.0],
+/// ]);
+/// ```
+///
+/// # Panics
+///
+/// If `target_width` is `0` or greater than the width of `grid`.
+pub fn seam_carve(grid: &Grid<f64>, target_width: usize) -> Grid<f64> {
+    let (width, _) = grid.size();
+    assert!(
+        target_width > 0 && target_width <= width,
+        "Cannot seam carve grid of width {width} to width {target_width}"
+    );
+    let mut carved = grid.clone();
+    for _ in target_width..width {
+        let seam = find_seam(&energy(&carved));
+        carved = remove_seam(&carved, &seam);
+    }
+    carved
+}
+
+/// Returns a copy of `grid` shortened to `target_height`, by repeatedly removing the horizontal
+/// seam with the lowest [`energy`].
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, seam::seam_carve_height};
+///
+/// let grid = Grid::with_width(2, vec![
+///     0.0, 0.0,
+///     9.0, 9.0,
+///     0.0, 0.0,
+///     0.0, 0.0,
+/// ]);
+///
+/// assert_eq!(seam_carve_height(&grid, 3).to_matrix(), vec![
+///     vec![0.0, 0.0],
+///     vec![9.0, 9.0],
+///     vec![0.0, 0.0],
+/// ]);
+/// ```
+///
+/// # Panics
+///
+/// If `target_height` is `0` or greater than the height of `grid`.
+pub fn seam_carve_height(grid: &Grid<f64>, target_height: usize) -> Grid<f64> {
+    let (_, height) = grid.size();
+    assert!(
+        target_height > 0 && target_height <= height,
+        "Cannot seam carve grid of height {height} to height {target_height}"
+    );
+    seam_carve(&grid.transpose(), target_height).transpose()
+}
+
+/// Returns a copy of `grid` without the cell at `seam[y]` in each row `y`.
+fn remove_seam(grid: &Grid<f64>, seam: &[usize]) -> Grid<f64> {
+    let width = grid.width();
+    let data = grid
+        .as_vec()
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| i % width != seam[i / width])
+        .map(|(_, cell)| *cell)
+        .collect();
+    Grid::with_width(width - 1, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seam_carve_preserves_feature() {
+        let grid = Grid::with_width(
+            8,
+            (0..48)
+                .map(|i| if i % 8 == 5 || i % 8 == 6 { 100.0 } else { 1.0 })
+                .collect(),
+        );
+        let carved = seam_carve(&grid, 4);
+
+        assert_eq!(carved.width(), 4);
+        assert_eq!(carved.height(), 6);
+        assert_eq!(carved.count(|cell| *cell == 100.0), 12);
+    }
+
+    #[test]
+    fn find_seam_is_connected() {
+        let energy = Grid::with_width(5, (0..25).map(|i| ((i * 37) % 11) as f64).collect());
+        let seam = find_seam(&energy);
+
+        assert_eq!(seam.len(), 5);
+        assert!(seam.windows(2).all(|pair| pair[0].abs_diff(pair[1]) <= 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot seam carve grid of width 2 to width 3")]
+    fn seam_carve_wider() {
+        seam_carve(&Grid::new(2, 2, 0.0), 3);
+    }
+}
@@ -0,0 +1,330 @@
+//! Simulating incompressible fluids on a staggered grid, in the style of [Stable Fluids].
+//!
+//! Requires the `sim` feature.
+//!
+//! [Stable Fluids]: https://pages.cs.wisc.edu/~chaol/data/cs777/stam-stable_fluids.pdf
+//!
+//! See [`Fluid`] for details.
+
+use crate::{edge::EdgeGrid, grid::Grid, point::Connectivity};
+
+/// A 2-dimensional incompressible fluid on a [staggered (MAC) grid] of `width` by `height` cells.
+///
+/// [staggered (MAC) grid]: https://en.wikipedia.org/wiki/Staggered_grid
+///
+/// Density is stored at the center of each cell, while velocity is stored on the edges between
+/// cells: the horizontal component on [vertical edges](EdgeGrid::vertical_edges), and the vertical
+/// component on [horizontal edges](EdgeGrid::horizontal_edges). The borders of the grid are solid
+/// walls, and cells are `1` unit wide.
+///
+/// # Examples
+///
+/// ```
+/// use grud::sim::Fluid;
+///
+/// let mut fluid = Fluid::new(8, 8);
+/// fluid.density_mut()[(4, 4)] = 1.0;
+/// fluid.velocity_mut().horizontal_edges_mut()[(4, 4)] = -2.0;
+///
+/// fluid.step(0.1, 200);
+///
+/// let divergence = fluid.divergence();
+/// assert!(divergence.as_vec().iter().all(|d| d.abs() < 1e-6));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Fluid {
+    velocity: EdgeGrid<f64>,
+    density: Grid<f64>,
+}
+
+impl Fluid {
+    /// Creates a fluid at rest of `width` by `height` cells, with a density of `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::sim::Fluid;
+    ///
+    /// let fluid = Fluid::new(4, 3);
+    /// assert_eq!(fluid.density().width(), 4);
+    /// assert_eq!(fluid.velocity().height(), 3);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width` or `height` is `0`.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            velocity: EdgeGrid::new(width, height, 0.0),
+            density: Grid::new(width, height, 0.0),
+        }
+    }
+
+    /// Returns the velocity, stored on the edges between cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::sim::Fluid;
+    ///
+    /// let fluid = Fluid::new(4, 3);
+    /// assert_eq!(fluid.velocity().between((0, 0), (1, 0)), Some(&0.0));
+    /// ```
+    pub fn velocity(&self) -> &EdgeGrid<f64> {
+        &self.velocity
+    }
+
+    /// Returns the velocity as mutable, stored on the edges between cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::sim::Fluid;
+    ///
+    /// let mut fluid = Fluid::new(4, 3);
+    /// *fluid.velocity_mut().between_mut((0, 0), (1, 0)).unwrap() = 1.0;
+    /// ```
+    pub fn velocity_mut(&mut self) -> &mut EdgeGrid<f64> {
+        &mut self.velocity
+    }
+
+    /// Returns the density, stored at the center of each cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::sim::Fluid;
+    ///
+    /// let fluid = Fluid::new(4, 3);
+    /// assert_eq!(fluid.density()[(0, 0)], 0.0);
+    /// ```
+    pub fn density(&self) -> &Grid<f64> {
+        &self.density
+    }
+
+    /// Returns the density as mutable, stored at the center of each cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::sim::Fluid;
+    ///
+    /// let mut fluid = Fluid::new(4, 3);
+    /// fluid.density_mut()[(1, 1)] = 1.0;
+    /// ```
+    pub fn density_mut(&mut self) -> &mut Grid<f64> {
+        &mut self.density
+    }
+
+    /// Advances the simulation by `dt`, using `iterations` to solve for pressure.
+    ///
+    /// This is the same as calling [`Fluid::advect`], followed by [`Fluid::project`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::sim::Fluid;
+    ///
+    /// let mut fluid = Fluid::new(4, 4);
+    /// fluid.step(0.1, 20);
+    /// ```
+    pub fn step(&mut self, dt: f64, iterations: usize) {
+        self.advect(dt);
+        self.project(iterations);
+    }
+
+    /// Moves velocity and density along the velocity field by `dt`.
+    ///
+    /// Uses semi-Lagrangian advection: each value is traced backwards through the velocity field
+    /// and bilinearly sampled from where it came from, which is unconditionally stable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::sim::Fluid;
+    ///
+    /// let mut fluid = Fluid::new(3, 1);
+    /// fluid.density_mut()[(0, 0)] = 1.0;
+    /// for x in 0..4 {
+    ///     fluid.velocity_mut().vertical_edges_mut()[(x, 0)] = 1.0;
+    /// }
+    ///
+    /// fluid.advect(1.0);
+    /// assert_eq!(fluid.density().as_vec(), &vec![1.0, 1.0, 0.0]);
+    /// ```
+    pub fn advect(&mut self, dt: f64) {
+        let u = self.velocity.vertical_edges();
+        let v = self.velocity.horizontal_edges();
+        let velocity_at = |x: f64, y: f64| (sample(u, x, y - 0.5), sample(v, x - 0.5, y));
+        let trace = |x: f64, y: f64| {
+            let (vx, vy) = velocity_at(x, y);
+            (x - dt * vx, y - dt * vy)
+        };
+
+        let next_u = map(u, |i, j| {
+            let (x, y) = trace(i, j + 0.5);
+            sample(u, x, y - 0.5)
+        });
+        let next_v = map(v, |i, j| {
+            let (x, y) = trace(i + 0.5, j);
+            sample(v, x - 0.5, y)
+        });
+        let next_density = map(&self.density, |i, j| {
+            let (x, y) = trace(i + 0.5, j + 0.5);
+            sample(&self.density, x - 0.5, y - 0.5)
+        });
+
+        self.velocity.vertical_edges_mut().replace(next_u);
+        self.velocity.horizontal_edges_mut().replace(next_v);
+        self.density = next_density;
+    }
+
+    /// Removes divergence from the velocity field, making the fluid incompressible.
+    ///
+    /// Velocity through the borders of the grid is set to `0`, and pressure is solved for using
+    /// `iterations` of [Gauss-Seidel relaxation]; more iterations are more accurate.
+    ///
+    /// [Gauss-Seidel relaxation]: https://en.wikipedia.org/wiki/Gauss%E2%80%93Seidel_method
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::sim::Fluid;
+    ///
+    /// let mut fluid = Fluid::new(2, 1);
+    /// fluid.velocity_mut().vertical_edges_mut()[(0, 0)] = 1.0;
+    ///
+    /// fluid.project(1);
+    /// assert_eq!(fluid.velocity().vertical_edges().as_vec(), &vec![0.0, 0.0, 0.0]);
+    /// ```
+    pub fn project(&mut self, iterations: usize) {
+        let (width, height) = (self.velocity.width(), self.velocity.height());
+        {
+            let mut u = self.velocity.vertical_edges_mut();
+            for y in 0..height {
+                u[(0, y)] = 0.0;
+                u[(width, y)] = 0.0;
+            }
+            let mut v = self.velocity.horizontal_edges_mut();
+            for x in 0..width {
+                v[(x, 0)] = 0.0;
+                v[(x, height)] = 0.0;
+            }
+        }
+
+        let divergence = self.divergence();
+        let mut pressure = Grid::new(width, height, 0.0);
+        for _ in 0..iterations {
+            for y in 0..height {
+                for x in 0..width {
+                    let (mut sum, mut count) = (0.0, 0.0);
+                    for neighbor in Connectivity::Four.neighbors((x, y), width, height) {
+                        sum += pressure[neighbor];
+                        count += 1.0;
+                    }
+                    if count > 0.0 {
+                        pressure[(x, y)] = (sum - divergence[(x, y)]) / count;
+                    }
+                }
+            }
+        }
+
+        let mut u = self.velocity.vertical_edges_mut();
+        for y in 0..height {
+            for x in 1..width {
+                u[(x, y)] -= pressure[(x, y)] - pressure[(x - 1, y)];
+            }
+        }
+        let mut v = self.velocity.horizontal_edges_mut();
+        for y in 1..height {
+            for x in 0..width {
+                v[(x, y)] -= pressure[(x, y)] - pressure[(x, y - 1)];
+            }
+        }
+    }
+
+    /// Returns the divergence of the velocity field at each cell, i.e. the net flow out of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::sim::Fluid;
+    ///
+    /// let mut fluid = Fluid::new(2, 1);
+    /// fluid.velocity_mut().vertical_edges_mut()[(1, 0)] = 1.0;
+    ///
+    /// assert_eq!(fluid.divergence().as_vec(), &vec![1.0, -1.0]);
+    /// ```
+    pub fn divergence(&self) -> Grid<f64> {
+        let u = self.velocity.vertical_edges();
+        let v = self.velocity.horizontal_edges();
+        map(&self.density, |x, y| {
+            let (x, y) = (x as usize, y as usize);
+            (u[(x + 1, y)] - u[(x, y)]) + (v[(x, y + 1)] - v[(x, y)])
+        })
+    }
+}
+
+/// Creates a grid the same size as `grid`, computing each cell from its position.
+fn map(grid: &Grid<f64>, mut f: impl FnMut(f64, f64) -> f64) -> Grid<f64> {
+    let mut data = Vec::with_capacity(grid.area());
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            data.push(f(x as f64, y as f64));
+        }
+    }
+    Grid::with_width(grid.width(), data)
+}
+
+/// Bilinearly samples `grid` at `(x, y)`, clamping to the edges of the grid.
+fn sample(grid: &Grid<f64>, x: f64, y: f64) -> f64 {
+    let x = x.clamp(0.0, (grid.width() - 1) as f64);
+    let y = y.clamp(0.0, (grid.height() - 1) as f64);
+    let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+    let (x1, y1) = (
+        (x0 + 1).min(grid.width() - 1),
+        (y0 + 1).min(grid.height() - 1),
+    );
+    let (fx, fy) = (x - x0 as f64, y - y0 as f64);
+    let top = grid[(x0, y0)] * (1.0 - fx) + grid[(x1, y0)] * fx;
+    let bottom = grid[(x0, y1)] * (1.0 - fx) + grid[(x1, y1)] * fx;
+    top * (1.0 - fy) + bottom * fy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_bilinear() {
+        let grid = Grid::with_width(2, vec![0.0, 1.0, 2.0, 3.0]);
+
+        assert_eq!(sample(&grid, 0.5, 0.5), 1.5);
+        assert_eq!(sample(&grid, -1.0, 5.0), 2.0);
+    }
+
+    #[test]
+    fn project_removes_divergence() {
+        let mut fluid = Fluid::new(6, 5);
+        let mut u = fluid.velocity_mut().vertical_edges_mut();
+        u[(2, 2)] = 3.0;
+        u[(4, 1)] = -1.0;
+        fluid.velocity_mut().horizontal_edges_mut()[(3, 3)] = 2.0;
+
+        fluid.project(200);
+
+        for d in fluid.divergence().as_vec() {
+            assert!(d.abs() < 1e-6, "divergence {d}");
+        }
+    }
+
+    #[test]
+    fn advect_at_rest_is_unchanged() {
+        let mut fluid = Fluid::new(3, 3);
+        fluid.density_mut()[(1, 1)] = 1.0;
+        fluid.advect(1.0);
+
+        assert_eq!(fluid.density()[(1, 1)], 1.0);
+        assert_eq!(fluid.density().as_vec().iter().sum::<f64>(), 1.0);
+    }
+}
@@ -0,0 +1,332 @@
+//! Traversing grids in spiral order, i.e. for matrix puzzles or searching for the nearest cell.
+//!
+//! See [`Grid::iter_spiral`] and [`spiral_from`] for details.
+
+use std::iter::FusedIterator;
+
+use crate::{
+    grid::Grid,
+    point::{Direction, Point},
+};
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns an iterator over every cell, spiraling clockwise inward from the top-left corner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![
+    ///     1, 2, 3,
+    ///     4, 5, 6,
+    ///     7, 8, 9,
+    /// ]);
+    ///
+    /// let cells: Vec<_> = grid.iter_spiral().copied().collect();
+    /// assert_eq!(cells, vec![1, 2, 3, 6, 9, 8, 7, 4, 5]);
+    /// ```
+    pub fn iter_spiral(&self) -> Spiral<'_, T> {
+        let (width, height) = self.size();
+        Spiral {
+            grid: self,
+            x: 0,
+            y: 0,
+            direction: Direction::East,
+            left: 0,
+            top: 0,
+            right: width.saturating_sub(1),
+            bottom: height.saturating_sub(1),
+            remaining: width * height,
+        }
+    }
+
+    /// Returns an iterator over every point of the grid, spiraling clockwise outward from
+    /// `center`.
+    ///
+    /// This is the same as [`spiral_from`] with the size of this grid.
+    ///
+    /// # Examples
+    ///
+    /// Finding the nearest matching cell, by ring distance:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(4, vec![
+    ///     '.', '.', '.', '#',
+    ///     '.', '.', '.', '.',
+    ///     '#', '.', '.', '.',
+    /// ]);
+    ///
+    /// let nearest = grid.spiral_from((2, 1)).find(|point| grid[*point] == '#');
+    /// assert_eq!(nearest, Some((3, 0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `center` is outside of the grid.
+    pub fn spiral_from(&self, center: impl Point) -> SpiralFrom {
+        let (width, height) = self.size();
+        spiral_from(center, width, height)
+    }
+}
+
+/// An iterator over the cells of a grid, spiraling clockwise inward.
+///
+/// This struct is created by [`Grid::iter_spiral`].
+#[derive(Debug)]
+pub struct Spiral<'a, T>
+where
+    T: Clone,
+{
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+    direction: Direction,
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Spiral<'a, T>
+where
+    T: Clone,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let cell = &self.grid[(self.x, self.y)];
+        if self.remaining > 0 {
+            // Walk along the edge until reaching a corner, then shrink the bounds behind us and
+            // turn; the remaining count guarantees the next position is always within bounds.
+            match self.direction {
+                Direction::East if self.x < self.right => self.x += 1,
+                Direction::East => {
+                    self.top += 1;
+                    self.y += 1;
+                    self.direction = Direction::South;
+                }
+                Direction::South if self.y < self.bottom => self.y += 1,
+                Direction::South => {
+                    self.right -= 1;
+                    self.x -= 1;
+                    self.direction = Direction::West;
+                }
+                Direction::West if self.x > self.left => self.x -= 1,
+                Direction::West => {
+                    self.bottom -= 1;
+                    self.y -= 1;
+                    self.direction = Direction::North;
+                }
+                Direction::North if self.y > self.top => self.y -= 1,
+                _ => {
+                    self.left += 1;
+                    self.x += 1;
+                    self.direction = Direction::East;
+                }
+            }
+        }
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Spiral<'_, T> where T: Clone {}
+
+impl<T> FusedIterator for Spiral<'_, T> where T: Clone {}
+
+/// Returns an iterator over every point of a `width` by `height` grid, spiraling clockwise
+/// outward from `center`.
+///
+/// The spiral starts at `center`, steps east, and turns clockwise, visiting every ring of points
+/// at [Chebyshev distance] `1`, then `2`, and so on; points outside of the grid are skipped. This
+/// is useful for searching for the nearest matching cell.
+///
+/// [Chebyshev distance]: https://en.wikipedia.org/wiki/Chebyshev_distance
+///
+/// # Examples
+///
+/// ```
+/// use grud::spiral::spiral_from;
+///
+/// let points: Vec<_> = spiral_from((1, 1), 3, 3).collect();
+/// assert_eq!(points, vec![
+///     (1, 1), (2, 1), (2, 2), (1, 2), (0, 2), (0, 1), (0, 0), (1, 0), (2, 0),
+/// ]);
+///
+/// let points: Vec<_> = spiral_from((0, 0), 2, 2).collect();
+/// assert_eq!(points, vec![(0, 0), (1, 0), (1, 1), (0, 1)]);
+/// ```
+///
+/// # Panics
+///
+/// If `center` is outside of the grid.
+pub fn spiral_from(center: impl Point, width: usize, height: usize) -> SpiralFrom {
+    assert!(
+        center.x() < width && center.y() < height,
+        "Point ({}, {}) out of bounds of {width}x{height} grid",
+        center.x(),
+        center.y()
+    );
+    let signed = |n: usize| isize::try_from(n).expect("Size larger than isize::MAX");
+    SpiralFrom {
+        width: signed(width),
+        height: signed(height),
+        x: signed(center.x()),
+        y: signed(center.y()),
+        direction: Direction::East,
+        legs: 0,
+        // Before the first leg, offset 0 (the center itself) is the only point to yield.
+        next: 0,
+        end: 1,
+        remaining: width * height,
+    }
+}
+
+/// An iterator over the points of a grid, spiraling clockwise outward.
+///
+/// This struct is created by [`spiral_from`] and [`Grid::spiral_from`].
+#[derive(Clone, Debug)]
+pub struct SpiralFrom {
+    width: isize,
+    height: isize,
+    x: isize,
+    y: isize,
+    direction: Direction,
+    legs: usize,
+    next: usize,
+    end: usize,
+    remaining: usize,
+}
+
+impl SpiralFrom {
+    /// Moves to the start of the next leg, and finds the offsets along it that are in bounds.
+    fn next_leg(&mut self) {
+        let length = (self.legs / 2 + 1) as isize;
+        let (dx, dy) = self.direction.delta();
+        if self.legs > 0 {
+            // The previous leg ends where this one starts.
+            let previous = ((self.legs - 1) / 2 + 1) as isize;
+            let (px, py) = self.direction.turn_left().delta();
+            self.x += px * previous;
+            self.y += py * previous;
+        }
+        let (x_start, x_end) = span(self.x, dx, self.width);
+        let (y_start, y_end) = span(self.y, dy, self.height);
+        let start = x_start.max(y_start).max(1);
+        let end = x_end.min(y_end).min(length + 1);
+        (self.next, self.end) = if start < end {
+            (start as usize, end as usize)
+        } else {
+            (0, 0)
+        };
+        self.legs += 1;
+    }
+}
+
+/// Returns the range of offsets `i` where `origin + delta * i` is within `0..len`.
+fn span(origin: isize, delta: isize, len: isize) -> (isize, isize) {
+    match delta {
+        0 if (0..len).contains(&origin) => (isize::MIN, isize::MAX),
+        0 => (0, 0),
+        1 => (-origin, len - origin),
+        _ => (origin - len + 1, origin + 1),
+    }
+}
+
+impl Iterator for SpiralFrom {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        while self.next >= self.end {
+            if self.legs > 0 {
+                self.direction = self.direction.turn_right();
+            }
+            self.next_leg();
+        }
+        let (dx, dy) = self.direction.delta();
+        let i = self.next as isize;
+        self.next += 1;
+        self.remaining -= 1;
+        Some(((self.x + dx * i) as usize, (self.y + dy * i) as usize))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for SpiralFrom {}
+
+impl FusedIterator for SpiralFrom {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spiral_single_row_and_column() {
+        let row = Grid::with_width(3, vec![1, 2, 3]);
+        let col = Grid::with_width(1, vec![1, 2, 3]);
+
+        assert_eq!(
+            row.iter_spiral().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            col.iter_spiral().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn spiral_rectangular() {
+        let grid = Grid::with_width(4, (0..12).collect::<Vec<_>>());
+
+        assert_eq!(
+            grid.iter_spiral().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 7, 11, 10, 9, 8, 4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn spiral_empty_grid() {
+        assert_eq!(Grid::<u8>::from(Vec::new()).iter_spiral().count(), 0);
+    }
+
+    #[test]
+    fn spiral_from_visits_every_point_once_by_ring() {
+        let (width, height) = (7, 3);
+        let center = (5, 2);
+        let points: Vec<_> = spiral_from(center, width, height).collect();
+        let ring = |(x, y): (usize, usize)| x.abs_diff(center.0).max(y.abs_diff(center.1));
+
+        let mut sorted = points.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), width * height);
+        assert!(points.windows(2).all(|w| ring(w[0]) <= ring(w[1])));
+    }
+
+    #[test]
+    #[should_panic]
+    fn spiral_from_outside_grid() {
+        spiral_from((3, 0), 3, 3);
+    }
+}
@@ -0,0 +1,405 @@
+//! Identifying cells across structural edits, i.e. for selections in editors.
+//!
+//! See [`StableGrid`] for details.
+
+use std::{
+    collections::HashMap,
+    ops::{Index, IndexMut},
+};
+
+use crate::{grid::Grid, point::Point};
+
+/// An identifier for a cell of a [`StableGrid`], which does not change as rows and columns are
+/// inserted or removed.
+///
+/// Identifiers are never reused within a grid, even after their cell is removed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CellId(u64);
+
+/// A grid where each cell carries a stable [`CellId`], so cells can be tracked as rows and columns
+/// are inserted or removed.
+///
+/// Cells are read and written with [`Index`] and [`IndexMut`]; writing a cell does not change its
+/// id. Structural edits (i.e. [`StableGrid::insert_row`]) only update the position of the cells
+/// they move, i.e. inserting a row updates the rows below it, and inserting a column updates the
+/// columns to its right.
+///
+/// # Examples
+///
+/// A selection that follows its cell:
+///
+/// ```
+/// use grud::{Grid, stable::StableGrid};
+///
+/// let mut grid = StableGrid::from_grid(Grid::with_width(2, vec!['a', 'b', 'c', 'd']));
+/// let selected = grid.id_at((1, 1));
+///
+/// grid.insert_row(0, ['x', 'y']);
+/// grid.remove_col(0);
+///
+/// assert_eq!(grid.position(selected), Some((0, 2)));
+/// assert_eq!(grid[(0, 2)], 'd');
+/// ```
+#[derive(Clone, Debug)]
+pub struct StableGrid<T>
+where
+    T: Clone,
+{
+    cells: Grid<T>,
+    ids: Grid<CellId>,
+    positions: HashMap<CellId, (usize, usize)>,
+    next_id: u64,
+}
+
+impl<T> StableGrid<T>
+where
+    T: Clone,
+{
+    /// Creates a `width` by `height` grid where every cell is `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::stable::StableGrid;
+    ///
+    /// let grid = StableGrid::new(3, 2, 0);
+    /// assert_eq!(grid.as_grid().as_vec(), &vec![0; 6]);
+    /// ```
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self::from_grid(Grid::new(width, height, value))
+    }
+
+    /// Creates a grid from the cells of `grid`, giving each cell a new id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, stable::StableGrid};
+    ///
+    /// let grid = StableGrid::from_grid(Grid::with_width(2, vec![1, 2]));
+    /// assert_ne!(grid.id_at((0, 0)), grid.id_at((1, 0)));
+    /// ```
+    pub fn from_grid(grid: Grid<T>) -> Self {
+        let (width, _) = grid.size();
+        let area = grid.as_vec().len() as u64;
+        let ids = if area == 0 {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(width, (0..area).map(CellId).collect())
+        };
+        let mut stable = Self {
+            cells: grid,
+            ids,
+            positions: HashMap::new(),
+            next_id: area,
+        };
+        stable.update_rows(0);
+        stable
+    }
+
+    /// Returns the cells, without ids.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::stable::StableGrid;
+    ///
+    /// let grid = StableGrid::new(2, 1, 'a');
+    /// assert_eq!(grid.as_grid().to_string(), "aa\n");
+    /// ```
+    pub fn as_grid(&self) -> &Grid<T> {
+        &self.cells
+    }
+
+    /// Returns the cells without ids, consuming the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::stable::StableGrid;
+    ///
+    /// let grid = StableGrid::new(2, 1, 'a');
+    /// assert_eq!(grid.into_grid().as_vec(), &vec!['a', 'a']);
+    /// ```
+    pub fn into_grid(self) -> Grid<T> {
+        self.cells
+    }
+
+    /// Returns the id of the cell at `point`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::stable::StableGrid;
+    ///
+    /// let grid = StableGrid::new(2, 2, ());
+    /// let id = grid.id_at((1, 0));
+    ///
+    /// assert_eq!(grid.position(id), Some((1, 0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds.
+    pub fn id_at(&self, point: impl Point) -> CellId {
+        self.ids[point]
+    }
+
+    /// Returns the current point of the cell with `id`, or `None` if it has been removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::stable::StableGrid;
+    ///
+    /// let mut grid = StableGrid::new(2, 2, ());
+    /// let id = grid.id_at((0, 1));
+    /// grid.remove_row(1);
+    ///
+    /// assert_eq!(grid.position(id), None);
+    /// ```
+    pub fn position(&self, id: CellId) -> Option<(usize, usize)> {
+        self.positions.get(&id).copied()
+    }
+
+    /// Inserts `row` as row `y` with new ids, shifting later rows down.
+    ///
+    /// See [`Grid::insert_row`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::stable::StableGrid;
+    ///
+    /// let mut grid = StableGrid::new(2, 1, 0);
+    /// let id = grid.id_at((0, 0));
+    /// grid.insert_row(0, [1, 1]);
+    ///
+    /// assert_eq!(grid.position(id), Some((0, 1)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `y` is greater than the height, or `row` is not the same length as the width.
+    pub fn insert_row(&mut self, y: usize, row: impl IntoIterator<Item = T>) {
+        let row: Vec<T> = row.into_iter().collect();
+        let ids = self.new_ids(row.len());
+        self.cells.insert_row(y, row);
+        self.ids.insert_row(y, ids);
+        self.update_rows(y);
+    }
+
+    /// Removes and returns row `y`, shifting later rows up.
+    ///
+    /// See [`Grid::remove_row`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, stable::StableGrid};
+    ///
+    /// let mut grid = StableGrid::from_grid(Grid::with_width(1, vec![1, 2]));
+    /// let id = grid.id_at((0, 1));
+    ///
+    /// assert_eq!(grid.remove_row(0), vec![1]);
+    /// assert_eq!(grid.position(id), Some((0, 0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `y` is out of bounds.
+    pub fn remove_row(&mut self, y: usize) -> Vec<T> {
+        let removed = self.cells.remove_row(y);
+        let ids = self.ids.remove_row(y);
+        self.forget(ids);
+        self.update_rows(y);
+        removed
+    }
+
+    /// Inserts `col` as column `x` with new ids, shifting later columns right.
+    ///
+    /// See [`Grid::insert_col`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::stable::StableGrid;
+    ///
+    /// let mut grid = StableGrid::new(1, 2, 0);
+    /// let id = grid.id_at((0, 1));
+    /// grid.insert_col(0, [1, 1]);
+    ///
+    /// assert_eq!(grid.position(id), Some((1, 1)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `x` is greater than the width, or `col` is not the same length as the height.
+    pub fn insert_col(&mut self, x: usize, col: impl IntoIterator<Item = T>) {
+        let col: Vec<T> = col.into_iter().collect();
+        let ids = self.new_ids(col.len());
+        self.cells.insert_col(x, col);
+        self.ids.insert_col(x, ids);
+        self.update_cols(x);
+    }
+
+    /// Removes and returns column `x`, shifting later columns left.
+    ///
+    /// See [`Grid::remove_col`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, stable::StableGrid};
+    ///
+    /// let mut grid = StableGrid::from_grid(Grid::with_width(2, vec![1, 2]));
+    /// let id = grid.id_at((1, 0));
+    ///
+    /// assert_eq!(grid.remove_col(0), vec![1]);
+    /// assert_eq!(grid.position(id), Some((0, 0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `x` is out of bounds.
+    pub fn remove_col(&mut self, x: usize) -> Vec<T> {
+        let removed = self.cells.remove_col(x);
+        let ids = self.ids.remove_col(x);
+        self.forget(ids);
+        self.update_cols(x);
+        removed
+    }
+
+    /// Returns `len` ids that have never been used.
+    fn new_ids(&mut self, len: usize) -> Vec<CellId> {
+        let start = self.next_id;
+        self.next_id += len as u64;
+        (start..self.next_id).map(CellId).collect()
+    }
+
+    /// Updates the position of every id in rows `y` and below, after they moved.
+    fn update_rows(&mut self, y: usize) {
+        let (width, height) = self.ids.size();
+        for y in y..height {
+            for x in 0..width {
+                self.positions.insert(self.ids[(x, y)], (x, y));
+            }
+        }
+    }
+
+    /// Updates the position of every id in columns `x` and to the right, after they moved.
+    fn update_cols(&mut self, x: usize) {
+        let (width, height) = self.ids.size();
+        for y in 0..height {
+            for x in x..width {
+                self.positions.insert(self.ids[(x, y)], (x, y));
+            }
+        }
+    }
+
+    /// Removes the positions of `ids`, after their cells were removed.
+    fn forget(&mut self, ids: Vec<CellId>) {
+        for id in ids {
+            self.positions.remove(&id);
+        }
+    }
+}
+
+impl<T, I> Index<I> for StableGrid<T>
+where
+    T: Clone,
+    I: Point,
+{
+    type Output = T;
+
+    /// Given a two-dimensional coordinate [`Point`], returns the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::stable::StableGrid;
+    ///
+    /// let grid = StableGrid::new(2, 2, 'a');
+    /// assert_eq!(grid[(1, 1)], 'a');
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index(&self, index: I) -> &Self::Output {
+        &self.cells[index]
+    }
+}
+
+impl<T, I> IndexMut<I> for StableGrid<T>
+where
+    T: Clone,
+    I: Point,
+{
+    /// Given a two-dimensional coordinate [`Point`], returns a mutable reference to the cell.
+    ///
+    /// The cell keeps its id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::stable::StableGrid;
+    ///
+    /// let mut grid = StableGrid::new(2, 2, 'a');
+    /// let id = grid.id_at((1, 1));
+    /// grid[(1, 1)] = 'b';
+    ///
+    /// assert_eq!(grid.id_at((1, 1)), id);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.cells[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stable_ids_never_reused() {
+        let mut grid = StableGrid::new(1, 1, 0);
+        let removed = grid.id_at((0, 0));
+        grid.remove_row(0);
+        grid.insert_row(0, [1]);
+
+        assert_ne!(grid.id_at((0, 0)), removed);
+        assert_eq!(grid.position(removed), None);
+    }
+
+    #[test]
+    fn stable_ids_follow_column_edits() {
+        let mut grid = StableGrid::from_grid(Grid::with_width(3, (0..6).collect::<Vec<_>>()));
+        let ids: Vec<_> = (0..3).map(|x| grid.id_at((x, 1))).collect();
+        grid.insert_col(3, [9, 9]);
+        grid.remove_col(0);
+
+        assert_eq!(grid.position(ids[0]), None);
+        assert_eq!(grid.position(ids[1]), Some((0, 1)));
+        assert_eq!(grid.position(ids[2]), Some((1, 1)));
+    }
+
+    #[test]
+    fn stable_ids_follow_row_edits() {
+        let mut grid = StableGrid::from_grid(Grid::with_width(2, (0..6).collect::<Vec<_>>()));
+        let ids: Vec<_> = (0..3).map(|y| grid.id_at((1, y))).collect();
+        grid.insert_row(1, [9, 9]);
+        grid.remove_row(0);
+
+        assert_eq!(grid.position(ids[0]), None);
+        assert_eq!(grid.position(ids[1]), Some((1, 1)));
+        assert_eq!(grid.position(ids[2]), Some((1, 2)));
+        for (point, id) in grid.ids.iter_in::<crate::order::RowMajor>() {
+            assert_eq!(grid.position(*id), Some(point));
+        }
+        assert_eq!(grid.positions.len(), 6);
+    }
+}
@@ -0,0 +1,151 @@
+//! Golden-file snapshot testing, i.e. for asserting on the rendered output of grid transformations.
+//!
+//! See [`assert_matches_snapshot`] for details.
+
+use std::{env, fmt::Display, fs, io, path::Path};
+
+use crate::grid::Grid;
+
+/// The environment variable that, when set to anything other than an empty string or `0`, makes
+/// snapshot assertions write the actual output to the snapshot file instead of comparing.
+pub const UPDATE_SNAPSHOTS: &str = "GRUD_UPDATE_SNAPSHOTS";
+
+/// Asserts that `grid`, rendered with [`Display`], matches the contents of the file at `path`.
+///
+/// If the [`UPDATE_SNAPSHOTS`] environment variable is set, the file (and any missing parent
+/// directories) is written with the rendered grid instead, i.e. to bless a new or changed
+/// snapshot:
+///
+/// ```sh
+/// GRUD_UPDATE_SNAPSHOTS=1 cargo test
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, testing::assert_matches_snapshot};
+///
+/// let path = std::env::temp_dir().join("grud_snapshot_example.txt");
+/// std::fs::write(&path, "#.\n.#\n").unwrap();
+///
+/// let grid = Grid::with_width(2, vec!['#', '.', '.', '#']);
+/// assert_matches_snapshot(&grid, &path);
+/// ```
+///
+/// # Panics
+///
+/// If the rendered grid does not match the snapshot, the snapshot does not exist, or reading or
+/// writing the snapshot fails.
+#[track_caller]
+pub fn assert_matches_snapshot<T>(grid: &Grid<T>, path: impl AsRef<Path>)
+where
+    T: Clone + Display,
+{
+    assert_matches_snapshot_with(grid, path, Grid::to_string);
+}
+
+/// Asserts that `grid`, rendered with `render`, matches the contents of the file at `path`.
+///
+/// See [`assert_matches_snapshot`] for details.
+///
+/// # Examples
+///
+/// Rendering with the Life RLE format:
+///
+/// ```
+/// use grud::{Grid, testing::assert_matches_snapshot_with};
+///
+/// let path = std::env::temp_dir().join("grud_snapshot_with_example.rle");
+/// std::fs::write(&path, "x = 2, y = 1\nbo!\n").unwrap();
+///
+/// let grid = Grid::with_width(2, vec![false, true]);
+/// assert_matches_snapshot_with(&grid, &path, |grid| {
+///     let mut rle = Vec::new();
+///     grid.write_life_rle(&mut rle).unwrap();
+///     String::from_utf8(rle).unwrap()
+/// });
+/// ```
+///
+/// # Panics
+///
+/// If the rendered grid does not match the snapshot, the snapshot does not exist, or reading or
+/// writing the snapshot fails.
+#[track_caller]
+pub fn assert_matches_snapshot_with<T>(
+    grid: &Grid<T>,
+    path: impl AsRef<Path>,
+    render: impl FnOnce(&Grid<T>) -> String,
+) where
+    T: Clone,
+{
+    let update =
+        env::var_os(UPDATE_SNAPSHOTS).is_some_and(|value| !value.is_empty() && value != "0");
+    check_snapshot(&render(grid), path.as_ref(), update);
+}
+
+/// Compares `actual` to the snapshot at `path`, or writes it if `update` is `true`.
+#[track_caller]
+fn check_snapshot(actual: &str, path: &Path, update: bool) {
+    if update {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap_or_else(|error| {
+                panic!("Could not create directory {}: {error}", parent.display())
+            });
+        }
+        fs::write(path, actual)
+            .unwrap_or_else(|error| panic!("Could not write snapshot {}: {error}", path.display()));
+        return;
+    }
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => panic!(
+            "Snapshot {} does not exist; set {UPDATE_SNAPSHOTS}=1 to create it. Actual:\n{actual}",
+            path.display()
+        ),
+        Err(error) => panic!("Could not read snapshot {}: {error}", path.display()),
+    };
+    if expected == actual {
+        return;
+    }
+    let line = expected
+        .lines()
+        .zip(actual.lines())
+        .position(|(expected, actual)| expected != actual)
+        .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+    panic!(
+        "Snapshot {} differs at line {}; set {UPDATE_SNAPSHOTS}=1 to update it.\nExpected:\n{expected}\nActual:\n{actual}",
+        path.display(),
+        line + 1
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_update_then_match() {
+        let path = env::temp_dir()
+            .join("grud_snapshot_tests")
+            .join("update.txt");
+        let _ = fs::remove_file(&path);
+
+        check_snapshot("ab\ncd\n", &path, true);
+        check_snapshot("ab\ncd\n", &path, false);
+    }
+
+    #[test]
+    #[should_panic(expected = "differs at line 2")]
+    fn snapshot_mismatch() {
+        let path = env::temp_dir().join("grud_snapshot_mismatch.txt");
+        fs::write(&path, "ab\ncd\n").unwrap();
+
+        check_snapshot("ab\nce\n", &path, false);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not exist; set GRUD_UPDATE_SNAPSHOTS=1 to create it")]
+    fn snapshot_missing() {
+        check_snapshot("", Path::new("/nonexistent/grud/snapshot.txt"), false);
+    }
+}
@@ -0,0 +1,203 @@
+//! Geometric transforms of grids, i.e. cropping, filling, rotating, and flipping.
+//!
+//! See [`Grid::crop`] and [`Grid::rotate_clockwise`] for details.
+
+use alloc::vec::Vec;
+
+use crate::{grid::Grid, point::Rect};
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns a copy of the cells inside `rect`, which is empty if `rect` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let grid = Grid::with_width(3, (0..9).collect());
+    /// assert_eq!(grid.crop(Rect::new(1, 0, 2, 2)).to_matrix(), vec![vec![1, 2], vec![4, 5]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the rectangle is not entirely within the grid.
+    pub fn crop(&self, rect: Rect) -> Grid<T> {
+        self.view(rect).to_grid()
+    }
+
+    /// Sets every cell inside `rect` to `value`.
+    ///
+    /// Parts of the rectangle outside of the grid are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let mut grid = Grid::new(3, 2, 0);
+    /// grid.fill_rect(Rect::new(1, 1, 5, 5), 1);
+    ///
+    /// assert_eq!(grid.to_matrix(), vec![vec![0, 0, 0], vec![0, 1, 1]]);
+    /// ```
+    pub fn fill_rect(&mut self, rect: Rect, value: T) {
+        let (width, height) = self.size();
+        let Some(rect) = rect.intersect(&Rect::new(0, 0, width, height)) else {
+            return;
+        };
+        for point in rect.iter_points() {
+            self[point] = value.clone();
+        }
+    }
+
+    /// Returns a copy of the grid with rows and columns swapped, so `(x, y)` moves to `(y, x)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(grid.transpose().to_matrix(), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    /// ```
+    pub fn transpose(&self) -> Grid<T> {
+        let (_, height) = self.size();
+        self.remap(height, |x, y| (y, x))
+    }
+
+    /// Returns a copy of the grid rotated a quarter turn clockwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(grid.rotate_clockwise().to_matrix(), vec![vec![4, 1], vec![5, 2], vec![6, 3]]);
+    /// ```
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        let (_, height) = self.size();
+        self.remap(height, |x, y| (y, height - 1 - x))
+    }
+
+    /// Returns a copy of the grid rotated a quarter turn counter-clockwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(
+    ///     grid.rotate_counter_clockwise().to_matrix(),
+    ///     vec![vec![3, 6], vec![2, 5], vec![1, 4]],
+    /// );
+    /// ```
+    pub fn rotate_counter_clockwise(&self) -> Grid<T> {
+        let (width, height) = self.size();
+        self.remap(height, |x, y| (width - 1 - y, x))
+    }
+
+    /// Returns a copy of the grid rotated a half turn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(grid.rotate_180().to_matrix(), vec![vec![6, 5, 4], vec![3, 2, 1]]);
+    /// ```
+    pub fn rotate_180(&self) -> Grid<T> {
+        let (width, height) = self.size();
+        self.remap(width, |x, y| (width - 1 - x, height - 1 - y))
+    }
+
+    /// Returns a copy of the grid mirrored left-to-right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(grid.flip_horizontal().to_matrix(), vec![vec![3, 2, 1], vec![6, 5, 4]]);
+    /// ```
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let width = self.width();
+        self.remap(width, |x, y| (width - 1 - x, y))
+    }
+
+    /// Returns a copy of the grid mirrored top-to-bottom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(grid.flip_vertical().to_matrix(), vec![vec![4, 5, 6], vec![1, 2, 3]]);
+    /// ```
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let (width, height) = self.size();
+        self.remap(width, |x, y| (x, height - 1 - y))
+    }
+
+    /// Returns a grid of `width`, where the cell at `(x, y)` is copied from `source(x, y)`.
+    fn remap(&self, width: usize, source: impl Fn(usize, usize) -> (usize, usize)) -> Grid<T> {
+        if self.as_vec().is_empty() {
+            return Grid::from(Vec::new());
+        }
+        let data = (0..self.as_vec().len())
+            .map(|i| self[source(i % width, i / width)].clone())
+            .collect();
+        Grid::with_width(width, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate_four_times_is_identity() {
+        let grid = Grid::with_width(3, (0..6).collect::<Vec<_>>());
+        let rotated = grid
+            .rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise();
+
+        assert_eq!(rotated.to_matrix(), grid.to_matrix());
+    }
+
+    #[test]
+    fn rotate_counter_clockwise_undoes_clockwise() {
+        let grid = Grid::with_width(2, (0..6).collect::<Vec<_>>());
+
+        assert_eq!(
+            grid.rotate_clockwise()
+                .rotate_counter_clockwise()
+                .to_matrix(),
+            grid.to_matrix()
+        );
+    }
+
+    #[test]
+    fn transform_empty_grid() {
+        let grid: Grid<i32> = Grid::from(Vec::new());
+
+        assert!(grid.transpose().as_vec().is_empty());
+        assert!(grid.rotate_180().as_vec().is_empty());
+    }
+
+    #[test]
+    fn fill_rect_outside_grid() {
+        let mut grid = Grid::new(2, 2, 0);
+        grid.fill_rect(Rect::new(2, 0, 1, 1), 1);
+
+        assert_eq!(grid.as_vec(), &vec![0; 4]);
+    }
+}
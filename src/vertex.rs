@@ -0,0 +1,274 @@
+//! Storing data on the corners of cells, i.e. for marching squares or staggered grids.
+//!
+//! See [`VertexGrid`] for details.
+
+use crate::{grid::Grid, point::Point};
+
+/// A grid that stores elements on the corners (vertices) of a `width` by `height` grid of cells.
+///
+/// Vertices are laid out as a `(width + 1)` by `(height + 1)` grid, where the vertex at `(x, y)`
+/// is the top-left corner of cell `(x, y)`.
+///
+/// # Examples
+///
+/// Computing a [marching squares] case for every cell:
+///
+/// [marching squares]: https://en.wikipedia.org/wiki/Marching_squares
+///
+/// ```
+/// use grud::vertex::VertexGrid;
+///
+/// let mut heights = VertexGrid::new(2, 1, 0.0);
+/// heights.vertices_mut()[(1, 0)] = 1.0;
+/// heights.vertices_mut()[(1, 1)] = 1.0;
+///
+/// let cases = heights.to_cells(|corners| {
+///     corners.iter().fold(0, |case, h| case << 1 | usize::from(**h > 0.5))
+/// });
+///
+/// assert_eq!(cases.as_vec(), &vec![0b0110, 0b1001]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct VertexGrid<T>
+where
+    T: Clone,
+{
+    vertices: Grid<T>,
+}
+
+impl<T> VertexGrid<T>
+where
+    T: Clone,
+{
+    /// Creates vertices for a grid of `width` by `height` cells, filling with `default`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::vertex::VertexGrid;
+    ///
+    /// let vertices = VertexGrid::new(3, 2, 0);
+    /// assert_eq!(vertices.vertices().width(), 4);
+    /// assert_eq!(vertices.vertices().height(), 3);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width` or `height` is `0`.
+    pub fn new(width: usize, height: usize, default: T) -> Self {
+        assert!(
+            width > 0 && height > 0,
+            "Cannot create vertices for a {width}x{height} grid"
+        );
+        Self {
+            vertices: Grid::new(width + 1, height + 1, default),
+        }
+    }
+
+    /// Creates vertices for the cells of `grid`, where each vertex is computed by `reducer` from
+    /// the cells that share it (in row-major order).
+    ///
+    /// Vertices in the interior are shared by 4 cells, on the borders by 2, and in the corners by
+    /// 1 cell.
+    ///
+    /// # Examples
+    ///
+    /// Averaging cells onto their corners:
+    ///
+    /// ```
+    /// use grud::{Grid, vertex::VertexGrid};
+    ///
+    /// let cells = Grid::with_width(2, vec![0.0, 4.0]);
+    /// let vertices = VertexGrid::from_cells(&cells, |shared| {
+    ///     shared.iter().copied().sum::<f64>() / shared.len() as f64
+    /// });
+    ///
+    /// assert_eq!(vertices.vertices().as_vec(), &vec![0.0, 2.0, 4.0, 0.0, 2.0, 4.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `grid` is empty.
+    pub fn from_cells<U>(grid: &Grid<U>, mut reducer: impl FnMut(&[&U]) -> T) -> Self
+    where
+        U: Clone,
+    {
+        assert!(
+            !grid.as_vec().is_empty(),
+            "Cannot create vertices for an empty grid"
+        );
+        let (width, height) = (grid.width(), grid.height());
+        let mut data = Vec::with_capacity((width + 1) * (height + 1));
+        let mut shared = Vec::with_capacity(4);
+        for y in 0..=height {
+            for x in 0..=width {
+                shared.clear();
+                for j in y.saturating_sub(1)..(y + 1).min(height) {
+                    for i in x.saturating_sub(1)..(x + 1).min(width) {
+                        shared.push(&grid[(i, j)]);
+                    }
+                }
+                data.push(reducer(&shared));
+            }
+        }
+        Self {
+            vertices: Grid::with_width(width + 1, data),
+        }
+    }
+
+    /// Returns the width of the grid of cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::vertex::VertexGrid;
+    ///
+    /// assert_eq!(VertexGrid::new(3, 2, 0).width(), 3);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.vertices.width() - 1
+    }
+
+    /// Returns the height of the grid of cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::vertex::VertexGrid;
+    ///
+    /// assert_eq!(VertexGrid::new(3, 2, 0).height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.vertices.height() - 1
+    }
+
+    /// Returns the vertices, where the vertex at `(x, y)` is the top-left corner of cell `(x, y)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::vertex::VertexGrid;
+    ///
+    /// let vertices = VertexGrid::new(3, 2, 0);
+    /// assert_eq!(vertices.vertices().area(), 4 * 3);
+    /// ```
+    pub fn vertices(&self) -> &Grid<T> {
+        &self.vertices
+    }
+
+    /// Returns the vertices as mutable, where the vertex at `(x, y)` is the top-left corner of
+    /// cell `(x, y)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::vertex::VertexGrid;
+    ///
+    /// let mut vertices = VertexGrid::new(3, 2, 0);
+    /// vertices.vertices_mut()[(3, 2)] = 1;
+    /// assert_eq!(vertices.corners((2, 1))[2], &1);
+    /// ```
+    pub fn vertices_mut(&mut self) -> &mut Grid<T> {
+        &mut self.vertices
+    }
+
+    /// Returns the corners of the cell at `point`, clockwise from the top-left corner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, vertex::VertexGrid};
+    ///
+    /// let vertices = VertexGrid::from_cells(&Grid::new(1, 1, ()), |_| 'v');
+    /// assert_eq!(vertices.corners((0, 0)), [&'v', &'v', &'v', &'v']);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `point` is out of bounds of the grid of cells.
+    pub fn corners(&self, point: impl Point) -> [&T; 4] {
+        let (x, y) = (point.x(), point.y());
+        assert!(
+            x < self.width() && y < self.height(),
+            "Point ({}, {}) out of bounds of {}x{} grid",
+            x,
+            y,
+            self.width(),
+            self.height()
+        );
+        [
+            &self.vertices[(x, y)],
+            &self.vertices[(x + 1, y)],
+            &self.vertices[(x + 1, y + 1)],
+            &self.vertices[(x, y + 1)],
+        ]
+    }
+
+    /// Creates a grid of cells, where each cell is computed by `reducer` from its corners,
+    /// clockwise from the top-left corner.
+    ///
+    /// # Examples
+    ///
+    /// Sampling the top-left corner of every cell:
+    ///
+    /// ```
+    /// use grud::vertex::VertexGrid;
+    ///
+    /// let mut vertices = VertexGrid::new(2, 1, 0);
+    /// vertices.vertices_mut()[(1, 0)] = 5;
+    ///
+    /// let cells = vertices.to_cells(|[top_left, ..]| *top_left);
+    /// assert_eq!(cells.as_vec(), &vec![0, 5]);
+    /// ```
+    pub fn to_cells<U>(&self, mut reducer: impl FnMut([&T; 4]) -> U) -> Grid<U>
+    where
+        U: Clone,
+    {
+        let mut data = Vec::with_capacity(self.width() * self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                data.push(reducer(self.corners((x, y))));
+            }
+        }
+        Grid::with_width(self.width(), data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vertex_grid_from_cells_shared_counts() {
+        let cells = Grid::new(3, 2, ());
+        let vertices = VertexGrid::from_cells(&cells, |shared| shared.len());
+
+        assert_eq!(
+            vertices.vertices().as_vec(),
+            &vec![1, 2, 2, 1, 2, 4, 4, 2, 1, 2, 2, 1]
+        );
+    }
+
+    #[test]
+    fn vertex_grid_round_trip_average() {
+        let cells = Grid::new(3, 3, 2);
+        let vertices = VertexGrid::from_cells(&cells, |shared| {
+            shared.iter().copied().sum::<i32>() / shared.len() as i32
+        });
+        let back = vertices.to_cells(|c| c.into_iter().sum::<i32>() / 4);
+
+        assert_eq!(back.as_vec(), cells.as_vec());
+    }
+
+    #[test]
+    #[should_panic]
+    fn vertex_grid_corners_out_of_bounds() {
+        VertexGrid::new(2, 2, 0).corners((2, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn vertex_grid_empty() {
+        VertexGrid::new(1, 0, 0);
+    }
+}
@@ -0,0 +1,748 @@
+//! Borrowed rectangular views into a grid, i.e. for sliding windows or tiles.
+//!
+//! See [`GridView`], [`Grid::windows`], and [`Grid::chunks`] for details, and [`GridMut`] for
+//! changing the cells of a grid owned by another structure without resizing it.
+
+use alloc::vec::Vec;
+use core::{
+    mem,
+    ops::{Deref, Index, IndexMut},
+};
+
+use crate::{
+    grid::Grid,
+    point::{Point, Rect},
+};
+
+/// A borrowed, read-only rectangle of cells within a [`Grid`].
+///
+/// Points are relative to the top-left corner of the view.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, point::Rect};
+///
+/// let grid = Grid::with_width(3, (0..9).collect());
+/// let view = grid.view(Rect::new(1, 1, 2, 2));
+///
+/// assert_eq!(view[(0, 0)], 4);
+/// assert_eq!(view.iter().copied().collect::<Vec<_>>(), vec![4, 5, 7, 8]);
+/// ```
+#[derive(Debug)]
+pub struct GridView<'a, T>
+where
+    T: Clone,
+{
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Clone for GridView<'_, T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> where T: Clone {}
+
+impl<'a, T> GridView<'a, T>
+where
+    T: Clone,
+{
+    /// Creates a view of the `width` by `height` rectangle of `grid` at `(x, y)`.
+    fn new(grid: &'a Grid<T>, x: usize, y: usize, width: usize, height: usize) -> Self {
+        let (grid_width, grid_height) = grid.size();
+        strict_assert!(
+            Rect::new(x, y, width, height).is_within(grid_width, grid_height),
+            "View at ({x}, {y}) of {width}x{height} out of bounds of {grid_width}x{grid_height} grid"
+        );
+        Self {
+            grid,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the position of the top-left corner of the view within the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let grid = Grid::new(4, 4, 0);
+    /// assert_eq!(grid.view(Rect::new(1, 2, 3, 2)).origin(), (1, 2));
+    /// ```
+    pub fn origin(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+
+    /// Returns the width of the view.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let grid = Grid::new(4, 4, 0);
+    /// assert_eq!(grid.view(Rect::new(1, 2, 3, 2)).width(), 3);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the view.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let grid = Grid::new(4, 4, 0);
+    /// assert_eq!(grid.view(Rect::new(1, 2, 3, 2)).height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the cell at `point`, relative to the view, or `None` if out of bounds of the view.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let grid = Grid::with_width(3, (0..9).collect());
+    /// let view = grid.view(Rect::new(1, 1, 2, 2));
+    ///
+    /// assert_eq!(view.get((1, 1)), Some(&8));
+    /// assert_eq!(view.get((2, 0)), None);
+    /// ```
+    pub fn get(&self, point: impl Point) -> Option<&'a T> {
+        if point.x() < self.width && point.y() < self.height {
+            Some(&self.grid[(self.x + point.x(), self.y + point.y())])
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the rows of the view, as slices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let grid = Grid::with_width(3, (0..9).collect());
+    /// let rows: Vec<_> = grid.view(Rect::new(0, 1, 2, 2)).rows().collect();
+    ///
+    /// assert_eq!(rows, vec![&[3, 4], &[6, 7]]);
+    /// ```
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + 'a {
+        let (grid, x, width) = (self.grid, self.x, self.width);
+        (self.y..self.y + self.height).map(move |y| {
+            let start = y * grid.width() + x;
+            &grid.as_vec()[start..start + width]
+        })
+    }
+
+    /// Returns an iterator over the cells of the view, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let grid = Grid::with_width(3, (0..9).collect());
+    /// let sum: i32 = grid.view(Rect::new(1, 0, 2, 3)).iter().sum();
+    ///
+    /// assert_eq!(sum, 1 + 2 + 4 + 5 + 7 + 8);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        self.rows().flatten()
+    }
+
+    /// Copies the view into a new grid, which is empty if the view is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let grid = Grid::with_width(3, (0..9).collect());
+    /// let copy = grid.view(Rect::new(1, 1, 2, 2)).to_grid();
+    ///
+    /// assert_eq!(copy.as_vec(), &vec![4, 5, 7, 8]);
+    /// ```
+    pub fn to_grid(&self) -> Grid<T> {
+        let data: Vec<T> = self.iter().cloned().collect();
+        if data.is_empty() {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(self.width, data)
+        }
+    }
+}
+
+impl<T, I> Index<I> for GridView<'_, T>
+where
+    T: Clone,
+    I: Point,
+{
+    type Output = T;
+
+    /// Given a two-dimensional coordinate [`Point`] relative to the view, returns the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let grid = Grid::with_width(2, vec!['a', 'b', 'c', 'd']);
+    /// assert_eq!(grid.view(Rect::new(1, 0, 1, 2))[(0, 1)], 'd');
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds of the view.
+    fn index(&self, index: I) -> &Self::Output {
+        self.get(index).unwrap_or_else(|| {
+            panic!(
+                "Point ({}, {}) out of bounds of {}x{} view",
+                index.x(),
+                index.y(),
+                self.width,
+                self.height
+            )
+        })
+    }
+}
+
+/// A mutable borrow of a [`Grid`] that can change its cells, but not its size.
+///
+/// Structures that keep several grids of related sizes return this instead of `&mut Grid<T>`, so
+/// their invariants cannot be broken by resizing or replacing a grid. Every read-only method of
+/// [`Grid`] is available through [`Deref`].
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, view::GridMut};
+///
+/// let mut grid = Grid::new(2, 2, 0);
+/// let mut cells = GridMut::new(&mut grid);
+/// cells[(1, 0)] = 5;
+///
+/// assert_eq!(cells.width(), 2);
+/// assert_eq!(grid.as_vec(), &vec![0, 5, 0, 0]);
+/// ```
+#[derive(Debug)]
+pub struct GridMut<'a, T>
+where
+    T: Clone,
+{
+    grid: &'a mut Grid<T>,
+}
+
+impl<'a, T> GridMut<'a, T>
+where
+    T: Clone,
+{
+    /// Borrows `grid`, allowing its cells to be changed but not its size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, view::GridMut};
+    ///
+    /// let mut grid = Grid::new(3, 1, 'a');
+    /// assert_eq!(GridMut::new(&mut grid).area(), 3);
+    /// ```
+    pub fn new(grid: &'a mut Grid<T>) -> Self {
+        Self { grid }
+    }
+
+    /// Returns a mutable reference to the cell at `point`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, view::GridMut};
+    ///
+    /// let mut grid = Grid::new(2, 1, 0);
+    /// let mut cells = GridMut::new(&mut grid);
+    ///
+    /// *cells.get_mut((1, 0)).unwrap() = 1;
+    /// assert!(cells.get_mut((2, 0)).is_none());
+    /// assert_eq!(cells.as_vec(), &vec![0, 1]);
+    /// ```
+    pub fn get_mut(&mut self, point: impl Point) -> Option<&mut T> {
+        self.grid.get_mut(point)
+    }
+
+    /// Returns an iterator over mutable references to every cell, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, view::GridMut};
+    ///
+    /// let mut grid = Grid::with_width(2, vec![1, 2]);
+    /// GridMut::new(&mut grid).iter_mut().for_each(|cell| *cell *= 10);
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![10, 20]);
+    /// ```
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
+        self.grid.as_mut_slice().iter_mut()
+    }
+
+    /// Sets every cell to `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, view::GridMut};
+    ///
+    /// let mut grid = Grid::new(2, 2, 0);
+    /// GridMut::new(&mut grid).fill(7);
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![7; 4]);
+    /// ```
+    pub fn fill(&mut self, value: T) {
+        self.grid.as_mut_slice().fill(value);
+    }
+
+    /// Replaces every cell with the cells of `grid`, which must be the same size, and returns the
+    /// previous cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, view::GridMut};
+    ///
+    /// let mut grid = Grid::new(2, 1, 0);
+    /// let old = GridMut::new(&mut grid).replace(Grid::with_width(2, vec![1, 2]));
+    ///
+    /// assert_eq!(old.as_vec(), &vec![0, 0]);
+    /// assert_eq!(grid.as_vec(), &vec![1, 2]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `grid` is not the same size as the borrowed grid.
+    pub fn replace(&mut self, grid: Grid<T>) -> Grid<T> {
+        let ((width, height), (other_width, other_height)) = (self.grid.size(), grid.size());
+        assert!(
+            (width, height) == (other_width, other_height),
+            "Cannot replace {width}x{height} grid with {other_width}x{other_height} grid"
+        );
+        mem::replace(self.grid, grid)
+    }
+}
+
+impl<T> Deref for GridMut<'_, T>
+where
+    T: Clone,
+{
+    type Target = Grid<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.grid
+    }
+}
+
+impl<T, I> Index<I> for GridMut<'_, T>
+where
+    T: Clone,
+    I: Point,
+{
+    type Output = T;
+
+    /// Given a two-dimensional coordinate [`Point`], returns the cell.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index(&self, index: I) -> &Self::Output {
+        &self.grid[index]
+    }
+}
+
+impl<T, I> IndexMut<I> for GridMut<'_, T>
+where
+    T: Clone,
+    I: Point,
+{
+    /// Given a two-dimensional coordinate [`Point`], sets the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, view::GridMut};
+    ///
+    /// let mut grid = Grid::new(1, 2, 'a');
+    /// GridMut::new(&mut grid)[(0, 1)] = 'b';
+    ///
+    /// assert_eq!(grid.as_vec(), &vec!['a', 'b']);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.grid[index]
+    }
+}
+
+/// An iterator over every `width` by `height` view of a grid.
+///
+/// This struct is created by [`Grid::windows`].
+#[derive(Debug)]
+pub struct Windows<'a, T>
+where
+    T: Clone,
+{
+    grid: &'a Grid<T>,
+    width: usize,
+    height: usize,
+    next: (usize, usize),
+}
+
+impl<'a, T> Iterator for Windows<'a, T>
+where
+    T: Clone,
+{
+    type Item = GridView<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = self.next;
+        if self.len() == 0 {
+            return None;
+        }
+        self.next = if x + self.width < self.grid.width() {
+            (x + 1, y)
+        } else {
+            (0, y + 1)
+        };
+        Some(GridView::new(self.grid, x, y, self.width, self.height))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for Windows<'_, T>
+where
+    T: Clone,
+{
+    fn len(&self) -> usize {
+        if self.grid.as_vec().is_empty() {
+            return 0;
+        }
+        let (Some(across), Some(down)) = (
+            (self.grid.width() + 1).checked_sub(self.width),
+            (self.grid.height() + 1).checked_sub(self.height),
+        ) else {
+            return 0;
+        };
+        let (x, y) = self.next;
+        down.saturating_sub(y) * across - if y < down { x } else { 0 }
+    }
+}
+
+/// An iterator over non-overlapping `width` by `height` views of a grid.
+///
+/// This struct is created by [`Grid::chunks`].
+#[derive(Debug)]
+pub struct Chunks<'a, T>
+where
+    T: Clone,
+{
+    grid: &'a Grid<T>,
+    width: usize,
+    height: usize,
+    next: (usize, usize),
+}
+
+impl<'a, T> Iterator for Chunks<'a, T>
+where
+    T: Clone,
+{
+    type Item = GridView<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = self.next;
+        if self.len() == 0 {
+            return None;
+        }
+        self.next = if x + self.width < self.grid.width() {
+            (x + self.width, y)
+        } else {
+            (0, y + self.height)
+        };
+        Some(GridView::new(
+            self.grid,
+            x,
+            y,
+            self.width.min(self.grid.width() - x),
+            self.height.min(self.grid.height() - y),
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for Chunks<'_, T>
+where
+    T: Clone,
+{
+    fn len(&self) -> usize {
+        if self.grid.as_vec().is_empty() {
+            return 0;
+        }
+        let across = self.grid.width().div_ceil(self.width);
+        let down = self.grid.height().div_ceil(self.height);
+        let (x, y) = (self.next.0 / self.width, self.next.1 / self.height);
+        down.saturating_sub(y) * across - if y < down { x } else { 0 }
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns a view of the cells inside `rect`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect};
+    ///
+    /// let grid = Grid::with_width(3, (0..9).collect());
+    /// let view = grid.view(Rect::new(1, 1, 2, 2));
+    ///
+    /// assert_eq!(view.to_grid().as_vec(), &vec![4, 5, 7, 8]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the rectangle is not entirely within the grid.
+    pub fn view(&self, rect: Rect) -> GridView<'_, T> {
+        let (width, height) = self.size();
+        assert!(
+            rect.is_within(width, height),
+            "Rectangle at ({}, {}) of {}x{} out of bounds of {width}x{height} grid",
+            rect.x(),
+            rect.y(),
+            rect.width(),
+            rect.height()
+        );
+        GridView::new(self, rect.x(), rect.y(), rect.width(), rect.height())
+    }
+
+    /// Returns an iterator over every `width` by `height` view of the grid, in row-major order of
+    /// the top-left corner of each view.
+    ///
+    /// This is the two-dimensional equivalent of [`slice::windows`]; views overlap, and if the
+    /// grid is smaller than a window in either axis, the iterator yields nothing.
+    ///
+    /// # Examples
+    ///
+    /// Finding every 2x2 block of the same cell:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::from(vec![
+    ///     "##.".chars().collect(),
+    ///     "##.".chars().collect(),
+    ///     "...".chars().collect(),
+    /// ]);
+    ///
+    /// let blocks: Vec<_> = grid
+    ///     .windows(2, 2)
+    ///     .filter(|w| w.iter().all(|c| *c == w[(0, 0)]))
+    ///     .map(|w| w.origin())
+    ///     .collect();
+    ///
+    /// assert_eq!(blocks, vec![(0, 0)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width` or `height` is `0`.
+    pub fn windows(&self, width: usize, height: usize) -> Windows<'_, T> {
+        assert!(
+            width > 0 && height > 0,
+            "Window size must be greater than 0, got {width}x{height}"
+        );
+        Windows {
+            grid: self,
+            width,
+            height,
+            next: (0, 0),
+        }
+    }
+
+    /// Returns an iterator over non-overlapping `width` by `height` views that cover the grid, in
+    /// row-major order of the top-left corner of each view.
+    ///
+    /// This is the two-dimensional equivalent of [`slice::chunks`]; if the grid is not evenly
+    /// divisible by the chunk size, the chunks on the right and bottom edges are smaller, so that
+    /// every cell belongs to exactly one chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, (0..6).collect());
+    /// let chunks: Vec<_> = grid.chunks(2, 2).map(|c| c.to_grid().to_matrix()).collect();
+    ///
+    /// assert_eq!(chunks, vec![
+    ///     vec![vec![0, 1], vec![3, 4]],
+    ///     vec![vec![2], vec![5]],
+    /// ]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `width` or `height` is `0`.
+    pub fn chunks(&self, width: usize, height: usize) -> Chunks<'_, T> {
+        assert!(
+            width > 0 && height > 0,
+            "Chunk size must be greater than 0, got {width}x{height}"
+        );
+        Chunks {
+            grid: self,
+            width,
+            height,
+            next: (0, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_view_to_grid() {
+        let grid = Grid::new(3, 3, 0);
+
+        for rect in [
+            Rect::new(1, 1, 0, 2),
+            Rect::new(1, 1, 2, 0),
+            Rect::new(3, 3, 0, 0),
+        ] {
+            let copy = grid.view(rect).to_grid();
+            assert_eq!(copy.size(), (0, 0));
+            assert!(grid.crop(rect).as_vec().is_empty());
+        }
+    }
+
+    #[test]
+    fn windows_count_and_order() {
+        let grid = Grid::with_width(3, (0..6).collect::<Vec<_>>());
+        let windows = grid.windows(2, 1);
+
+        assert_eq!(windows.len(), 4);
+        assert_eq!(
+            windows.map(|w| w.origin()).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (0, 1), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn windows_len_after_next() {
+        let grid = Grid::new(4, 3, 0);
+        let mut windows = grid.windows(2, 2);
+        windows.next();
+
+        assert_eq!(windows.len(), 5);
+        assert_eq!(windows.count(), 5);
+    }
+
+    #[test]
+    fn windows_larger_than_grid() {
+        let grid = Grid::new(2, 2, 0);
+
+        assert_eq!(grid.windows(3, 1).count(), 0);
+        assert_eq!(grid.windows(1, 3).count(), 0);
+    }
+
+    #[test]
+    fn windows_same_size_as_grid() {
+        let grid = Grid::with_width(2, vec![1, 2, 3, 4]);
+        let windows: Vec<_> = grid.windows(2, 2).collect();
+
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].to_grid().as_vec(), grid.as_vec());
+    }
+
+    #[test]
+    #[should_panic]
+    fn windows_zero_size() {
+        Grid::new(2, 2, 0).windows(0, 1);
+    }
+
+    #[test]
+    fn chunks_cover_every_cell_once() {
+        let grid = Grid::new(7, 5, 0);
+        let mut covered = Grid::new(7, 5, 0);
+        let chunks = grid.chunks(3, 2);
+
+        assert_eq!(chunks.len(), 9);
+        for chunk in chunks {
+            let (x, y) = chunk.origin();
+            for j in 0..chunk.height() {
+                for i in 0..chunk.width() {
+                    covered[(x + i, y + j)] += 1;
+                }
+            }
+        }
+        assert!(covered.as_vec().iter().all(|c| *c == 1));
+    }
+
+    #[test]
+    fn chunks_larger_than_grid() {
+        let grid = Grid::new(2, 2, 0);
+        let chunks: Vec<_> = grid.chunks(5, 5).collect();
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!((chunks[0].width(), chunks[0].height()), (2, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn chunks_zero_size() {
+        Grid::new(2, 2, 0).chunks(1, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn view_out_of_bounds() {
+        Grid::new(2, 2, 0).view(Rect::new(1, 0, 2, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn view_index_out_of_bounds() {
+        let grid = Grid::new(3, 3, 0);
+        let _ = grid.view(Rect::new(0, 0, 2, 2))[(2, 0)];
+    }
+}
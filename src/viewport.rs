@@ -0,0 +1,367 @@
+//! Mapping between world grid coordinates and screen-space cells.
+//!
+//! See [`Viewport`] for details.
+
+use crate::{grid::Grid, point::Rect};
+
+/// A camera over a grid, mapping world coordinates to a fixed-size rectangle of screen cells.
+///
+/// The viewport has an _origin_, which is the world coordinate displayed in the top-left screen
+/// cell, and a _zoom_, which is how many screen cells (in each axis) a single world cell covers.
+/// The origin is signed, so the viewport may extend (or move entirely) past the edges of the grid;
+/// such areas are simply not visible.
+///
+/// # Examples
+///
+/// ```
+/// use grud::{Grid, viewport::Viewport};
+///
+/// let grid = Grid::with_width(4, (0..16).collect());
+///
+/// let mut viewport = Viewport::new(2, 2);
+/// viewport.pan(1, 1);
+///
+/// let visible: Vec<_> = viewport.visible_cells(&grid).map(|(_, _, c)| *c).collect();
+/// assert_eq!(visible, vec![5, 6, 9, 10]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Viewport {
+    origin: (isize, isize),
+    width: usize,
+    height: usize,
+    zoom: usize,
+}
+
+impl Viewport {
+    /// Creates a viewport of `width` by `height` screen cells, with the origin at `(0, 0)` and a
+    /// zoom of `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// let viewport = Viewport::new(80, 24);
+    /// assert_eq!(viewport.origin(), (0, 0));
+    /// assert_eq!(viewport.zoom(), 1);
+    /// ```
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            origin: (0, 0),
+            width,
+            height,
+            zoom: 1,
+        }
+    }
+
+    /// Returns the width of the viewport, in screen cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// assert_eq!(Viewport::new(80, 24).width(), 80);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the viewport, in screen cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// assert_eq!(Viewport::new(80, 24).height(), 24);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the world coordinate displayed in the top-left screen cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// let mut viewport = Viewport::new(80, 24);
+    /// viewport.pan(-3, 2);
+    /// assert_eq!(viewport.origin(), (-3, 2));
+    /// ```
+    pub fn origin(&self) -> (isize, isize) {
+        self.origin
+    }
+
+    /// Sets the world coordinate displayed in the top-left screen cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// let mut viewport = Viewport::new(80, 24);
+    /// viewport.set_origin(10, -10);
+    /// assert_eq!(viewport.origin(), (10, -10));
+    /// ```
+    pub fn set_origin(&mut self, x: isize, y: isize) {
+        self.origin = (x, y);
+    }
+
+    /// Moves the origin by `dx` and `dy` world cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// let mut viewport = Viewport::new(80, 24);
+    /// viewport.pan(5, 0);
+    /// viewport.pan(-2, 1);
+    /// assert_eq!(viewport.origin(), (3, 1));
+    /// ```
+    pub fn pan(&mut self, dx: isize, dy: isize) {
+        self.origin = (self.origin.0 + dx, self.origin.1 + dy);
+    }
+
+    /// Moves the origin so that the world coordinate `(x, y)` is (as close as possible to) the
+    /// center of the viewport.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// let mut viewport = Viewport::new(5, 5);
+    /// viewport.center_on(10, 10);
+    /// assert_eq!(viewport.origin(), (8, 8));
+    /// ```
+    pub fn center_on(&mut self, x: isize, y: isize) {
+        let (w, h) = self.world_size();
+        self.origin = (x - (w / 2) as isize, y - (h / 2) as isize);
+    }
+
+    /// Returns how many screen cells (in each axis) a single world cell covers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// assert_eq!(Viewport::new(80, 24).zoom(), 1);
+    /// ```
+    pub fn zoom(&self) -> usize {
+        self.zoom
+    }
+
+    /// Sets how many screen cells (in each axis) a single world cell covers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// let mut viewport = Viewport::new(80, 24);
+    /// viewport.set_zoom(2);
+    /// assert_eq!(viewport.world_size(), (40, 12));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `zoom` is `0`.
+    pub fn set_zoom(&mut self, zoom: usize) {
+        assert!(zoom > 0, "Zoom must be greater than 0");
+        self.zoom = zoom;
+    }
+
+    /// Returns how many world cells are (at least partially) visible in each axis.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// let mut viewport = Viewport::new(5, 4);
+    /// viewport.set_zoom(2);
+    /// assert_eq!(viewport.world_size(), (3, 2));
+    /// ```
+    pub fn world_size(&self) -> (usize, usize) {
+        (
+            self.width.div_ceil(self.zoom),
+            self.height.div_ceil(self.zoom),
+        )
+    }
+
+    /// Converts a screen cell to the world coordinate it displays.
+    ///
+    /// Returns `None` if the screen cell is outside of the viewport.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// let mut viewport = Viewport::new(4, 4);
+    /// viewport.set_origin(-1, 0);
+    /// viewport.set_zoom(2);
+    ///
+    /// assert_eq!(viewport.screen_to_world(3, 1), Some((0, 0)));
+    /// assert_eq!(viewport.screen_to_world(4, 0), None);
+    /// ```
+    pub fn screen_to_world(&self, x: usize, y: usize) -> Option<(isize, isize)> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some((
+            self.origin.0 + (x / self.zoom) as isize,
+            self.origin.1 + (y / self.zoom) as isize,
+        ))
+    }
+
+    /// Converts a world coordinate to the top-left screen cell that displays it.
+    ///
+    /// Returns `None` if the world coordinate is not visible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::viewport::Viewport;
+    ///
+    /// let mut viewport = Viewport::new(4, 4);
+    /// viewport.set_origin(-1, 0);
+    /// viewport.set_zoom(2);
+    ///
+    /// assert_eq!(viewport.world_to_screen(0, 1), Some((2, 2)));
+    /// assert_eq!(viewport.world_to_screen(-2, 0), None);
+    /// ```
+    pub fn world_to_screen(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+        let dx = usize::try_from(x - self.origin.0).ok()?;
+        let dy = usize::try_from(y - self.origin.1).ok()?;
+        let (sx, sy) = (dx.checked_mul(self.zoom)?, dy.checked_mul(self.zoom)?);
+        (sx < self.width && sy < self.height).then_some((sx, sy))
+    }
+
+    /// Returns the visible part of `grid`, in grid coordinates.
+    ///
+    /// Returns `None` if no part of the grid is visible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, point::Rect, viewport::Viewport};
+    ///
+    /// let grid = Grid::new(10, 10, ());
+    /// let mut viewport = Viewport::new(4, 4);
+    /// viewport.set_origin(-2, 8);
+    ///
+    /// assert_eq!(viewport.visible_rect(&grid), Some(Rect::new(0, 8, 2, 2)));
+    /// ```
+    pub fn visible_rect<T>(&self, grid: &Grid<T>) -> Option<Rect>
+    where
+        T: Clone,
+    {
+        let (w, h) = self.world_size();
+        let clip = |origin: isize, size: usize, bound: usize| {
+            let start = origin.max(0);
+            let end = (origin + size as isize).min(bound as isize);
+            (start < end).then_some((start as usize, (end - start) as usize))
+        };
+        let (x, width) = clip(self.origin.0, w, grid.width())?;
+        let (y, height) = clip(self.origin.1, h, grid.height())?;
+        Some(Rect::new(x, y, width, height))
+    }
+
+    /// Returns an iterator over every visible cell of `grid`, in row-major order.
+    ///
+    /// Each item is the top-left screen cell the grid cell is displayed at, the grid coordinate,
+    /// and the cell itself. When zoomed, each grid cell covers `zoom` by `zoom` screen cells
+    /// (clipped to the edges of the viewport).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::{Grid, viewport::Viewport};
+    ///
+    /// let grid = Grid::with_width(2, vec!['a', 'b', 'c', 'd']);
+    /// let mut viewport = Viewport::new(3, 3);
+    /// viewport.set_zoom(2);
+    ///
+    /// let visible: Vec<_> = viewport.visible_cells(&grid).collect();
+    /// assert_eq!(visible, vec![
+    ///     ((0, 0), (0, 0), &'a'),
+    ///     ((2, 0), (1, 0), &'b'),
+    ///     ((0, 2), (0, 1), &'c'),
+    ///     ((2, 2), (1, 1), &'d'),
+    /// ]);
+    /// ```
+    pub fn visible_cells<'a, T>(
+        &self,
+        grid: &'a Grid<T>,
+    ) -> impl Iterator<Item = ((usize, usize), (usize, usize), &'a T)> + 'a
+    where
+        T: Clone,
+    {
+        let viewport = *self;
+        let rect = self.visible_rect(grid).unwrap_or_default();
+        rect.iter_points().map(move |(i, j)| {
+            let screen = viewport
+                .world_to_screen(i as isize, j as isize)
+                .expect("visible cell has a screen position");
+            (screen, (i, j), &grid[(i, j)])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn viewport_entirely_off_grid() {
+        let grid = Grid::new(4, 4, ());
+        let mut viewport = Viewport::new(2, 2);
+        viewport.set_origin(-5, 0);
+
+        assert_eq!(viewport.visible_rect(&grid), None);
+        assert_eq!(viewport.visible_cells(&grid).count(), 0);
+    }
+
+    #[test]
+    fn viewport_larger_than_grid() {
+        let grid = Grid::new(2, 2, ());
+        let mut viewport = Viewport::new(10, 10);
+        viewport.set_origin(-1, -1);
+
+        assert_eq!(viewport.visible_rect(&grid), Some(Rect::new(0, 0, 2, 2)));
+        assert_eq!(
+            viewport
+                .visible_cells(&grid)
+                .map(|(s, _, _)| s)
+                .collect::<Vec<_>>(),
+            vec![(1, 1), (2, 1), (1, 2), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn viewport_round_trip() {
+        let mut viewport = Viewport::new(9, 7);
+        viewport.set_origin(-3, 4);
+        viewport.set_zoom(3);
+
+        for y in 0..7 {
+            for x in 0..9 {
+                let (wx, wy) = viewport.screen_to_world(x, y).unwrap();
+                let (sx, sy) = viewport.world_to_screen(wx, wy).unwrap();
+                assert_eq!((sx, sy), (x - x % 3, y - y % 3));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn viewport_zero_zoom() {
+        Viewport::new(1, 1).set_zoom(0);
+    }
+}
@@ -0,0 +1,111 @@
+//! Accessing grids with coordinates that wrap around the edges, i.e. for toroidal maps.
+//!
+//! See [`Grid::get_wrapping`] for details, and [`Grid::step_wrapping`] for cellular automata.
+
+use crate::grid::Grid;
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns the point that `(x, y)` wraps around to, as if the grid repeated infinitely in
+    /// every direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::new(4, 3, 0);
+    ///
+    /// assert_eq!(grid.wrap(5, -1), (1, 2));
+    /// assert_eq!(grid.wrap(-4, 3), (0, 0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty.
+    pub fn wrap(&self, x: isize, y: isize) -> (usize, usize) {
+        let (width, height) = self.size();
+        assert!(width > 0 && height > 0, "Cannot wrap within an empty grid");
+        (wrap(x, width), wrap(y, height))
+    }
+
+    /// Returns a reference to the cell at `(x, y)`, wrapping around the edges of the grid.
+    ///
+    /// # Examples
+    ///
+    /// A Pac-Man style tunnel, where leaving one side enters the other:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let grid = Grid::with_width(3, vec!['a', 'b', 'c']);
+    ///
+    /// assert_eq!(grid.get_wrapping(-1, 0), &'c');
+    /// assert_eq!(grid.get_wrapping(3, 5), &'a');
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let point = self.wrap(x, y);
+        &self[point]
+    }
+
+    /// Returns a mutable reference to the cell at `(x, y)`, wrapping around the edges of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let mut grid = Grid::new(2, 2, 0);
+    /// *grid.get_wrapping_mut(-1, -1) = 1;
+    ///
+    /// assert_eq!(grid.as_vec(), &vec![0, 0, 0, 1]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty.
+    pub fn get_wrapping_mut(&mut self, x: isize, y: isize) -> &mut T {
+        let point = self.wrap(x, y);
+        &mut self[point]
+    }
+}
+
+/// Wraps `n` into `0..len`.
+fn wrap(n: isize, len: usize) -> usize {
+    match isize::try_from(len) {
+        Ok(len) => n.rem_euclid(len) as usize,
+        // `len` is larger than any `isize`, so only negative values need to wrap.
+        Err(_) if n < 0 => (n as usize).wrapping_add(len),
+        Err(_) => n as usize,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_far_outside() {
+        let grid = Grid::new(3, 2, 0);
+
+        assert_eq!(grid.wrap(-301, 201), (2, 1));
+    }
+
+    #[test]
+    fn wrap_huge_len() {
+        assert_eq!(wrap(-1, usize::MAX), usize::MAX - 1);
+        assert_eq!(wrap(isize::MAX, usize::MAX), isize::MAX as usize);
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrap_empty_grid() {
+        Grid::<u8>::from(Vec::new()).wrap(0, 0);
+    }
+}
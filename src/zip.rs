@@ -0,0 +1,110 @@
+//! Combining two grids cell-by-cell, i.e. for diffing, masking, or layering.
+//!
+//! See [`Grid::zip`] and [`Grid::zip_with`] for details.
+
+use crate::{error::SizeMismatch, grid::Grid};
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Returns a grid where each cell is a pair of the cells at the same point in both grids.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let terrain = Grid::with_width(2, vec!['.', '#']);
+    /// let visible = Grid::with_width(2, vec![true, false]);
+    ///
+    /// let zipped = terrain.zip(&visible).unwrap();
+    /// assert_eq!(zipped.as_vec(), &vec![('.', true), ('#', false)]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the grids are not the same size.
+    pub fn zip<U>(&self, other: &Grid<U>) -> Result<Grid<(T, U)>, SizeMismatch>
+    where
+        U: Clone,
+    {
+        self.zip_with(other, |a, b| (a.clone(), b.clone()))
+    }
+
+    /// Returns a grid where each cell is `combine` applied to the cells at the same point in both
+    /// grids.
+    ///
+    /// # Examples
+    ///
+    /// Finding changed cells:
+    ///
+    /// ```
+    /// use grud::Grid;
+    ///
+    /// let before = Grid::with_width(2, vec![1, 2, 3, 4]);
+    /// let after = Grid::with_width(2, vec![1, 5, 3, 0]);
+    ///
+    /// let changed = before.zip_with(&after, |a, b| a != b).unwrap();
+    /// assert_eq!(changed.as_vec(), &vec![false, true, false, true]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the grids are not the same size.
+    ///
+    /// ```
+    /// use grud::{Grid, error::SizeMismatch};
+    ///
+    /// let error = Grid::new(2, 2, 0).zip_with(&Grid::new(4, 1, 0), |a, b| a + b).unwrap_err();
+    /// assert_eq!(error, SizeMismatch { expected: (2, 2), found: (4, 1) });
+    /// ```
+    pub fn zip_with<U, V>(
+        &self,
+        other: &Grid<U>,
+        mut combine: impl FnMut(&T, &U) -> V,
+    ) -> Result<Grid<V>, SizeMismatch>
+    where
+        U: Clone,
+        V: Clone,
+    {
+        let (width, height) = self.size();
+        if (width, height) != other.size() {
+            return Err(SizeMismatch {
+                expected: (width, height),
+                found: other.size(),
+            });
+        }
+        let data: Vec<V> = self
+            .as_vec()
+            .iter()
+            .zip(other.as_vec())
+            .map(|(a, b)| combine(a, b))
+            .collect();
+        Ok(if data.is_empty() {
+            Grid::from(Vec::new())
+        } else {
+            Grid::with_width(width, data)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zip_empty_grids() {
+        let a: Grid<u8> = Grid::from(Vec::new());
+        let b: Grid<char> = Grid::from(Vec::new());
+
+        assert!(a.zip(&b).unwrap().as_vec().is_empty());
+    }
+
+    #[test]
+    fn zip_size_mismatch_message() {
+        let error = Grid::new(3, 1, 0).zip(&Grid::new(1, 3, 0)).unwrap_err();
+
+        assert_eq!(error.to_string(), "Expected grid of 3x1 cells, found 1x3");
+    }
+}